@@ -2,7 +2,7 @@ use {
     super::component_name::ComponentName,
     crate::{
         app_error::AppError,
-        tg::td_enums::{TdChatList, TdMessageReplyToMessage},
+        tg::td_enums::{TdChatList, TdMessageReplyToMessage, TdReportReason},
     },
     crossterm::event::{KeyCode, KeyModifiers},
     ratatui::layout::Rect,
@@ -66,6 +66,18 @@ impl From<Modifiers> for KeyModifiers {
     }
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// `NotifyLevel` is an enum that represents the severity of a notification.
+/// It is used to choose the style of the notification in the status bar.
+pub enum NotifyLevel {
+    /// An informative notification.
+    Info,
+    /// A warning, something did not go as expected.
+    Warn,
+    /// An error, for example returned by Telegram.
+    Error,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 // Action` is an enum that represents an action that can be handled by the
 /// main application loop and the components of the user interface.
@@ -104,6 +116,10 @@ pub enum Action {
     SendMessageEdited(i64, String),
     /// GetChatHistory action.
     GetChatHistory,
+    /// GetNewerMessages action.
+    /// It loads the messages arrived in the open chat since its cached
+    /// messages were loaded.
+    GetNewerMessages,
     /// DeleteMessages action.
     /// The first parameter is the `message_ids` and the second parameter is the `revoke`.
     /// If `revoke` is true, the message will be deleted for everyone.
@@ -111,11 +127,175 @@ pub enum Action {
     DeleteMessages(Vec<i64>, bool),
     /// ViewAllMessages action.
     ViewAllMessages,
+    /// GetCallbackQueryAnswer action.
+    /// The first parameter is the `message_id` and the second parameter is the
+    /// `data` of the pressed inline keyboard button.
+    GetCallbackQueryAnswer(i64, String),
+    /// OpenMessageThread action.
+    /// It opens the comments of a channel post, the parameter is the
+    /// `message_id` of the post.
+    OpenMessageThread(i64),
+    /// DownloadFile action.
+    /// It downloads the file of a message, the parameter is the `file_id`.
+    DownloadFile(i32),
+    /// OpenFile action.
+    /// It downloads the file of a message if needed and opens it with the
+    /// default application, the parameter is the `file_id`.
+    OpenFile(i32),
+    /// DeleteChatDraft action.
+    /// It removes the draft of a chat, the parameter is the `chat_id`.
+    DeleteChatDraft(i64),
+    /// GetMessageEditHistory action.
+    /// It shows the previous versions of a message of the open chat in a
+    /// popup, the parameter is the `message_id`.
+    GetMessageEditHistory(i64),
+    /// GetMessageViewers action.
+    /// It shows the users who read an outgoing message of the open chat in a
+    /// popup, the parameter is the `message_id`.
+    GetMessageViewers(i64),
+    /// CheckChatInviteLink action.
+    /// It shows the chat of an invite link and asks to join it, the
+    /// parameter is the invite link.
+    CheckChatInviteLink(String),
+    /// JoinChatByInviteLink action.
+    /// It joins the chat of an invite link and opens it, the parameter is the
+    /// invite link.
+    JoinChatByInviteLink(String),
+    /// GetPinnedMessages action.
+    /// It loads the pinned messages of a chat and shows them in a list, the
+    /// parameter is the `chat_id`.
+    GetPinnedMessages(i64),
+    /// JumpToNextMention action.
+    /// It selects the oldest unread mention of a chat and marks it as read,
+    /// the parameter is the `chat_id`.
+    JumpToNextMention(i64),
+    /// SetUserBlocked action.
+    /// It blocks or unblocks a user, the parameters are the `user_id` and
+    /// whether the user is blocked.
+    SetUserBlocked(i64, bool),
+    /// GetBlockedUsers action.
+    /// It loads the users blocked by the user and shows them in a list.
+    GetBlockedUsers,
+    /// GetUserProfile action.
+    /// It loads the full info of a user and shows its profile, the parameter
+    /// is the `user_id`.
+    GetUserProfile(i64),
+    /// ReadChat action.
+    /// It marks all the messages of a chat as read without opening it, the
+    /// parameter is the `chat_id`.
+    ReadChat(i64),
+    /// SetChatPinned action.
+    /// It pins or unpins a chat in the chat list, the parameters are the
+    /// `chat_id` and whether the chat is pinned.
+    SetChatPinned(i64, bool),
+    /// SetChatMuted action.
+    /// It mutes or unmutes the notifications of a chat, the parameters are
+    /// the `chat_id` and whether the chat is muted.
+    SetChatMuted(i64, bool),
+    /// MarkChatAsUnread action.
+    /// It marks a chat as unread, the parameter is the `chat_id`.
+    MarkChatAsUnread(i64),
+    /// ArchiveChat action.
+    /// It moves a chat to the archive, the parameter is the `chat_id`.
+    ArchiveChat(i64),
+    /// DeleteChat action.
+    /// It deletes the history of a chat and removes it from the chat list,
+    /// the groups and the channels are left. The parameter is the `chat_id`.
+    DeleteChat(i64),
+    /// ReportChat action.
+    /// It reports a chat, or some of its messages if any, to the moderators
+    /// of Telegram. The parameters are the `chat_id`, the `message_ids` and
+    /// the reason of the report.
+    ReportChat(i64, Vec<i64>, TdReportReason),
+    /// ReadChats action.
+    /// It marks all the messages of some chats as read, the parameter is the
+    /// `chat_ids`.
+    ReadChats(Vec<i64>),
+    /// ReadChatList action.
+    /// It marks all the chats of the main chat list as read.
+    ReadChatList,
+    /// RefreshChats action.
+    /// It reloads the chat list from the start and rebuilds it.
+    RefreshChats,
+    /// GetUser action.
+    /// It fetches a user missing from the cache, the parameter is the
+    /// `user_id`.
+    GetUser(i64),
+    /// OptimizeStorage action.
+    /// It removes the cached files that exceed the storage limits.
+    OptimizeStorage,
+    /// ShowStorageStatistics action.
+    /// It shows the size of the cached files in the status bar.
+    ShowStorageStatistics,
+    /// Reconnect action.
+    /// It forces an immediate reconnection attempt to the Telegram servers.
+    Reconnect,
+    /// ToggleMouseCapture action.
+    /// It enables or disables the mouse capture, to select text with the
+    /// mouse of the terminal.
+    ToggleMouseCapture,
+    /// CycleTitleUnreadCount action.
+    /// It changes the chat list whose unread messages are counted first in
+    /// the title of the terminal.
+    CycleTitleUnreadCount,
+    /// NavigateBack action.
+    /// It goes back to the previous chat or message visited.
+    NavigateBack,
+    /// NavigateForward action.
+    /// It goes forward to the chat or message left going back.
+    NavigateForward,
+    /// PromptSendDice action.
+    /// It sends an animated dice, with the dice emoji typed in the prompt.
+    PromptSendDice,
+    /// SendDice action with the emoji of the dice.
+    SendDice(String),
+    /// PromptSendLocation action.
+    /// It sends the location typed in the prompt, or the configured one.
+    PromptSendLocation,
+    /// SendLocation action with the location typed in the prompt.
+    SendLocation(String),
+    /// PromptSendSplit action.
+    /// It sends the text of the prompt, too long for a message, in several
+    /// messages, once it is confirmed.
+    PromptSendSplit,
+    /// LoadStickerSets action.
+    /// It loads the sticker sets installed by the user.
+    LoadStickerSets,
+    /// SendSticker action.
+    /// It sends a sticker to the open chat, the parameters are the `file_id`
+    /// and the `emoji` of the sticker.
+    SendSticker(i32, String),
+    /// NotificationSound action with the chat id.
+    /// It plays the notification sound of a chat where a message arrived.
+    NotificationSound(i64),
+    /// ShowPopup action with the title and the lines of the popup.
+    /// It shows a read-only popup over the core window.
+    ShowPopup(String, Vec<String>),
+    /// ShowConfirm action with the title and the lines of the popup, and the
+    /// action to confirm.
+    /// It shows a popup asking to confirm an action, the action is sent when
+    /// Enter or y is pressed and dropped when Esc, n or q is pressed.
+    ShowConfirm(String, Vec<String>, Box<Action>),
+    /// ShowChoice action with the title of the popup and the entries
+    /// offered, each with its label and the action sent when it is chosen.
+    /// It shows a popup to choose one of the entries with the arrows, the
+    /// action of the entry is sent with Enter and dropped with Esc or q.
+    ShowChoice(String, Vec<(String, Action)>),
+    /// Lock action.
+    /// It hides the chats behind the passphrase prompt until the passphrase
+    /// is entered.
+    Lock,
 
     /// Focus action with a `ComponentName`.
     FocusComponent(ComponentName),
     /// Unfocus action.
     UnfocusComponent,
+    /// FocusNext action.
+    /// It moves the focus to the next component in the focus order.
+    FocusNext,
+    /// FocusPrevious action.
+    /// It moves the focus to the previous component in the focus order.
+    FocusPrevious,
     /// Toggle ChatList action.
     ToggleChatList,
     /// Increase ChatList size action.
@@ -126,6 +306,22 @@ pub enum Action {
     IncreasePromptSize,
     /// Decrease Prompt size action.
     DecreasePromptSize,
+    /// ToggleResizeMode action.
+    /// In the resize mode the arrow keys resize the components, the layout
+    /// is saved when leaving it.
+    ToggleResizeMode,
+    /// ToggleUsernames action.
+    /// It switches between the usernames and the full names of the users.
+    ToggleUsernames,
+    /// ToggleMessageTags action.
+    /// It shows or hides the overview of the locally tagged messages.
+    ToggleMessageTags,
+    /// ToggleStickers action.
+    /// It shows or hides the stickers of the installed sticker sets.
+    ToggleStickers,
+    /// ToggleOnlineContacts action.
+    /// It shows or hides the panel of the contacts online now.
+    ToggleOnlineContacts,
     /// Key action with a key code.
     Key(KeyCode, Modifiers),
     /// Update area action with a rectangular area.
@@ -134,6 +330,22 @@ pub enum Action {
     ShowChatWindowReply,
     /// HideChatWindowReply action.
     HideChatWindowReply,
+    /// OpenChat action.
+    /// It opens a chat as if it was selected in the chat list, the parameter
+    /// is the `chat_id`.
+    OpenChat(i64),
+    /// ForceReply action.
+    /// It is sent when a bot asks the user to reply to its message, the
+    /// parameters are the `message_id`, the text of the message and the
+    /// placeholder of the prompt.
+    ForceReply(i64, String, String),
+    /// StatusMessage action with a `String`.
+    /// It is used to show a message in the status bar.
+    StatusMessage(String),
+    /// Notify action with a `String` and a `NotifyLevel`.
+    /// It shows a notification in the status bar, it expires after the
+    /// `notification_timeout`. `StatusMessage` is an `Info` notification.
+    Notify(String, NotifyLevel),
 
     /// ChatListNext action.
     ChatListNext,
@@ -143,6 +355,42 @@ pub enum Action {
     ChatListUnselect,
     /// ChatListOpen action.
     ChatListOpen,
+    /// ToggleChatListGrouping action.
+    /// It toggles the grouping of the chats by category.
+    ToggleChatListGrouping,
+    /// ChatListDeleteDraft action.
+    /// It removes the draft of the selected chat.
+    ChatListDeleteDraft,
+    /// ChatListRefresh action.
+    /// It reloads the chat list, keeping the selected chat.
+    ChatListRefresh,
+    /// ChatListCopyUsername action.
+    /// It copies the username of the selected chat, or its identifier if it
+    /// has no username.
+    ChatListCopyUsername,
+    /// ChatListQuickJump action.
+    /// It opens the chat shown on screen whose number, from 1 to 9, is the
+    /// next key pressed.
+    ChatListQuickJump,
+    /// ChatListToggleBlock action.
+    /// It blocks or unblocks the user of the selected chat, once confirmed
+    /// by pressing the key again.
+    ChatListToggleBlock,
+    /// ChatListBlockedUsers action.
+    /// It shows the list of the users blocked by the user.
+    ChatListBlockedUsers,
+    /// ChatListReadAll action.
+    /// It marks all the chats as read, once confirmed in a popup.
+    ChatListReadAll,
+    /// ChatListReadVisible action.
+    /// It marks the chats shown on screen as read, once confirmed in a popup.
+    ChatListReadVisible,
+    /// ChatListContextMenu action.
+    /// It opens the menu of the operations on the selected chat.
+    ChatListContextMenu,
+    /// ToggleChatListCompact action.
+    /// It toggles the compact mode of the chat list, one line per chat.
+    ToggleChatListCompact,
 
     /// ChatWindowNext action.
     ChatWindowNext,
@@ -157,9 +405,85 @@ pub enum Action {
     /// It is used to delete a message only for the current user.
     ChatWindowDeleteForMe,
     /// ChatWindowCopy action.
+    /// It copies the plain text of the selected message.
     ChatWindowCopy,
+    /// ChatWindowCopyFormatted action.
+    /// It copies the selected message with its formatting converted to
+    /// markdown.
+    ChatWindowCopyFormatted,
     /// ChatWindowEdit action.
     ChatWindowEdit,
+    /// ChatWindowNextButton action.
+    /// It selects the next inline keyboard button of the selected message.
+    ChatWindowNextButton,
+    /// ChatWindowPreviousButton action.
+    /// It selects the previous inline keyboard button of the selected message.
+    ChatWindowPreviousButton,
+    /// ChatWindowPressButton action.
+    /// It presses the selected inline keyboard button of the selected message.
+    ChatWindowPressButton,
+    /// ChatWindowOpenThread action.
+    /// It opens the comments of the selected channel post.
+    ChatWindowOpenThread,
+    /// ChatWindowToggleSpoiler action.
+    /// It reveals or obscures the spoilers of the selected message.
+    ChatWindowToggleSpoiler,
+    /// ChatWindowToggleTimestamp action.
+    /// It shows the full date and time of the selected message, or shows it
+    /// in the configured format again.
+    ChatWindowToggleTimestamp,
+    /// ChatWindowDownload action.
+    /// It downloads the media of the selected message, regardless of the
+    /// automatic download policy.
+    ChatWindowDownload,
+    /// ChatWindowOpenMedia action.
+    /// It opens the media of the selected message with the default
+    /// application, for example to play an animation.
+    ChatWindowOpenMedia,
+    /// ChatWindowJumpToLatest action.
+    /// It scrolls the chat to the newest message.
+    ChatWindowJumpToLatest,
+    /// ChatWindowTag action.
+    /// It gives the next configured tag to the selected message.
+    ChatWindowTag,
+    /// ChatWindowUntag action.
+    /// It removes the tag of the selected message.
+    ChatWindowUntag,
+    /// ChatWindowCopyCode action.
+    /// It copies a code block of the selected message, the next one at each
+    /// use.
+    ChatWindowCopyCode,
+    /// ChatWindowReplyPrivately action.
+    /// It replies to the selected group message in the private chat with its
+    /// sender.
+    ChatWindowReplyPrivately,
+    /// ChatWindowEditHistory action.
+    /// It shows the previous versions of the selected edited message.
+    ChatWindowEditHistory,
+    /// ChatWindowReadBy action.
+    /// It shows the users who read the selected outgoing message.
+    ChatWindowReadBy,
+    /// ChatWindowPinnedMessages action.
+    /// It shows the list of the pinned messages of the open chat.
+    ChatWindowPinnedMessages,
+    /// ChatWindowNextMention action.
+    /// It jumps to the next unread mention of the user in the open chat.
+    ChatWindowNextMention,
+    /// ChatWindowOpenViaBot action.
+    /// It opens the chat with the inline bot the selected message was sent
+    /// via.
+    ChatWindowOpenViaBot,
+    /// ChatWindowQuickReaction action.
+    /// It adds the configured quick reaction to the selected message, or
+    /// removes it if it is already added.
+    ChatWindowQuickReaction,
+    /// ChatWindowReport action.
+    /// It reports the selected message, after choosing the reason.
+    ChatWindowReport,
+    /// ChatWindowSenderProfile action.
+    /// It shows the profile of the sender of the selected message, or of the
+    /// user of the open private chat.
+    ChatWindowSenderProfile,
 
     /// EditMessage action with a `String`.
     /// This action is used to edit a message.
@@ -169,6 +493,30 @@ pub enum Action {
     /// This event is used to reply to a message.
     /// The first parameter is the `message_id` and the second parameter is the `text`.
     ReplyMessage(i64, String),
+    /// ReplyPrivately action.
+    /// It opens the private chat with the sender of a group message to reply
+    /// to it there. The parameters are the `user_id` of the sender, the
+    /// `message_id` and the `text` of the message.
+    ReplyPrivately(i64, i64, String),
+    /// OpenPrivateChat action.
+    /// It opens the private chat with a user, creating it if needed, the
+    /// parameter is the `user_id`.
+    OpenPrivateChat(i64),
+    /// SetReaction action.
+    /// It adds or removes an emoji reaction on a message of the open chat.
+    /// The parameters are the `message_id`, the `emoji` and whether the
+    /// reaction is added.
+    SetReaction(i64, String, bool),
+    /// ChooseReaction action.
+    /// It is sent when a reaction is not allowed on a message, to choose
+    /// another one. The parameters are the `message_id`, the `emoji` refused
+    /// and the emojis allowed.
+    ChooseReaction(i64, String, Vec<String>),
+    /// ReplyInOtherChat action.
+    /// It replies in the open chat to a message of another chat. The
+    /// parameters are the `chat_id` and the `message_id` of the replied
+    /// message and its `text`.
+    ReplyInOtherChat(i64, i64, String),
 }
 /// Implement the `Action` enum.
 impl Action {
@@ -198,23 +546,73 @@ impl FromStr for Action {
             "focus_chat" => Ok(Action::FocusComponent(ComponentName::Chat)),
             "focus_prompt" => Ok(Action::FocusComponent(ComponentName::Prompt)),
             "unfocus_component" => Ok(Action::UnfocusComponent),
+            "reconnect" => Ok(Action::Reconnect),
+            "toggle_mouse_capture" => Ok(Action::ToggleMouseCapture),
+            "cycle_title_unread_count" => Ok(Action::CycleTitleUnreadCount),
+            "navigate_back" => Ok(Action::NavigateBack),
+            "navigate_forward" => Ok(Action::NavigateForward),
+            "prompt_send_dice" => Ok(Action::PromptSendDice),
+            "prompt_send_location" => Ok(Action::PromptSendLocation),
+            "lock" => Ok(Action::Lock),
+            "optimize_storage" => Ok(Action::OptimizeStorage),
+            "show_storage_statistics" => Ok(Action::ShowStorageStatistics),
+            "focus_next" => Ok(Action::FocusNext),
+            "focus_previous" => Ok(Action::FocusPrevious),
             "toggle_chat_list" => Ok(Action::ToggleChatList),
             "increase_chat_list_size" => Ok(Action::IncreaseChatListSize),
             "decrease_chat_list_size" => Ok(Action::DecreaseChatListSize),
             "increase_prompt_size" => Ok(Action::IncreasePromptSize),
             "decrease_prompt_size" => Ok(Action::DecreasePromptSize),
+            "toggle_message_tags" => Ok(Action::ToggleMessageTags),
+            "toggle_stickers" => Ok(Action::ToggleStickers),
+            "toggle_online_contacts" => Ok(Action::ToggleOnlineContacts),
+            "toggle_usernames" => Ok(Action::ToggleUsernames),
+            "toggle_resize_mode" => Ok(Action::ToggleResizeMode),
             "chat_list_next" => Ok(Action::ChatListNext),
             "chat_list_previous" => Ok(Action::ChatListPrevious),
             "chat_list_unselect" => Ok(Action::ChatListUnselect),
             "chat_list_open" => Ok(Action::ChatListOpen),
+            "toggle_chat_list_grouping" => Ok(Action::ToggleChatListGrouping),
+            "chat_list_delete_draft" => Ok(Action::ChatListDeleteDraft),
+            "chat_list_refresh" => Ok(Action::ChatListRefresh),
+            "chat_list_copy_username" => Ok(Action::ChatListCopyUsername),
+            "chat_list_quick_jump" => Ok(Action::ChatListQuickJump),
+            "chat_list_toggle_block" => Ok(Action::ChatListToggleBlock),
+            "chat_list_blocked_users" => Ok(Action::ChatListBlockedUsers),
+            "chat_list_read_all" => Ok(Action::ChatListReadAll),
+            "chat_list_read_visible" => Ok(Action::ChatListReadVisible),
+            "chat_list_context_menu" => Ok(Action::ChatListContextMenu),
+            "toggle_chat_list_compact" => Ok(Action::ToggleChatListCompact),
             "chat_window_next" => Ok(Action::ChatWindowNext),
             "chat_window_previous" => Ok(Action::ChatWindowPrevious),
             "chat_window_unselect" => Ok(Action::ChatWindowUnselect),
             "chat_window_delete_for_everyone" => Ok(Action::ChatWindowDeleteForEveryone),
             "chat_window_delete_for_me" => Ok(Action::ChatWindowDeleteForMe),
             "chat_window_copy" => Ok(Action::ChatWindowCopy),
+            "chat_window_copy_formatted" => Ok(Action::ChatWindowCopyFormatted),
             "chat_window_edit" => Ok(Action::ChatWindowEdit),
             "chat_window_reply" => Ok(Action::ShowChatWindowReply),
+            "chat_window_next_button" => Ok(Action::ChatWindowNextButton),
+            "chat_window_previous_button" => Ok(Action::ChatWindowPreviousButton),
+            "chat_window_press_button" => Ok(Action::ChatWindowPressButton),
+            "chat_window_open_thread" => Ok(Action::ChatWindowOpenThread),
+            "chat_window_toggle_spoiler" => Ok(Action::ChatWindowToggleSpoiler),
+            "chat_window_toggle_timestamp" => Ok(Action::ChatWindowToggleTimestamp),
+            "chat_window_download" => Ok(Action::ChatWindowDownload),
+            "chat_window_open_media" => Ok(Action::ChatWindowOpenMedia),
+            "chat_window_jump_to_latest" => Ok(Action::ChatWindowJumpToLatest),
+            "chat_window_tag" => Ok(Action::ChatWindowTag),
+            "chat_window_untag" => Ok(Action::ChatWindowUntag),
+            "chat_window_copy_code" => Ok(Action::ChatWindowCopyCode),
+            "chat_window_reply_privately" => Ok(Action::ChatWindowReplyPrivately),
+            "chat_window_edit_history" => Ok(Action::ChatWindowEditHistory),
+            "chat_window_read_by" => Ok(Action::ChatWindowReadBy),
+            "chat_window_pinned_messages" => Ok(Action::ChatWindowPinnedMessages),
+            "chat_window_next_mention" => Ok(Action::ChatWindowNextMention),
+            "chat_window_open_via_bot" => Ok(Action::ChatWindowOpenViaBot),
+            "chat_window_quick_reaction" => Ok(Action::ChatWindowQuickReaction),
+            "chat_window_report" => Ok(Action::ChatWindowReport),
+            "chat_window_sender_profile" => Ok(Action::ChatWindowSenderProfile),
             _ => Err(AppError::InvalidAction(s.to_string())),
         }
     }
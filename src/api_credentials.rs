@@ -0,0 +1,150 @@
+use std::{fmt, fs, io, io::Write, path::Path};
+
+/// The path of the file where the API credentials entered in the setup wizard
+/// are stored, relative to the `tgt` directory.
+pub const API_CREDENTIALS_FILE: &str = ".data/api_credentials";
+/// The length of an API hash, in hex digits.
+const API_HASH_LEN: usize = 32;
+
+/// `ApiCredentials` are the identifier and the hash of the application used to
+/// access the Telegram API, obtained at https://my.telegram.org.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiCredentials {
+    /// The API ID.
+    pub api_id: i32,
+    /// The API hash.
+    pub api_hash: String,
+}
+
+impl ApiCredentials {
+    /// Parse the API credentials, as written in the configuration or typed
+    /// by the user.
+    ///
+    /// # Arguments
+    /// * `api_id` - The API ID, a positive number.
+    /// * `api_hash` - The API hash, 32 hex digits.
+    ///
+    /// # Returns
+    /// * `Option<ApiCredentials>` - The credentials, or `None` if they are not
+    ///   valid.
+    pub fn parse(api_id: &str, api_hash: &str) -> Option<Self> {
+        let api_id = api_id.trim().parse().ok().filter(|id| *id > 0)?;
+        let api_hash = api_hash.trim().to_lowercase();
+        if !Self::is_valid_hash(&api_hash) {
+            return None;
+        }
+        Some(Self { api_id, api_hash })
+    }
+
+    /// Check whether a string looks like an API hash.
+    ///
+    /// # Arguments
+    /// * `api_hash` - The string to check.
+    pub fn is_valid_hash(api_hash: &str) -> bool {
+        api_hash.len() == API_HASH_LEN && api_hash.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    /// Load the API credentials saved by the setup wizard.
+    /// A missing file is not an error, the wizard has not run yet.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the credentials file.
+    ///
+    /// # Returns
+    /// * `Option<ApiCredentials>` - The saved credentials, if any.
+    pub fn load(path: &Path) -> Option<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let mut lines = content.lines();
+                let credentials = Self::parse(
+                    lines.next().unwrap_or_default(),
+                    lines.next().unwrap_or_default(),
+                );
+                if credentials.is_none() {
+                    tracing::error!("Invalid API credentials file {:?}", path);
+                }
+                credentials
+            }
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    tracing::error!("Failed to read the API credentials {:?}: {e}", path);
+                }
+                None
+            }
+        }
+    }
+
+    /// Save the API credentials, the API ID on the first line and the API
+    /// hash on the second one. On Unix the file is readable only by the
+    /// user.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the credentials file.
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - An Ok result or an error.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // The mode is only used when the file is created.
+            if path.exists() {
+                fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+            }
+        }
+        let mut file = options.open(path)?;
+        writeln!(file, "{}\n{}", self.api_id, self.api_hash)
+    }
+}
+
+/// The API hash is not written to the logs.
+impl fmt::Debug for ApiCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ApiCredentials({}, ..)", self.api_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "a3406de8d171bb422bb6ddf3bbd800e2";
+
+    #[test]
+    fn test_parse_api_credentials() {
+        let credentials = ApiCredentials::parse(" 94575 ", &HASH.to_uppercase()).unwrap();
+        assert_eq!(credentials.api_id, 94575);
+        assert_eq!(credentials.api_hash, HASH);
+        assert_eq!(ApiCredentials::parse("-1", HASH), None);
+        assert_eq!(ApiCredentials::parse("abc", HASH), None);
+        assert_eq!(ApiCredentials::parse("94575", ""), None);
+        assert_eq!(
+            ApiCredentials::parse("94575", &HASH.replace('a', "g")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_api_credentials() {
+        let dir = std::env::temp_dir().join(format!("tgt-api-credentials-{}", std::process::id()));
+        let path = dir.join(API_CREDENTIALS_FILE);
+        assert_eq!(ApiCredentials::load(&path), None);
+
+        let credentials = ApiCredentials::parse("94575", HASH).unwrap();
+        credentials.save(&path).unwrap();
+        assert_eq!(ApiCredentials::load(&path), Some(credentials));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
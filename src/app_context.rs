@@ -5,11 +5,12 @@ use crate::{
         app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
         telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
     },
+    lock_passphrase::LockPassphrase,
     tg::tg_context::TgContext,
 };
 use ratatui::style::Style;
 use std::sync::{atomic::AtomicBool, Arc, Mutex, MutexGuard};
-use std::{io, sync::atomic::Ordering};
+use std::{io, sync::atomic::Ordering, time::Instant};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 /// Generate a function that returns a style based on the theme configuration.
@@ -84,6 +85,14 @@ pub struct AppContext {
     /// A boolean flag that represents whether the application should quit or
     /// not.
     quit: AtomicBool,
+    /// A boolean flag that represents whether the application is locked
+    /// behind the passphrase prompt or not.
+    locked: AtomicBool,
+    /// The instant of the last input of the user, used to lock the
+    /// application after a period of inactivity.
+    last_input: Mutex<Instant>,
+    /// The passphrase that unlocks the application.
+    lock_passphrase: Mutex<LockPassphrase>,
     /// The Telegram context.
     tg_context: Arc<TgContext>,
     /// The CLI arguments for the application.
@@ -115,6 +124,7 @@ impl AppContext {
     ) -> Result<Self, io::Error> {
         let (action_tx, action_rx) = tokio::sync::mpsc::unbounded_channel::<Action>();
         let quit = false;
+        tg_context.set_max_chat_messages(app_config.max_messages_per_chat);
         Ok(Self {
             app_config: Mutex::new(app_config),
             keymap_config: Mutex::new(keymap_config),
@@ -124,10 +134,33 @@ impl AppContext {
             action_rx: Mutex::new(action_rx),
             action_tx: Mutex::new(action_tx),
             quit: AtomicBool::new(quit),
+            locked: AtomicBool::new(false),
+            last_input: Mutex::new(Instant::now()),
+            lock_passphrase: Mutex::new(LockPassphrase::default()),
             tg_context: Arc::new(tg_context),
             cli_args: Mutex::new(cli_args),
         })
     }
+    /// Create a new instance of the `App` struct with the default
+    /// configurations and an empty Telegram context.
+    /// It is used to test the rendering of the components.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `App` struct.
+    #[cfg(test)]
+    pub fn new_default() -> Self {
+        use clap::Parser;
+        Self::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap()
+    }
     /// Get the application configuration.
     /// This function takes the lock on the application configuration and returns
     /// the application configuration.
@@ -214,6 +247,47 @@ impl AppContext {
     pub fn quit_store(&self, value: bool) {
         self.quit.store(value, Ordering::Release);
     }
+    /// Get the locked flag.
+    /// This function returns whether the application is locked behind the
+    /// passphrase prompt.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the locked flag.
+    pub fn locked_acquire(&self) -> bool {
+        self.locked.load(Ordering::Acquire)
+    }
+    /// Set the locked flag.
+    /// This function sets whether the application is locked behind the
+    /// passphrase prompt.
+    pub fn locked_store(&self, value: bool) {
+        self.locked.store(value, Ordering::Release);
+    }
+    /// Record an input of the user, it resets the inactivity period.
+    pub fn touch_input(&self) {
+        *self.last_input.lock().unwrap() = Instant::now();
+    }
+    /// Get the instant of the last input of the user.
+    ///
+    /// # Returns
+    /// * `Instant` - The instant of the last input.
+    pub fn last_input(&self) -> Instant {
+        *self.last_input.lock().unwrap()
+    }
+    /// Get the passphrase that unlocks the application.
+    /// This function takes the lock on the passphrase and returns it.
+    ///
+    /// # Returns
+    /// * `MutexGuard<'_, LockPassphrase>` - The lock passphrase.
+    pub fn lock_passphrase(&self) -> MutexGuard<'_, LockPassphrase> {
+        self.lock_passphrase.lock().unwrap()
+    }
+    /// Set the passphrase that unlocks the application.
+    ///
+    /// # Arguments
+    /// * `lock_passphrase` - The lock passphrase.
+    pub fn set_lock_passphrase(&self, lock_passphrase: LockPassphrase) {
+        *self.lock_passphrase() = lock_passphrase;
+    }
     /// Get the Telegram context.
     /// This function returns the Telegram context.
     /// The Telegram context is a shared resource and the contained variables are
@@ -253,6 +327,13 @@ impl AppContext {
         chat_list,
         item_unread_counter
     );
+    theme_style_generate!(style_chat_list_group_header, chat_list, group_header);
+    theme_style_generate!(style_chat_list_item_draft, chat_list, item_draft);
+    theme_style_generate!(
+        style_chat_list_item_selected_pulse,
+        chat_list,
+        item_selected_pulse
+    );
 
     // ===== CHAT =====
     theme_style_generate!(style_chat, chat, self);
@@ -290,6 +371,34 @@ impl AppContext {
         chat,
         message_other_reply_content
     );
+    theme_style_generate!(
+        style_chat_message_inline_button,
+        chat,
+        message_inline_button
+    );
+    theme_style_generate!(
+        style_chat_message_inline_button_selected,
+        chat,
+        message_inline_button_selected
+    );
+    theme_style_generate!(
+        style_chat_new_messages_indicator,
+        chat,
+        new_messages_indicator
+    );
+    theme_style_generate!(style_chat_message_selected, chat, message_selected);
+    theme_style_generate!(
+        style_chat_message_selected_unfocused,
+        chat,
+        message_selected_unfocused
+    );
+    theme_style_generate!(
+        style_chat_message_forward_origin,
+        chat,
+        message_forward_origin
+    );
+    theme_style_generate!(style_chat_message_keyword, chat, message_keyword);
+    theme_style_generate!(style_chat_message_via_bot, chat, message_via_bot);
 
     // ===== PROMPT =====
     theme_style_generate!(style_prompt, prompt, self);
@@ -304,6 +413,12 @@ impl AppContext {
         prompt,
         message_preview_text
     );
+    theme_style_generate!(style_prompt_char_counter, prompt, char_counter);
+    theme_style_generate!(
+        style_prompt_char_counter_warning,
+        prompt,
+        char_counter_warning
+    );
 
     // ===== REPLY MESSAGE =====
     theme_style_generate!(style_reply_message, reply_message, self);
@@ -335,6 +450,14 @@ impl AppContext {
     );
     theme_style_generate!(style_status_bar_open_chat_text, status_bar, open_chat_text);
     theme_style_generate!(style_status_bar_open_chat_name, status_bar, open_chat_name);
+    theme_style_generate!(style_status_bar_status_message, status_bar, status_message);
+    theme_style_generate!(
+        style_status_bar_connection_state,
+        status_bar,
+        connection_state
+    );
+    theme_style_generate!(style_status_bar_notify_warn, status_bar, notify_warn);
+    theme_style_generate!(style_status_bar_notify_error, status_bar, notify_error);
 
     // ===== TITLE BAR =====
     theme_style_generate!(style_title_bar, title_bar, self);
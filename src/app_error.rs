@@ -26,6 +26,10 @@ pub enum AppError<T> {
     AlreadyBound,
     /// It is an invalid color.
     InvalidColor(String),
+    /// It is an invalid component name.
+    InvalidComponentName(String),
+    /// It is a directory that is not writable.
+    NotWritableDirectory(String),
 }
 impl<T> From<io::Error> for AppError<T> {
     fn from(error: io::Error) -> Self {
@@ -60,6 +64,12 @@ impl<T> Display for AppError<T> {
             Self::InvalidColor(color) => {
                 write!(f, "Invalid color: {}", color)
             }
+            Self::InvalidComponentName(name) => {
+                write!(f, "Invalid component name: {}", name)
+            }
+            Self::NotWritableDirectory(dir) => {
+                write!(f, "Directory is not writable: {}", dir)
+            }
         }
     }
 }
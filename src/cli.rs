@@ -39,6 +39,23 @@ pub struct TelegramCli {
         help = "Send a message to a chat"
     )]
     send_message: Option<Vec<String>>,
+
+    #[arg(
+        short,
+        long,
+        value_name = "CHAT",
+        conflicts_with = "chat_id",
+        help = "Open a chat at launch, by @username or by name"
+    )]
+    chat: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAT_ID",
+        allow_negative_numbers = true,
+        help = "Open a chat at launch, by identifier"
+    )]
+    chat_id: Option<i64>,
 }
 
 impl TelegramCli {
@@ -50,6 +67,14 @@ impl TelegramCli {
     pub fn send_message(&self) -> Option<&Vec<String>> {
         self.send_message.as_ref()
     }
+    /// Get the chat to open at launch, an @username or a chat name.
+    pub fn chat(&self) -> Option<&str> {
+        self.chat.as_deref()
+    }
+    /// Get the identifier of the chat to open at launch.
+    pub fn chat_id(&self) -> Option<i64> {
+        self.chat_id
+    }
 }
 
 // #[derive(Parser, Debug)]
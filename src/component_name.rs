@@ -1,4 +1,8 @@
-use std::fmt::{Display, Formatter, Result};
+use crate::app_error::AppError;
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// `ComponentName` is an enum that represents the name of a component in the
@@ -14,10 +18,26 @@ pub enum ComponentName {
     Prompt,
     /// The reply message window.
     ReplyMessage,
+    /// The overview of the tagged messages.
+    MessageTags,
+    /// The list of the pinned messages of the open chat.
+    PinnedMessages,
+    /// The panel of the contacts online now.
+    OnlineContacts,
+    /// The list of the users blocked by the user.
+    BlockedUsers,
+    /// The profile of a user with its quick actions.
+    UserProfile,
+    /// The stickers of the installed sticker sets.
+    Stickers,
     /// The title bar.
     TitleBar,
     /// The status bar.
     StatusBar,
+    /// The read-only popup shown over the core window.
+    Popup,
+    /// The passphrase prompt shown when the application is locked.
+    LockScreen,
 }
 
 impl Display for ComponentName {
@@ -30,6 +50,29 @@ impl Display for ComponentName {
             ComponentName::TitleBar => write!(f, "Title Bar"),
             ComponentName::StatusBar => write!(f, "Status Bar"),
             ComponentName::ReplyMessage => write!(f, "Reply Message"),
+            ComponentName::MessageTags => write!(f, "Message Tags"),
+            ComponentName::PinnedMessages => write!(f, "Pinned Messages"),
+            ComponentName::OnlineContacts => write!(f, "Online Contacts"),
+            ComponentName::BlockedUsers => write!(f, "Blocked Users"),
+            ComponentName::UserProfile => write!(f, "User Profile"),
+            ComponentName::Stickers => write!(f, "Stickers"),
+            ComponentName::Popup => write!(f, "Popup"),
+            ComponentName::LockScreen => write!(f, "Lock Screen"),
+        }
+    }
+}
+
+/// Implement the `FromStr` trait for `ComponentName`.
+/// Only the components that can be focused can be parsed.
+impl FromStr for ComponentName {
+    type Err = AppError<()>;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "chat_list" => Ok(ComponentName::ChatList),
+            "chat" => Ok(ComponentName::Chat),
+            "prompt" => Ok(ComponentName::Prompt),
+            _ => Err(AppError::InvalidComponentName(s.to_string())),
         }
     }
 }
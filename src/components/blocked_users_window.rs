@@ -0,0 +1,235 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    components::component_traits::{Component, HandleFocus},
+    event::Event,
+    utils::strip_emoji_line,
+};
+use ratatui::{
+    layout::Rect,
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListDirection, ListState},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// `BlockedUsersWindow` is a struct that represents the list of the users
+/// blocked by the user.
+/// It uses the key bindings of the chat list to select a user and to unblock
+/// it.
+pub struct BlockedUsersWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `BlockedUsersWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The identifier and the name of each blocked user, the most recently
+    /// blocked first.
+    blocked_users: Vec<(i64, String)>,
+    /// The state of the list.
+    blocked_users_state: ListState,
+    /// Indicates whether the `BlockedUsersWindow` is focused or not.
+    focused: bool,
+}
+
+impl BlockedUsersWindow {
+    /// Create a new instance of the `BlockedUsersWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `BlockedUsersWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let name = "".to_string();
+        let action_tx = None;
+        let blocked_users = vec![];
+        let blocked_users_state = ListState::default();
+        let focused = false;
+        BlockedUsersWindow {
+            app_context,
+            name,
+            action_tx,
+            blocked_users,
+            blocked_users_state,
+            focused,
+        }
+    }
+    /// Set the name of the `BlockedUsersWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `BlockedUsersWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `BlockedUsersWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Take the blocked users loaded, their names are known once TDLib sent
+    /// them. The selection stays on the same row, within the list.
+    fn refresh(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        let user_ids = tg_context.blocked_users().clone();
+        self.blocked_users = user_ids
+            .into_iter()
+            .map(|user_id| (user_id, tg_context.sender_name(user_id)))
+            .collect();
+        if let Some(i) = self.blocked_users_state.selected() {
+            self.blocked_users_state
+                .select(if self.blocked_users.is_empty() {
+                    None
+                } else {
+                    Some(i.min(self.blocked_users.len() - 1))
+                });
+        }
+    }
+
+    /// Select the next blocked user in the list.
+    fn next(&mut self) {
+        if self.blocked_users.is_empty() {
+            return;
+        }
+        let i = match self.blocked_users_state.selected() {
+            Some(i) if i + 1 < self.blocked_users.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.blocked_users_state.select(Some(i));
+    }
+
+    /// Select the previous blocked user in the list.
+    fn previous(&mut self) {
+        if self.blocked_users.is_empty() {
+            return;
+        }
+        let i = match self.blocked_users_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.blocked_users_state.select(Some(i));
+    }
+
+    /// Unselect the blocked user in the list.
+    fn unselect(&mut self) {
+        self.blocked_users_state.select(None);
+    }
+
+    /// Unblock the selected user, it leaves the list once TDLib confirms.
+    fn unblock_selected(&mut self) {
+        let Some(user_id) = self
+            .blocked_users_state
+            .selected()
+            .and_then(|i| self.blocked_users.get(i))
+            .map(|(user_id, _)| *user_id)
+        else {
+            return;
+        };
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::SetUserBlocked(user_id, false));
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `BlockedUsersWindow` struct.
+/// This trait allows the `BlockedUsersWindow` to be focused or unfocused.
+impl HandleFocus for BlockedUsersWindow {
+    /// Set the `focused` flag for the `BlockedUsersWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.refresh();
+    }
+    /// Set the `focused` flag for the `BlockedUsersWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `BlockedUsersWindow` struct.
+impl Component for BlockedUsersWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ChatListNext => self.next(),
+            Action::ChatListPrevious => self.previous(),
+            Action::ChatListUnselect => self.unselect(),
+            Action::ChatListOpen => self.unblock_selected(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        self.refresh();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            self.app_context.style_chat_list()
+        };
+        let items = self.blocked_users.iter().map(|(_, name)| {
+            Line::from(vec![
+                Span::raw("🚫 "),
+                Span::styled(name.clone(), self.app_context.style_chat_chat_name()),
+            ])
+        });
+        let strip_emoji = self.app_context.app_config().strip_emoji;
+        let items = items.map(|mut line| {
+            if strip_emoji {
+                strip_emoji_line(&mut line);
+            }
+            line
+        });
+        let title = format!("{} ({})", self.name, self.blocked_users.len());
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(style_border_focused)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .title(title);
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_stateful_widget(list, area, &mut self.blocked_users_state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unblock_selected_user() {
+        let app_context = Arc::new(AppContext::new_default());
+        let tg_context = app_context.tg_context();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_blocked_users(vec![30, 10]);
+
+        let mut window = BlockedUsersWindow::new(Arc::clone(&app_context));
+        window.focus();
+        assert_eq!(window.blocked_users.len(), 2);
+
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListOpen);
+        // The unknown users are requested first to show their names.
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok())
+            .any(|event| matches!(event, Event::SetUserBlocked(10, false))));
+
+        // The selection stays in the list once the user is unblocked.
+        tg_context.set_blocked_users(vec![30]);
+        window.refresh();
+        assert_eq!(window.blocked_users_state.selected(), Some(0));
+    }
+}
@@ -1,29 +1,217 @@
-use crate::action::Action;
+use crate::action::{Action, NotifyLevel};
 use crate::app_context::AppContext;
-use crate::component_name::ComponentName::Prompt;
+use crate::component_name::ComponentName::{self, Prompt};
 use crate::components::component_traits::{Component, HandleFocus};
+use crate::components::popup_window::report_choice;
+use crate::configs::custom::app_custom::ScrollAcceleration;
 use crate::event::Event;
 use crate::tg::message_entry::MessageEntry;
+use crate::tg::navigation_history::Position;
+use crate::tg::tg_context::UNKNOWN_USER_NAME;
+use crate::utils::{self, blend_colors, strip_emoji_line};
+use arboard::Clipboard;
+use crossterm::event::KeyCode;
 use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::symbols::border::PLAIN;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::block::{Block, Title};
 use ratatui::widgets::Borders;
-use ratatui::widgets::{List, ListDirection, ListState};
+use ratatui::widgets::{Clear, List, ListDirection, ListState};
 use ratatui::Frame;
-use std::sync::Arc;
-use tdlib_rs::enums::{ChatList, UserStatus};
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tdlib_rs::enums::{ChatList, SecretChatState};
 use tdlib_rs::types::User;
 use tokio::sync::mpsc::UnboundedSender;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The duration of the pulse of the highlight of a newly selected chat.
+const SELECTION_PULSE_DURATION: Duration = Duration::from_millis(250);
+
+/// The longest pause between two moves of the selection for them to be in a
+/// row, the key repeat of a held key is faster.
+const SCROLL_ACCELERATION_WINDOW: Duration = Duration::from_millis(200);
+
+/// The number of moves in a row after which the step of the selection grows.
+const SCROLL_ACCELERATION_MOVES: usize = 5;
+
+/// The colors of the avatars, as in the Telegram clients: red, orange,
+/// violet, green, cyan, blue and pink.
+const AVATAR_COLORS: [Color; 7] = [
+    Color::Rgb(0xe1, 0x70, 0x76),
+    Color::Rgb(0xfa, 0xa7, 0x74),
+    Color::Rgb(0xa6, 0x95, 0xe7),
+    Color::Rgb(0x7b, 0xc8, 0x62),
+    Color::Rgb(0x6e, 0xc9, 0xcb),
+    Color::Rgb(0x65, 0xaa, 0xdd),
+    Color::Rgb(0xee, 0x7a, 0xae),
+];
+
+/// Build the avatar of a chat: the initials of the first two words of its
+/// name on a background color chosen from the chat identifier, so the same
+/// chat always gets the same color.
+///
+/// # Arguments
+/// * `chat_name` - The name of the chat.
+/// * `chat_id` - The identifier of the chat.
+///
+/// # Returns
+/// * `Span<'static>` - The avatar.
+fn avatar_span(chat_name: &str, chat_id: i64) -> Span<'static> {
+    let initials: String = chat_name
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+    let initials = if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    };
+    let color = AVATAR_COLORS[chat_id.rem_euclid(AVATAR_COLORS.len() as i64) as usize];
+    Span::styled(
+        format!(" {:<2}", initials),
+        Style::default()
+            .fg(Color::White)
+            .bg(color)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+/// `ChatCategory` is an enum that represents the category of a chat, it is
+/// used to group the chats in the chat list.
+pub enum ChatCategory {
+    /// A private chat with a user.
+    #[default]
+    Private,
+    /// A basic group or a supergroup.
+    Group,
+    /// A channel.
+    Channel,
+    /// A private chat with a bot.
+    Bot,
+}
+impl Display for ChatCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatCategory::Private => write!(f, "Private"),
+            ChatCategory::Group => write!(f, "Groups"),
+            ChatCategory::Channel => write!(f, "Channels"),
+            ChatCategory::Bot => write!(f, "Bots"),
+        }
+    }
+}
+
+/// `ChatListRow` is an enum that represents a row of the chat list, it is
+/// either the header of a section or a chat.
+#[derive(Debug, Clone, Copy)]
+enum ChatListRow {
+    /// The header of the section of a category.
+    Header(ChatCategory),
+    /// A chat, with its index in the chat list.
+    Chat(usize),
+}
+
+/// `ChatMenuEntry` is an operation of the context menu of a chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChatMenuEntry {
+    /// Open the chat.
+    Open,
+    /// Pin or unpin the chat.
+    TogglePin,
+    /// Mute or unmute the chat.
+    ToggleMute,
+    /// Mark the chat as read, or as unread if it is read.
+    ToggleRead,
+    /// Move the chat to the archive.
+    Archive,
+    /// Delete the chat, after a confirmation.
+    Delete,
+    /// Report the chat, after choosing the reason.
+    Report,
+    /// Show the information about the chat.
+    Info,
+}
+impl ChatMenuEntry {
+    /// The entries of the menu, in order.
+    const ALL: [ChatMenuEntry; 8] = [
+        ChatMenuEntry::Open,
+        ChatMenuEntry::TogglePin,
+        ChatMenuEntry::ToggleMute,
+        ChatMenuEntry::ToggleRead,
+        ChatMenuEntry::Archive,
+        ChatMenuEntry::Delete,
+        ChatMenuEntry::Report,
+        ChatMenuEntry::Info,
+    ];
+
+    /// Get the label of the entry for a chat, the toggles name what they
+    /// do to the chat.
+    ///
+    /// # Arguments
+    /// * `chat` - The chat of the menu.
+    fn label(&self, chat: &ChatListEntry) -> &'static str {
+        match self {
+            ChatMenuEntry::Open => "Open",
+            ChatMenuEntry::TogglePin if chat.is_pinned => "Unpin",
+            ChatMenuEntry::TogglePin => "Pin",
+            ChatMenuEntry::ToggleMute if chat.is_muted => "Unmute",
+            ChatMenuEntry::ToggleMute => "Mute",
+            ChatMenuEntry::ToggleRead if chat.is_unread() => "Mark as read",
+            ChatMenuEntry::ToggleRead => "Mark as unread",
+            ChatMenuEntry::Archive => "Archive",
+            ChatMenuEntry::Delete => "Delete",
+            ChatMenuEntry::Report => "Report",
+            ChatMenuEntry::Info => "Chat info",
+        }
+    }
+}
+
+/// `ChatContextMenu` is the menu of the operations on a chat, shown over the
+/// chat list next to the chat.
+#[derive(Debug)]
+struct ChatContextMenu {
+    /// The identifier of the chat.
+    chat_id: i64,
+    /// The labels of the entries, in the order of `ChatMenuEntry::ALL`.
+    labels: Vec<&'static str>,
+    /// The state of the list of the entries.
+    state: ListState,
+}
 
 #[derive(Debug)]
 pub struct ChatListEntry {
     chat_id: i64,
     chat_name: String,
+    category: ChatCategory,
     last_message: Option<MessageEntry>,
     user: Option<User>,
+    /// The username of the chat, from its user or its supergroup, without
+    /// the `@`.
+    username: Option<String>,
+    /// The state of the secret chat, if the chat is a secret chat.
+    secret_chat_state: Option<SecretChatState>,
+    /// The text of the draft of the chat, it is shown instead of the last
+    /// message.
+    draft: Option<String>,
     is_marked_as_unread: bool,
     unread_count: i32,
+    /// The number of the unread messages mentioning the user.
+    unread_mention_count: i32,
+    /// Indicates whether the chat is in a block list.
+    is_blocked: bool,
+    /// Indicates whether the chat is pinned in the chat list.
+    is_pinned: bool,
+    /// Indicates whether the notifications of the chat are muted.
+    is_muted: bool,
     /// Identifier of the last read incoming message
     last_read_inbox_message_id: Option<i64>,
     /// Identifier of the last read outgoing message
@@ -39,10 +227,18 @@ impl ChatListEntry {
         Self {
             chat_id: 0,
             chat_name: String::new(),
+            category: ChatCategory::default(),
             last_message: None,
             user: None,
+            username: None,
+            secret_chat_state: None,
+            draft: None,
             is_marked_as_unread: false,
             unread_count: 0,
+            unread_mention_count: 0,
+            is_blocked: false,
+            is_pinned: false,
+            is_muted: false,
             last_read_inbox_message_id: None,
             last_read_outbox_message_id: None,
         }
@@ -54,18 +250,42 @@ impl ChatListEntry {
     pub fn set_chat_name(&mut self, chat_name: String) {
         self.chat_name = chat_name;
     }
+    pub fn set_category(&mut self, category: ChatCategory) {
+        self.category = category;
+    }
     pub fn set_last_message(&mut self, last_message: MessageEntry) {
         self.last_message = Some(last_message);
     }
     pub fn set_user(&mut self, user: User) {
         self.user = Some(user);
     }
+    pub fn set_username(&mut self, username: String) {
+        self.username = Some(username);
+    }
+    pub fn set_secret_chat_state(&mut self, secret_chat_state: SecretChatState) {
+        self.secret_chat_state = Some(secret_chat_state);
+    }
+    pub fn set_draft(&mut self, draft: String) {
+        self.draft = Some(draft);
+    }
     pub fn set_is_marked_as_unread(&mut self, is_marked_as_unread: bool) {
         self.is_marked_as_unread = is_marked_as_unread;
     }
     pub fn set_unread_count(&mut self, unread_count: i32) {
         self.unread_count = unread_count;
     }
+    pub fn set_unread_mention_count(&mut self, unread_mention_count: i32) {
+        self.unread_mention_count = unread_mention_count;
+    }
+    pub fn set_is_blocked(&mut self, is_blocked: bool) {
+        self.is_blocked = is_blocked;
+    }
+    pub fn set_is_pinned(&mut self, is_pinned: bool) {
+        self.is_pinned = is_pinned;
+    }
+    pub fn set_is_muted(&mut self, is_muted: bool) {
+        self.is_muted = is_muted;
+    }
     pub fn set_last_read_inbox_message_id(&mut self, last_read_inbox_message_id: i64) {
         self.last_read_inbox_message_id = Some(last_read_inbox_message_id);
     }
@@ -73,18 +293,57 @@ impl ChatListEntry {
         self.last_read_outbox_message_id = Some(last_read_outbox_message_id);
     }
 
-    fn get_text_styled(&self, app_context: &AppContext) -> Text {
+    /// Check whether the chat has unread messages or is marked as unread.
+    fn is_unread(&self) -> bool {
+        self.unread_count > 0 || self.is_marked_as_unread
+    }
+
+    /// Get the lines of the information about the chat.
+    fn info_lines(&self) -> Vec<String> {
+        let yes_no = |value| if value { "yes" } else { "no" };
+        vec![
+            format!("Name: {}", self.chat_name),
+            format!("Type: {}", self.category),
+            format!("ID: {}", self.chat_id),
+            format!(
+                "Username: {}",
+                self.username
+                    .as_ref()
+                    .map_or_else(|| "none".to_string(), |u| format!("@{u}"))
+            ),
+            format!("Unread messages: {}", self.unread_count),
+            format!("Pinned: {}", yes_no(self.is_pinned)),
+            format!("Muted: {}", yes_no(self.is_muted)),
+        ]
+    }
+
+    /// Get a reference to the chat that can be shared, the `@username` of
+    /// the chat or its identifier if it has no username.
+    ///
+    /// # Returns
+    /// * `String` - The reference to the chat.
+    pub fn username_or_id(&self) -> String {
+        self.username
+            .as_ref()
+            .map_or_else(|| self.chat_id.to_string(), |u| format!("@{u}"))
+    }
+
+    /// Get the text of the chat in the chat list.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    /// * `compact` - A boolean that indicates whether the chat is shown on a
+    ///   single line, without the preview of its last message.
+    /// * `width` - The width available for the chat, the name of the chat is
+    ///   truncated so the first line fits in it.
+    ///
+    /// # Returns
+    /// * `Text` - The styled text of the chat.
+    fn get_text_styled(&self, app_context: &AppContext, compact: bool, width: u16) -> Text<'_> {
         let mut online_symbol = "";
         let mut verificated_symbol = "";
         if let Some(user) = &self.user {
-            online_symbol = match user.status {
-                UserStatus::Online(_) => "🟢 ",
-                UserStatus::Offline(_) => "",
-                UserStatus::Empty => "",
-                UserStatus::Recently(_) => "",
-                UserStatus::LastWeek(_) => "",
-                UserStatus::LastMonth(_) => "",
-            };
+            online_symbol = utils::online_symbol(&user.status);
             verificated_symbol = if user.is_verified { "✅" } else { "" };
         }
         let unread_info = if self.is_marked_as_unread {
@@ -92,15 +351,62 @@ impl ChatListEntry {
         } else {
             "".to_string()
         };
+        // The unread mentions are announced by an `@` before the counter.
+        let unread_info = if self.unread_mention_count > 0 {
+            format!("@{unread_info}")
+        } else {
+            unread_info
+        };
+
+        // A chat whose title is not known yet is never shown without a name.
+        let chat_name = if self.chat_name.is_empty() {
+            UNKNOWN_USER_NAME
+        } else {
+            self.chat_name.as_str()
+        };
+        // The secret chats are end-to-end encrypted, they are marked with a
+        // lock and their state is shown until they are ready.
+        let chat_name = match self.secret_chat_state {
+            Some(SecretChatState::Ready) => format!("🔒 {}", chat_name),
+            Some(SecretChatState::Pending) => format!("🔒 {} (pending)", chat_name),
+            Some(SecretChatState::Closed) => format!("🔒 {} (closed)", chat_name),
+            None => chat_name.to_string(),
+        };
+        let chat_name = if self.is_blocked {
+            format!("🚫 {}", chat_name)
+        } else {
+            chat_name
+        };
+
+        // The chat is highlighted while its last message is unread, incoming
+        // and matches one of the keywords.
+        let highlight_keywords = app_context.app_config().chat_list_highlight_keywords;
+        let keywords = app_context.app_config().highlight_keywords.clone();
+        let is_highlighted = highlight_keywords
+            && self.unread_count > 0
+            && self.last_message.as_ref().is_some_and(|m| {
+                m.sender_id() != app_context.tg_context().me() && m.matches_keywords(&keywords)
+            });
+        let style_chat_name = if is_highlighted {
+            app_context
+                .style_chat_list_item_chat_name()
+                .patch(app_context.style_chat_message_keyword())
+        } else {
+            app_context.style_chat_list_item_chat_name()
+        };
 
         let preview_lines = -1;
+        let timestamp_format = app_context.app_config().chat_list_timestamp_format;
         let mut entry = Text::default();
-        entry.extend(vec![Line::from(vec![
+        let mut spans = vec![];
+        if app_context.app_config().chat_list_avatars {
+            spans.push(avatar_span(&self.chat_name, self.chat_id));
+            spans.push(Span::raw(" "));
+        }
+        let name_index = spans.len() + 1;
+        spans.extend([
             Span::raw(online_symbol),
-            Span::styled(
-                self.chat_name.clone(),
-                app_context.style_chat_list_item_chat_name(),
-            ),
+            Span::styled(String::new(), style_chat_name),
             Span::raw(" "),
             Span::styled(
                 unread_info,
@@ -110,15 +416,41 @@ impl ChatListEntry {
             Span::raw(verificated_symbol),
             Span::raw(" | "),
             self.last_message.as_ref().map_or_else(Span::default, |e| {
-                e.timestamp().get_span_styled(app_context)
+                e.timestamp().get_span_styled(app_context, timestamp_format)
             }),
-        ])]);
+        ]);
+        // The name is truncated last, once the width of the rest of the line
+        // is known, so the unread badge and the timestamp are always visible.
+        let other_width = spans.iter().map(Span::width).sum::<usize>();
+        let name_width = (width as usize).saturating_sub(other_width).max(1);
+        spans[name_index].content = truncate_with_ellipsis(&chat_name, name_width).into();
+        entry.extend(vec![Line::from(spans)]);
+        if compact {
+            return entry;
+        }
+        if let Some(draft) = &self.draft {
+            entry.push_line(Line::from(vec![
+                Span::styled("Draft: ", app_context.style_chat_list_item_draft()),
+                Span::styled(
+                    draft.split_whitespace().collect::<Vec<_>>().join(" "),
+                    app_context.style_chat_list_item_message_content(),
+                ),
+            ]));
+            return entry;
+        }
         entry.extend(self.last_message.as_ref().map_or_else(Line::default, |e| {
-            e.get_lines_styled_with_style(
+            let mut line = e.get_lines_styled_with_style(
                 app_context.style_chat_list_item_message_content(),
                 preview_lines,
             )[0]
-            .clone()
+            .clone();
+            if e.is_forwarded() {
+                line.spans.insert(
+                    0,
+                    Span::styled("↪ ", app_context.style_chat_list_item_message_content()),
+                );
+            }
+            line
         }));
 
         entry
@@ -136,8 +468,44 @@ pub struct ChatListWindow {
     command_tx: Option<UnboundedSender<Action>>,
     /// A list of chat items to be displayed in the `ChatListWindow`.
     chat_list: Vec<ChatListEntry>,
+    /// The rows displayed in the `ChatListWindow`, the selection of the list
+    /// refers to these rows.
+    rows: Vec<ChatListRow>,
     /// The state of the list.
     chat_list_state: ListState,
+    /// Indicates whether the chats are grouped by category.
+    grouped: bool,
+    /// Indicates whether each chat is shown on a single line.
+    compact: bool,
+    /// When the current chat was selected, the highlight pulses for a short
+    /// time after it.
+    selected_at: Option<Instant>,
+    /// Indicates whether the selected chat has already been marked as read
+    /// because the selection rested on it.
+    read_on_dwell: bool,
+    /// The chat to select again once the chat list is refreshed, it was
+    /// selected when the refresh started.
+    refresh_selection: Option<i64>,
+    /// The rows shown on screen at the last draw, the quick jump opens one of
+    /// their chats.
+    visible_rows: Range<usize>,
+    /// Indicates whether the quick jump is waiting for the number of the chat
+    /// to open.
+    quick_jump_pending: bool,
+    /// The chat whose user is blocked or unblocked if the key is pressed
+    /// again, right after, to confirm.
+    block_pending: Option<i64>,
+    /// Indicates whether a key has been pressed since the block was asked,
+    /// the block is cancelled if it is not the key of the block.
+    block_key_pressed: bool,
+    /// When the selection last moved and whether it moved down, the moves in
+    /// the same direction without a pause accelerate the selection.
+    scrolled_at: Option<(Instant, bool)>,
+    /// The number of moves of the selection in a row before the last one.
+    scroll_streak: usize,
+    /// The context menu of a chat, when it is open. It takes the keys until
+    /// it is closed.
+    context_menu: Option<ChatContextMenu>,
     /// Indicates whether the `ChatListWindow` is focused or not.
     focused: bool,
 }
@@ -154,7 +522,20 @@ impl ChatListWindow {
         let name = "".to_string();
         let command_tx = None;
         let chat_list = vec![];
+        let rows = vec![];
         let chat_list_state = ListState::default();
+        let grouped = app_context.app_config().chat_list_group_by_category;
+        let compact = app_context.app_config().chat_list_compact;
+        let selected_at = None;
+        let read_on_dwell = false;
+        let refresh_selection = None;
+        let visible_rows = 0..0;
+        let quick_jump_pending = false;
+        let block_pending = None;
+        let block_key_pressed = false;
+        let scrolled_at = None;
+        let scroll_streak = 0;
+        let context_menu = None;
         let focused = false;
 
         ChatListWindow {
@@ -162,7 +543,20 @@ impl ChatListWindow {
             name,
             command_tx,
             chat_list,
+            rows,
             chat_list_state,
+            grouped,
+            compact,
+            selected_at,
+            read_on_dwell,
+            refresh_selection,
+            visible_rows,
+            quick_jump_pending,
+            block_pending,
+            block_key_pressed,
+            scrolled_at,
+            scroll_streak,
+            context_menu,
             focused,
         }
     }
@@ -177,69 +571,621 @@ impl ChatListWindow {
         self.name = name.as_ref().to_string();
         self
     }
-    /// Select the next chat item in the list.
-    fn next(&mut self) {
-        let i = match self.chat_list_state.selected() {
-            Some(i) => {
-                if i == self.chat_list.len() / 2 {
-                    if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                        event_tx
-                            .send(Event::LoadChats(ChatList::Main.into(), 20))
-                            .unwrap();
-                    }
-                }
-
-                if i >= self.chat_list.len() - 1 {
-                    i
-                } else {
-                    i + 1
-                }
+    /// Build the rows of the list from the chat list.
+    /// When the chats are grouped, they are sorted by category keeping the
+    /// order by activity within each category, and a header is added before
+    /// each category.
+    fn update_rows(&mut self) {
+        if !self.grouped {
+            self.rows = (0..self.chat_list.len()).map(ChatListRow::Chat).collect();
+            return;
+        }
+        let mut indexes = (0..self.chat_list.len()).collect::<Vec<usize>>();
+        indexes.sort_by_key(|i| self.chat_list[*i].category);
+        self.rows = Vec::with_capacity(indexes.len());
+        for i in indexes {
+            let category = self.chat_list[i].category;
+            if !matches!(self.rows.last(), Some(ChatListRow::Chat(j)) if self.chat_list[*j].category == category)
+            {
+                self.rows.push(ChatListRow::Header(category));
             }
-            None => 0,
+            self.rows.push(ChatListRow::Chat(i));
+        }
+    }
+    /// Get the chat of a row of the list, if the row is not a header.
+    ///
+    /// # Arguments
+    /// * `row` - The index of the row.
+    fn chat_at_row(&self, row: usize) -> Option<&ChatListEntry> {
+        match self.rows.get(row) {
+            Some(ChatListRow::Chat(i)) => self.chat_list.get(*i),
+            _ => None,
+        }
+    }
+    /// Toggle the grouping of the chats by category.
+    fn toggle_grouping(&mut self) {
+        self.grouped = !self.grouped;
+        self.update_rows();
+        self.chat_list_state.select(None);
+    }
+    /// Toggle the compact mode, where each chat is shown on a single line.
+    /// The selection is kept, only the height of the rows changes.
+    fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+    }
+    /// Select a row of the list and start the pulse of its highlight.
+    ///
+    /// # Arguments
+    /// * `row` - The index of the row.
+    fn select(&mut self, row: usize) {
+        self.refresh_selection = None;
+        if self.chat_list_state.selected() != Some(row) {
+            self.selected_at = Some(Instant::now());
+            self.read_on_dwell = false;
+        }
+        self.chat_list_state.select(Some(row));
+    }
+    /// Get the highlight style of the selected chat. Right after the
+    /// selection it moves from the pulse style to the selected style.
+    ///
+    /// # Arguments
+    /// * `now` - The current instant.
+    fn highlight_style(&self, now: Instant) -> Style {
+        let style_selected = self.app_context.style_chat_list_item_selected();
+        let Some(selected_at) = self.selected_at else {
+            return style_selected;
         };
-        self.chat_list_state.select(Some(i));
+        let elapsed = now.duration_since(selected_at);
+        if elapsed >= SELECTION_PULSE_DURATION || !self.app_context.app_config().selection_pulse {
+            return style_selected;
+        }
+        let style_pulse = self.app_context.style_chat_list_item_selected_pulse();
+        let progress = elapsed.as_secs_f64() / SELECTION_PULSE_DURATION.as_secs_f64();
+        match (style_pulse.bg, style_selected.bg) {
+            (Some(from), Some(to)) => style_selected.bg(blend_colors(from, to, progress)),
+            _ => style_selected,
+        }
     }
-    /// Select the previous chat item in the list.
+    /// Get the chat to mark as read because the selection rested on it for
+    /// `chat_list_read_on_dwell_ms`, if any. The chat is returned only once
+    /// for each selection, and never when `send_read_receipts` is disabled.
+    ///
+    /// # Arguments
+    /// * `now` - The current instant.
+    fn chat_to_read_on_dwell(&mut self, now: Instant) -> Option<i64> {
+        let delay = self.app_context.app_config().chat_list_read_on_dwell_ms;
+        let send_read_receipts = self.app_context.app_config().send_read_receipts;
+        if delay == 0 || self.read_on_dwell || !send_read_receipts {
+            return None;
+        }
+        let selected_at = self.selected_at?;
+        if now.duration_since(selected_at) < Duration::from_millis(delay) {
+            return None;
+        }
+        let chat = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_at_row(i))?;
+        if chat.unread_count == 0 && !chat.is_marked_as_unread {
+            return None;
+        }
+        let chat_id = chat.chat_id;
+        self.read_on_dwell = true;
+        Some(chat_id)
+    }
+    /// Get the number of chats the selection moves by, following
+    /// `chat_list_scroll_acceleration`. The step grows while the selection
+    /// keeps moving in the same direction without a pause.
+    ///
+    /// # Arguments
+    /// * `down` - A boolean that indicates whether the selection moves down.
+    /// * `now` - The current instant.
+    fn scroll_step(&mut self, down: bool, now: Instant) -> usize {
+        let (acceleration, max_step) = {
+            let app_config = self.app_context.app_config();
+            (
+                app_config.chat_list_scroll_acceleration,
+                app_config.chat_list_scroll_acceleration_max_step,
+            )
+        };
+        let in_a_row = self.scrolled_at.is_some_and(|(at, was_down)| {
+            was_down == down && now.duration_since(at) <= SCROLL_ACCELERATION_WINDOW
+        });
+        self.scroll_streak = if in_a_row { self.scroll_streak + 1 } else { 0 };
+        self.scrolled_at = Some((now, down));
+        let level = self.scroll_streak / SCROLL_ACCELERATION_MOVES;
+        let step = match acceleration {
+            ScrollAcceleration::Off => 1,
+            ScrollAcceleration::Linear => level.saturating_add(1),
+            ScrollAcceleration::Exponential => 2usize.saturating_pow(level as u32),
+        };
+        step.min(max_step)
+    }
+    /// Select the next chat item in the list, the headers are skipped.
+    fn next(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let start = self.chat_list_state.selected().map_or(0, |i| i + 1);
+        // More chats are loaded before reaching the end of the list.
+        let distance = self.app_context.app_config().chat_list_load_more_distance;
+        if let Some(i) = self.chat_list_state.selected() {
+            // The chats are requested again only once the previous ones
+            // arrived.
+            let tg_context = self.app_context.tg_context();
+            if i + distance >= self.rows.len()
+                && tg_context.start_loading_chats()
+                && !tg_context.try_send_event(Event::LoadChats(ChatList::Main.into(), 20))
+            {
+                tg_context.set_loading_chats(false);
+            }
+        }
+        if let Some(i) = (start..self.rows.len()).find(|i| self.chat_at_row(*i).is_some()) {
+            self.select(i);
+        }
+    }
+    /// Select the previous chat item in the list, the headers are skipped.
     fn previous(&mut self) {
         let i = match self.chat_list_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    0
-                } else {
-                    i - 1
-                }
-            }
-            None => 0,
+            Some(i) => (0..i).rev().find(|i| self.chat_at_row(*i).is_some()),
+            None => (0..self.rows.len()).find(|i| self.chat_at_row(*i).is_some()),
         };
-        self.chat_list_state.select(Some(i));
+        if let Some(i) = i {
+            self.select(i);
+        }
     }
     /// Unselect the chat item in the list.
     fn unselect(&mut self) {
+        self.refresh_selection = None;
         self.chat_list_state.select(None);
     }
+    /// Reload the chat list from the start. The list is emptied until the
+    /// chats are loaded again, then the chat that was selected is selected
+    /// again.
+    fn refresh(&mut self) {
+        self.refresh_selection = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_at_row(i))
+            .map(|chat| chat.chat_id)
+            .or(self.refresh_selection);
+        self.chat_list.clear();
+        self.rows.clear();
+        self.chat_list_state.select(None);
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::RefreshChats);
+    }
+    /// Select again the chat that was selected when the chat list was
+    /// refreshed, as soon as it is in the list.
+    fn restore_refresh_selection(&mut self) {
+        let Some(chat_id) = self.refresh_selection else {
+            return;
+        };
+        if let Some(row) = (0..self.rows.len())
+            .find(|i| self.chat_at_row(*i).is_some_and(|c| c.chat_id == chat_id))
+        {
+            self.refresh_selection = None;
+            self.chat_list_state.select(Some(row));
+        }
+    }
+    /// Delete the draft of the selected chat, if any.
+    fn delete_selected_draft(&self) {
+        let Some(chat) = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_at_row(i))
+        else {
+            return;
+        };
+        if chat.draft.is_some() {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::DeleteChatDraft(chat.chat_id));
+        } else {
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(
+                    "This chat has no draft".to_string(),
+                    NotifyLevel::Warn,
+                ))
+                .unwrap();
+        }
+    }
+    /// Copy the username of the selected chat, or its identifier if it has
+    /// no username.
+    fn copy_selected_username(&self) {
+        let Some(chat) = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_at_row(i))
+        else {
+            return;
+        };
+        let username_or_id = chat.username_or_id();
+        let notification = match Clipboard::new().and_then(|mut c| c.set_text(&username_or_id)) {
+            Ok(()) => (format!("Copied {username_or_id}"), NotifyLevel::Info),
+            Err(e) => {
+                tracing::error!("Failed to copy {username_or_id}: {e}");
+                (
+                    "Failed to copy to the clipboard".to_string(),
+                    NotifyLevel::Error,
+                )
+            }
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::Notify(notification.0, notification.1))
+            .unwrap();
+    }
     /// Confirm the selection of the chat item in the list.
     fn confirm_selection(&mut self) {
         if let Some(i) = self.chat_list_state.selected() {
-            if let Some(chat) = self.chat_list.get(i) {
+            if let Some(chat) = self.chat_at_row(i) {
+                self.open_chat(chat.chat_id, chat.user.clone());
+            }
+        }
+    }
+    /// Wait for the number of the visible chat to open, the next key is
+    /// taken as the number.
+    fn start_quick_jump(&mut self) {
+        self.quick_jump_pending = true;
+        self.app_context
+            .action_tx()
+            .send(Action::StatusMessage(
+                "Quick jump: press 1-9 to open a chat on screen".to_string(),
+            ))
+            .unwrap();
+    }
+    /// Open the chat with the given number among the chats shown on screen,
+    /// the first one is 1.
+    ///
+    /// # Arguments
+    /// * `number` - The number of the chat, from 1 to 9.
+    fn quick_jump(&mut self, number: usize) {
+        let row = self
+            .visible_rows
+            .clone()
+            .filter(|row| self.chat_at_row(*row).is_some())
+            .nth(number.saturating_sub(1));
+        match row {
+            Some(row) => {
+                self.select(row);
+                self.confirm_selection();
+            }
+            None => self
+                .app_context
+                .action_tx()
+                .send(Action::StatusMessage(format!("No chat {number} on screen")))
+                .unwrap(),
+        }
+    }
+    /// Block or unblock the user of the selected chat. The first press asks
+    /// for a confirmation, pressing the key again right after confirms.
+    fn toggle_block_selected(&mut self) {
+        let Some(chat) = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_at_row(i))
+        else {
+            return;
+        };
+        let Some(user_id) = chat.user.as_ref().map(|user| user.id) else {
+            self.block_pending = None;
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(
+                    "Only the users can be blocked".to_string(),
+                ))
+                .unwrap();
+            return;
+        };
+        let blocked = !chat.is_blocked;
+        if self.block_pending != Some(chat.chat_id) {
+            let message = format!(
+                "Press the key again to {} {}",
+                if blocked { "block" } else { "unblock" },
+                chat.chat_name
+            );
+            self.block_pending = Some(chat.chat_id);
+            self.block_key_pressed = false;
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(message))
+                .unwrap();
+            return;
+        }
+        self.block_pending = None;
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::SetUserBlocked(user_id, blocked));
+    }
+    /// Ask to confirm marking the unread chats as read, in a popup.
+    ///
+    /// # Arguments
+    /// * `visible` - A boolean that indicates whether only the chats shown
+    ///   on screen are read, otherwise all the chats are read.
+    fn read_chats(&self, visible: bool) {
+        let rows = if visible {
+            self.visible_rows.clone()
+        } else {
+            0..self.rows.len()
+        };
+        let chat_ids = rows
+            .filter_map(|row| self.chat_at_row(row))
+            .filter(|chat| chat.unread_count > 0 || chat.is_marked_as_unread)
+            .map(|chat| chat.chat_id)
+            .collect::<Vec<_>>();
+        let action = if chat_ids.is_empty() {
+            Action::StatusMessage("No unread chats".to_string())
+        } else {
+            let (question, confirmed) = if visible {
+                (
+                    format!(
+                        "Mark the {} unread chats on screen as read?",
+                        chat_ids.len()
+                    ),
+                    Action::ReadChats(chat_ids),
+                )
+            } else {
+                (
+                    format!("Mark all the {} unread chats as read?", chat_ids.len()),
+                    Action::ReadChatList,
+                )
+            };
+            Action::ShowConfirm(
+                "Mark as read".to_string(),
+                vec![question],
+                Box::new(confirmed),
+            )
+        };
+        self.app_context.action_tx().send(action).unwrap();
+    }
+    /// Open the context menu of the selected chat.
+    fn open_context_menu(&mut self) {
+        let Some(chat) = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_at_row(i))
+        else {
+            return;
+        };
+        self.context_menu = Some(ChatContextMenu {
+            chat_id: chat.chat_id,
+            labels: ChatMenuEntry::ALL
+                .iter()
+                .map(|entry| entry.label(chat))
+                .collect(),
+            state: ListState::default().with_selected(Some(0)),
+        });
+    }
+    /// Handle a key pressed while the context menu is open: the arrows move
+    /// the selection, Enter or the right arrow run the selected entry, Esc or
+    /// the left arrow close the menu.
+    ///
+    /// # Arguments
+    /// * `key` - The key pressed.
+    fn handle_context_menu_key(&mut self, key: KeyCode) {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return;
+        };
+        let selected = menu.state.selected().unwrap_or(0);
+        match key {
+            KeyCode::Down => {
+                menu.state
+                    .select(Some((selected + 1).min(ChatMenuEntry::ALL.len() - 1)));
+            }
+            KeyCode::Up => menu.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Enter | KeyCode::Right => {
+                let chat_id = menu.chat_id;
+                self.context_menu = None;
+                self.run_context_menu_entry(chat_id, ChatMenuEntry::ALL[selected]);
+            }
+            KeyCode::Esc | KeyCode::Left => self.context_menu = None,
+            _ => {}
+        }
+    }
+    /// Run an entry of the context menu on a chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `entry` - The entry of the menu.
+    fn run_context_menu_entry(&mut self, chat_id: i64, entry: ChatMenuEntry) {
+        let Some(row) = (0..self.rows.len())
+            .find(|i| self.chat_at_row(*i).is_some_and(|c| c.chat_id == chat_id))
+        else {
+            return;
+        };
+        let Some(chat) = self.chat_at_row(row) else {
+            return;
+        };
+        let event = match entry {
+            ChatMenuEntry::Open => {
+                self.select(row);
+                self.confirm_selection();
+                return;
+            }
+            ChatMenuEntry::TogglePin => Event::SetChatPinned(chat_id, !chat.is_pinned),
+            ChatMenuEntry::ToggleMute => Event::SetChatMuted(chat_id, !chat.is_muted),
+            ChatMenuEntry::ToggleRead if chat.is_unread() => Event::ReadChat(chat_id),
+            ChatMenuEntry::ToggleRead => Event::MarkChatAsUnread(chat_id),
+            ChatMenuEntry::Archive => Event::ArchiveChat(chat_id),
+            ChatMenuEntry::Delete => {
+                let question = match chat.category {
+                    ChatCategory::Group | ChatCategory::Channel => {
+                        format!("Leave {} and delete it from the chat list?", chat.chat_name)
+                    }
+                    _ => format!("Delete the history of {} for you?", chat.chat_name),
+                };
                 self.app_context
-                    .tg_context()
-                    .set_open_chat_user(chat.user.clone());
-                self.app_context.tg_context().set_open_chat_id(chat.chat_id);
-                self.app_context.tg_context().clear_open_chat_messages();
+                    .action_tx()
+                    .send(Action::ShowConfirm(
+                        "Delete chat".to_string(),
+                        vec![question],
+                        Box::new(Action::DeleteChat(chat_id)),
+                    ))
+                    .unwrap();
+                return;
+            }
+            ChatMenuEntry::Report => {
+                self.app_context
+                    .action_tx()
+                    .send(report_choice(chat_id, vec![], &chat.chat_name))
+                    .unwrap();
+                return;
+            }
+            ChatMenuEntry::Info => {
                 self.app_context
                     .action_tx()
-                    .send(Action::FocusComponent(Prompt))
+                    .send(Action::ShowPopup(
+                        "Chat info".to_string(),
+                        chat.info_lines(),
+                    ))
                     .unwrap();
+                return;
+            }
+        };
+        self.app_context.tg_context().try_send_event(event);
+    }
+    /// Draw the context menu next to its chat, below it if there is room,
+    /// otherwise above it.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw to.
+    /// * `area` - The area of the chat list.
+    /// * `heights` - The heights of the rows of the list.
+    fn draw_context_menu(&mut self, frame: &mut Frame<'_>, area: Rect, heights: &[usize]) {
+        let Some(row) = self.context_menu.as_ref().and_then(|menu| {
+            (0..self.rows.len()).find(|i| {
+                self.chat_at_row(*i)
+                    .is_some_and(|c| c.chat_id == menu.chat_id)
+            })
+        }) else {
+            self.context_menu = None;
+            return;
+        };
+        let Some(menu) = self.context_menu.as_mut() else {
+            return;
+        };
+        // The rows above the chat on screen, below the top border.
+        let offset = self.chat_list_state.offset();
+        let top = area.y
+            + 1
+            + heights
+                .get(offset..row.max(offset))
+                .map_or(0, |rows| rows.iter().sum::<usize>()) as u16;
+        let bottom = top + heights.get(row).copied().unwrap_or(1) as u16;
+        let width = (menu
+            .labels
+            .iter()
+            .map(|label| label.width())
+            .max()
+            .unwrap_or(0)
+            + 4)
+        .min(frame.area().width as usize) as u16;
+        let height = (menu.labels.len() as u16 + 2).min(frame.area().height);
+        let y = if bottom + height <= frame.area().bottom() {
+            bottom
+        } else {
+            top.saturating_sub(height)
+        };
+        let x = (area.x + 2).min(frame.area().right().saturating_sub(width));
+        let menu_area = Rect::new(x, y, width, height).intersection(frame.area());
+        let list = List::new(menu.labels.iter().map(|label| Line::from(*label)))
+            .block(
+                Block::default()
+                    .border_set(PLAIN)
+                    .borders(Borders::ALL)
+                    .border_style(self.app_context.style_border_component_focused()),
+            )
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected());
+        frame.render_widget(Clear, menu_area);
+        frame.render_stateful_widget(list, menu_area, &mut menu.state);
+    }
+    /// Go back or forward in the history of the visited chats and messages.
+    ///
+    /// # Arguments
+    /// * `back` - A boolean that indicates whether to go back, otherwise the
+    ///   navigation goes forward.
+    fn navigate(&self, back: bool) {
+        let position = if back {
+            self.app_context.tg_context().navigation_history().back()
+        } else {
+            self.app_context.tg_context().navigation_history().forward()
+        };
+        let Some(position) = position else {
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(
+                    if back {
+                        "No previous chat to go back to"
+                    } else {
+                        "No next chat to go forward to"
+                    }
+                    .to_string(),
+                ))
+                .unwrap();
+            return;
+        };
+        self.app_context.tg_context().open_position(position);
+        self.app_context
+            .action_tx()
+            .send(Action::FocusComponent(ComponentName::Chat))
+            .unwrap();
+    }
+    /// Open a chat, load its history and focus the prompt.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `user` - The user of the chat, if it is a private chat.
+    fn open_chat(&self, chat_id: i64, user: Option<User>) {
+        self.app_context
+            .tg_context()
+            .navigation_history()
+            .visit(Position {
+                chat_id,
+                message_id: None,
+            });
+        self.app_context.tg_context().cache_open_chat_messages();
+        self.app_context.tg_context().set_open_chat_user(user);
+        self.app_context.tg_context().set_open_chat_id(chat_id);
+        self.app_context.tg_context().set_open_thread_id(0);
+        // A chat visited recently shows its cached messages at once, only
+        // the messages arrived since are loaded.
+        let cached = self
+            .app_context
+            .tg_context()
+            .restore_cached_chat_messages(chat_id);
+        self.app_context
+            .action_tx()
+            .send(Action::FocusComponent(Prompt))
+            .unwrap();
 
-                if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                    self.app_context.tg_context().set_from_message_id(0);
-                    // Load chat history
-                    event_tx.send(Event::GetChatHistory).unwrap();
+        // Load chat history
+        self.app_context.tg_context().try_send_event(if cached {
+            Event::GetNewerMessages
+        } else {
+            Event::GetChatHistory
+        });
 
-                    // Mark all unread messages as read
-                    event_tx.send(Event::ViewAllMessages).unwrap();
-                }
-            }
+        // Mark all unread messages as read, at once or after
+        // `read_on_open_delay_ms`. Opening a chat drops the pending read of
+        // the previous one.
+        let (send_read_receipts, delay) = {
+            let app_config = self.app_context.app_config();
+            (
+                app_config.send_read_receipts,
+                app_config.read_on_open_delay_ms,
+            )
+        };
+        let pending_read = (send_read_receipts && delay > 0)
+            .then(|| (chat_id, Instant::now() + Duration::from_millis(delay)));
+        self.app_context.tg_context().set_pending_read(pending_read);
+        if send_read_receipts && delay == 0 {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::ViewAllMessages);
         }
     }
 }
@@ -254,6 +1200,7 @@ impl HandleFocus for ChatListWindow {
     /// Set the `focused` flag for the `ChatListWindow`.
     fn unfocus(&mut self) {
         self.focused = false;
+        self.context_menu = None;
     }
 }
 
@@ -265,11 +1212,74 @@ impl Component for ChatListWindow {
     }
 
     fn update(&mut self, action: Action) {
+        // The open context menu takes the keys, the actions bound to them in
+        // the chat list are ignored until it is closed.
+        if self.context_menu.is_some() {
+            match action {
+                Action::Key(key, _) => {
+                    self.handle_context_menu_key(key);
+                    return;
+                }
+                Action::OpenChat(_) | Action::NavigateBack | Action::NavigateForward => {
+                    self.context_menu = None;
+                }
+                _ => return,
+            }
+        }
+        // The key following the quick jump is the number of the chat, any
+        // other key cancels it.
+        if self.quick_jump_pending {
+            if let Action::Key(key, _) = action {
+                self.quick_jump_pending = false;
+                if let KeyCode::Char(c @ '1'..='9') = key {
+                    self.quick_jump(c as usize - '0' as usize);
+                }
+                return;
+            }
+        }
+        // The block is confirmed only by the key pressed right after it.
+        if self.block_pending.is_some() {
+            if let Action::Key(..) = action {
+                if self.block_key_pressed {
+                    self.block_pending = None;
+                }
+                self.block_key_pressed = true;
+            }
+        }
         match action {
-            Action::ChatListNext => self.next(),
-            Action::ChatListPrevious => self.previous(),
+            Action::ChatListQuickJump => self.start_quick_jump(),
+            Action::ChatListToggleBlock => self.toggle_block_selected(),
+            Action::ChatListBlockedUsers => {
+                self.app_context
+                    .tg_context()
+                    .try_send_event(Event::GetBlockedUsers);
+            }
+            Action::ChatListReadAll => self.read_chats(false),
+            Action::ChatListReadVisible => self.read_chats(true),
+            Action::ChatListContextMenu => self.open_context_menu(),
+            Action::ChatListNext => {
+                for _ in 0..self.scroll_step(true, Instant::now()) {
+                    self.next();
+                }
+            }
+            Action::ChatListPrevious => {
+                for _ in 0..self.scroll_step(false, Instant::now()) {
+                    self.previous();
+                }
+            }
             Action::ChatListUnselect => self.unselect(),
             Action::ChatListOpen => self.confirm_selection(),
+            Action::ToggleChatListGrouping => self.toggle_grouping(),
+            Action::ChatListDeleteDraft => self.delete_selected_draft(),
+            Action::ChatListRefresh => self.refresh(),
+            Action::ChatListCopyUsername => self.copy_selected_username(),
+            Action::ToggleChatListCompact => self.toggle_compact(),
+            Action::NavigateBack => self.navigate(true),
+            Action::NavigateForward => self.navigate(false),
+            Action::OpenChat(chat_id) => {
+                let user = self.app_context.tg_context().user_of_private_chat(chat_id);
+                self.open_chat(chat_id, user);
+            }
             _ => {}
         }
     }
@@ -280,28 +1290,671 @@ impl Component for ChatListWindow {
         } else {
             self.app_context.style_chat_list()
         };
-        if let Ok(Some(items)) = self.app_context.tg_context().get_chats_index() {
-            self.chat_list = items;
-        }
-        let items = self
-            .chat_list
-            .iter()
-            .map(|item| item.get_text_styled(&self.app_context));
         let block = Block::default()
             .border_set(PLAIN)
             .border_style(style_border_focused)
             .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
-            .title(Title::from(self.name.as_str()));
+            .title(Title::from(self.name.clone()));
+        // An area too small for the borders, on extreme resizes or when the
+        // chat list is hidden, only gets the borders that fit.
+        if block.inner(area).is_empty() {
+            frame.render_widget(block, area);
+            self.visible_rows = 0..0;
+            return Ok(());
+        }
+
+        if let Ok(Some(items)) = self.app_context.tg_context().get_chats_index() {
+            self.chat_list = items;
+        }
+        self.update_rows();
+        self.restore_refresh_selection();
+        let mut items = self
+            .rows
+            .iter()
+            .map(|row| match row {
+                ChatListRow::Header(category) => Text::from(Line::from(Span::styled(
+                    category.to_string(),
+                    self.app_context.style_chat_list_group_header(),
+                ))),
+                // The list only has the left border.
+                ChatListRow::Chat(i) => self.chat_list[*i].get_text_styled(
+                    &self.app_context,
+                    self.compact,
+                    area.width.saturating_sub(1),
+                ),
+            })
+            .collect::<Vec<_>>();
+        if self.app_context.app_config().strip_emoji {
+            items
+                .iter_mut()
+                .for_each(|item| item.lines.iter_mut().for_each(strip_emoji_line));
+        }
+        let heights = items.iter().map(Text::height).collect::<Vec<_>>();
 
         let list = List::new(items)
             .block(block)
             .style(self.app_context.style_chat_list())
-            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .highlight_style(self.highlight_style(Instant::now()))
             .direction(ListDirection::TopToBottom);
         // .highlight_symbol("➤ ")
         // .repeat_highlight_symbol(true)
 
         frame.render_stateful_widget(list, area, &mut self.chat_list_state);
+
+        // The list has the top and the bottom borders.
+        let mut space = area.height.saturating_sub(2) as usize;
+        let offset = self.chat_list_state.offset();
+        let end = heights
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .find(|(_, height)| match space.checked_sub(**height) {
+                Some(left) => {
+                    space = left;
+                    false
+                }
+                None => true,
+            })
+            .map_or(heights.len(), |(row, _)| row);
+        self.visible_rows = offset..end;
+        if self.context_menu.is_some() {
+            self.draw_context_menu(frame, area, &heights);
+        }
+
+        if let Some(chat_id) = self.chat_to_read_on_dwell(Instant::now()) {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::ReadChat(chat_id));
+        }
         Ok(())
     }
 }
+
+/// Truncate a text to a maximum display width, ending it with an ellipsis
+/// when it is truncated. The text is cut between graphemes, so an emoji or an
+/// accented letter is never split, and the wide characters, like the CJK
+/// ones, take two columns.
+///
+/// # Arguments
+/// * `text` - The text to truncate.
+/// * `max_width` - The maximum display width, including the ellipsis.
+///
+/// # Returns
+/// * `String` - The truncated text.
+fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true) {
+        width += grapheme.width();
+        if width > max_width.saturating_sub(1) {
+            break;
+        }
+        truncated.push_str(grapheme);
+    }
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::Modifiers,
+        tg::{message_entry::DateTimeEntry, tg_context::test_user},
+    };
+    use crossterm::event::KeyModifiers;
+    use tdlib_rs::{enums::UserStatus, types::UserStatusOnline};
+
+    fn user(status: UserStatus, is_verified: bool) -> User {
+        User {
+            is_verified,
+            ..test_user(1, "Alice", status)
+        }
+    }
+
+    fn spans_content(line: &Line) -> Vec<String> {
+        line.spans
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_chat_list_entry_online_verified() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_name("Alice".to_string());
+        entry.set_user(user(
+            UserStatus::Online(UserStatusOnline { expires: 0 }),
+            true,
+        ));
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        let spans = spans_content(&text.lines[0]);
+        assert_eq!(spans[0], "🟢 ");
+        assert_eq!(spans[1], "Alice");
+        assert_eq!(spans[5], "✅");
+        assert_eq!(
+            text.lines[0].spans[1].style,
+            app_context.style_chat_list_item_chat_name()
+        );
+    }
+
+    #[test]
+    fn test_chat_list_entry_offline_unverified() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        entry.set_user(user(UserStatus::Empty, false));
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        let spans = spans_content(&text.lines[0]);
+        assert_eq!(spans[0], "");
+        assert_eq!(spans[1], UNKNOWN_USER_NAME);
+        assert_eq!(spans[5], "");
+    }
+
+    #[test]
+    fn test_chat_list_entry_secret_chat() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_name("Alice".to_string());
+        entry.set_secret_chat_state(SecretChatState::Ready);
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines[0].spans[1].content, "🔒 Alice");
+        entry.set_secret_chat_state(SecretChatState::Closed);
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines[0].spans[1].content, "🔒 Alice (closed)");
+    }
+
+    #[test]
+    fn test_chat_list_entry_unread_badge() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        entry.set_unread_count(3);
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines[0].spans[3].content, "");
+        entry.set_is_marked_as_unread(true);
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines[0].spans[3].content, "(3)");
+        assert_eq!(
+            text.lines[0].spans[3].style,
+            app_context.style_chat_list_item_unread_counter()
+        );
+        entry.set_unread_mention_count(1);
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines[0].spans[3].content, "@(3)");
+    }
+
+    #[test]
+    fn test_chat_list_entry_last_message() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(text.lines[0].spans[7].content, "");
+
+        let timestamp = 1_700_000_000;
+        entry.set_last_message(MessageEntry::new_text(1, "Hello", timestamp));
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(
+            text.lines[0].spans[7].content,
+            DateTimeEntry::convert_time(timestamp, app_context.app_config().timezone)
+        );
+        assert_eq!(text.lines[0].spans[7].style, app_context.style_timestamp());
+        assert_eq!(text.lines[1].to_string(), "Hello");
+
+        entry.set_draft("half\ntyped".to_string());
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines.len(), 2);
+        assert_eq!(text.lines[1].to_string(), "Draft: half typed");
+        assert_eq!(
+            text.lines[1].spans[0].style,
+            app_context.style_chat_list_item_draft()
+        );
+    }
+
+    #[test]
+    fn test_chat_list_entry_compact() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        let timestamp = 1_700_000_000;
+        entry.set_last_message(MessageEntry::new_text(1, "Hello", timestamp));
+        assert_eq!(
+            entry
+                .get_text_styled(&app_context, false, u16::MAX)
+                .lines
+                .len(),
+            2
+        );
+        let text = entry.get_text_styled(&app_context, true, u16::MAX);
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(
+            text.lines[0].spans[7].content,
+            DateTimeEntry::convert_time(timestamp, app_context.app_config().timezone)
+        );
+
+        entry.set_draft("half typed".to_string());
+        assert_eq!(
+            entry
+                .get_text_styled(&app_context, true, u16::MAX)
+                .lines
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        assert_eq!(truncate_with_ellipsis("tgt", 3), "tgt");
+        assert_eq!(truncate_with_ellipsis("telegram", 5), "tele…");
+        assert_eq!(truncate_with_ellipsis("café au lait", 5), "café…");
+        assert_eq!(
+            truncate_with_ellipsis("e\u{301}e\u{301}e\u{301}", 2),
+            "e\u{301}…"
+        );
+        assert_eq!(truncate_with_ellipsis("東京の友達", 5), "東京…");
+        assert_eq!(truncate_with_ellipsis("東京の友達", 4), "東…");
+    }
+
+    #[test]
+    fn test_chat_list_entry_name_truncated() {
+        let app_context = AppContext::new_default();
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_name("A very long name for a chat".to_string());
+        let full = entry.get_text_styled(&app_context, true, u16::MAX);
+        let full_width = full.lines[0].width();
+        let text = entry.get_text_styled(&app_context, true, 20);
+        assert_eq!(text.lines[0].width(), 20);
+        assert!(text.lines[0].spans[1].content.ends_with('…'));
+        assert!(full_width > 20);
+
+        // The wide characters take two columns, the line still fits.
+        for name in ["東京の友達とのグループチャット", "🎉🎉🎉🎉🎉🎉🎉🎉🎉🎉🎉🎉"]
+        {
+            entry.set_chat_name(name.to_string());
+            entry.set_unread_count(3);
+            entry.set_is_marked_as_unread(true);
+            for width in 18..24 {
+                let text = entry.get_text_styled(&app_context, true, width);
+                assert!(text.lines[0].width() <= width as usize);
+                assert!(text.lines[0].width() >= width as usize - 1);
+                assert_eq!(text.lines[0].spans[3].content, "(3)");
+            }
+        }
+    }
+
+    #[test]
+    fn test_username_or_id() {
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_id(-1001234);
+        assert_eq!(entry.username_or_id(), "-1001234");
+        entry.set_username("tgt_chat".to_string());
+        assert_eq!(entry.username_or_id(), "@tgt_chat");
+    }
+
+    #[test]
+    fn test_chat_list_entry_keyword_highlight() {
+        let app_context = AppContext::new_default();
+        app_context.app_config().highlight_keywords = vec!["tgt".to_string()];
+        app_context.tg_context().set_me(1);
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_name("Alice".to_string());
+        entry.set_last_message(MessageEntry::new_text(1, "about tgt", 0));
+        let style_chat_name = app_context.style_chat_list_item_chat_name();
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(text.lines[0].spans[1].style, style_chat_name);
+
+        entry.set_unread_count(1);
+        let text = entry.get_text_styled(&app_context, false, u16::MAX);
+        assert_eq!(
+            text.lines[0].spans[1].style,
+            style_chat_name.patch(app_context.style_chat_message_keyword())
+        );
+    }
+
+    #[test]
+    fn test_selection_pulse() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let style_selected = app_context.style_chat_list_item_selected();
+        assert_eq!(
+            chat_list_window.highlight_style(Instant::now()),
+            style_selected
+        );
+
+        chat_list_window.select(0);
+        let selected_at = chat_list_window.selected_at.unwrap();
+        assert_eq!(
+            chat_list_window.highlight_style(selected_at).bg,
+            app_context.style_chat_list_item_selected_pulse().bg
+        );
+        assert_eq!(
+            chat_list_window.highlight_style(selected_at + SELECTION_PULSE_DURATION),
+            style_selected
+        );
+        // Selecting the same row again does not restart the pulse.
+        chat_list_window.select(0);
+        assert_eq!(chat_list_window.selected_at, Some(selected_at));
+    }
+
+    #[test]
+    fn test_scroll_acceleration() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let start = Instant::now();
+        let steps = |window: &mut ChatListWindow, moves: u64, down: bool| {
+            (0..moves)
+                .map(|i| window.scroll_step(down, start + Duration::from_millis(50 * i)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(steps(&mut chat_list_window, 12, true), vec![1; 12]);
+
+        app_context.app_config().chat_list_scroll_acceleration = ScrollAcceleration::Linear;
+        chat_list_window.scrolled_at = None;
+        assert_eq!(
+            steps(&mut chat_list_window, 12, true),
+            vec![1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 3, 3]
+        );
+        // Changing direction resets the step.
+        assert_eq!(
+            chat_list_window.scroll_step(false, start + Duration::from_millis(600)),
+            1
+        );
+        assert_eq!(
+            chat_list_window.scroll_step(false, start + Duration::from_millis(650)),
+            1
+        );
+        // A pause resets the step.
+        chat_list_window.scroll_streak = 10;
+        assert_eq!(
+            chat_list_window.scroll_step(false, start + Duration::from_secs(1)),
+            1
+        );
+
+        app_context.app_config().chat_list_scroll_acceleration = ScrollAcceleration::Exponential;
+        app_context
+            .app_config()
+            .chat_list_scroll_acceleration_max_step = 3;
+        chat_list_window.scrolled_at = None;
+        assert_eq!(
+            steps(&mut chat_list_window, 16, true)[9..],
+            [2, 3, 3, 3, 3, 3, 3]
+        );
+    }
+
+    #[test]
+    fn test_read_on_dwell() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let mut read = ChatListEntry::new();
+        read.set_chat_id(1);
+        let mut unread = ChatListEntry::new();
+        unread.set_chat_id(2);
+        unread.set_unread_count(3);
+        chat_list_window.chat_list = vec![read, unread];
+        chat_list_window.update_rows();
+
+        chat_list_window.select(1);
+        let selected_at = chat_list_window.selected_at.unwrap();
+        let later = selected_at + Duration::from_secs(10);
+        // It is disabled by default.
+        assert_eq!(chat_list_window.chat_to_read_on_dwell(later), None);
+
+        app_context.app_config().chat_list_read_on_dwell_ms = 1000;
+        // Nor when the read receipts are not sent.
+        app_context.app_config().send_read_receipts = false;
+        assert_eq!(chat_list_window.chat_to_read_on_dwell(later), None);
+        app_context.app_config().send_read_receipts = true;
+        assert_eq!(chat_list_window.chat_to_read_on_dwell(selected_at), None);
+        assert_eq!(chat_list_window.chat_to_read_on_dwell(later), Some(2));
+        // The chat is read only once while it stays selected.
+        assert_eq!(chat_list_window.chat_to_read_on_dwell(later), None);
+
+        chat_list_window.select(0);
+        let later = chat_list_window.selected_at.unwrap() + Duration::from_secs(10);
+        assert_eq!(chat_list_window.chat_to_read_on_dwell(later), None);
+    }
+
+    #[test]
+    fn test_navigate_back_and_forward() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        chat_list_window.update(Action::OpenChat(1));
+        chat_list_window.update(Action::OpenChat(2));
+        assert_eq!(app_context.tg_context().open_chat_id(), 2);
+
+        chat_list_window.update(Action::NavigateBack);
+        assert_eq!(app_context.tg_context().open_chat_id(), 1);
+        chat_list_window.update(Action::NavigateBack);
+        assert_eq!(app_context.tg_context().open_chat_id(), 1);
+        chat_list_window.update(Action::NavigateForward);
+        assert_eq!(app_context.tg_context().open_chat_id(), 2);
+    }
+
+    #[test]
+    fn test_refresh_keeps_selection() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let entry = |chat_id| {
+            let mut entry = ChatListEntry::new();
+            entry.set_chat_id(chat_id);
+            entry
+        };
+        chat_list_window.chat_list = vec![entry(1), entry(2), entry(3)];
+        chat_list_window.update_rows();
+        chat_list_window.select(1);
+
+        chat_list_window.update(Action::ChatListRefresh);
+        assert!(chat_list_window.chat_list.is_empty());
+        assert_eq!(chat_list_window.chat_list_state.selected(), None);
+
+        // The refreshed list has a new order.
+        chat_list_window.chat_list = vec![entry(3)];
+        chat_list_window.update_rows();
+        chat_list_window.restore_refresh_selection();
+        assert_eq!(chat_list_window.chat_list_state.selected(), None);
+        chat_list_window.chat_list = vec![entry(3), entry(1), entry(2)];
+        chat_list_window.update_rows();
+        chat_list_window.restore_refresh_selection();
+        assert_eq!(chat_list_window.chat_list_state.selected(), Some(2));
+        assert_eq!(chat_list_window.refresh_selection, None);
+    }
+
+    #[test]
+    fn test_quick_jump() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let entry = |chat_id| {
+            let mut entry = ChatListEntry::new();
+            entry.set_chat_id(chat_id);
+            entry
+        };
+        chat_list_window.chat_list = vec![entry(1), entry(2), entry(3), entry(4)];
+        chat_list_window.update_rows();
+        // The list is scrolled, the first chat on screen is the second one.
+        chat_list_window.visible_rows = 1..3;
+        let key = |c| Action::Key(KeyCode::Char(c), Modifiers::from(KeyModifiers::NONE));
+
+        // A number without the quick jump does nothing.
+        chat_list_window.update(key('1'));
+        assert_eq!(app_context.tg_context().open_chat_id(), 0);
+
+        chat_list_window.update(Action::ChatListQuickJump);
+        chat_list_window.update(key('2'));
+        assert_eq!(app_context.tg_context().open_chat_id(), 3);
+        assert_eq!(chat_list_window.chat_list_state.selected(), Some(2));
+
+        // The fourth chat is not on screen.
+        chat_list_window.update(Action::ChatListQuickJump);
+        chat_list_window.update(key('3'));
+        assert_eq!(app_context.tg_context().open_chat_id(), 3);
+
+        // Another key cancels the quick jump.
+        chat_list_window.update(Action::ChatListQuickJump);
+        chat_list_window.update(key('x'));
+        chat_list_window.update(key('1'));
+        assert_eq!(app_context.tg_context().open_chat_id(), 3);
+    }
+
+    #[test]
+    fn test_toggle_block_confirmation() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_id(7);
+        let mut blocked_user = user(UserStatus::Empty, false);
+        blocked_user.id = 7;
+        entry.set_user(blocked_user);
+        chat_list_window.chat_list = vec![entry];
+        chat_list_window.update_rows();
+        chat_list_window.select(0);
+        let key = |c| Action::Key(KeyCode::Char(c), Modifiers::from(KeyModifiers::NONE));
+        let press_block = |window: &mut ChatListWindow| {
+            window.update(key('b'));
+            window.update(Action::ChatListToggleBlock);
+        };
+
+        // Another key between the two presses cancels the block.
+        press_block(&mut chat_list_window);
+        chat_list_window.update(key('x'));
+        press_block(&mut chat_list_window);
+        assert!(event_rx.try_recv().is_err());
+
+        press_block(&mut chat_list_window);
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::SetUserBlocked(7, true))
+        ));
+    }
+
+    #[test]
+    fn test_read_chats_asks_confirmation() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        chat_list_window.chat_list = (1..=4)
+            .map(|chat_id| {
+                let mut entry = ChatListEntry::new();
+                entry.set_chat_id(chat_id);
+                entry.set_unread_count(if chat_id == 2 { 0 } else { 1 });
+                entry
+            })
+            .collect();
+        chat_list_window.update_rows();
+        chat_list_window.visible_rows = 0..3;
+
+        chat_list_window.update(Action::ChatListReadVisible);
+        match app_context.action_rx().try_recv() {
+            Ok(Action::ShowConfirm(_, _, action)) => {
+                assert_eq!(*action, Action::ReadChats(vec![1, 3]))
+            }
+            action => panic!("Unexpected action {:?}", action),
+        }
+
+        chat_list_window.update(Action::ChatListReadAll);
+        match app_context.action_rx().try_recv() {
+            Ok(Action::ShowConfirm(_, lines, action)) => {
+                assert_eq!(lines, vec!["Mark all the 3 unread chats as read?"]);
+                assert_eq!(*action, Action::ReadChatList);
+            }
+            action => panic!("Unexpected action {:?}", action),
+        }
+
+        chat_list_window.visible_rows = 1..2;
+        chat_list_window.update(Action::ChatListReadVisible);
+        assert_eq!(
+            app_context.action_rx().try_recv().ok(),
+            Some(Action::StatusMessage("No unread chats".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_context_menu() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let mut entry = ChatListEntry::new();
+        entry.set_chat_id(5);
+        entry.set_is_pinned(true);
+        chat_list_window.chat_list = vec![entry];
+        chat_list_window.update_rows();
+        chat_list_window.select(0);
+        let key = |code| Action::Key(code, Modifiers::from(KeyModifiers::NONE));
+
+        chat_list_window.update(Action::ChatListContextMenu);
+        let menu = chat_list_window.context_menu.as_ref().unwrap();
+        assert_eq!(
+            menu.labels,
+            [
+                "Open",
+                "Unpin",
+                "Mute",
+                "Mark as unread",
+                "Archive",
+                "Delete",
+                "Report",
+                "Chat info"
+            ]
+        );
+        // The actions bound to the keys do not move the selection.
+        chat_list_window.update(key(KeyCode::Down));
+        chat_list_window.update(Action::ChatListNext);
+        chat_list_window.update(key(KeyCode::Enter));
+        assert!(chat_list_window.context_menu.is_none());
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::SetChatPinned(5, false))
+        ));
+
+        chat_list_window.update(Action::ChatListContextMenu);
+        for _ in 0..5 {
+            chat_list_window.update(key(KeyCode::Down));
+        }
+        chat_list_window.update(key(KeyCode::Enter));
+        match app_context.action_rx().try_recv() {
+            Ok(Action::ShowConfirm(_, _, action)) => assert_eq!(*action, Action::DeleteChat(5)),
+            action => panic!("Unexpected action {:?}", action),
+        }
+
+        chat_list_window.update(Action::ChatListContextMenu);
+        chat_list_window.update(key(KeyCode::Esc));
+        assert!(chat_list_window.context_menu.is_none());
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_draw_without_room_for_the_list() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let app_context = Arc::new(AppContext::new_default());
+        let mut chat_list_window = ChatListWindow::new(Arc::clone(&app_context));
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+        for area in [
+            Rect::new(0, 0, 20, 2),
+            Rect::new(0, 0, 20, 1),
+            Rect::new(0, 0, 20, 0),
+            Rect::new(0, 0, 1, 10),
+            Rect::new(0, 0, 0, 10),
+        ] {
+            terminal
+                .draw(|frame| chat_list_window.draw(frame, area).unwrap())
+                .unwrap();
+            assert!(chat_list_window.visible_rows.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_avatar_span() {
+        let avatar = avatar_span("Alice Smith Jones", 1);
+        assert_eq!(avatar.content, " AS");
+        assert_eq!(avatar.style.bg, Some(AVATAR_COLORS[1]));
+        assert_eq!(avatar_span("bob", -1).content, " B ");
+        assert_eq!(avatar_span("bob", -1).style.bg, Some(AVATAR_COLORS[6]));
+        assert_eq!(avatar_span("🎉 party", 0).content, " P ");
+        assert_eq!(avatar_span("", 0).content, " ? ");
+    }
+}
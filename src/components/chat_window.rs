@@ -1,11 +1,17 @@
 use crate::{
-    action::Action,
+    action::{Action, NotifyLevel},
     app_context::AppContext,
-    components::component_traits::{Component, HandleFocus},
+    components::{
+        component_traits::{Component, HandleFocus},
+        popup_window::report_choice,
+        MAX_CHAT_SCROLL_STATES,
+    },
     event::Event,
-    tg::message_entry::MessageEntry,
+    tg::message_entry::{MessageEntry, RevokeWindow},
+    utils::{open_url, strip_emoji, strip_emoji_line},
 };
 use arboard::Clipboard;
+use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     symbols::{
@@ -15,7 +21,12 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListDirection, ListItem, ListState, Paragraph},
 };
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
+use tdlib_rs::enums::{ChatType, InlineKeyboardButtonType};
 use tokio::sync::mpsc::UnboundedSender;
 
 /// `ChatWindow` is a struct that represents a window for displaying a chat.
@@ -31,6 +42,39 @@ pub struct ChatWindow {
     message_list: Vec<MessageEntry>,
     /// The state of the list.
     message_list_state: ListState,
+    /// The index of the selected inline keyboard button of the selected
+    /// message. The buttons are counted row by row.
+    selected_button: Option<usize>,
+    /// The identifier of the chat currently displayed.
+    chat_id: i64,
+    /// The selected message of the recently visited chats, the messages are
+    /// loaded again when a chat is opened, so the position is restored by
+    /// message.
+    scroll_states: HashMap<i64, i64>,
+    /// The recently visited chats, the most recent is at the back. It is used
+    /// to evict the scroll positions of the chats not visited recently.
+    recent_chats: VecDeque<i64>,
+    /// The identifier of the message to select when the `ChatWindow` is
+    /// focused again.
+    restore_selected_message_id: Option<i64>,
+    /// The identifiers of the messages whose spoilers are revealed, they are
+    /// obscured again when the chat is left.
+    revealed_spoilers: HashSet<i64>,
+    /// The identifiers of the messages whose full date and time are shown,
+    /// they are shown in the configured format again when the chat is left.
+    full_timestamps: HashSet<i64>,
+    /// The identifier of the newest message displayed, it is used to detect
+    /// the messages received since the last draw.
+    newest_message_id: Option<i64>,
+    /// The number of messages received while the list is scrolled up, they
+    /// are announced by an indicator instead of scrolling the list.
+    new_messages_count: usize,
+    /// The message and the index of the code block copied last, copying the
+    /// code of the same message again copies its next code block.
+    copied_code_block: Option<(i64, usize)>,
+    /// The message and the reactions offered when the quick reaction is not
+    /// allowed on it, the next key chooses one of them.
+    reaction_choices: Option<(i64, Vec<String>)>,
     /// Indicates whether the `ChatWindow` is focused or not.
     focused: bool,
 }
@@ -48,6 +92,17 @@ impl ChatWindow {
         let action_tx = None;
         let message_list = vec![];
         let message_list_state = ListState::default();
+        let selected_button = None;
+        let chat_id = 0;
+        let scroll_states = HashMap::new();
+        let recent_chats = VecDeque::new();
+        let restore_selected_message_id = None;
+        let revealed_spoilers = HashSet::new();
+        let full_timestamps = HashSet::new();
+        let newest_message_id = None;
+        let new_messages_count = 0;
+        let copied_code_block = None;
+        let reaction_choices = None;
         let focused = false;
         ChatWindow {
             app_context,
@@ -55,6 +110,17 @@ impl ChatWindow {
             action_tx,
             message_list,
             message_list_state,
+            selected_button,
+            chat_id,
+            scroll_states,
+            recent_chats,
+            restore_selected_message_id,
+            revealed_spoilers,
+            full_timestamps,
+            newest_message_id,
+            new_messages_count,
+            copied_code_block,
+            reaction_choices,
             focused,
         }
     }
@@ -70,16 +136,93 @@ impl ChatWindow {
         self
     }
 
+    /// Get the identifier of the selected message item in the list.
+    fn selected_message_id(&self) -> Option<i64> {
+        self.message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+            .map(|message| message.id())
+    }
+
+    /// Get the selected message item in the list, or the selected message of
+    /// its album if it is an album.
+    fn selected_item(&self) -> Option<&MessageEntry> {
+        let message = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))?;
+        Some(
+            self.selected_button
+                .and_then(|i| message.album_item(i))
+                .unwrap_or(message),
+        )
+    }
+
+    /// Save the scroll position of the displayed chat and restore the one of
+    /// the newly opened chat, if it was visited recently.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the newly opened chat.
+    fn switch_chat(&mut self, chat_id: i64) {
+        // The chat 0 is shown before any chat is opened.
+        let selected_message_id = self
+            .selected_message_id()
+            .or(self.restore_selected_message_id)
+            .filter(|_| self.chat_id != 0);
+        if let Some(message_id) = selected_message_id {
+            self.scroll_states.insert(self.chat_id, message_id);
+            self.recent_chats.retain(|id| *id != self.chat_id);
+            self.recent_chats.push_back(self.chat_id);
+            while self.recent_chats.len() > MAX_CHAT_SCROLL_STATES {
+                if let Some(id) = self.recent_chats.pop_front() {
+                    self.scroll_states.remove(&id);
+                }
+            }
+        }
+
+        self.chat_id = chat_id;
+        self.selected_button = None;
+        self.revealed_spoilers.clear();
+        self.full_timestamps.clear();
+        self.newest_message_id = None;
+        self.new_messages_count = 0;
+        self.recent_chats.retain(|id| *id != chat_id);
+        self.message_list_state = ListState::default();
+        self.restore_selected_message_id = self.scroll_states.remove(&chat_id);
+    }
+
+    /// Request the messages older than the loaded ones, unless the beginning
+    /// of the chat is already reached or they are already being loaded.
+    fn load_older_messages(&self) {
+        let tg_context = self.app_context.tg_context();
+        if tg_context.at_oldest() || !tg_context.start_loading_history() {
+            return;
+        }
+        if !tg_context.try_send_event(Event::GetChatHistory) {
+            tg_context.set_loading_history(false);
+        }
+    }
+
+    /// Mark the open chat as read if its read was delayed by
+    /// `read_on_open_delay_ms`, once the delay elapsed or at once when
+    /// `now` is `None`, because the messages are scrolled.
+    ///
+    /// # Arguments
+    /// * `now` - The current instant, `None` to read the chat at once.
+    fn read_pending(&self, now: Option<Instant>) {
+        if self.app_context.tg_context().take_pending_read(now) {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::ViewAllMessages);
+        }
+    }
+
     /// Select the next message item in the list.
     fn next(&mut self) {
+        self.read_pending(None);
+        self.selected_button = None;
         let i = match self.message_list_state.selected() {
             Some(i) => {
-                if i == self.message_list.len() / 2 {
-                    if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                        event_tx.send(Event::GetChatHistory).unwrap();
-                    }
-                }
-
                 if i == 0 {
                     0
                 } else {
@@ -93,12 +236,15 @@ impl ChatWindow {
 
     /// Select the previous message item in the list.
     fn previous(&mut self) {
+        self.read_pending(None);
+        self.selected_button = None;
         let i = match self.message_list_state.selected() {
             Some(i) => {
-                if i == self.message_list.len() / 2 {
-                    if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                        event_tx.send(Event::GetChatHistory).unwrap();
-                    }
+                // The older messages are loaded before reaching the oldest
+                // loaded message.
+                let distance = self.app_context.app_config().chat_load_more_distance;
+                if i + distance >= self.message_list.len() {
+                    self.load_older_messages();
                 }
 
                 if i >= self.message_list.len() - 1 {
@@ -112,41 +258,399 @@ impl ChatWindow {
         self.message_list_state.select(Some(i));
     }
 
+    /// Keep the scrolled list in place when new messages are received, they
+    /// are counted for the new messages indicator. If the list is at the
+    /// bottom, the new messages are simply shown.
+    fn handle_new_messages(&mut self) {
+        let newest_message_id = self.message_list.first().map(|m| m.id());
+        let received = match self.newest_message_id {
+            Some(id) => self
+                .message_list
+                .iter()
+                .position(|m| m.includes(id))
+                .unwrap_or(0),
+            None => 0,
+        };
+        self.newest_message_id = newest_message_id;
+        if received == 0 {
+            return;
+        }
+        let scrolled = self.message_list_state.selected().is_some_and(|i| i > 0)
+            || self.message_list_state.offset() > 0;
+        if scrolled {
+            if let Some(i) = self.message_list_state.selected() {
+                self.message_list_state.select(Some(i + received));
+            }
+            *self.message_list_state.offset_mut() += received;
+            self.new_messages_count += received;
+        }
+    }
+
+    /// Scroll to the newest message and hide the new messages indicator.
+    fn jump_to_latest(&mut self) {
+        self.selected_button = None;
+        self.new_messages_count = 0;
+        self.message_list_state = ListState::default();
+        if self.focused && !self.message_list.is_empty() {
+            self.message_list_state.select(Some(0));
+        }
+    }
+
     /// Unselect the message item in the list.
     fn unselect(&mut self) {
         self.message_list_state.select(None);
+        self.selected_button = None;
     }
 
-    /// Delete the selected message item in the list.
+    /// Select the next or the previous inline keyboard button of the selected
+    /// message item in the list, or the next or the previous media if it is
+    /// an album.
     ///
     /// # Arguments
-    /// * `revoke` - A boolean flag indicating whether the message should be revoked or not.
-    fn delete_selected(&mut self, revoke: bool) {
+    /// * `forward` - A boolean flag indicating whether the next or the
+    ///   previous button should be selected.
+    fn select_button(&mut self, forward: bool) {
         if let Some(selected) = self.message_list_state.selected() {
-            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                let sender_id = self.message_list[selected].sender_id();
-                if sender_id != self.app_context.tg_context().me() {
-                    return;
+            let len = match self.message_list.get(selected) {
+                Some(message) if message.album_len() > 0 => message.album_len(),
+                Some(message) => message.inline_keyboard_len(),
+                None => return,
+            };
+            if len == 0 {
+                return;
+            }
+            self.selected_button = Some(match self.selected_button {
+                Some(i) if forward => (i + 1) % len,
+                Some(i) => (i + len - 1) % len,
+                None if forward => 0,
+                None => len - 1,
+            });
+        }
+    }
+
+    /// Press the selected inline keyboard button of the selected message item
+    /// in the list.
+    /// The callback buttons send a callback query to the bot, while the URL
+    /// buttons are opened with the default application of the system. In an
+    /// album, the selected media is opened.
+    fn press_selected_button(&self) {
+        let (Some(selected), Some(button_index)) =
+            (self.message_list_state.selected(), self.selected_button)
+        else {
+            return;
+        };
+        let Some(message) = self.message_list.get(selected) else {
+            return;
+        };
+        if message.album_len() > 0 {
+            self.open_media_selected();
+            return;
+        }
+        let Some(button) = message.inline_keyboard_button(button_index) else {
+            return;
+        };
+        match &button.r#type {
+            InlineKeyboardButtonType::Callback(callback) => {
+                self.app_context
+                    .tg_context()
+                    .try_send_event(Event::GetCallbackQueryAnswer(
+                        message.id(),
+                        callback.data.clone(),
+                    ));
+            }
+            InlineKeyboardButtonType::Url(url) => {
+                if let Err(e) = open_url(&url.url) {
+                    tracing::error!("Failed to open url {}: {e:?}", url.url);
                 }
-                let message_id = self.message_list[selected].id();
-                event_tx
-                    .send(Event::DeleteMessages(vec![message_id], revoke))
-                    .unwrap();
-                self.app_context.tg_context().delete_message(message_id);
             }
+            _ => {
+                if let Some(action_tx) = self.action_tx.as_ref() {
+                    action_tx
+                        .send(Action::Notify(
+                            format!("The button \"{}\" is not supported", button.text),
+                            NotifyLevel::Warn,
+                        ))
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    /// Reveal the spoilers of the selected message, or obscure them again if
+    /// they are already revealed.
+    fn toggle_spoiler_selected(&mut self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        if !message.has_spoiler() {
+            return;
+        }
+        let message_id = message.id();
+        if !self.revealed_spoilers.remove(&message_id) {
+            self.revealed_spoilers.insert(message_id);
+        }
+    }
+
+    /// Show the full date and time of the selected message, or show it in the
+    /// configured format again.
+    fn toggle_timestamp_selected(&mut self) {
+        let Some(message_id) = self.selected_message_id() else {
+            return;
+        };
+        if !self.full_timestamps.remove(&message_id) {
+            self.full_timestamps.insert(message_id);
+        }
+    }
+
+    /// Download the media of the selected message.
+    fn download_selected(&self) {
+        let Some(message) = self.selected_item() else {
+            return;
+        };
+        if let Some(media_file) = message.media_file() {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::DownloadFile(media_file.file_id));
+        } else if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::Notify(
+                    "This message has no media to download".to_string(),
+                    NotifyLevel::Warn,
+                ))
+                .unwrap();
+        }
+    }
+
+    /// Open the media of the selected message with the default application,
+    /// for example to play an animation.
+    fn open_media_selected(&self) {
+        let Some(message) = self.selected_item() else {
+            return;
+        };
+        if let Some(media_file) = message.media_file() {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::OpenFile(media_file.file_id));
+        } else if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::Notify(
+                    "This message has no media to open".to_string(),
+                    NotifyLevel::Warn,
+                ))
+                .unwrap();
         }
     }
 
+    /// Open the comments of the selected message, if it is a channel post
+    /// with a discussion group.
+    fn open_thread_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        if message.can_get_message_thread() {
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::OpenMessageThread(message.id()));
+        } else if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::Notify(
+                    "This message has no comments".to_string(),
+                    NotifyLevel::Warn,
+                ))
+                .unwrap();
+        }
+    }
+
+    /// Tag the selected message with the next configured tag, or remove its
+    /// tag. The tags are stored locally and listed in the tags overview.
+    ///
+    /// # Arguments
+    /// * `untag` - A boolean flag indicating whether the tag should be removed
+    ///   instead of moving to the next one.
+    fn tag_selected(&self, untag: bool) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        let tg_context = self.app_context.tg_context();
+        let mut message_tags = tg_context.message_tags();
+        let tag = if untag {
+            None
+        } else {
+            self.app_context
+                .app_config()
+                .next_message_tag(message_tags.tag_of(self.chat_id, message.id()))
+        };
+        let status = match tag.as_ref() {
+            Some(tag) => format!("Message tagged as \"{}\"", tag),
+            None => "Message untagged".to_string(),
+        };
+        message_tags.set_tag(
+            self.chat_id,
+            message.id(),
+            tag,
+            &message.message_content_to_plain_text(),
+        );
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx.send(Action::StatusMessage(status)).unwrap();
+        }
+    }
+
+    /// Delete the selected message item in the list, the whole album unless
+    /// one of its media is selected, after confirming. The confirmation
+    /// tells how TDLib allows to delete the message: a message that can no
+    /// longer be deleted for everyone is offered to be deleted for the user
+    /// only, and the one that can only be deleted for everyone is never
+    /// offered to be deleted for the user only.
+    ///
+    /// # Arguments
+    /// * `revoke` - A boolean flag indicating whether the message should be revoked or not.
+    fn delete_selected(&mut self, revoke: bool) {
+        let Some(message) = self.selected_item() else {
+            return;
+        };
+        if message.sender_id() != self.app_context.tg_context().me() {
+            return;
+        }
+        let message_ids = message.message_ids();
+        let tg_context = self.app_context.tg_context();
+        let private = matches!(
+            tg_context
+                .chats()
+                .get(&tg_context.open_chat_id())
+                .map(|chat| &chat.r#type),
+            Some(ChatType::Private(_) | ChatType::Secret(_))
+        );
+        let now = chrono::Utc::now().timestamp();
+        let window = message.revoke_window(tg_context.revoke_time_limit(private), now);
+        let (window_line, revoke) = match window {
+            RevokeWindow::Unlimited => (
+                "It can be deleted for everyone or for you only.".to_string(),
+                revoke,
+            ),
+            RevokeWindow::Left(seconds) => (
+                format!(
+                    "It can be deleted for everyone for {} more.",
+                    duration_text(seconds)
+                ),
+                revoke,
+            ),
+            RevokeWindow::EveryoneOnly => (
+                "It can only be deleted for everyone in this chat.".to_string(),
+                true,
+            ),
+            RevokeWindow::SelfOnly => (
+                "It can no longer be deleted for everyone.".to_string(),
+                false,
+            ),
+            RevokeWindow::Forbidden => {
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        "This message can not be deleted".to_string(),
+                        NotifyLevel::Warn,
+                    ))
+                    .unwrap();
+                return;
+            }
+        };
+        let what = match message_ids.len() {
+            1 => "the message".to_string(),
+            n => format!("the {} messages of the album", n),
+        };
+        let question = if revoke {
+            format!("Delete {} for everyone?", what)
+        } else {
+            format!("Delete {} for you only?", what)
+        };
+        self.selected_button = None;
+        self.app_context
+            .action_tx()
+            .send(Action::ShowConfirm(
+                "Delete message".to_string(),
+                vec![window_line, question],
+                Box::new(Action::DeleteMessages(message_ids, revoke)),
+            ))
+            .unwrap();
+    }
+
     /// Copy the selected message item in the list.
-    fn copy_selected(&self) {
+    ///
+    /// # Arguments
+    /// * `formatted` - A boolean that indicates whether the formatting of the
+    ///   message is kept as markdown, otherwise the plain text is copied.
+    fn copy_selected(&self, formatted: bool) {
         if let Some(selected) = self.message_list_state.selected() {
-            let message = self.message_list[selected].message_content_to_string();
+            let message = if formatted {
+                self.message_list[selected].message_content_to_markdown()
+            } else {
+                self.message_list[selected].message_content_to_plain_text()
+            };
+            let message = if self.app_context.app_config().strip_emoji {
+                strip_emoji(&message)
+            } else {
+                message
+            };
             if let Ok(mut clipboard) = Clipboard::new() {
                 clipboard.set_text(message).unwrap();
             }
         }
     }
 
+    /// Copy a code block of the selected message item in the list, without
+    /// the text around it. Copying again the same message copies its next
+    /// code block.
+    fn copy_code_block_selected(&mut self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        let code_blocks = message.code_blocks();
+        if code_blocks.is_empty() {
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(
+                    "This message has no code block".to_string(),
+                    NotifyLevel::Warn,
+                ))
+                .unwrap();
+            return;
+        }
+        let index = match self.copied_code_block {
+            Some((message_id, index)) if message_id == message.id() => {
+                (index + 1) % code_blocks.len()
+            }
+            _ => 0,
+        };
+        self.copied_code_block = Some((message.id(), index));
+        if let Ok(mut clipboard) = Clipboard::new() {
+            clipboard.set_text(code_blocks[index].clone()).unwrap();
+        }
+        let status = if code_blocks.len() > 1 {
+            format!("Copied code block {}/{}", index + 1, code_blocks.len())
+        } else {
+            "Copied code block".to_string()
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::StatusMessage(status))
+            .unwrap();
+    }
+
     /// Edit the selected message item in the list.
     fn edit_selected(&self) {
         if let Some(selected) = self.message_list_state.selected() {
@@ -156,24 +660,269 @@ impl ChatWindow {
             }
             let message = self.message_list[selected].message_content_to_string();
             let message_id = self.message_list[selected].id();
-            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                event_tx
-                    .send(Event::EditMessage(message_id, message))
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::EditMessage(message_id, message));
+        }
+    }
+
+    /// Reply privately to the selected message, in the private chat with its
+    /// sender. It is only possible for the messages of the other users in a
+    /// group.
+    fn reply_privately_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        let tg_context = self.app_context.tg_context();
+        let is_group = match tg_context.chats().get(&self.chat_id).map(|c| &c.r#type) {
+            Some(ChatType::BasicGroup(_)) => true,
+            Some(ChatType::Supergroup(supergroup)) => !supergroup.is_channel,
+            _ => false,
+        };
+        let warning = match message.sender_user_id() {
+            _ if !is_group => Some("Only the group messages can be replied privately"),
+            Some(user_id) if user_id == tg_context.me() => {
+                Some("You cannot reply privately to your own message")
+            }
+            Some(user_id) => {
+                tg_context.try_send_event(Event::ReplyPrivately(
+                    user_id,
+                    message.id(),
+                    message.message_content_to_string(),
+                ));
+                None
+            }
+            None => Some("This message was not sent by a user"),
+        };
+        if let (Some(warning), Some(action_tx)) = (warning, self.action_tx.as_ref()) {
+            action_tx
+                .send(Action::Notify(warning.to_string(), NotifyLevel::Warn))
+                .unwrap();
+        }
+    }
+
+    /// Show the previous versions of the selected message, if it has been
+    /// edited.
+    fn edit_history_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        if !message.is_edited() {
+            if let Some(action_tx) = self.action_tx.as_ref() {
+                action_tx
+                    .send(Action::Notify(
+                        "This message has not been edited".to_string(),
+                        NotifyLevel::Warn,
+                    ))
+                    .unwrap();
+            }
+            return;
+        }
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::GetMessageEditHistory(message.id()));
+    }
+
+    /// Show the users who read the selected message, only the outgoing
+    /// messages tell who read them.
+    fn read_by_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        if message.sender_id() != self.app_context.tg_context().me() {
+            if let Some(action_tx) = self.action_tx.as_ref() {
+                action_tx
+                    .send(Action::Notify(
+                        "Only your own messages show who read them".to_string(),
+                        NotifyLevel::Warn,
+                    ))
                     .unwrap();
             }
+            return;
+        }
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::GetMessageViewers(message.id()));
+    }
+
+    /// Show the list of the pinned messages of the chat.
+    fn show_pinned_messages(&self) {
+        if self.chat_id == 0 {
+            return;
+        }
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::GetPinnedMessages(self.chat_id));
+    }
+
+    /// Jump to the next unread mention of the chat.
+    fn jump_to_next_mention(&self) {
+        if self.chat_id == 0 {
+            return;
+        }
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::JumpToNextMention(self.chat_id));
+    }
+
+    /// Open the chat with the inline bot the selected message was sent via.
+    fn open_via_bot_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        match message.via_bot_user_id() {
+            Some(bot_user_id) => {
+                self.app_context
+                    .tg_context()
+                    .try_send_event(Event::OpenPrivateChat(bot_user_id));
+            }
+            None => {
+                if let Some(action_tx) = self.action_tx.as_ref() {
+                    action_tx
+                        .send(Action::Notify(
+                            "This message was not sent via a bot".to_string(),
+                            NotifyLevel::Warn,
+                        ))
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    /// Add the quick reaction to the selected message, or remove it if it is
+    /// already added.
+    fn quick_react_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        let emoji = self.app_context.app_config().chat_quick_reaction.clone();
+        if emoji.is_empty() {
+            if let Some(action_tx) = self.action_tx.as_ref() {
+                action_tx
+                    .send(Action::StatusMessage(
+                        "No quick reaction configured".to_string(),
+                    ))
+                    .unwrap();
+            }
+            return;
+        }
+        let chosen = !message.is_reaction_chosen(&emoji);
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::SetReaction(message.id(), emoji, chosen));
+    }
+
+    /// Report the selected message to the moderators of Telegram, the reason
+    /// is chosen in a popup and the report is confirmed.
+    fn report_selected(&self) {
+        let Some(message) = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        else {
+            return;
+        };
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        if message.sender_id() == self.app_context.tg_context().me() {
+            action_tx
+                .send(Action::StatusMessage(
+                    "Your own messages can not be reported".to_string(),
+                ))
+                .unwrap();
+            return;
+        }
+        action_tx
+            .send(report_choice(
+                self.chat_id,
+                vec![message.id()],
+                "the message",
+            ))
+            .unwrap();
+    }
+
+    /// Show the profile of the sender of the selected message, or of the user
+    /// of the open private chat when no message is selected.
+    fn show_sender_profile(&self) {
+        let tg_context = self.app_context.tg_context();
+        let user_id = match self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+        {
+            Some(message) => message.sender_user_id(),
+            None => match tg_context.chats().get(&self.chat_id).map(|c| &c.r#type) {
+                Some(ChatType::Private(private)) => Some(private.user_id),
+                _ => None,
+            },
+        };
+        let action = match user_id {
+            Some(user_id) => Action::GetUserProfile(user_id),
+            None => Action::Notify(
+                "Select a message sent by a user to show the profile".to_string(),
+                NotifyLevel::Warn,
+            ),
+        };
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx.send(action).unwrap();
         }
     }
 
+    /// Offer the reactions allowed on a message, the next key is taken as
+    /// the number of the reaction.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    /// * `emoji` - The reaction refused.
+    /// * `emojis` - The reactions allowed.
+    fn choose_reaction(&mut self, message_id: i64, emoji: &str, mut emojis: Vec<String>) {
+        emojis.truncate(9);
+        let choices = emojis
+            .iter()
+            .enumerate()
+            .map(|(i, emoji)| format!("{} {}", i + 1, emoji))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::StatusMessage(format!(
+                    "The {emoji} reaction is not allowed here, press 1-{} to react with: {choices}",
+                    emojis.len()
+                )))
+                .unwrap();
+        }
+        self.reaction_choices = Some((message_id, emojis));
+    }
+
     /// Reply to the selected message item in the list.
     fn reply_selected(&self) {
         if let Some(selected) = self.message_list_state.selected() {
             let message_id = self.message_list[selected].id();
             let text = self.message_list[selected].message_content_to_string();
-            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                event_tx
-                    .send(Event::ReplyMessage(message_id, text))
-                    .unwrap();
-            }
+            self.app_context
+                .tg_context()
+                .try_send_event(Event::ReplyMessage(message_id, text));
         }
     }
 }
@@ -182,12 +931,29 @@ impl ChatWindow {
 /// This trait allows the `ChatListWindow` to be focused or unfocused.
 impl HandleFocus for ChatWindow {
     /// Set the `focused` flag for the `ChatWindow`.
+    /// It also restores the last selected message of the chat, if any.
     fn focus(&mut self) {
         self.focused = true;
+        if self.message_list_state.selected().is_none() {
+            if let Some(message_id) = self.restore_selected_message_id {
+                let index = self
+                    .message_list
+                    .iter()
+                    .position(|m| m.includes(message_id));
+                if index.is_some() {
+                    self.message_list_state.select(index);
+                    self.restore_selected_message_id = None;
+                }
+            }
+        }
     }
     /// Set the `focused` flag for the `ChatWindow`.
+    /// It also remembers the selected message to restore it on focus.
     fn unfocus(&mut self) {
         self.focused = false;
+        if let Some(message_id) = self.selected_message_id() {
+            self.restore_selected_message_id = Some(message_id);
+        }
     }
 }
 
@@ -199,26 +965,94 @@ impl Component for ChatWindow {
     }
 
     fn update(&mut self, action: Action) {
+        // The key following the reactions offered is the number of the
+        // reaction, any other key cancels it.
+        if let Action::Key(key, _) = action {
+            if let Some((message_id, emojis)) = self.reaction_choices.take() {
+                if let KeyCode::Char(c @ '1'..='9') = key {
+                    if let Some(emoji) = emojis.get(c as usize - '1' as usize) {
+                        self.app_context
+                            .tg_context()
+                            .try_send_event(Event::SetReaction(message_id, emoji.clone(), true));
+                    }
+                }
+                return;
+            }
+        }
         match action {
             Action::ChatWindowNext => self.next(),
             Action::ChatWindowPrevious => self.previous(),
             Action::ChatWindowUnselect => self.unselect(),
             Action::ChatWindowDeleteForEveryone => self.delete_selected(true),
             Action::ChatWindowDeleteForMe => self.delete_selected(false),
-            Action::ChatWindowCopy => self.copy_selected(),
+            Action::ChatWindowCopy => self.copy_selected(false),
+            Action::ChatWindowCopyFormatted => self.copy_selected(true),
             Action::ChatWindowEdit => self.edit_selected(),
             Action::ShowChatWindowReply => self.reply_selected(),
+            Action::ChatWindowNextButton => self.select_button(true),
+            Action::ChatWindowPreviousButton => self.select_button(false),
+            Action::ChatWindowPressButton => self.press_selected_button(),
+            Action::ChatWindowOpenThread => self.open_thread_selected(),
+            Action::ChatWindowToggleSpoiler => self.toggle_spoiler_selected(),
+            Action::ChatWindowToggleTimestamp => self.toggle_timestamp_selected(),
+            Action::ChatWindowDownload => self.download_selected(),
+            Action::ChatWindowOpenMedia => self.open_media_selected(),
+            Action::ChatWindowJumpToLatest => self.jump_to_latest(),
+            Action::ChatWindowTag => self.tag_selected(false),
+            Action::ChatWindowReplyPrivately => self.reply_privately_selected(),
+            Action::ChatWindowEditHistory => self.edit_history_selected(),
+            Action::ChatWindowReadBy => self.read_by_selected(),
+            Action::ChatWindowPinnedMessages => self.show_pinned_messages(),
+            Action::ChatWindowOpenViaBot => self.open_via_bot_selected(),
+            Action::ChatWindowQuickReaction => self.quick_react_selected(),
+            Action::ChatWindowReport => self.report_selected(),
+            Action::ChatWindowSenderProfile => self.show_sender_profile(),
+            Action::ChatWindowNextMention => self.jump_to_next_mention(),
+            Action::ChooseReaction(message_id, emoji, emojis) => {
+                self.choose_reaction(message_id, &emoji, emojis)
+            }
+            Action::ChatWindowUntag => self.tag_selected(true),
+            Action::ChatWindowCopyCode => self.copy_code_block_selected(),
             _ => {}
         }
     }
 
     fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> std::io::Result<()> {
+        let open_chat_id = self.app_context.tg_context().open_chat_id();
+        if open_chat_id != self.chat_id {
+            self.switch_chat(open_chat_id);
+        }
+        self.read_pending(Some(Instant::now()));
+        if let Some(message_id) = self.app_context.tg_context().take_jump_to_message_id() {
+            self.message_list_state = ListState::default();
+            self.restore_selected_message_id = Some(message_id);
+        }
         if !self.focused {
             self.message_list_state.select(None);
+            self.selected_button = None;
         }
 
         self.message_list
             .clone_from(&self.app_context.tg_context().open_chat_messages());
+        self.message_list = MessageEntry::group_albums(std::mem::take(&mut self.message_list));
+        for message in self.message_list.iter_mut() {
+            message.set_spoiler_revealed(self.revealed_spoilers.contains(&message.id()));
+            message.set_full_timestamp(self.full_timestamps.contains(&message.id()));
+        }
+        self.handle_new_messages();
+        // The message to restore may only be loaded after the focus.
+        if self.focused && self.message_list_state.selected().is_none() {
+            if let Some(message_id) = self.restore_selected_message_id {
+                if let Some(index) = self
+                    .message_list
+                    .iter()
+                    .position(|m| m.includes(message_id))
+                {
+                    self.message_list_state.select(Some(index));
+                    self.restore_selected_message_id = None;
+                }
+            }
+        }
 
         let chat_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -240,34 +1074,42 @@ impl Component for ChatWindow {
         let mut is_unread_outbox = true;
         let mut is_unread_inbox = true;
         let wrap_width = (area.width / 2) as i32;
-        let items = self.message_list.iter().map(|message_entry| {
-            let (myself, name_style, content_style, alignment) = if message_entry.sender_id()
-                == self.app_context.tg_context().me()
-            {
-                if message_entry.id() == self.app_context.tg_context().last_read_outbox_message_id()
-                {
-                    is_unread_outbox = false;
-                }
-                (
-                    true,
-                    self.app_context.style_chat_message_myself_name(),
-                    self.app_context.style_chat_message_myself_content(),
-                    Alignment::Right,
-                )
-            } else {
-                if message_entry.id() == self.app_context.tg_context().last_read_inbox_message_id()
-                {
-                    is_unread_inbox = false;
-                }
-                (
-                    false,
-                    self.app_context.style_chat_message_other_name(),
-                    self.app_context.style_chat_message_other_content(),
-                    Alignment::Left,
-                )
-            };
-            return ListItem::new(
-                message_entry
+        let selected = self.message_list_state.selected();
+        // The selection is kept dimmed while the chat is not focused.
+        let selected_unfocused = self.restore_selected_message_id.filter(|_| !self.focused);
+        let style_selected_unfocused = self.app_context.style_chat_message_selected_unfocused();
+        let mut items: Vec<ListItem> = self
+            .message_list
+            .iter()
+            .enumerate()
+            .map(|(i, message_entry)| {
+                let (myself, name_style, content_style, alignment) =
+                    if message_entry.sender_id() == self.app_context.tg_context().me() {
+                        if message_entry.id()
+                            == self.app_context.tg_context().last_read_outbox_message_id()
+                        {
+                            is_unread_outbox = false;
+                        }
+                        (
+                            true,
+                            self.app_context.style_chat_message_myself_name(),
+                            self.app_context.style_chat_message_myself_content(),
+                            Alignment::Right,
+                        )
+                    } else {
+                        if message_entry.id()
+                            == self.app_context.tg_context().last_read_inbox_message_id()
+                        {
+                            is_unread_inbox = false;
+                        }
+                        (
+                            false,
+                            self.app_context.style_chat_message_other_name(),
+                            self.app_context.style_chat_message_other_content(),
+                            Alignment::Left,
+                        )
+                    };
+                let mut text = message_entry
                     .get_text_styled(
                         myself,
                         &self.app_context,
@@ -275,10 +1117,36 @@ impl Component for ChatWindow {
                         name_style,
                         content_style,
                         wrap_width,
+                        if selected == Some(i) {
+                            self.selected_button
+                        } else {
+                            None
+                        },
                     )
-                    .alignment(alignment),
-            );
-        });
+                    .alignment(alignment);
+                if selected_unfocused == Some(message_entry.id()) {
+                    // The spans have their own background, the style is
+                    // patched on each of them and on the rest of the rows.
+                    text.lines
+                        .iter_mut()
+                        .flat_map(|line| line.spans.iter_mut())
+                        .for_each(|span| span.style = span.style.patch(style_selected_unfocused));
+                    ListItem::new(text).style(style_selected_unfocused)
+                } else {
+                    ListItem::new(text)
+                }
+            })
+            .collect();
+        // The list is drawn from the bottom, so the last item is at the top.
+        if self.app_context.tg_context().at_oldest() {
+            items.push(ListItem::new(
+                Line::from(Span::styled(
+                    "Beginning of chat",
+                    self.app_context.style_timestamp(),
+                ))
+                .alignment(Alignment::Center),
+            ));
+        }
 
         let block = Block::new()
             .border_set(border)
@@ -288,7 +1156,7 @@ impl Component for ChatWindow {
         let list = List::new(items)
             .block(block)
             .style(self.app_context.style_chat())
-            .highlight_style(self.app_context.style_item_selected())
+            .highlight_style(self.app_context.style_chat_message_selected())
             .repeat_highlight_symbol(true)
             .direction(ListDirection::BottomToTop);
 
@@ -303,7 +1171,7 @@ impl Component for ChatWindow {
             .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
             .style(self.app_context.style_chat())
             .title(self.name.as_str());
-        let header = Paragraph::new(Line::from(vec![
+        let mut header = Line::from(vec![
             Span::styled(
                 self.app_context
                     .tg_context()
@@ -313,16 +1181,78 @@ impl Component for ChatWindow {
             ),
             Span::raw(" "),
             Span::styled(
-                self.app_context.tg_context().open_chat_user_status(),
+                if self.app_context.tg_context().open_thread_id() != 0 {
+                    "› 💬 Thread".to_string()
+                } else {
+                    String::new()
+                },
+                self.app_context.style_chat_chat_name(),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                self.app_context
+                    .tg_context()
+                    .open_chat_user_status(self.app_context.app_config().timezone),
+                self.app_context.style_timestamp(),
+            ),
+            Span::styled(
+                if self.app_context.tg_context().is_chat_blocked(open_chat_id) {
+                    " 🚫 Blocked"
+                } else {
+                    ""
+                },
                 self.app_context.style_timestamp(),
             ),
-        ]))
-        .block(block_header)
-        .alignment(Alignment::Center);
+            Span::styled(
+                if self.app_context.app_config().send_read_receipts {
+                    ""
+                } else {
+                    " 👁 Receipts off"
+                },
+                self.app_context.style_timestamp(),
+            ),
+        ]);
+        if self.app_context.app_config().strip_emoji {
+            strip_emoji_line(&mut header);
+        }
+        let header = Paragraph::new(header)
+            .block(block_header)
+            .alignment(Alignment::Center);
 
         frame.render_widget(header, chat_layout[0]);
         frame.render_stateful_widget(list, chat_layout[1], &mut self.message_list_state);
 
+        if self.message_list_state.selected() == Some(0) || self.message_list_state.offset() == 0 {
+            self.new_messages_count = 0;
+        }
+        if self.new_messages_count > 0 && chat_layout[1].height > 1 {
+            let indicator = Line::from(Span::styled(
+                format!(" ↓ {} new messages ", self.new_messages_count),
+                self.app_context.style_chat_new_messages_indicator(),
+            ));
+            let width = (indicator.width() as u16).min(chat_layout[1].width);
+            let indicator_area = Rect {
+                x: chat_layout[1].x + (chat_layout[1].width - width) / 2,
+                y: chat_layout[1].bottom() - 1,
+                width,
+                height: 1,
+            };
+            frame.render_widget(indicator, indicator_area);
+        }
+
         Ok(())
     }
 }
+
+/// Get the text of a duration in hours and minutes, like `1 h 05 min`.
+///
+/// # Arguments
+/// * `seconds` - The duration in seconds.
+fn duration_text(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "less than a minute".to_string(),
+        (0, minutes) => format!("{} min", minutes),
+        (hours, minutes) => format!("{} h {:02} min", hours, minutes),
+    }
+}
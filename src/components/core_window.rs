@@ -4,21 +4,37 @@ use crate::{
     app_error::AppError,
     component_name::ComponentName,
     components::{
+        blocked_users_window::BlockedUsersWindow,
         chat_list_window::ChatListWindow,
         chat_window::ChatWindow,
         component_traits::{Component, HandleFocus},
+        message_tags_window::MessageTagsWindow,
+        online_contacts_window::OnlineContactsWindow,
+        pinned_messages_window::PinnedMessagesWindow,
+        popup_window::PopupWindow,
         prompt_window::PromptWindow,
+        stickers_window::StickersWindow,
+        user_profile_window::UserProfileWindow,
     },
-    components::{MAX_CHAT_LIST_SIZE, MAX_PROMPT_SIZE, MIN_CHAT_LIST_SIZE, MIN_PROMPT_SIZE},
-    configs::custom::keymap_custom::ActionBinding,
+    components::{
+        MAX_CHAT_LIST_SIZE, MAX_PROMPT_SIZE, MIN_CHAT_LIST_SIZE, MIN_PROMPT_SIZE,
+        ONLINE_CONTACTS_SIZE,
+    },
+    configs::custom::{app_custom::PromptPosition, keymap_custom::ActionBinding},
     event::Event,
+    utils,
 };
+use crossterm::event::KeyCode;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use std::{collections::HashMap, io, sync::Arc};
+use std::{collections::HashMap, fs, io, path::PathBuf, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::reply_message::ReplyMessage;
 
+/// The path of the file where the layout chosen in the resize mode is saved,
+/// relative to the `tgt` directory.
+const LAYOUT_FILE: &str = ".data/layout";
+
 /// `CoreWindow` is a struct that represents the core window of the application.
 /// It is responsible for managing the layout and rendering of the core window.
 pub struct CoreWindow {
@@ -39,6 +55,16 @@ pub struct CoreWindow {
     size_message_reply: u16,
     /// The size of the chat list component.
     size_chat_list: u16,
+    /// The size of the chat list component to restore when it is shown
+    /// again.
+    size_chat_list_hidden: u16,
+    /// Indicates whether the chat list is hidden because the terminal is
+    /// narrower than the `chat_list_auto_hide_width`.
+    chat_list_auto_hidden: bool,
+    /// Indicates whether the chat list was shown manually while it is hidden
+    /// automatically. It is reset when the terminal is wide again, the
+    /// manual preference (`size_chat_list`) is kept apart.
+    show_chat_list_when_narrow: bool,
     /// The name of the component that currently has focus. It is an optional
     /// value because no component may have focus. The focus is a component
     /// inside the `CoreWindow`.
@@ -47,6 +73,31 @@ pub struct CoreWindow {
     focused: bool,
     /// Indicates whether the reply message should be shown.
     show_reply_message: bool,
+    /// Indicates whether the overview of the tagged messages is shown in
+    /// place of the chat.
+    show_message_tags: bool,
+    /// Indicates whether the list of the pinned messages of the open chat is
+    /// shown in place of the chat.
+    show_pinned_messages: bool,
+    /// Indicates whether the list of the blocked users is shown in place of
+    /// the chat.
+    show_blocked_users: bool,
+    /// Indicates whether the profile of a user is shown in place of the chat.
+    show_user_profile: bool,
+    /// Indicates whether the stickers are shown in place of the chat.
+    show_stickers: bool,
+    /// Indicates whether the panel of the contacts online now is shown below
+    /// the chat list.
+    show_online_contacts: bool,
+    /// Indicates whether the arrow keys resize the components instead of
+    /// being handled by the focused component.
+    resize_mode: bool,
+    /// Indicates whether the popup is shown over the other components, it
+    /// receives the keys until it is closed.
+    show_popup: bool,
+    /// The action sent if the popup shown is a confirmation and it is
+    /// confirmed.
+    popup_confirm: Option<Action>,
 }
 
 impl CoreWindow {
@@ -83,6 +134,48 @@ impl CoreWindow {
                     .with_name(ComponentName::ReplyMessage.to_string())
                     .new_boxed(),
             ),
+            (
+                ComponentName::MessageTags,
+                MessageTagsWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::MessageTags.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::PinnedMessages,
+                PinnedMessagesWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::PinnedMessages.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::BlockedUsers,
+                BlockedUsersWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::BlockedUsers.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::UserProfile,
+                UserProfileWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::UserProfile.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Stickers,
+                StickersWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Stickers.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::OnlineContacts,
+                OnlineContactsWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::OnlineContacts.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Popup,
+                PopupWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Popup.to_string())
+                    .new_boxed(),
+            ),
         ];
 
         let app_context = app_context;
@@ -90,13 +183,32 @@ impl CoreWindow {
         let action_tx = None;
         let components: HashMap<ComponentName, Box<dyn Component>> =
             components_iter.into_iter().collect();
-        let size_prompt = 3;
+        let (size_chat_list, size_prompt) = Self::load_layout().unwrap_or_else(|| {
+            let app_config = app_context.app_config();
+            (
+                app_config.layout_chat_list_size,
+                app_config.layout_prompt_size,
+            )
+        });
+        let size_chat_list = size_chat_list.clamp(MIN_CHAT_LIST_SIZE, MAX_CHAT_LIST_SIZE);
+        let size_chat_list_hidden = size_chat_list;
+        let chat_list_auto_hidden = false;
+        let show_chat_list_when_narrow = false;
+        let size_prompt = size_prompt.clamp(MIN_PROMPT_SIZE, MAX_PROMPT_SIZE);
         let size_message_reply = 2;
-        let size_chat_list = 20;
         let small_area = false;
         let component_focused = None;
         let focused = true;
         let show_reply_message = false;
+        let show_message_tags = false;
+        let show_pinned_messages = false;
+        let show_blocked_users = false;
+        let show_user_profile = false;
+        let show_stickers = false;
+        let show_online_contacts = false;
+        let resize_mode = false;
+        let show_popup = false;
+        let popup_confirm = None;
 
         CoreWindow {
             app_context,
@@ -104,12 +216,24 @@ impl CoreWindow {
             action_tx,
             components,
             size_chat_list,
+            size_chat_list_hidden,
+            chat_list_auto_hidden,
+            show_chat_list_when_narrow,
             size_prompt,
             size_message_reply,
             small_area,
             component_focused,
             focused,
             show_reply_message,
+            show_message_tags,
+            show_pinned_messages,
+            show_blocked_users,
+            show_user_profile,
+            show_stickers,
+            show_online_contacts,
+            resize_mode,
+            show_popup,
+            popup_confirm,
         }
     }
     /// Set the name of the `CoreWindow`.
@@ -133,8 +257,126 @@ impl CoreWindow {
     }
 
     /// Toggle the chat list component.
+    /// While the chat list is hidden automatically, only the chat list of
+    /// the narrow terminal is toggled.
     pub fn toggle_chat_list(&mut self) {
-        self.size_chat_list = if self.size_chat_list == 0 { 20 } else { 0 };
+        if self.chat_list_auto_hidden {
+            self.show_chat_list_when_narrow = !self.show_chat_list_when_narrow;
+        } else if self.size_chat_list == 0 {
+            self.size_chat_list = self.size_chat_list_hidden;
+        } else {
+            self.size_chat_list_hidden = self.size_chat_list;
+            self.size_chat_list = 0;
+        }
+    }
+    /// Check whether the chat list is shown, according to the manual
+    /// preference and to the width of the terminal.
+    fn chat_list_visible(&self) -> bool {
+        if self.chat_list_auto_hidden {
+            self.show_chat_list_when_narrow
+        } else {
+            self.size_chat_list != 0
+        }
+    }
+    /// Hide or show the chat list automatically according to the width of
+    /// the area of the `CoreWindow`.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the area in columns.
+    fn update_chat_list_auto_hide(&mut self, width: u16) {
+        let auto_hide_width = self.app_context.app_config().chat_list_auto_hide_width;
+        let auto_hidden = width < auto_hide_width;
+        if !auto_hidden {
+            self.show_chat_list_when_narrow = false;
+        }
+        self.chat_list_auto_hidden = auto_hidden;
+    }
+    /// Get the constraint of the width of the chat list.
+    fn chat_list_constraint(&self) -> Constraint {
+        if !self.chat_list_visible() {
+            return Constraint::Length(0);
+        }
+        let width = self.app_context.app_config().layout_chat_list_width;
+        if width > 0 {
+            Constraint::Length(width)
+        } else {
+            // When the chat list is shown on a narrow terminal the manual
+            // size may be 0, the size to restore is used instead.
+            Constraint::Percentage(if self.size_chat_list == 0 {
+                self.size_chat_list_hidden
+            } else {
+                self.size_chat_list
+            })
+        }
+    }
+    /// Get the path of the file where the layout is saved.
+    fn layout_path() -> Option<PathBuf> {
+        utils::tgt_dir().ok().map(|dir| dir.join(LAYOUT_FILE))
+    }
+    /// Load the layout saved in the resize mode, if any.
+    ///
+    /// # Returns
+    /// * `Option<(u16, u16)>` - The size of the chat list and the size of the
+    ///   prompt.
+    fn load_layout() -> Option<(u16, u16)> {
+        let content = fs::read_to_string(Self::layout_path()?).ok()?;
+        let mut sizes = content.split_whitespace().map(|size| size.parse().ok());
+        Some((sizes.next()??, sizes.next()??))
+    }
+    /// Save the current layout, it is loaded at the next startup.
+    fn save_layout(&self) {
+        let Some(path) = Self::layout_path() else {
+            return;
+        };
+        let size_chat_list = if self.size_chat_list == 0 {
+            self.size_chat_list_hidden
+        } else {
+            self.size_chat_list
+        };
+        if let Err(e) = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, format!("{} {}\n", size_chat_list, self.size_prompt)))
+        {
+            tracing::error!("Failed to save the layout {:?}: {e}", path);
+        }
+    }
+    /// Enter or leave the resize mode, the layout is saved when leaving it.
+    fn toggle_resize_mode(&mut self) {
+        self.resize_mode = !self.resize_mode;
+        let message = if self.resize_mode {
+            "Resize mode: ←/→ chat list, ↑/↓ prompt, Enter or Esc to save".to_string()
+        } else {
+            self.save_layout();
+            "Layout saved".to_string()
+        };
+        if let Some(tx) = self.action_tx.as_ref() {
+            tx.send(Action::StatusMessage(message))
+                .unwrap_or_else(|_| panic!("Failed to send action StatusMessage from CoreWindow"));
+        }
+    }
+    /// Check whether the prompt is above the chat.
+    fn prompt_at_top(&self) -> bool {
+        self.app_context.app_config().layout_prompt_position == PromptPosition::Top
+    }
+    /// Resize the components with the arrow keys in the resize mode.
+    /// The arrows move the border between the chat and the prompt, so their
+    /// effect on the prompt depends on its position.
+    ///
+    /// # Arguments
+    /// * `key` - The pressed key.
+    fn handle_resize_key(&mut self, key: KeyCode) {
+        let prompt_at_top = self.prompt_at_top();
+        match key {
+            KeyCode::Left => self.decrease_chat_list_size(),
+            KeyCode::Right => self.increase_chat_list_size(),
+            KeyCode::Up if prompt_at_top => self.decrease_size_prompt(),
+            KeyCode::Down if prompt_at_top => self.increase_size_prompt(),
+            KeyCode::Up => self.increase_size_prompt(),
+            KeyCode::Down => self.decrease_size_prompt(),
+            KeyCode::Enter | KeyCode::Esc => self.toggle_resize_mode(),
+            _ => {}
+        }
     }
     /// Increase the size of the chat list component.
     pub fn increase_chat_list_size(&mut self) {
@@ -152,7 +394,7 @@ impl CoreWindow {
     }
     /// Decrease the size of the chat list component.
     pub fn decrease_chat_list_size(&mut self) {
-        if self.size_chat_list == MIN_CHAT_LIST_SIZE {
+        if self.size_chat_list <= MIN_CHAT_LIST_SIZE {
             return;
         }
         self.size_chat_list -= 1;
@@ -164,6 +406,92 @@ impl CoreWindow {
         }
         self.size_prompt -= 1;
     }
+    /// Show the overview of the tagged messages and focus it, or hide it and
+    /// focus the chat.
+    fn toggle_message_tags(&mut self) {
+        let component_name = if self.show_message_tags {
+            ComponentName::Chat
+        } else {
+            ComponentName::MessageTags
+        };
+        if let Some(tx) = self.action_tx.as_ref() {
+            tx.send(Action::FocusComponent(component_name))
+                .unwrap_or_else(|_| panic!("Failed to send action FocusComponent from CoreWindow"));
+        }
+    }
+    /// Show the stickers and focus them, or hide them and focus the prompt.
+    fn toggle_stickers(&mut self) {
+        let component_name = if self.show_stickers {
+            ComponentName::Prompt
+        } else {
+            ComponentName::Stickers
+        };
+        if let Some(tx) = self.action_tx.as_ref() {
+            tx.send(Action::FocusComponent(component_name))
+                .unwrap_or_else(|_| panic!("Failed to send action FocusComponent from CoreWindow"));
+        }
+    }
+    /// Show the panel of the contacts online now and focus it, or hide it and
+    /// focus the chat list.
+    fn toggle_online_contacts(&mut self) {
+        self.show_online_contacts = !self.show_online_contacts;
+        let component_name = if self.show_online_contacts {
+            ComponentName::OnlineContacts
+        } else {
+            ComponentName::ChatList
+        };
+        if let Some(tx) = self.action_tx.as_ref() {
+            tx.send(Action::FocusComponent(component_name))
+                .unwrap_or_else(|_| panic!("Failed to send action FocusComponent from CoreWindow"));
+        }
+    }
+    /// Get the components that can be focused when cycling the focus.
+    /// The order is taken from the application configuration and the hidden
+    /// components are skipped. When the prompt is above the chat, the chat
+    /// and the prompt are swapped to follow their order on the screen.
+    ///
+    /// # Returns
+    /// * `Vec<ComponentName>` - The components in the focus order.
+    fn focus_cycle(&self) -> Vec<ComponentName> {
+        let prompt_at_top = self.prompt_at_top();
+        self.app_context
+            .app_config()
+            .focus_order
+            .iter()
+            .filter(|name| **name != ComponentName::ChatList || self.chat_list_visible())
+            .map(|name| match name {
+                ComponentName::Chat if prompt_at_top => ComponentName::Prompt,
+                ComponentName::Prompt if prompt_at_top => ComponentName::Chat,
+                name => *name,
+            })
+            .collect()
+    }
+    /// Move the focus to the next or previous component in the focus order.
+    /// If no component is focused, the first (or the last when moving
+    /// backwards) component of the focus order is focused.
+    ///
+    /// # Arguments
+    /// * `forward` - A flag indicating whether the focus should move forward.
+    fn cycle_focus(&mut self, forward: bool) {
+        let cycle = self.focus_cycle();
+        if cycle.is_empty() {
+            return;
+        }
+        let len = cycle.len();
+        let next = match self
+            .component_focused
+            .and_then(|focused| cycle.iter().position(|name| *name == focused))
+        {
+            Some(i) if forward => (i + 1) % len,
+            Some(i) => (i + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+        if let Some(tx) = self.action_tx.as_ref() {
+            tx.send(Action::FocusComponent(cycle[next]))
+                .unwrap_or_else(|_| panic!("Failed to send action FocusComponent from CoreWindow"));
+        }
+    }
 }
 /// Implement the `HandleFocus` trait for the `CoreWindow` struct.
 /// This trait allows the `CoreWindow` to be focused or unfocused.
@@ -189,6 +517,10 @@ impl Component for CoreWindow {
     }
 
     fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>, AppError<Action>> {
+        // The keys are used to resize the components or by the popup.
+        if self.resize_mode || self.show_popup {
+            return Ok(Some(Action::Unknown));
+        }
         let binding = self.app_context.keymap_config();
         let map = binding.get_map_of(self.component_focused);
         if let Some(action_binding) = map.get(&event.unwrap()) {
@@ -206,9 +538,76 @@ impl Component for CoreWindow {
     }
 
     fn update(&mut self, action: Action) {
+        if self.resize_mode {
+            if let Action::Key(key, _) = action {
+                self.handle_resize_key(key);
+                return;
+            }
+        }
+        if self.show_popup {
+            if let Action::Key(key, _) = action {
+                match key {
+                    KeyCode::Enter | KeyCode::Char('y') if self.popup_confirm.is_some() => {
+                        self.show_popup = false;
+                        if let (Some(action), Some(tx)) =
+                            (self.popup_confirm.take(), self.action_tx.as_ref())
+                        {
+                            tx.send(action).unwrap_or_else(|_| {
+                                panic!("Failed to send the confirmed action from CoreWindow")
+                            });
+                        }
+                    }
+                    KeyCode::Char('n') if self.popup_confirm.is_some() => {
+                        self.show_popup = false;
+                        self.popup_confirm = None;
+                    }
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                        self.show_popup = false;
+                        self.popup_confirm = None;
+                        // The popup sends the entry chosen with Enter, if any.
+                        if key == KeyCode::Enter {
+                            self.components
+                                .get_mut(&ComponentName::Popup)
+                                .unwrap_or_else(|| {
+                                    panic!("Failed to get component: {}", ComponentName::Popup)
+                                })
+                                .update(action);
+                        }
+                    }
+                    _ => self
+                        .components
+                        .get_mut(&ComponentName::Popup)
+                        .unwrap_or_else(|| {
+                            panic!("Failed to get component: {}", ComponentName::Popup)
+                        })
+                        .update(action),
+                }
+                return;
+            }
+        }
         match action {
+            Action::ShowPopup(..) | Action::ShowConfirm(..) | Action::ShowChoice(..) => {
+                self.show_popup = true;
+                self.popup_confirm = match &action {
+                    Action::ShowConfirm(_, _, confirm) => Some(*confirm.clone()),
+                    _ => None,
+                };
+                self.components
+                    .get_mut(&ComponentName::Popup)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Popup))
+                    .update(action);
+                return;
+            }
+            Action::ToggleResizeMode => {
+                self.toggle_resize_mode();
+            }
             Action::FocusComponent(component_name) => {
                 self.component_focused = Some(component_name);
+                self.show_message_tags = component_name == ComponentName::MessageTags;
+                self.show_pinned_messages = component_name == ComponentName::PinnedMessages;
+                self.show_blocked_users = component_name == ComponentName::BlockedUsers;
+                self.show_user_profile = component_name == ComponentName::UserProfile;
+                self.show_stickers = component_name == ComponentName::Stickers;
                 self.components
                     .get_mut(&component_name)
                     .unwrap_or_else(|| panic!("Failed to get component: {}", component_name))
@@ -221,10 +620,34 @@ impl Component for CoreWindow {
             Action::UnfocusComponent => {
                 self.component_focused = None;
                 self.show_reply_message = false;
+                self.show_message_tags = false;
+                self.show_pinned_messages = false;
+                self.show_blocked_users = false;
+                self.show_user_profile = false;
+                self.show_stickers = false;
                 for (_, component) in self.components.iter_mut() {
                     component.unfocus();
                 }
             }
+            Action::FocusNext => {
+                self.cycle_focus(true);
+            }
+            Action::FocusPrevious => {
+                self.cycle_focus(false);
+            }
+            Action::ToggleUsernames => {
+                let tg_context = self.app_context.tg_context();
+                tg_context.set_show_usernames(!tg_context.show_usernames());
+            }
+            Action::ToggleMessageTags => {
+                self.toggle_message_tags();
+            }
+            Action::ToggleStickers => {
+                self.toggle_stickers();
+            }
+            Action::ToggleOnlineContacts => {
+                self.toggle_online_contacts();
+            }
             Action::ToggleChatList => {
                 self.toggle_chat_list();
             }
@@ -240,14 +663,56 @@ impl Component for CoreWindow {
             Action::DecreasePromptSize => {
                 self.decrease_size_prompt();
             }
-            Action::TryQuit => {
-                if self.component_focused != Some(ComponentName::Prompt) {
-                    self.action_tx
-                        .as_ref()
-                        .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
-                        .send(Action::Quit)
-                        .unwrap_or_else(|_| panic!("Failed to send action Quit from CoreWindow"));
-                }
+            // The prompt and the search of the stickers take the letters.
+            Action::TryQuit
+                if !matches!(
+                    self.component_focused,
+                    Some(ComponentName::Prompt | ComponentName::Stickers)
+                ) =>
+            {
+                self.action_tx
+                    .as_ref()
+                    .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
+                    .send(Action::Quit)
+                    .unwrap_or_else(|_| panic!("Failed to send action Quit from CoreWindow"));
+            }
+            Action::OpenChat(..) | Action::NavigateBack | Action::NavigateForward => {
+                self.components
+                    .get_mut(&ComponentName::ChatList)
+                    .unwrap_or_else(|| {
+                        panic!("Failed to get component: {}", ComponentName::ChatList)
+                    })
+                    .update(action);
+                return;
+            }
+            Action::ForceReply(..) | Action::ReplyInOtherChat(..) => {
+                self.show_reply_message = true;
+                // The prompt enters the reply mode even if it is not focused
+                // yet, it is focused right after.
+                self.components
+                    .get_mut(&ComponentName::Prompt)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
+                    .update(action);
+                return;
+            }
+            // The dice, the location and the long messages split are sent
+            // from the prompt, that takes their text, even if it is not
+            // focused, the confirmation popup takes the focus.
+            Action::PromptSendDice | Action::PromptSendLocation | Action::PromptSendSplit => {
+                self.components
+                    .get_mut(&ComponentName::Prompt)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
+                    .update(action);
+                return;
+            }
+            // The reaction is chosen in the chat, even if the prompt took
+            // the focus meanwhile.
+            Action::ChooseReaction(..) => {
+                self.components
+                    .get_mut(&ComponentName::Chat)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
+                    .update(action);
+                return;
             }
             Action::ShowChatWindowReply => {
                 self.show_reply_message = true;
@@ -267,38 +732,80 @@ impl Component for CoreWindow {
     }
 
     fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        self.update_chat_list_auto_hide(area.width);
         let core_layout = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(self.size_chat_list),
-                Constraint::Percentage(100 - self.size_chat_list),
-            ])
+            .constraints([self.chat_list_constraint(), Constraint::Fill(1)])
             .split(area);
 
+        // The panel of the contacts online now takes the bottom of the column
+        // of the chat list.
+        let side_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                if self.show_online_contacts {
+                    Constraint::Length(ONLINE_CONTACTS_SIZE)
+                } else {
+                    Constraint::Length(0)
+                },
+            ])
+            .split(core_layout[0]);
         self.components
             .get_mut(&ComponentName::ChatList)
             .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::ChatList))
-            .draw(frame, core_layout[0])?;
+            .draw(frame, side_layout[0])?;
+        if self.show_online_contacts {
+            self.components
+                .get_mut(&ComponentName::OnlineContacts)
+                .unwrap_or_else(|| {
+                    panic!("Failed to get component: {}", ComponentName::OnlineContacts)
+                })
+                .draw(frame, side_layout[1])?;
+        }
 
+        let mut constraints = [
+            Constraint::Fill(1),
+            {
+                if self.show_reply_message {
+                    Constraint::Length(self.size_message_reply)
+                } else {
+                    Constraint::Length(0)
+                }
+            },
+            Constraint::Length(self.size_prompt),
+        ];
+        // The reply message stays between the chat and the prompt.
+        if self.prompt_at_top() {
+            constraints.reverse();
+        }
         let sub_core_layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Fill(1),
-                {
-                    if self.show_reply_message {
-                        Constraint::Length(self.size_message_reply)
-                    } else {
-                        Constraint::Length(0)
-                    }
-                },
-                Constraint::Length(self.size_prompt),
-            ])
+            .constraints(constraints)
             .split(core_layout[1]);
+        let (chat_area, reply_message_area, prompt_area) = if self.prompt_at_top() {
+            (sub_core_layout[2], sub_core_layout[1], sub_core_layout[0])
+        } else {
+            (sub_core_layout[0], sub_core_layout[1], sub_core_layout[2])
+        };
 
+        let main_component = if self.show_message_tags {
+            ComponentName::MessageTags
+        } else if self.show_pinned_messages {
+            ComponentName::PinnedMessages
+        } else if self.show_blocked_users {
+            ComponentName::BlockedUsers
+        } else if self.show_user_profile {
+            ComponentName::UserProfile
+        } else if self.show_stickers {
+            ComponentName::Stickers
+        } else {
+            ComponentName::Chat
+        };
         self.components
-            .get_mut(&ComponentName::Chat)
-            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
-            .draw(frame, sub_core_layout[0])?;
+            .get_mut(&main_component)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", main_component))
+            .draw(frame, chat_area)?;
 
         if self.show_reply_message {
             self.components
@@ -306,13 +813,195 @@ impl Component for CoreWindow {
                 .unwrap_or_else(|| {
                     panic!("Failed to get component: {}", ComponentName::ReplyMessage)
                 })
-                .draw(frame, sub_core_layout[1])?;
+                .draw(frame, reply_message_area)?;
         }
         self.components
             .get_mut(&ComponentName::Prompt)
             .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
-            .draw(frame, sub_core_layout[2])?;
+            .draw(frame, prompt_area)?;
+
+        if self.show_popup {
+            self.components
+                .get_mut(&ComponentName::Popup)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Popup))
+                .draw(frame, area)?;
+        }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::popup_window::report_choice, tg::td_enums::TdReportReason};
+
+    fn next_focus(core_window: &mut CoreWindow, forward: bool) -> Option<Action> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        core_window.register_action_handler(tx).unwrap();
+        core_window.cycle_focus(forward);
+        rx.try_recv().ok()
+    }
+
+    #[test]
+    fn test_cycle_focus() {
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        assert_eq!(
+            next_focus(&mut core_window, true),
+            Some(Action::FocusComponent(ComponentName::ChatList))
+        );
+        core_window.component_focused = Some(ComponentName::Prompt);
+        assert_eq!(
+            next_focus(&mut core_window, true),
+            Some(Action::FocusComponent(ComponentName::ChatList))
+        );
+        assert_eq!(
+            next_focus(&mut core_window, false),
+            Some(Action::FocusComponent(ComponentName::Chat))
+        );
+    }
+
+    #[test]
+    fn test_try_quit_ignored_in_prompt() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        core_window.register_action_handler(tx).unwrap();
+        core_window.update(Action::FocusComponent(ComponentName::Prompt));
+        core_window.update(Action::TryQuit);
+        while let Ok(action) = rx.try_recv() {
+            assert_ne!(action, Action::Quit);
+        }
+        core_window.update(Action::FocusComponent(ComponentName::Chat));
+        core_window.update(Action::TryQuit);
+        assert_eq!(rx.try_recv().ok(), Some(Action::Quit));
+    }
+
+    #[test]
+    fn test_chat_list_auto_hide() {
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        core_window.update_chat_list_auto_hide(60);
+        assert!(!core_window.chat_list_visible());
+        assert_eq!(core_window.chat_list_constraint(), Constraint::Length(0));
+        // Showing the chat list on a narrow terminal keeps the manual
+        // preference untouched.
+        core_window.toggle_chat_list();
+        assert!(core_window.chat_list_visible());
+        core_window.update_chat_list_auto_hide(120);
+        core_window.toggle_chat_list();
+        assert!(!core_window.chat_list_visible());
+        core_window.update_chat_list_auto_hide(60);
+        assert!(!core_window.chat_list_visible());
+        core_window.update_chat_list_auto_hide(120);
+        assert!(!core_window.chat_list_visible());
+        core_window.toggle_chat_list();
+        assert!(core_window.chat_list_visible());
+    }
+
+    #[test]
+    fn test_popup_captures_keys_until_closed() {
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        core_window.update(Action::FocusComponent(ComponentName::Chat));
+        core_window.update(Action::ShowPopup(
+            "Edit history".to_string(),
+            vec!["first".to_string()],
+        ));
+        assert!(core_window.show_popup);
+        let key = Event::Key(KeyCode::Char('d'), crossterm::event::KeyModifiers::NONE);
+        assert_eq!(
+            core_window.handle_events(Some(key)).unwrap(),
+            Some(Action::Unknown)
+        );
+        core_window.update(Action::Key(
+            KeyCode::Esc,
+            crossterm::event::KeyModifiers::NONE.into(),
+        ));
+        assert!(!core_window.show_popup);
+    }
+
+    #[test]
+    fn test_confirm_popup() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        core_window.register_action_handler(tx).unwrap();
+        let confirm = || {
+            Action::ShowConfirm(
+                "Mark as read".to_string(),
+                vec!["Mark 3 chats as read?".to_string()],
+                Box::new(Action::ReadChats(vec![1, 2, 3])),
+            )
+        };
+        let key = |code| Action::Key(code, crossterm::event::KeyModifiers::NONE.into());
+
+        core_window.update(confirm());
+        core_window.update(key(KeyCode::Esc));
+        assert!(!core_window.show_popup);
+        assert!(rx.try_recv().is_err());
+
+        core_window.update(confirm());
+        core_window.update(key(KeyCode::Char('y')));
+        assert!(!core_window.show_popup);
+        assert_eq!(rx.try_recv().ok(), Some(Action::ReadChats(vec![1, 2, 3])));
+
+        // A plain popup closed with Enter confirms nothing.
+        core_window.update(Action::ShowPopup("Info".to_string(), vec![]));
+        core_window.update(key(KeyCode::Enter));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_choice_popup() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        core_window.register_action_handler(tx).unwrap();
+        let key = |code| Action::Key(code, crossterm::event::KeyModifiers::NONE.into());
+
+        core_window.update(report_choice(5, vec![7], "the message"));
+        assert!(core_window.show_popup);
+        core_window.update(key(KeyCode::Down));
+        core_window.update(key(KeyCode::Enter));
+        assert!(!core_window.show_popup);
+        let Ok(confirm) = rx.try_recv() else {
+            panic!("No entry chosen");
+        };
+        assert!(matches!(
+            confirm,
+            Action::ShowConfirm(_, _, ref action)
+                if **action == Action::ReportChat(5, vec![7], TdReportReason::Violence)
+        ));
+
+        core_window.update(report_choice(5, vec![], "Cats"));
+        core_window.update(key(KeyCode::Esc));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_cycle_focus_prompt_at_top() {
+        let app_context = Arc::new(AppContext::new_default());
+        app_context.app_config().layout_prompt_position = PromptPosition::Top;
+        let mut core_window = CoreWindow::new(app_context);
+        core_window.component_focused = Some(ComponentName::ChatList);
+        assert_eq!(
+            next_focus(&mut core_window, true),
+            Some(Action::FocusComponent(ComponentName::Prompt))
+        );
+        core_window.component_focused = Some(ComponentName::Prompt);
+        assert_eq!(
+            next_focus(&mut core_window, true),
+            Some(Action::FocusComponent(ComponentName::Chat))
+        );
+        let size_prompt = core_window.size_prompt;
+        core_window.handle_resize_key(KeyCode::Down);
+        assert_eq!(core_window.size_prompt, size_prompt + 1);
+    }
+
+    #[test]
+    fn test_cycle_focus_skips_hidden_chat_list() {
+        let mut core_window = CoreWindow::new(Arc::new(AppContext::new_default()));
+        core_window.toggle_chat_list();
+        core_window.component_focused = Some(ComponentName::Prompt);
+        assert_eq!(
+            next_focus(&mut core_window, true),
+            Some(Action::FocusComponent(ComponentName::Chat))
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    components::component_traits::{Component, HandleFocus},
+};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// The width of the loading message.
+const MESSAGE_WIDTH: u16 = 46;
+/// The height of the loading message.
+const MESSAGE_HEIGHT: u16 = 4;
+
+/// `LoadingScreen` is a struct that represents the overlay shown on startup,
+/// while `tgt` connects to Telegram and loads the first chats.
+/// It is dismissed automatically once the startup is complete, so an empty
+/// chat list is never shown while the chats are still arriving.
+pub struct LoadingScreen {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `LoadingScreen`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// Indicates whether the startup is complete. Once it is, the loading
+    /// screen is not shown again, even if the connection is lost later.
+    done: bool,
+}
+
+impl LoadingScreen {
+    /// Create a new instance of the `LoadingScreen` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `LoadingScreen` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        LoadingScreen {
+            app_context,
+            name: "".to_string(),
+            action_tx: None,
+            done: false,
+        }
+    }
+    /// Set the name of the `LoadingScreen`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `LoadingScreen`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `LoadingScreen`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Check whether the loading screen has to be drawn.
+    ///
+    /// # Returns
+    /// * `bool` - `true` until the startup is complete.
+    pub fn is_visible(&mut self) -> bool {
+        if !self.done && self.app_context.tg_context().startup_complete() {
+            tracing::info!("Startup complete, hiding the loading screen");
+            self.done = true;
+        }
+        !self.done
+    }
+
+    /// Get the area of the loading message, centered in the given area.
+    ///
+    /// # Arguments
+    /// * `area` - The area of the whole screen.
+    ///
+    /// # Returns
+    /// * `Rect` - The area of the loading message.
+    fn message_area(area: Rect) -> Rect {
+        let [message_area] = Layout::vertical([Constraint::Length(MESSAGE_HEIGHT)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [message_area] = Layout::horizontal([Constraint::Length(MESSAGE_WIDTH)])
+            .flex(Flex::Center)
+            .areas(message_area);
+        message_area
+    }
+
+    /// Get the description of the current startup step.
+    ///
+    /// # Returns
+    /// * `String` - The description.
+    fn status(&self) -> String {
+        self.app_context
+            .tg_context()
+            .connection_status()
+            .unwrap_or_else(|| "Loading chats".to_string())
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `LoadingScreen` struct.
+/// The loading screen never takes the focus, it only covers the core window.
+impl HandleFocus for LoadingScreen {
+    fn focus(&mut self) {}
+    fn unfocus(&mut self) {}
+}
+
+/// Implement the `Component` trait for the `LoadingScreen` struct.
+impl Component for LoadingScreen {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, _action: Action) {}
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        frame.render_widget(Clear, area);
+        frame.render_widget(Block::default().style(self.app_context.style_chat()), area);
+
+        let message_area = Self::message_area(area);
+        let text = vec![
+            Line::from(Span::styled(
+                "Connecting to Telegram…",
+                self.app_context.style_chat(),
+            )),
+            Line::from(Span::styled(
+                self.status(),
+                self.app_context.style_status_bar_connection_state(),
+            )),
+        ];
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(self.app_context.style_border_component_focused())
+            .borders(Borders::ALL)
+            .title(self.name.as_str());
+
+        frame.render_widget(
+            Paragraph::new(text)
+                .block(block)
+                .alignment(Alignment::Center)
+                .style(self.app_context.style_chat()),
+            message_area,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdlib_rs::enums::ConnectionState;
+
+    #[test]
+    fn test_loading_screen_dismissed_on_startup_complete() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut loading_screen = LoadingScreen::new(Arc::clone(&app_context));
+        assert!(loading_screen.is_visible());
+        assert_eq!(loading_screen.status(), "Loading chats");
+
+        app_context
+            .tg_context()
+            .set_connection_state(ConnectionState::Connecting);
+        assert!(loading_screen.is_visible());
+        assert!(loading_screen.status().starts_with("Connecting"));
+
+        // The cached chats are shown while offline.
+        app_context.tg_context().set_chats_loaded(true);
+        assert!(!loading_screen.is_visible());
+
+        // A connection lost later is shown in the status bar instead.
+        app_context
+            .tg_context()
+            .set_connection_state(ConnectionState::WaitingForNetwork);
+        assert!(!loading_screen.is_visible());
+    }
+}
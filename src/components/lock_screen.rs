@@ -0,0 +1,302 @@
+use crate::{
+    action::{Action, Modifiers},
+    app_context::AppContext,
+    components::component_traits::{Component, HandleFocus},
+};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// The width of the passphrase prompt.
+const PROMPT_WIDTH: u16 = 46;
+/// The height of the passphrase prompt.
+const PROMPT_HEIGHT: u16 = 5;
+
+/// `Mode` is an enum that represents what the passphrase prompt is asking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    /// The passphrase that unlocks `tgt`.
+    Unlock,
+    /// A new passphrase, when no passphrase has been chosen yet.
+    Choose,
+    /// The new passphrase again. The parameter is the passphrase entered the
+    /// first time.
+    Confirm(String),
+}
+
+/// `LockScreen` is a struct that represents the passphrase prompt shown when
+/// `tgt` is locked.
+/// While `tgt` is locked the lock screen is the only component drawn, and it
+/// receives all the input of the user.
+pub struct LockScreen {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `LockScreen`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// What the passphrase prompt is asking.
+    mode: Mode,
+    /// The passphrase typed so far, it is never drawn.
+    input: String,
+    /// The error of the last attempt, if any.
+    error: Option<&'static str>,
+}
+
+impl LockScreen {
+    /// Create a new instance of the `LockScreen` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `LockScreen` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        LockScreen {
+            app_context,
+            name: "".to_string(),
+            action_tx: None,
+            mode: Mode::Unlock,
+            input: String::new(),
+            error: None,
+        }
+    }
+    /// Set the name of the `LockScreen`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `LockScreen`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `LockScreen`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Lock `tgt`. If no passphrase has been chosen yet, the prompt asks for a
+    /// new one.
+    fn lock(&mut self) {
+        if self.app_context.locked_acquire() {
+            return;
+        }
+        self.mode = if self.app_context.lock_passphrase().is_set() {
+            Mode::Unlock
+        } else {
+            Mode::Choose
+        };
+        self.input.clear();
+        self.error = None;
+        self.app_context.locked_store(true);
+    }
+
+    /// Unlock `tgt`, the other components are drawn again as they were before
+    /// locking.
+    fn unlock(&mut self) {
+        self.input.clear();
+        self.error = None;
+        self.app_context.touch_input();
+        self.app_context.locked_store(false);
+    }
+
+    /// Check the typed passphrase, or choose it if no passphrase has been
+    /// chosen yet.
+    fn submit(&mut self) {
+        let input = std::mem::take(&mut self.input);
+        self.error = None;
+        match std::mem::replace(&mut self.mode, Mode::Unlock) {
+            Mode::Unlock => {
+                if self.app_context.lock_passphrase().verify(&input) {
+                    self.unlock();
+                } else {
+                    self.error = Some("Wrong passphrase");
+                }
+            }
+            Mode::Choose => {
+                if input.is_empty() {
+                    self.mode = Mode::Choose;
+                    self.error = Some("The passphrase can not be empty");
+                } else {
+                    self.mode = Mode::Confirm(input);
+                }
+            }
+            Mode::Confirm(first) => {
+                if input == first {
+                    // `tgt` stays locked, now behind the new passphrase.
+                    self.app_context.lock_passphrase().set(&input);
+                } else {
+                    self.mode = Mode::Choose;
+                    self.error = Some("The passphrases do not match");
+                }
+            }
+        }
+    }
+
+    /// Get the area of the passphrase prompt, centered in the given area.
+    /// It is computed at every draw, so the prompt follows the resizes of the
+    /// terminal.
+    ///
+    /// # Arguments
+    /// * `area` - The area where the prompt is centered.
+    fn prompt_area(area: Rect) -> Rect {
+        let [prompt_area] = Layout::vertical([Constraint::Length(PROMPT_HEIGHT)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [prompt_area] = Layout::horizontal([Constraint::Length(PROMPT_WIDTH)])
+            .flex(Flex::Center)
+            .areas(prompt_area);
+        prompt_area
+    }
+
+    /// Handle a key pressed while `tgt` is locked.
+    ///
+    /// # Arguments
+    /// * `key_code` - The code of the key.
+    /// * `modifiers` - The modifiers of the key.
+    fn handle_key(&mut self, key_code: KeyCode, modifiers: Modifiers) {
+        match key_code {
+            KeyCode::Enter => self.submit(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Esc => {
+                // Nothing is protected until a passphrase is chosen, so
+                // choosing it can be cancelled.
+                if self.mode == Mode::Unlock {
+                    self.input.clear();
+                } else {
+                    self.unlock();
+                }
+            }
+            KeyCode::Char(c) if !modifiers.control && !modifiers.alt => self.input.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `LockScreen` struct.
+/// The lock screen is never focused, it receives the input only while `tgt`
+/// is locked.
+impl HandleFocus for LockScreen {
+    fn focus(&mut self) {}
+    fn unfocus(&mut self) {}
+}
+
+/// Implement the `Component` trait for the `LockScreen` struct.
+impl Component for LockScreen {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::Lock => self.lock(),
+            Action::Key(key_code, modifiers) if self.app_context.locked_acquire() => {
+                self.handle_key(key_code, modifiers)
+            }
+            Action::Paste(text) if self.app_context.locked_acquire() => {
+                self.input.push_str(text.lines().next().unwrap_or_default());
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        frame.render_widget(Clear, area);
+        frame.render_widget(Block::default().style(self.app_context.style_chat()), area);
+
+        let prompt_area = Self::prompt_area(area);
+
+        let label = match self.mode {
+            Mode::Unlock => "Enter the passphrase to unlock",
+            Mode::Choose => "Choose a passphrase to lock tgt",
+            Mode::Confirm(_) => "Enter the passphrase again",
+        };
+        let mut text = vec![
+            Line::from(Span::styled(label, self.app_context.style_chat())),
+            Line::from(Span::styled(
+                "*".repeat(self.input.chars().count()),
+                self.app_context.style_prompt_message_text(),
+            )),
+        ];
+        if let Some(error) = self.error {
+            text.push(Line::from(Span::styled(
+                error,
+                self.app_context.style_status_bar_notify_error(),
+            )));
+        }
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(self.app_context.style_border_component_focused())
+            .borders(Borders::ALL)
+            .title(self.name.as_str());
+
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(
+            Paragraph::new(text)
+                .block(block)
+                .style(self.app_context.style_chat()),
+            prompt_area,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn type_and_submit(lock_screen: &mut LockScreen, passphrase: &str) {
+        passphrase.chars().for_each(|c| {
+            lock_screen.update(Action::Key(
+                KeyCode::Char(c),
+                Modifiers::from(KeyModifiers::NONE),
+            ))
+        });
+        lock_screen.update(Action::Key(
+            KeyCode::Enter,
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+    }
+
+    #[test]
+    fn test_lock_screen_choose_and_unlock() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut lock_screen = LockScreen::new(Arc::clone(&app_context));
+
+        // Typing while unlocked does nothing.
+        type_and_submit(&mut lock_screen, "ignored");
+        assert!(!app_context.locked_acquire());
+
+        lock_screen.update(Action::Lock);
+        assert!(app_context.locked_acquire());
+        assert_eq!(lock_screen.mode, Mode::Choose);
+        type_and_submit(&mut lock_screen, "secret");
+        type_and_submit(&mut lock_screen, "secreT");
+        assert_eq!(lock_screen.mode, Mode::Choose);
+        assert!(!app_context.lock_passphrase().is_set());
+        type_and_submit(&mut lock_screen, "secret");
+        type_and_submit(&mut lock_screen, "secret");
+        assert!(app_context.lock_passphrase().is_set());
+        assert!(app_context.locked_acquire());
+
+        type_and_submit(&mut lock_screen, "wrong");
+        assert!(app_context.locked_acquire());
+        assert_eq!(lock_screen.error, Some("Wrong passphrase"));
+        lock_screen.update(Action::Key(
+            KeyCode::Esc,
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+        assert!(app_context.locked_acquire());
+        type_and_submit(&mut lock_screen, "secret");
+        assert!(!app_context.locked_acquire());
+    }
+}
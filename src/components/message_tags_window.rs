@@ -0,0 +1,216 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    component_name::ComponentName,
+    components::component_traits::{Component, HandleFocus},
+    tg::{message_tags::TaggedMessage, navigation_history::Position},
+    utils::strip_emoji_line,
+};
+use ratatui::{
+    layout::Rect,
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListDirection, ListState},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// `MessageTagsWindow` is a struct that represents the overview of the
+/// messages tagged locally, across all the chats.
+/// It uses the key bindings of the chat list to select a tagged message and
+/// to jump to it.
+pub struct MessageTagsWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `MessageTagsWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The tagged messages, grouped by tag.
+    tagged_messages: Vec<TaggedMessage>,
+    /// The state of the list.
+    tagged_messages_state: ListState,
+    /// Indicates whether the `MessageTagsWindow` is focused or not.
+    focused: bool,
+}
+
+impl MessageTagsWindow {
+    /// Create a new instance of the `MessageTagsWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `MessageTagsWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let name = "".to_string();
+        let action_tx = None;
+        let tagged_messages = vec![];
+        let tagged_messages_state = ListState::default();
+        let focused = false;
+        MessageTagsWindow {
+            app_context,
+            name,
+            action_tx,
+            tagged_messages,
+            tagged_messages_state,
+            focused,
+        }
+    }
+    /// Set the name of the `MessageTagsWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `MessageTagsWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `MessageTagsWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Select the next tagged message in the list.
+    fn next(&mut self) {
+        if self.tagged_messages.is_empty() {
+            return;
+        }
+        let i = match self.tagged_messages_state.selected() {
+            Some(i) if i + 1 < self.tagged_messages.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.tagged_messages_state.select(Some(i));
+    }
+
+    /// Select the previous tagged message in the list.
+    fn previous(&mut self) {
+        if self.tagged_messages.is_empty() {
+            return;
+        }
+        let i = match self.tagged_messages_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.tagged_messages_state.select(Some(i));
+    }
+
+    /// Unselect the tagged message in the list.
+    fn unselect(&mut self) {
+        self.tagged_messages_state.select(None);
+    }
+
+    /// Open the chat of the selected tagged message and select the message
+    /// in the chat window.
+    fn jump_to_selected(&mut self) {
+        let Some(tagged) = self
+            .tagged_messages_state
+            .selected()
+            .and_then(|i| self.tagged_messages.get(i))
+        else {
+            return;
+        };
+        let position = Position {
+            chat_id: tagged.chat_id,
+            message_id: Some(tagged.message_id),
+        };
+        let tg_context = self.app_context.tg_context();
+        tg_context.navigation_history().visit(position);
+        tg_context.open_position(position);
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::FocusComponent(ComponentName::Chat))
+                .unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `MessageTagsWindow` struct.
+/// This trait allows the `MessageTagsWindow` to be focused or unfocused.
+impl HandleFocus for MessageTagsWindow {
+    /// Set the `focused` flag for the `MessageTagsWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    /// Set the `focused` flag for the `MessageTagsWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `MessageTagsWindow` struct.
+impl Component for MessageTagsWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ChatListNext => self.next(),
+            Action::ChatListPrevious => self.previous(),
+            Action::ChatListUnselect => self.unselect(),
+            Action::ChatListOpen => self.jump_to_selected(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        self.tagged_messages = self
+            .app_context
+            .tg_context()
+            .message_tags()
+            .entries()
+            .to_vec();
+        self.tagged_messages.sort_by(|a, b| a.tag.cmp(&b.tag));
+        if self
+            .tagged_messages_state
+            .selected()
+            .is_some_and(|i| i >= self.tagged_messages.len())
+        {
+            self.tagged_messages_state.select(None);
+        }
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            self.app_context.style_chat_list()
+        };
+        let items = self.tagged_messages.iter().map(|tagged| {
+            let chat_name = self
+                .app_context
+                .tg_context()
+                .name_from_chats(tagged.chat_id)
+                .unwrap_or_else(|| tagged.chat_id.to_string());
+            Line::from(vec![
+                Span::styled(
+                    format!("[{}] ", tagged.tag),
+                    self.app_context.style_chat_list_group_header(),
+                ),
+                Span::styled(chat_name, self.app_context.style_chat_chat_name()),
+                Span::raw(": "),
+                Span::raw(tagged.preview.clone()),
+            ])
+        });
+        let strip_emoji = self.app_context.app_config().strip_emoji;
+        let items = items.map(|mut line| {
+            if strip_emoji {
+                strip_emoji_line(&mut line);
+            }
+            line
+        });
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(style_border_focused)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .title(self.name.as_str());
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_stateful_widget(list, area, &mut self.tagged_messages_state);
+        Ok(())
+    }
+}
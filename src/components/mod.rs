@@ -4,12 +4,23 @@ pub const MAX_CHAT_LIST_SIZE: u16 = 25;
 pub const MIN_CHAT_LIST_SIZE: u16 = 10;
 pub const MAX_PROMPT_SIZE: u16 = 20;
 pub const MIN_PROMPT_SIZE: u16 = 3;
+pub const MAX_CHAT_SCROLL_STATES: usize = 20;
+pub const ONLINE_CONTACTS_SIZE: u16 = 8;
 
+pub mod blocked_users_window;
 pub mod chat_list_window;
 pub mod chat_window;
 pub mod component_traits;
 pub mod core_window;
+pub mod loading_screen;
+pub mod lock_screen;
+pub mod message_tags_window;
+pub mod online_contacts_window;
+pub mod pinned_messages_window;
+pub mod popup_window;
 pub mod prompt_window;
 pub mod reply_message;
 pub mod status_bar;
+pub mod stickers_window;
 pub mod title_bar;
+pub mod user_profile_window;
@@ -0,0 +1,284 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    components::component_traits::{Component, HandleFocus},
+    utils::{online_symbol, strip_emoji_line},
+};
+use ratatui::{
+    layout::Rect,
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListDirection, ListState},
+    Frame,
+};
+use std::{collections::HashMap, sync::Arc};
+use tdlib_rs::{enums::UserStatus, types::User};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// `OnlineContactsWindow` is a struct that represents the panel of the
+/// contacts online now, shown below the chat list.
+/// It uses the key bindings of the chat list to select a contact and to open
+/// the private chat with it.
+pub struct OnlineContactsWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `OnlineContactsWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The identifier and the name of the contacts online, the most recently
+    /// active first.
+    contacts: Vec<(i64, String)>,
+    /// The state of the list.
+    contacts_state: ListState,
+    /// Indicates whether the `OnlineContactsWindow` is focused or not.
+    focused: bool,
+}
+
+impl OnlineContactsWindow {
+    /// Create a new instance of the `OnlineContactsWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `OnlineContactsWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let name = "".to_string();
+        let action_tx = None;
+        let contacts = vec![];
+        let contacts_state = ListState::default();
+        let focused = false;
+        OnlineContactsWindow {
+            app_context,
+            name,
+            action_tx,
+            contacts,
+            contacts_state,
+            focused,
+        }
+    }
+    /// Set the name of the `OnlineContactsWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `OnlineContactsWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `OnlineContactsWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Get the contacts online among the known users. The contact whose
+    /// online status expires last is the most recently active, it comes
+    /// first.
+    ///
+    /// # Arguments
+    /// * `users` - The known users.
+    /// * `me` - The identifier of the user of `tgt`, it is never listed.
+    ///
+    /// # Returns
+    /// * `Vec<(i64, String)>` - The identifier and the name of each contact.
+    fn online_contacts(users: &HashMap<i64, User>, me: i64) -> Vec<(i64, String)> {
+        let mut contacts = users
+            .values()
+            .filter(|user| user.is_contact && user.id != me)
+            .filter_map(|user| match &user.status {
+                UserStatus::Online(online) => Some((online.expires, user)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        contacts.sort_by(|(a_expires, a), (b_expires, b)| {
+            b_expires
+                .cmp(a_expires)
+                .then_with(|| a.first_name.cmp(&b.first_name))
+        });
+        contacts
+            .into_iter()
+            .map(|(_, user)| {
+                let name = format!("{} {}", user.first_name, user.last_name);
+                (user.id, name.trim().to_string())
+            })
+            .collect()
+    }
+
+    /// Take the contacts online now, the selection follows the selected
+    /// contact while the list changes.
+    fn refresh(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        let contacts = Self::online_contacts(&tg_context.users(), tg_context.me());
+        let selected_id = self
+            .contacts_state
+            .selected()
+            .and_then(|i| self.contacts.get(i))
+            .map(|(user_id, _)| *user_id);
+        self.contacts_state
+            .select(selected_id.and_then(|id| contacts.iter().position(|(u, _)| *u == id)));
+        self.contacts = contacts;
+    }
+
+    /// Select the next contact in the list.
+    fn next(&mut self) {
+        if self.contacts.is_empty() {
+            return;
+        }
+        let i = match self.contacts_state.selected() {
+            Some(i) if i + 1 < self.contacts.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.contacts_state.select(Some(i));
+    }
+
+    /// Select the previous contact in the list.
+    fn previous(&mut self) {
+        if self.contacts.is_empty() {
+            return;
+        }
+        let i = match self.contacts_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.contacts_state.select(Some(i));
+    }
+
+    /// Unselect the contact in the list.
+    fn unselect(&mut self) {
+        self.contacts_state.select(None);
+    }
+
+    /// Open the private chat with the selected contact, its identifier is
+    /// the one of the user.
+    fn open_selected(&mut self) {
+        let Some(user_id) = self
+            .contacts_state
+            .selected()
+            .and_then(|i| self.contacts.get(i))
+            .map(|(user_id, _)| *user_id)
+        else {
+            return;
+        };
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx.send(Action::OpenChat(user_id)).unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `OnlineContactsWindow` struct.
+/// This trait allows the `OnlineContactsWindow` to be focused or unfocused.
+impl HandleFocus for OnlineContactsWindow {
+    /// Set the `focused` flag for the `OnlineContactsWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.refresh();
+    }
+    /// Set the `focused` flag for the `OnlineContactsWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `OnlineContactsWindow` struct.
+impl Component for OnlineContactsWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ChatListNext => self.next(),
+            Action::ChatListPrevious => self.previous(),
+            Action::ChatListUnselect => self.unselect(),
+            Action::ChatListOpen => self.open_selected(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        self.refresh();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            self.app_context.style_chat_list()
+        };
+        let online = online_symbol(&UserStatus::Online(Default::default()));
+        let items = self.contacts.iter().map(|(_, name)| {
+            Line::from(vec![
+                Span::raw(online),
+                Span::styled(
+                    name.clone(),
+                    self.app_context.style_chat_list_item_chat_name(),
+                ),
+            ])
+        });
+        let strip_emoji = self.app_context.app_config().strip_emoji;
+        let items = items.map(|mut line| {
+            if strip_emoji {
+                strip_emoji_line(&mut line);
+            }
+            line
+        });
+        let title = format!("{} ({})", self.name, self.contacts.len());
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(style_border_focused)
+            .borders(Borders::LEFT | Borders::BOTTOM)
+            .title(title);
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_stateful_widget(list, area, &mut self.contacts_state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tg::tg_context::test_user;
+    use tdlib_rs::types::{UserStatusOffline, UserStatusOnline};
+
+    fn contact(id: i64, first_name: &str, status: UserStatus, is_contact: bool) -> User {
+        User {
+            is_contact,
+            ..test_user(id, first_name, status)
+        }
+    }
+
+    #[test]
+    fn test_online_contacts_sorted_by_activity() {
+        let online = |expires| UserStatus::Online(UserStatusOnline { expires });
+        let users = [
+            contact(1, "Alice", online(100), true),
+            contact(2, "Bob", online(300), true),
+            contact(
+                3,
+                "Carol",
+                UserStatus::Offline(UserStatusOffline { was_online: 1 }),
+                true,
+            ),
+            contact(4, "Dave", online(500), false),
+            contact(5, "Me", online(900), true),
+            contact(6, "Eve", online(100), true),
+        ]
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect::<HashMap<_, _>>();
+
+        let contacts = OnlineContactsWindow::online_contacts(&users, 5);
+        assert_eq!(
+            contacts,
+            vec![
+                (2, "Bob".to_string()),
+                (1, "Alice".to_string()),
+                (6, "Eve".to_string()),
+            ]
+        );
+    }
+}
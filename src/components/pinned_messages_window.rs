@@ -0,0 +1,276 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    component_name::ComponentName,
+    components::component_traits::{Component, HandleFocus},
+    tg::{message_entry::DateTimeEntry, navigation_history::Position},
+    utils::strip_emoji_line,
+};
+use ratatui::{
+    layout::Rect,
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListDirection, ListState},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// `PinnedMessagesWindow` is a struct that represents the list of the pinned
+/// messages of the open chat.
+/// It uses the key bindings of the chat list to select a pinned message and
+/// to jump to it.
+pub struct PinnedMessagesWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `PinnedMessagesWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The identifier of the chat of the pinned messages.
+    chat_id: i64,
+    /// The identifier, the date, the sender and the preview of each pinned
+    /// message, the most recent first.
+    pinned_messages: Vec<(i64, i32, String, String)>,
+    /// The state of the list.
+    pinned_messages_state: ListState,
+    /// Indicates whether the `PinnedMessagesWindow` is focused or not.
+    focused: bool,
+}
+
+impl PinnedMessagesWindow {
+    /// Create a new instance of the `PinnedMessagesWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `PinnedMessagesWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let name = "".to_string();
+        let action_tx = None;
+        let chat_id = 0;
+        let pinned_messages = vec![];
+        let pinned_messages_state = ListState::default();
+        let focused = false;
+        PinnedMessagesWindow {
+            app_context,
+            name,
+            action_tx,
+            chat_id,
+            pinned_messages,
+            pinned_messages_state,
+            focused,
+        }
+    }
+    /// Set the name of the `PinnedMessagesWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `PinnedMessagesWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `PinnedMessagesWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Take the pinned messages loaded for the open chat.
+    /// The selection is kept while the list is the same.
+    fn refresh(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        let pinned_messages: Vec<(i64, i32, String, String)> = tg_context
+            .pinned_messages()
+            .iter()
+            .map(|message| {
+                (
+                    message.id(),
+                    message.timestamp().timestamp,
+                    tg_context.sender_name(message.sender_id()),
+                    message.message_content_to_plain_text().replace('\n', " "),
+                )
+            })
+            .collect();
+        let chat_id = tg_context.open_chat_id();
+        let ids_changed = pinned_messages.len() != self.pinned_messages.len()
+            || pinned_messages
+                .iter()
+                .zip(self.pinned_messages.iter())
+                .any(|(a, b)| a.0 != b.0);
+        if chat_id != self.chat_id || ids_changed {
+            self.pinned_messages_state.select(None);
+        }
+        self.chat_id = chat_id;
+        self.pinned_messages = pinned_messages;
+    }
+
+    /// Select the next pinned message in the list.
+    fn next(&mut self) {
+        if self.pinned_messages.is_empty() {
+            return;
+        }
+        let i = match self.pinned_messages_state.selected() {
+            Some(i) if i + 1 < self.pinned_messages.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.pinned_messages_state.select(Some(i));
+    }
+
+    /// Select the previous pinned message in the list.
+    fn previous(&mut self) {
+        if self.pinned_messages.is_empty() {
+            return;
+        }
+        let i = match self.pinned_messages_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.pinned_messages_state.select(Some(i));
+    }
+
+    /// Unselect the pinned message in the list.
+    fn unselect(&mut self) {
+        self.pinned_messages_state.select(None);
+    }
+
+    /// Select the selected pinned message in the chat window, loading the
+    /// history of the chat around it.
+    fn jump_to_selected(&mut self) {
+        let Some(message_id) = self
+            .pinned_messages_state
+            .selected()
+            .and_then(|i| self.pinned_messages.get(i))
+            .map(|(message_id, ..)| *message_id)
+        else {
+            return;
+        };
+        let position = Position {
+            chat_id: self.chat_id,
+            message_id: Some(message_id),
+        };
+        let tg_context = self.app_context.tg_context();
+        tg_context.navigation_history().visit(position);
+        tg_context.open_position(position);
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::FocusComponent(ComponentName::Chat))
+                .unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `PinnedMessagesWindow` struct.
+/// This trait allows the `PinnedMessagesWindow` to be focused or unfocused.
+impl HandleFocus for PinnedMessagesWindow {
+    /// Set the `focused` flag for the `PinnedMessagesWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.refresh();
+    }
+    /// Set the `focused` flag for the `PinnedMessagesWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `PinnedMessagesWindow` struct.
+impl Component for PinnedMessagesWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ChatListNext => self.next(),
+            Action::ChatListPrevious => self.previous(),
+            Action::ChatListUnselect => self.unselect(),
+            Action::ChatListOpen => self.jump_to_selected(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        self.refresh();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            self.app_context.style_chat_list()
+        };
+        let timezone = self.app_context.app_config().timezone;
+        let items = self
+            .pinned_messages
+            .iter()
+            .map(|(_, timestamp, sender, preview)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", DateTimeEntry::convert_time(*timestamp, timezone)),
+                        self.app_context.style_timestamp(),
+                    ),
+                    Span::styled(sender.clone(), self.app_context.style_chat_chat_name()),
+                    Span::raw(": "),
+                    Span::raw(preview.clone()),
+                ])
+            });
+        let strip_emoji = self.app_context.app_config().strip_emoji;
+        let items = items.map(|mut line| {
+            if strip_emoji {
+                strip_emoji_line(&mut line);
+            }
+            line
+        });
+        let title = format!("{} ({})", self.name, self.pinned_messages.len());
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(style_border_focused)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .title(title);
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_stateful_widget(list, area, &mut self.pinned_messages_state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::Event, tg::message_entry::MessageEntry};
+
+    #[test]
+    fn test_jump_to_pinned_message() {
+        let app_context = Arc::new(AppContext::new_default());
+        let tg_context = app_context.tg_context();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(7);
+        tg_context.set_pinned_messages(vec![
+            MessageEntry::new_text(30, "Meeting at noon", 300),
+            MessageEntry::new_text(10, "Rules", 100),
+        ]);
+
+        let mut window = PinnedMessagesWindow::new(Arc::clone(&app_context));
+        let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel();
+        window.register_action_handler(action_tx).unwrap();
+        window.focus();
+        assert_eq!(window.pinned_messages.len(), 2);
+        assert_eq!(window.pinned_messages[0].3, "Meeting at noon");
+
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListOpen);
+        assert_eq!(tg_context.take_jump_to_message_id(), Some(10));
+        assert_eq!(tg_context.from_message_id(), 10);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::GetChatHistory)));
+        assert!(matches!(
+            action_rx.try_recv(),
+            Ok(Action::FocusComponent(ComponentName::Chat))
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    components::component_traits::{Component, HandleFocus},
+    tg::td_enums::TdReportReason,
+    utils::strip_emoji_line,
+};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// The maximum width of the popup, in columns.
+const MAX_POPUP_WIDTH: u16 = 70;
+
+/// Get the popup to report a chat or some of its messages: the reason is
+/// chosen first, then the report is confirmed.
+///
+/// # Arguments
+/// * `chat_id` - The identifier of the chat.
+/// * `message_ids` - The identifiers of the messages reported, empty to
+///   report the chat.
+/// * `subject` - What is reported, like `the message`.
+pub fn report_choice(chat_id: i64, message_ids: Vec<i64>, subject: &str) -> Action {
+    let title = if message_ids.is_empty() {
+        "Report chat"
+    } else {
+        "Report message"
+    };
+    let entries = TdReportReason::ALL
+        .iter()
+        .map(|reason| {
+            let confirm = Action::ShowConfirm(
+                title.to_string(),
+                vec![
+                    format!("Report {} to the moderators of Telegram?", subject),
+                    format!("Reason: {}", reason.label()),
+                ],
+                Box::new(Action::ReportChat(chat_id, message_ids.clone(), *reason)),
+            );
+            (reason.label().to_string(), confirm)
+        })
+        .collect();
+    Action::ShowChoice(title.to_string(), entries)
+}
+
+/// `PopupWindow` is a struct that represents a read-only popup shown over the
+/// core window, for example to show the edit history of a message.
+/// While it is shown it receives the keys: the arrows scroll its content and
+/// Esc, Enter or q close it. When it offers a choice, the arrows select an
+/// entry and Enter sends its action.
+pub struct PopupWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `PopupWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The title of the popup.
+    title: String,
+    /// The lines of the content of the popup.
+    lines: Vec<String>,
+    /// The number of lines scrolled from the top of the content.
+    scroll: u16,
+    /// Indicates whether the popup asks to confirm an action.
+    confirm: bool,
+    /// The actions of the entries offered, in the order of the lines, empty
+    /// if the popup offers no choice.
+    choices: Vec<Action>,
+    /// The index of the selected entry.
+    selected: usize,
+}
+
+impl PopupWindow {
+    /// Create a new instance of the `PopupWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `PopupWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        PopupWindow {
+            app_context,
+            name: "".to_string(),
+            action_tx: None,
+            title: String::new(),
+            lines: vec![],
+            scroll: 0,
+            confirm: false,
+            choices: vec![],
+            selected: 0,
+        }
+    }
+    /// Set the name of the `PopupWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `PopupWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `PopupWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Get the area of the popup, centered in the given area.
+    /// It is computed at every draw, so the popup follows the resizes of the
+    /// terminal.
+    ///
+    /// # Arguments
+    /// * `area` - The area where the popup is centered.
+    fn popup_area(&self, area: Rect) -> Rect {
+        let width = (area.width * 3 / 4).clamp(area.width.min(30), MAX_POPUP_WIDTH);
+        // The borders take two lines, the hint is drawn on the bottom one.
+        let height =
+            (self.lines.len() as u16 + 2).min((area.height * 3 / 4).max(area.height.min(5)));
+        let [popup_area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+        popup_area
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `PopupWindow` struct.
+/// The popup is never focused, the core window sends it the keys while it is
+/// shown.
+impl HandleFocus for PopupWindow {
+    fn focus(&mut self) {}
+    fn unfocus(&mut self) {}
+}
+
+/// Implement the `Component` trait for the `PopupWindow` struct.
+impl Component for PopupWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ShowPopup(title, lines) => {
+                self.title = title;
+                self.lines = lines;
+                self.scroll = 0;
+                self.confirm = false;
+                self.choices.clear();
+            }
+            Action::ShowConfirm(title, lines, _) => {
+                self.title = title;
+                self.lines = lines;
+                self.scroll = 0;
+                self.confirm = true;
+                self.choices.clear();
+            }
+            Action::ShowChoice(title, entries) => {
+                self.title = title;
+                (self.lines, self.choices) = entries.into_iter().unzip();
+                self.scroll = 0;
+                self.confirm = false;
+                self.selected = 0;
+            }
+            Action::Key(KeyCode::Down, _) if !self.choices.is_empty() => {
+                self.selected = (self.selected + 1).min(self.choices.len() - 1);
+            }
+            Action::Key(KeyCode::Up, _) if !self.choices.is_empty() => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            Action::Key(KeyCode::Enter, _) => {
+                if let (Some(action), Some(tx)) =
+                    (self.choices.get(self.selected), self.action_tx.as_ref())
+                {
+                    tx.send(action.clone()).unwrap();
+                }
+            }
+            Action::Key(KeyCode::Down, _) if (self.scroll as usize) + 1 < self.lines.len() => {
+                self.scroll += 1;
+            }
+            Action::Key(KeyCode::Up, _) => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        let popup_area = self.popup_area(area);
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(self.app_context.style_border_component_focused())
+            .borders(Borders::ALL)
+            .title(format!(
+                " {} ",
+                if self.title.is_empty() {
+                    &self.name
+                } else {
+                    &self.title
+                }
+            ))
+            .title_bottom(Line::from(Span::styled(
+                if self.confirm {
+                    " Enter or y confirm, Esc or n cancel "
+                } else if !self.choices.is_empty() {
+                    " ↑/↓ select, Enter choose, Esc cancel "
+                } else {
+                    " ↑/↓ scroll, Esc or q close "
+                },
+                self.app_context.style_timestamp(),
+            )));
+        let mut text: Vec<Line> = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                if !self.choices.is_empty() && i == self.selected {
+                    Line::from(Span::styled(
+                        l.as_str(),
+                        self.app_context.style_chat_list_item_selected(),
+                    ))
+                } else {
+                    Line::from(l.as_str())
+                }
+            })
+            .collect();
+        if self.app_context.app_config().strip_emoji {
+            text.iter_mut().for_each(strip_emoji_line);
+        }
+        if !self.choices.is_empty() {
+            // The content scrolls to keep the selected entry on screen.
+            let height = block.inner(popup_area).height as usize;
+            self.scroll = (self.selected + 1).saturating_sub(height) as u16;
+        }
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text)
+                .block(block)
+                .style(self.app_context.style_chat())
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            popup_area,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    /// Find the top left corner of the border of the popup.
+    fn popup_corner(terminal: &Terminal<TestBackend>) -> Option<(u16, u16)> {
+        let buffer = terminal.backend().buffer();
+        let area = buffer.area;
+        (area.top()..area.bottom())
+            .flat_map(|y| (area.left()..area.right()).map(move |x| (x, y)))
+            .find(|(x, y)| buffer[(*x, *y)].symbol() == PLAIN.top_left)
+    }
+
+    #[test]
+    fn test_popup_follows_resize() {
+        let mut popup = PopupWindow::new(Arc::new(AppContext::new_default()));
+        popup.update(Action::ShowPopup(
+            "History".to_string(),
+            (0..4).map(|i| format!("version {i}")).collect(),
+        ));
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| popup.draw(frame, frame.area()).unwrap())
+            .unwrap();
+        let expected = popup.popup_area(Rect::new(0, 0, 100, 30));
+        assert_eq!(expected.width, MAX_POPUP_WIDTH);
+        assert_eq!(popup_corner(&terminal), Some((expected.x, expected.y)));
+
+        terminal.backend_mut().resize(40, 10);
+        terminal
+            .draw(|frame| popup.draw(frame, frame.area()).unwrap())
+            .unwrap();
+        let expected = popup.popup_area(Rect::new(0, 0, 40, 10));
+        assert_eq!(expected.width, 30);
+        assert_eq!(popup_corner(&terminal), Some((expected.x, expected.y)));
+        assert!(expected.right() <= 40 && expected.bottom() <= 10);
+    }
+}
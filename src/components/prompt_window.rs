@@ -1,13 +1,15 @@
 use crate::{
-    action::{Action, Modifiers},
+    action::{Action, Modifiers, NotifyLevel},
     app_context::AppContext,
     component_name::ComponentName,
     components::component_traits::{Component, HandleFocus},
+    configs::custom::app_custom::EmptyEnter,
     event::Event,
     tg::td_enums::TdMessageReplyToMessage,
+    utils::{invite_link, parse_location, split_message},
 };
 use arboard::Clipboard;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     layout::{Position, Rect},
     symbols::{
@@ -18,8 +20,24 @@ use ratatui::{
     widgets::{block::Block, Borders, Paragraph},
     Frame,
 };
-use std::{io, sync::Arc};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    sync::Arc,
+};
 use tokio::sync::mpsc::UnboundedSender;
+use unicode_width::UnicodeWidthChar;
+
+/// The maximum number of characters of a message allowed by Telegram.
+const MAX_MESSAGE_LENGTH: usize = 4096;
+/// The number of characters from which the character counter warns that the
+/// message is close to the limit.
+const CHAR_COUNTER_WARNING: usize = MAX_MESSAGE_LENGTH * 9 / 10;
+/// The emoji of the animated dice that Telegram can roll.
+const DICE_EMOJIS: [&str; 6] = ["🎲", "🎯", "🏀", "⚽", "🎳", "🎰"];
+/// The number of sent messages remembered for each chat to be recalled with
+/// Up and Down.
+const PROMPT_HISTORY_LIMIT: usize = 50;
 
 /// `DirSelection` is an enum that represents the direction of the selection.
 /// It is used to keep track of the direction of the selection when the user
@@ -50,6 +68,11 @@ enum Mode {
     /// Usually, when the prompt is replying to a message.
     /// The parameter is the message id of the message that is being replied.
     Reply(i64),
+    /// The reply mode of the prompt for a message of another chat, for
+    /// example when replying privately to a group message.
+    /// The parameters are the chat id and the message id of the message that
+    /// is being replied.
+    ReplyInOtherChat(i64, i64),
 }
 /// `InputCell` is a struct that represents a cell of the input.
 /// It is responsible for managing the input cell of the prompt.
@@ -89,6 +112,9 @@ struct Input {
     /// The mode of the input.
     /// It is used to keep track of the mode of the input.
     mode: Mode,
+    /// The maximum number of lines the prompt grows to, the longer texts
+    /// are scrolled.
+    max_lines: usize,
 }
 /// Implement the `Input` struct.
 impl Input {
@@ -99,9 +125,13 @@ impl Input {
     fn set_command_tx(&mut self, command_tx: UnboundedSender<Action>) {
         self.action_tx = Some(command_tx);
     }
-    /// Get the cursor x position of the `Input` struct.
-    fn cursor_x(&self) -> usize {
-        self.cursor.0
+    /// Get the column of the cursor on the screen, the wide characters
+    /// before it, like the CJK ones and the emoji, take two columns.
+    fn cursor_column(&self) -> usize {
+        self.text[self.cursor.1][..self.cursor.0]
+            .iter()
+            .map(|cell| cell.c.width().unwrap_or(0))
+            .sum()
     }
     /// Get the cursor y position of the `Input` struct.
     fn cursor_y(&self) -> usize {
@@ -129,32 +159,50 @@ impl Input {
     /// A newline is inserted at the current cursor position.
     /// The text after the cursor position is moved to the next line.
     fn insert_newline(&mut self) {
-        self.insert('\n');
+        // The newline is inserted without wrapping, the wrapping inserts a
+        // newline itself.
+        self.text[self.cursor.1].insert(
+            self.cursor.0,
+            InputCell {
+                c: '\n',
+                selected: false,
+            },
+        );
+        self.cursor.0 += 1;
         let line = &mut self.text[self.cursor.1];
         let right = line[self.cursor.0..].to_vec();
         line.truncate(self.cursor.0);
         self.text.insert(self.cursor.1 + 1, right);
         self.cursor.0 = 0;
         self.cursor.1 += 1;
-        if let Some(tx) = self.action_tx.as_ref() {
+        self.sync_prompt_size();
+    }
+    /// Grow or shrink the prompt to fit the lines of the text, up to the
+    /// maximum number of lines.
+    fn sync_prompt_size(&mut self) {
+        let Some(tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        // The prompt has one line of text without any increase.
+        let target = self.text.len().min(self.max_lines.max(1)) - 1;
+        while self.correct_prompt_size < target {
             self.correct_prompt_size += 1;
-            tx.send(Action::IncreasePromptSize).unwrap()
+            tx.send(Action::IncreasePromptSize).unwrap();
+        }
+        while self.correct_prompt_size > target {
+            self.correct_prompt_size -= 1;
+            tx.send(Action::DecreasePromptSize).unwrap();
         }
     }
     /// Delete the character before the cursor position.
     fn backspace(&mut self) {
-        if self.text[self.cursor.1].is_empty() && self.cursor.1 > 0 {
-            if let Some(tx) = self.action_tx.as_ref() {
-                self.correct_prompt_size -= 1;
-                tx.send(Action::DecreasePromptSize).unwrap();
-            }
-        }
         if self.cursor.0 == 0 {
             if self.cursor.1 > 0 {
                 let line = self.text.remove(self.cursor.1);
                 self.cursor.1 -= 1;
                 self.cursor.0 = self.text[self.cursor.1].len();
                 self.text[self.cursor.1].extend(line);
+                self.sync_prompt_size();
             }
         } else {
             self.text[self.cursor.1].remove(self.cursor.0 - 1);
@@ -167,6 +215,7 @@ impl Input {
             if self.cursor.1 < self.text.len() - 1 {
                 let line = self.text.remove(self.cursor.1 + 1);
                 self.text[self.cursor.1].extend(line);
+                self.sync_prompt_size();
             }
         } else {
             self.text[self.cursor.1].remove(self.cursor.0);
@@ -415,16 +464,65 @@ impl Input {
             clipboard.set_text(text).unwrap();
         }
     }
+    /// Get how much of a text fits in the message when pasted, the newlines
+    /// inserted to wrap the text count as characters of the message.
+    ///
+    /// # Arguments
+    /// * `text` - The text to paste.
+    /// * `room` - The number of characters left in the message.
+    ///
+    /// # Returns
+    /// * `usize` - The byte index where the text must be cut.
+    fn paste_end(&self, text: &str, room: usize) -> usize {
+        let width = (self.area_input.width as usize).saturating_sub(2);
+        let mut line_len = self.cursor.0;
+        let mut count = 0;
+        for (i, c) in text.char_indices() {
+            let wraps = c != '\n' && line_len + 1 == width;
+            count += if wraps { 2 } else { 1 };
+            if count > room {
+                return i;
+            }
+            line_len = match (c, wraps) {
+                ('\n', _) => 0,
+                (_, true) => 1,
+                (_, false) => line_len + 1,
+            };
+        }
+        text.len()
+    }
     /// Paste text into the `Input` struct.
-    /// The text is pasted at the current cursor position.
+    /// The text is pasted at the current cursor position, it is wrapped like
+    /// the typed text. The lines are built apart and inserted at once, to
+    /// paste long texts quickly.
     fn paste(&mut self, text: String) {
+        let width = (self.area_input.width as usize).saturating_sub(2);
+        let newline = InputCell {
+            c: '\n',
+            selected: false,
+        };
+        let right = self.text[self.cursor.1].split_off(self.cursor.0);
+        let mut lines = vec![];
+        let mut line = std::mem::take(&mut self.text[self.cursor.1]);
         for c in text.chars() {
+            // The -1 is to account the cursor at the end of the line, like
+            // when the text is typed.
+            if c != '\n' && line.len() + 1 == width {
+                line.push(newline.clone());
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(InputCell { c, selected: false });
             if c == '\n' {
-                self.insert_newline();
-            } else {
-                self.insert(c);
+                lines.push(std::mem::take(&mut line));
             }
         }
+        self.cursor.0 = line.len();
+        line.extend(right);
+        lines.push(line);
+        let first = self.cursor.1;
+        self.cursor.1 += lines.len() - 1;
+        self.text.splice(first..=first, lines);
+        self.sync_prompt_size();
     }
     /// Edit a message.
     ///
@@ -441,56 +539,145 @@ impl Input {
                     .collect()
             })
             .collect();
-        for _ in 0..self.text.len() - 1 {
-            if let Some(tx) = self.action_tx.as_ref() {
-                tx.send(Action::IncreasePromptSize).unwrap();
-            }
-        }
+        self.sync_prompt_size();
         self.cursor = (0, 0);
     }
+    /// Replace the text of the `Input` struct, the cursor is moved to the end
+    /// of the text.
+    ///
+    /// # Arguments
+    /// * `text` - The new text.
+    fn set_text(&mut self, text: &str) {
+        let lines: Vec<&str> = text.split('\n').collect();
+        let last = lines.len() - 1;
+        self.text = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let mut cells: Vec<InputCell> = line
+                    .chars()
+                    .map(|c| InputCell { c, selected: false })
+                    .collect();
+                // The lines keep their newline like when typed.
+                if i < last {
+                    cells.push(InputCell {
+                        c: '\n',
+                        selected: false,
+                    });
+                }
+                cells
+            })
+            .collect();
+        self.dir_selection = DirSelection::Empty;
+        self.sync_prompt_size();
+        self.cursor = (self.text[last].len(), last);
+    }
 
     /// Send a message.
     /// The message is sent to the main event loop for processing.
     ///
     /// # Arguments
     /// * `app_context` - An Arc wrapped AppContext struct.
-    fn send_message(&mut self, app_context: Arc<AppContext>) {
-        if let Some(event_tx) = app_context.tg_context().event_tx().as_ref() {
-            match self.mode {
-                Mode::Normal => {
-                    event_tx
-                        .send(Event::SendMessage(self.text_to_string(), None))
-                        .unwrap();
-                    self.text = vec![vec![]];
-                    self.set_prompt_size_to_one_focused();
-                }
-                Mode::Edit(message_id) => {
-                    event_tx
-                        .send(Event::SendMessageEdited(message_id, self.text_to_string()))
-                        .unwrap();
-                    self.text = vec![vec![]];
-                    self.set_prompt_size_to_one_focused();
-                    self.mode = Mode::Normal;
-                }
-                Mode::Reply(message_id) => {
-                    event_tx
-                        .send(Event::SendMessage(
-                            self.text_to_string(),
-                            Some(TdMessageReplyToMessage {
-                                chat_id: 0, // This must be  0 and not `app_context.tg_context().open_chat_id()` because the tdlib (maybe from the version 1.8.29 or before)  is able to know the chat id from the message_id; it will infer the chat id from the message_id.
-                                message_id,
-                            }),
-                        ))
-                        .unwrap();
-                    self.text = vec![vec![]];
-                    self.set_prompt_size_to_one_focused();
-                    self.mode = Mode::Normal;
-                }
+    ///
+    /// # Returns
+    /// * `bool` - Whether the message has been sent.
+    fn send_message(&mut self, app_context: Arc<AppContext>) -> bool {
+        let event = match self.mode {
+            Mode::Normal => Event::SendMessage(self.text_to_string(), None),
+            Mode::Edit(message_id) => Event::SendMessageEdited(message_id, self.text_to_string()),
+            Mode::Reply(message_id) => Event::SendMessage(
+                self.text_to_string(),
+                Some(TdMessageReplyToMessage {
+                    chat_id: 0, // This must be  0 and not `app_context.tg_context().open_chat_id()` because the tdlib (maybe from the version 1.8.29 or before)  is able to know the chat id from the message_id; it will infer the chat id from the message_id.
+                    message_id,
+                }),
+            ),
+            Mode::ReplyInOtherChat(chat_id, message_id) => Event::SendMessage(
+                self.text_to_string(),
+                Some(TdMessageReplyToMessage {
+                    chat_id,
+                    message_id,
+                }),
+            ),
+        };
+        // The text is kept if it could not be sent.
+        if !app_context.tg_context().try_send_event(event) {
+            return false;
+        }
+        self.text = vec![vec![]];
+        self.set_prompt_size_to_one_focused();
+        self.mode = Mode::Normal;
+        true
+    }
+    /// Send a message too long for a single message in several parts, in
+    /// order. Only the first part replies to the message replied to, the
+    /// edited messages are not split.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    /// * `parts` - The parts of the message.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the parts were sent, the text is kept otherwise.
+    ///   If only some parts were sent, the prompt keeps the others.
+    fn send_message_parts(&mut self, app_context: Arc<AppContext>, parts: Vec<String>) -> bool {
+        let mut reply_to = match self.mode {
+            Mode::Normal => None,
+            Mode::Edit(_) => return false,
+            Mode::Reply(message_id) => Some(TdMessageReplyToMessage {
+                chat_id: 0,
+                message_id,
+            }),
+            Mode::ReplyInOtherChat(chat_id, message_id) => Some(TdMessageReplyToMessage {
+                chat_id,
+                message_id,
+            }),
+        };
+        if parts.is_empty() {
+            return false;
+        }
+        let total = parts.len();
+        for (i, part) in parts.iter().enumerate() {
+            let event = Event::SendMessage(part.clone(), reply_to.take());
+            if app_context.tg_context().try_send_event(event) {
+                continue;
             }
+            // The parts already sent are removed, sending again does not
+            // send them twice. The reply went with the first part.
+            if i > 0 {
+                self.set_text(&parts[i..].join("\n"));
+                self.mode = Mode::Normal;
+                app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        format!(
+                            "Only {} of the {} parts of the message were sent, the others are kept in the prompt",
+                            i, total
+                        ),
+                        NotifyLevel::Error,
+                    ))
+                    .unwrap();
+            }
+            return false;
+        }
+        self.text = vec![vec![]];
+        self.set_prompt_size_to_one_focused();
+        self.mode = Mode::Normal;
+        true
+    }
+    /// Get the number of characters of the message.
+    fn char_count(&self) -> usize {
+        let count = self.text_to_string().chars().count();
+        // An empty last line is only where the cursor stands, its newline is
+        // already counted at the end of the line before.
+        if self.text.last().is_some_and(Vec::is_empty) {
+            count - 1
+        } else {
+            count
         }
     }
     /// Convert the text of the `Input` struct to a string.
-    fn text_to_string(&mut self) -> String {
+    fn text_to_string(&self) -> String {
         // TODO: Parse into markdown
         let mut message = String::new();
         self.text.iter().for_each(|e| {
@@ -515,6 +702,7 @@ impl Default for Input {
             correct_prompt_size: 0,
             is_restored: true,
             mode: Mode::Normal,
+            max_lines: 1,
         }
     }
 }
@@ -534,6 +722,22 @@ pub struct PromptWindow {
     focused_keys: Vec<Event>,
     /// The current input of the `PromptWindow`.
     input: Input,
+    /// The placeholder asked by a bot with a force reply, with the id of the
+    /// message of the bot. It is shown while replying to it with an empty
+    /// input.
+    force_reply_placeholder: Option<(i64, String)>,
+    /// The number of lines of the input scrolled out of the top of the
+    /// prompt, when the input has more lines than the prompt shows.
+    scroll: usize,
+    /// The messages sent in each chat, the most recent first, recalled with
+    /// Up and Down.
+    history: HashMap<i64, VecDeque<String>>,
+    /// The index in the history of the message recalled, `None` while
+    /// editing the draft.
+    history_index: Option<usize>,
+    /// The text typed before recalling the history, restored when going
+    /// down past the most recent message.
+    draft: String,
 }
 /// Implement the `PromptWindow` struct.
 impl PromptWindow {
@@ -553,7 +757,15 @@ impl PromptWindow {
             Action::FocusComponent(ComponentName::Prompt),
         );
 
-        let input = Input::default();
+        let input = Input {
+            max_lines: app_context.app_config().prompt_max_lines,
+            ..Default::default()
+        };
+        let force_reply_placeholder = None;
+        let scroll = 0;
+        let history = HashMap::new();
+        let history_index = None;
+        let draft = String::new();
 
         PromptWindow {
             app_context,
@@ -562,6 +774,11 @@ impl PromptWindow {
             focused,
             focused_keys,
             input,
+            force_reply_placeholder,
+            scroll,
+            history,
+            history_index,
+            draft,
         }
     }
     /// Set the name of the `PromptWindow`.
@@ -586,6 +803,281 @@ impl PromptWindow {
             self.input.area_input = area_input;
         }
     }
+    /// Handle the Enter key, it sends the message or inserts a newline
+    /// depending on the `prompt_send_on_enter` configuration. An empty
+    /// message is never sent, `prompt_empty_enter` tells what happens instead.
+    ///
+    /// # Arguments
+    /// * `with_modifier` - A boolean that indicates whether Enter was pressed
+    ///   with the `prompt_enter_modifier` configured.
+    fn handle_enter(&mut self, with_modifier: bool) {
+        self.input.unselect_all();
+        let send_on_enter = self.app_context.app_config().prompt_send_on_enter;
+        if send_on_enter != with_modifier {
+            if self.input.text_to_string().trim().is_empty() {
+                self.handle_empty_enter();
+                return;
+            }
+            let char_count = self.input.char_count();
+            if char_count > MAX_MESSAGE_LENGTH {
+                self.handle_too_long_message(char_count);
+                return;
+            }
+            self.send(false);
+        } else {
+            self.input.insert_newline();
+        }
+    }
+    /// Handle a message too long to be sent: ask to send it in several
+    /// messages if `prompt_split_long_messages` is enabled, otherwise refuse
+    /// to send it. The edited messages are never split.
+    ///
+    /// # Arguments
+    /// * `char_count` - The number of characters of the message.
+    fn handle_too_long_message(&mut self, char_count: usize) {
+        let split = self.app_context.app_config().prompt_split_long_messages
+            && !matches!(self.input.mode, Mode::Edit(_));
+        let action = if split {
+            let parts = split_message(&self.input.text_to_string(), MAX_MESSAGE_LENGTH).len();
+            Action::ShowConfirm(
+                "Send in several messages".to_string(),
+                vec![
+                    format!(
+                        "The message is too long: {} characters out of {}.",
+                        char_count, MAX_MESSAGE_LENGTH
+                    ),
+                    format!("Send it in {} messages?", parts),
+                ],
+                Box::new(Action::PromptSendSplit),
+            )
+        } else {
+            Action::Notify(
+                format!(
+                    "The message is too long: {} characters out of {}",
+                    char_count, MAX_MESSAGE_LENGTH
+                ),
+                NotifyLevel::Warn,
+            )
+        };
+        self.app_context.action_tx().send(action).unwrap();
+    }
+    /// Send the text of the prompt and remember it in the history of the
+    /// chat.
+    ///
+    /// # Arguments
+    /// * `split` - A boolean that indicates whether the text is split in
+    ///   several messages, see `split_message`.
+    fn send(&mut self, split: bool) {
+        let text = self.input.text_to_string();
+        let chat_id = match self.input.mode {
+            Mode::Normal | Mode::Reply(_) => Some(self.app_context.tg_context().open_chat_id()),
+            Mode::ReplyInOtherChat(chat_id, _) => Some(chat_id),
+            // The edited messages are not sent again.
+            Mode::Edit(_) => None,
+        };
+        let sent = if split {
+            let parts = split_message(&text, MAX_MESSAGE_LENGTH);
+            self.input
+                .send_message_parts(Arc::clone(&self.app_context), parts)
+        } else {
+            self.input.send_message(Arc::clone(&self.app_context))
+        };
+        if sent {
+            if let Some(chat_id) = chat_id {
+                self.push_history(chat_id, text);
+            }
+            self.history_index = None;
+            self.draft.clear();
+        }
+    }
+    /// Handle Enter on an empty prompt according to the `prompt_empty_enter`
+    /// configuration.
+    fn handle_empty_enter(&mut self) {
+        let empty_enter = self.app_context.app_config().prompt_empty_enter;
+        match empty_enter {
+            EmptyEnter::Ignore => {}
+            EmptyEnter::FocusChatList => {
+                self.app_context
+                    .action_tx()
+                    .send(Action::FocusComponent(ComponentName::ChatList))
+                    .unwrap();
+            }
+            EmptyEnter::MarkRead => {
+                self.app_context
+                    .tg_context()
+                    .try_send_event(Event::ViewAllMessages);
+            }
+        }
+    }
+    /// Remember a message sent in a chat, the oldest messages are forgotten
+    /// past `PROMPT_HISTORY_LIMIT`.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat where the message was sent.
+    /// * `text` - The text of the message.
+    fn push_history(&mut self, chat_id: i64, text: String) {
+        let history = self.history.entry(chat_id).or_default();
+        if history.front() != Some(&text) {
+            history.push_front(text);
+            history.truncate(PROMPT_HISTORY_LIMIT);
+        }
+    }
+    /// Recall the previous message sent in the open chat, the draft is kept
+    /// to be restored. The input is a copy, editing it does not change the
+    /// history.
+    ///
+    /// # Returns
+    /// * `bool` - Whether a message has been recalled.
+    fn recall_previous(&mut self) -> bool {
+        let chat_id = self.app_context.tg_context().open_chat_id();
+        let index = self.history_index.map_or(0, |i| i + 1);
+        let Some(text) = self.history.get(&chat_id).and_then(|h| h.get(index)) else {
+            return false;
+        };
+        let text = text.clone();
+        if self.history_index.is_none() {
+            self.draft = self.input.text_to_string();
+        }
+        self.history_index = Some(index);
+        self.input.set_text(&text);
+        true
+    }
+    /// Recall the next message sent in the open chat, the draft is restored
+    /// past the most recent one.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the input has changed.
+    fn recall_next(&mut self) -> bool {
+        let Some(index) = self.history_index else {
+            return false;
+        };
+        let chat_id = self.app_context.tg_context().open_chat_id();
+        let text = match index.checked_sub(1) {
+            Some(index) => {
+                let Some(text) = self.history.get(&chat_id).and_then(|h| h.get(index)) else {
+                    return false;
+                };
+                self.history_index = Some(index);
+                text.clone()
+            }
+            None => {
+                self.history_index = None;
+                std::mem::take(&mut self.draft)
+            }
+        };
+        self.input.set_text(&text);
+        true
+    }
+    /// Paste text into the prompt. A message is limited to
+    /// `MAX_MESSAGE_LENGTH` characters, unless `prompt_split_long_messages`
+    /// is enabled the text that does not fit is dropped, with a warning.
+    ///
+    /// # Arguments
+    /// * `text` - The text to paste.
+    fn paste(&mut self, mut text: String) {
+        let room = MAX_MESSAGE_LENGTH.saturating_sub(self.input.char_count());
+        let split = self.app_context.app_config().prompt_split_long_messages;
+        let end = self.input.paste_end(&text, room);
+        if end < text.len() {
+            let message = if split {
+                format!(
+                    "The text is too long for a message of {} characters, it will be sent in several messages",
+                    MAX_MESSAGE_LENGTH
+                )
+            } else {
+                let dropped = text[end..].chars().count();
+                text.truncate(end);
+                format!(
+                    "The pasted text is too long for a message of {} characters, its last {} characters were dropped",
+                    MAX_MESSAGE_LENGTH, dropped
+                )
+            };
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(message, NotifyLevel::Warn))
+                .unwrap();
+        }
+        self.input.paste(text);
+    }
+    /// Send an animated dice to the open chat. The emoji of the dice is the
+    /// text of the prompt if it is one of the dice, otherwise the prompt must
+    /// be empty and a 🎲 is rolled.
+    fn send_dice(&mut self) {
+        let text = self.input.text_to_string();
+        let emoji = match text.trim() {
+            "" => DICE_EMOJIS[0].to_string(),
+            emoji if DICE_EMOJIS.contains(&emoji) => emoji.to_string(),
+            _ => {
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        format!("Type one of {} to roll it", DICE_EMOJIS.join(" ")),
+                        NotifyLevel::Warn,
+                    ))
+                    .unwrap();
+                return;
+            }
+        };
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::SendDice(emoji));
+        self.input.text = vec![vec![]];
+        self.input.set_prompt_size_to_one_focused();
+    }
+    /// Send a location to the open chat. The prompt holds the coordinates,
+    /// optionally followed by the duration of a live location like `15m`,
+    /// without coordinates the configured location is sent.
+    fn send_location(&mut self) {
+        let text = self.input.text_to_string();
+        let location = parse_location(&text, &self.app_context.app_config().location);
+        if let Err(message) = location {
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(message, NotifyLevel::Warn))
+                .unwrap();
+            return;
+        }
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::SendLocation(text.trim().to_string()));
+        self.input.text = vec![vec![]];
+        self.input.set_prompt_size_to_one_focused();
+    }
+    /// Get the placeholder shown while the input is empty: the one of the
+    /// force reply while still replying to the message of the bot, otherwise
+    /// the name of the open chat if `prompt_placeholder` is enabled.
+    fn placeholder(&self) -> Option<String> {
+        if !self.input.text.iter().all(|l| l.is_empty()) {
+            return None;
+        }
+        if let Some((message_id, placeholder)) = self.force_reply_placeholder.as_ref() {
+            let replying = matches!(self.input.mode, Mode::Reply(id) if id == *message_id);
+            if replying && !placeholder.is_empty() {
+                return Some(placeholder.clone());
+            }
+        }
+        if !self.app_context.app_config().prompt_placeholder {
+            return None;
+        }
+        self.app_context
+            .tg_context()
+            .name_of_open_chat_id()
+            .map(|name| format!("Message {}…", name))
+    }
+    /// Get the hint that describes how to send a message and how to insert a
+    /// newline with the active binding.
+    ///
+    /// # Returns
+    /// * `String` - The hint.
+    fn enter_hint(&self) -> String {
+        let app_config = self.app_context.app_config();
+        let modifier = Event::Key(KeyCode::Enter, app_config.prompt_enter_modifier);
+        if app_config.prompt_send_on_enter {
+            format!("Enter: send, {}: newline", modifier)
+        } else {
+            format!("{}: send, Enter: newline", modifier)
+        }
+    }
 }
 
 /// Implement the `HandleFocus` trait for the `PromptWindow` struct.
@@ -600,6 +1092,8 @@ impl HandleFocus for PromptWindow {
         self.focused = false;
         self.input.mode = Mode::Normal;
         self.input.text = vec![vec![]];
+        self.history_index = None;
+        self.draft.clear();
     }
 }
 
@@ -705,7 +1199,7 @@ impl Component for PromptWindow {
                 (KeyCode::Char('v'), Modifiers { control: true, .. }) => {
                     if let Ok(mut clipboard) = Clipboard::new() {
                         if let Ok(text) = clipboard.get_text() {
-                            self.input.paste(text);
+                            self.paste(text);
                         }
                     }
                 }
@@ -720,9 +1214,11 @@ impl Component for PromptWindow {
                     self.input.move_cursor_to_next_word();
                 }
 
-                (KeyCode::Enter, Modifiers { alt: true, .. }) => {
-                    self.input.unselect_all();
-                    self.input.send_message(Arc::clone(&self.app_context));
+                (KeyCode::Enter, modifiers)
+                    if KeyModifiers::from(modifiers.clone())
+                        .contains(self.app_context.app_config().prompt_enter_modifier) =>
+                {
+                    self.handle_enter(true);
                 }
 
                 (KeyCode::Backspace, Modifiers { control: true, .. })
@@ -758,8 +1254,7 @@ impl Component for PromptWindow {
                 }
 
                 (KeyCode::Enter, ..) => {
-                    self.input.unselect_all();
-                    self.input.insert_newline();
+                    self.handle_enter(false);
                 }
 
                 (KeyCode::Left, ..) => {
@@ -774,18 +1269,29 @@ impl Component for PromptWindow {
 
                 (KeyCode::Up, ..) => {
                     self.input.unselect_all();
-                    self.input.move_cursor_up();
+                    // Like a shell, the history is recalled from the first line.
+                    if self.input.cursor_y() > 0 || !self.recall_previous() {
+                        self.input.move_cursor_up();
+                    }
                 }
 
                 (KeyCode::Down, ..) => {
                     self.input.unselect_all();
-                    self.input.move_cursor_down();
+                    if self.input.cursor_y() + 1 < self.input.text.len() || !self.recall_next() {
+                        self.input.move_cursor_down();
+                    }
                 }
                 _ => {}
             },
             Action::Paste(text) => {
+                // The link is pasted anyway, it may be meant to be shared.
+                if let Some(invite_link) = invite_link(&text) {
+                    self.app_context
+                        .tg_context()
+                        .try_send_event(Event::CheckChatInviteLink(invite_link));
+                }
                 self.input.unselect_all();
-                self.input.paste(text);
+                self.paste(text);
             }
             Action::EditMessage(message_id, message) => {
                 self.input.edit_message(message_id, message);
@@ -793,6 +1299,16 @@ impl Component for PromptWindow {
             Action::ReplyMessage(message_id, _) => {
                 self.input.mode = Mode::Reply(message_id);
             }
+            Action::ReplyInOtherChat(chat_id, message_id, _) => {
+                self.input.mode = Mode::ReplyInOtherChat(chat_id, message_id);
+            }
+            Action::ForceReply(message_id, _, placeholder) => {
+                self.input.mode = Mode::Reply(message_id);
+                self.force_reply_placeholder = Some((message_id, placeholder));
+            }
+            Action::PromptSendDice => self.send_dice(),
+            Action::PromptSendLocation => self.send_location(),
+            Action::PromptSendSplit => self.send(true),
             _ => {}
         }
     }
@@ -831,45 +1347,499 @@ impl Component for PromptWindow {
             })
             .collect::<Vec<Line>>();
 
-        let (text, style_text, style_border_focused) = if self.focused {
-            self.input.restore_prompt_size();
-            (
-                text,
-                self.app_context.style_prompt(),
-                self.app_context.style_border_component_focused(),
-            )
-        } else {
-            self.input.set_prompt_size_to_one_unfocused();
-            (
-                vec![Line::from(format!(
-                    "Press {} to send a message",
-                    self.focused_keys
-                        .iter()
-                        .map(|e| e.to_string())
-                        .collect::<Vec<String>>()
-                        .join(" or ")
-                ))],
-                self.app_context.style_prompt_message_preview_text(),
-                self.app_context.style_prompt(),
-            )
-        };
+        let (text, style_text, style_border_focused) =
+            if let Some(placeholder) = self.placeholder().filter(|_| self.focused) {
+                (
+                    vec![Line::from(placeholder)],
+                    self.app_context.style_prompt_message_preview_text(),
+                    self.app_context.style_border_component_focused(),
+                )
+            } else if self.focused {
+                self.input.restore_prompt_size();
+                (
+                    text,
+                    self.app_context.style_prompt(),
+                    self.app_context.style_border_component_focused(),
+                )
+            } else {
+                self.input.set_prompt_size_to_one_unfocused();
+                (
+                    vec![Line::from(format!(
+                        "Press {} to send a message",
+                        self.focused_keys
+                            .iter()
+                            .map(|e| e.to_string())
+                            .collect::<Vec<String>>()
+                            .join(" or ")
+                    ))],
+                    self.app_context.style_prompt_message_preview_text(),
+                    self.app_context.style_prompt(),
+                )
+            };
+
+        // Keep the line of the cursor visible when the input has more lines
+        // than the prompt shows.
+        let visible_lines = (area.height as usize).saturating_sub(2).max(1);
+        let hidden_lines = text.len().saturating_sub(visible_lines);
+        let cursor_y = self.input.cursor_y();
+        if cursor_y < self.scroll {
+            self.scroll = cursor_y;
+        } else if cursor_y >= self.scroll + visible_lines {
+            self.scroll = cursor_y + 1 - visible_lines;
+        }
+        self.scroll = self.scroll.min(hidden_lines);
 
-        let block = Block::new()
+        let mut block = Block::new()
             .border_set(collapsed_top_and_left_border_set)
             .border_style(style_border_focused)
             .borders(Borders::ALL)
-            .title(self.name.as_str());
+            .title(if self.focused {
+                format!("{} ({})", self.name, self.enter_hint())
+            } else {
+                self.name.clone()
+            });
+        if hidden_lines > 0 {
+            block = block.title_bottom(Line::from(format!("[+{} lines]", hidden_lines)));
+        }
+        let char_counter = self.app_context.app_config().prompt_char_counter;
+        if self.focused && char_counter {
+            let char_count = self.input.char_count();
+            let style_counter = if char_count >= CHAR_COUNTER_WARNING {
+                self.app_context.style_prompt_char_counter_warning()
+            } else {
+                self.app_context.style_prompt_char_counter()
+            };
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {}/{} ", char_count, MAX_MESSAGE_LENGTH),
+                    style_counter,
+                ))
+                .right_aligned(),
+            );
+        }
 
-        let input = Paragraph::new(text).style(style_text).block(block);
+        let input = Paragraph::new(text)
+            .style(style_text)
+            .block(block)
+            .scroll((self.scroll as u16, 0));
 
         frame.render_widget(input, area);
 
         if self.focused {
             frame.set_cursor_position(Position {
-                x: area.x + self.input.cursor_x() as u16 + 1,
-                y: area.y + self.input.cursor_y() as u16 + 1,
+                x: area.x + self.input.cursor_column() as u16 + 1,
+                y: area.y + (cursor_y - self.scroll) as u16 + 1,
             });
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_types_q() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut prompt = PromptWindow::new(Arc::new(AppContext::new_default()));
+        prompt.register_action_handler(tx).unwrap();
+        prompt.update_input(Rect::new(0, 0, 40, 3));
+        prompt.focus();
+        prompt.update(Action::Key(
+            KeyCode::Char('q'),
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+        assert_eq!(prompt.input.text_to_string(), "q");
+        while let Ok(action) = rx.try_recv() {
+            assert_ne!(action, Action::Quit);
+        }
+    }
+
+    #[test]
+    fn test_cursor_column() {
+        let mut prompt = PromptWindow::new(Arc::new(AppContext::new_default()));
+        prompt.update_input(Rect::new(0, 0, 40, 3));
+        prompt.focus();
+        prompt.update(Action::Paste("a漢😀".to_string()));
+        assert_eq!(prompt.input.cursor_column(), 5);
+        prompt.update(Action::Key(
+            KeyCode::Left,
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+        assert_eq!(prompt.input.cursor_column(), 3);
+    }
+
+    #[test]
+    fn test_force_reply_placeholder() {
+        let mut prompt = PromptWindow::new(Arc::new(AppContext::new_default()));
+        prompt.update_input(Rect::new(0, 0, 40, 3));
+        prompt.focus();
+        prompt.update(Action::ForceReply(
+            7,
+            "What is your name?".to_string(),
+            "Your name".to_string(),
+        ));
+        assert!(matches!(prompt.input.mode, Mode::Reply(7)));
+        assert_eq!(prompt.placeholder().as_deref(), Some("Your name"));
+        prompt.update(Action::Key(
+            KeyCode::Char('a'),
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+        assert_eq!(prompt.placeholder(), None);
+    }
+
+    #[test]
+    fn test_prompt_height_is_capped() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut prompt = PromptWindow::new(Arc::new(AppContext::new_default()));
+        prompt.register_action_handler(tx).unwrap();
+        prompt.update_input(Rect::new(0, 0, 40, 3));
+        prompt.focus();
+        let max_lines = prompt.input.max_lines;
+        prompt.update(Action::Paste("a\n".repeat(max_lines * 2)));
+        let mut size = 0;
+        while let Ok(action) = rx.try_recv() {
+            match action {
+                Action::IncreasePromptSize => size += 1,
+                Action::DecreasePromptSize => size -= 1,
+                _ => {}
+            }
+        }
+        assert_eq!(size, max_lines - 1);
+
+        // Removing lines shrinks the prompt only below the maximum.
+        (0..(max_lines * 2 - 2) * 3).for_each(|_| {
+            prompt.update(Action::Key(
+                KeyCode::Backspace,
+                Modifiers::from(KeyModifiers::NONE),
+            ));
+        });
+        while let Ok(action) = rx.try_recv() {
+            if action == Action::DecreasePromptSize {
+                size -= 1;
+            }
+        }
+        assert_eq!(prompt.input.text.len(), 3);
+        assert_eq!(size, 2);
+    }
+
+    #[test]
+    fn test_send_dice() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 40, 3));
+        prompt.focus();
+
+        prompt.update(Action::PromptSendDice);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::SendDice(e)) if e == "🎲"));
+
+        prompt.update(Action::Paste("🎯".to_string()));
+        prompt.update(Action::PromptSendDice);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::SendDice(e)) if e == "🎯"));
+        assert!(prompt.input.text_to_string().trim().is_empty());
+
+        prompt.update(Action::Paste("hello".to_string()));
+        prompt.update(Action::PromptSendDice);
+        assert!(event_rx.try_recv().is_err());
+        assert_eq!(prompt.input.text_to_string().trim(), "hello");
+    }
+
+    #[test]
+    fn test_send_location() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 40, 3));
+        prompt.focus();
+
+        for text in ["48.8584, 2.2945", "48.8584 2.2945 15m", "-33.86,151.2 8h"] {
+            prompt.update(Action::Paste(text.to_string()));
+            prompt.update(Action::PromptSendLocation);
+            assert!(matches!(event_rx.try_recv(), Ok(Event::SendLocation(l)) if l == text));
+            assert!(prompt.input.text_to_string().trim().is_empty());
+        }
+
+        // Without coordinates configured, they must be typed.
+        for text in ["", "15m", "91, 0", "48.8584, 2.2945 25h", "hello"] {
+            prompt.update(Action::Paste(text.to_string()));
+            prompt.update(Action::PromptSendLocation);
+            assert!(event_rx.try_recv().is_err());
+            assert_eq!(prompt.input.text_to_string().trim(), text);
+            assert!(matches!(
+                app_context.action_rx().try_recv(),
+                Ok(Action::Notify(_, NotifyLevel::Warn))
+            ));
+            prompt.input.set_text("");
+        }
+    }
+
+    #[test]
+    fn test_too_long_message_is_not_sent() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        app_context.app_config().prompt_split_long_messages = false;
+        // The prompt is wide enough for the text not to be wrapped.
+        prompt.update_input(Rect::new(0, 0, 5000, 3));
+        prompt.focus();
+        prompt.update(Action::Paste("a".repeat(MAX_MESSAGE_LENGTH)));
+        prompt.update(Action::Key(
+            KeyCode::Char('a'),
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+        let send_with_modifier = !app_context.app_config().prompt_send_on_enter;
+        prompt.handle_enter(send_with_modifier);
+        assert!(event_rx.try_recv().is_err());
+        assert_eq!(prompt.input.char_count(), MAX_MESSAGE_LENGTH + 1);
+        assert!(matches!(
+            app_context.action_rx().try_recv(),
+            Ok(Action::Notify(_, NotifyLevel::Warn))
+        ));
+
+        prompt.update(Action::Key(
+            KeyCode::Backspace,
+            Modifiers::from(KeyModifiers::NONE),
+        ));
+        prompt.handle_enter(send_with_modifier);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::SendMessage(..))));
+    }
+
+    #[test]
+    fn test_paste_too_long_text() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        app_context.app_config().prompt_split_long_messages = false;
+        prompt.update_input(Rect::new(0, 0, 5000, 3));
+        prompt.focus();
+        prompt.update(Action::Paste("hello ".to_string()));
+        prompt.update(Action::Paste("a".repeat(MAX_MESSAGE_LENGTH)));
+        assert_eq!(prompt.input.char_count(), MAX_MESSAGE_LENGTH);
+        assert!(matches!(
+            app_context.action_rx().try_recv(),
+            Ok(Action::Notify(m, NotifyLevel::Warn)) if m.contains("last 6 characters")
+        ));
+    }
+
+    #[test]
+    fn test_paste_too_long_text_wrapped() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        app_context.app_config().prompt_split_long_messages = false;
+        prompt.update_input(Rect::new(0, 0, 12, 3));
+        prompt.focus();
+        prompt.update(Action::Paste("hello world, ".to_string()));
+        prompt.update(Action::Paste("a".repeat(MAX_MESSAGE_LENGTH)));
+        // The newlines of the wrapping count, only what goes past the limit is
+        // dropped.
+        assert_eq!(prompt.input.char_count(), MAX_MESSAGE_LENGTH);
+        let kept = prompt.input.text_to_string().matches('a').count();
+        assert!(matches!(
+            app_context.action_rx().try_recv(),
+            Ok(Action::Notify(m, NotifyLevel::Warn))
+                if m.contains(&format!("last {} characters", MAX_MESSAGE_LENGTH - kept))
+        ));
+    }
+
+    #[test]
+    fn test_split_message() {
+        assert_eq!(split_message("  short  ", 10), ["short"]);
+        assert_eq!(
+            split_message("First one. Second one.", 15),
+            ["First one.", "Second one."]
+        );
+        assert_eq!(
+            split_message("Para one.\n\nPara two here", 16),
+            ["Para one.", "Para two here"]
+        );
+        // A boundary too close to the start is not taken.
+        assert_eq!(
+            split_message("A. long words here", 12),
+            ["A. long", "words here"]
+        );
+        assert_eq!(split_message("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(split_message("😀😀😀", 2), ["😀😀", "😀"]);
+    }
+
+    #[test]
+    fn test_send_long_message_split() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 5000, 3));
+        prompt.focus();
+        prompt.update(Action::ReplyMessage(7, "hi".to_string()));
+        let text = format!("{}\n\n{}", "a".repeat(3000), "b".repeat(3000));
+        prompt.update(Action::Paste(text));
+        // The text is kept whole, with a warning.
+        assert_eq!(prompt.input.char_count(), 6002);
+        assert!(matches!(
+            app_context.action_rx().try_recv(),
+            Ok(Action::Notify(_, NotifyLevel::Warn))
+        ));
+
+        let send_with_modifier = !app_context.app_config().prompt_send_on_enter;
+        prompt.handle_enter(send_with_modifier);
+        assert!(event_rx.try_recv().is_err());
+        assert!(matches!(
+            app_context.action_rx().try_recv(),
+            Ok(Action::ShowConfirm(_, lines, action))
+                if lines[1] == "Send it in 2 messages?" && *action == Action::PromptSendSplit
+        ));
+
+        prompt.update(Action::PromptSendSplit);
+        let Ok(Event::SendMessage(first, Some(reply_to))) = event_rx.try_recv() else {
+            panic!("The first part is not sent");
+        };
+        assert_eq!(first, "a".repeat(3000));
+        assert_eq!(reply_to.message_id, 7);
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::SendMessage(second, None)) if second == "b".repeat(3000)
+        ));
+        assert!(prompt.input.text_to_string().trim().is_empty());
+    }
+
+    #[test]
+    fn test_paste_wraps_like_typing() {
+        let app_context = Arc::new(AppContext::new_default());
+        let mut pasted = PromptWindow::new(Arc::clone(&app_context));
+        let mut typed = PromptWindow::new(Arc::clone(&app_context));
+        for prompt in [&mut pasted, &mut typed] {
+            prompt.update_input(Rect::new(0, 0, 12, 3));
+            prompt.focus();
+            prompt.update(Action::Paste("start end".to_string()));
+            prompt.input.cursor = (6, 0);
+        }
+        let text = "a long line to wrap\n\nand 漢字 😀 after";
+        pasted.update(Action::Paste(text.to_string()));
+        for c in text.chars() {
+            if c == '\n' {
+                typed.input.insert_newline();
+            } else {
+                typed.input.insert(c);
+            }
+        }
+        assert_eq!(pasted.input.text, typed.input.text);
+        assert_eq!(pasted.input.cursor, typed.input.cursor);
+    }
+
+    #[test]
+    fn test_send_with_backend_stopped() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        drop(event_rx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 80, 3));
+        prompt.focus();
+        prompt.update(Action::Paste("hello".to_string()));
+        let send_with_modifier = !app_context.app_config().prompt_send_on_enter;
+        prompt.handle_enter(send_with_modifier);
+        prompt.update(Action::PromptSendDice);
+        // The message is kept to be sent again.
+        assert_eq!(prompt.input.text_to_string().trim(), "hello");
+        assert!(app_context.tg_context().backend_stopped());
+    }
+
+    #[test]
+    fn test_paste_invite_link() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 80, 3));
+        prompt.focus();
+
+        for (text, link) in [
+            ("https://t.me/+AbC-d_1", "https://t.me/+AbC-d_1"),
+            (" t.me/joinchat/AbCd\n", "https://t.me/joinchat/AbCd"),
+            ("telegram.me/+AbCd", "https://t.me/+AbCd"),
+        ] {
+            prompt.update(Action::Paste(text.to_string()));
+            match event_rx.try_recv() {
+                Ok(Event::CheckChatInviteLink(invite_link)) => assert_eq!(invite_link, link),
+                event => panic!("Unexpected event for {text:?}: {event:?}"),
+            }
+        }
+        // A phone number link, a public chat and a sentence are not invite
+        // links, they are only pasted.
+        for text in ["t.me/+12345", "https://t.me/tgt", "join t.me/+AbCd"] {
+            prompt.update(Action::Paste(text.to_string()));
+            assert!(event_rx.try_recv().is_err());
+        }
+        assert!(prompt.input.text_to_string().contains("join t.me/+AbCd"));
+    }
+
+    #[test]
+    fn test_empty_enter() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 80, 3));
+        prompt.focus();
+        let send_with_modifier = !app_context.app_config().prompt_send_on_enter;
+
+        // By default an empty message is neither sent nor anything else.
+        prompt.update(Action::Paste("  ".to_string()));
+        prompt.handle_enter(send_with_modifier);
+        assert!(event_rx.try_recv().is_err());
+        assert!(app_context.action_rx().try_recv().is_err());
+
+        app_context.app_config().prompt_empty_enter = EmptyEnter::MarkRead;
+        prompt.handle_enter(send_with_modifier);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::ViewAllMessages)));
+
+        app_context.app_config().prompt_empty_enter = EmptyEnter::FocusChatList;
+        prompt.handle_enter(send_with_modifier);
+        assert_eq!(
+            app_context.action_rx().try_recv().ok(),
+            Some(Action::FocusComponent(ComponentName::ChatList))
+        );
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_history_recall() {
+        let app_context = Arc::new(AppContext::new_default());
+        let (event_tx, _event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        app_context.tg_context().set_open_chat_id(1);
+        let mut prompt = PromptWindow::new(Arc::clone(&app_context));
+        prompt.update_input(Rect::new(0, 0, 80, 3));
+        prompt.focus();
+        let send_with_modifier = !app_context.app_config().prompt_send_on_enter;
+        for message in ["first", "second"] {
+            prompt.update(Action::Paste(message.to_string()));
+            prompt.handle_enter(send_with_modifier);
+        }
+        let key = |code| Action::Key(code, Modifiers::from(KeyModifiers::NONE));
+
+        prompt.update(Action::Paste("draft".to_string()));
+        prompt.update(key(KeyCode::Up));
+        assert_eq!(prompt.input.text_to_string(), "second");
+        prompt.update(key(KeyCode::Up));
+        prompt.update(key(KeyCode::Up));
+        assert_eq!(prompt.input.text_to_string(), "first");
+        // Editing the recalled message leaves the history as it was.
+        prompt.update(key(KeyCode::Char('!')));
+        prompt.update(key(KeyCode::Down));
+        assert_eq!(prompt.input.text_to_string(), "second");
+        prompt.update(key(KeyCode::Up));
+        assert_eq!(prompt.input.text_to_string(), "first");
+        prompt.update(key(KeyCode::Down));
+        prompt.update(key(KeyCode::Down));
+        assert_eq!(prompt.input.text_to_string(), "draft");
+
+        // The history belongs to the chat.
+        app_context.tg_context().set_open_chat_id(2);
+        prompt.update(key(KeyCode::Up));
+        assert_eq!(prompt.input.text_to_string(), "draft");
+    }
+}
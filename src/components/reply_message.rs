@@ -3,6 +3,7 @@ use {
         action::Action,
         app_context::AppContext,
         components::component_traits::{Component, HandleFocus},
+        utils::strip_emoji_line,
     },
     ratatui::{
         layout::Rect,
@@ -81,6 +82,9 @@ impl Component for ReplyMessage {
             (*self.app_context.tg_context().reply_message_text()).to_string(),
             self.app_context.style_reply_message_message_text(),
         )])]);
+        if self.app_context.app_config().strip_emoji {
+            text.lines.iter_mut().for_each(strip_emoji_line);
+        }
 
         let collapsed_border = Set {
             top_left: NORMAL.vertical_right,
@@ -1,19 +1,42 @@
 use {
     crate::{
-        action::Action,
+        action::{Action, NotifyLevel},
         app_context::AppContext,
+        component_name::ComponentName,
         components::component_traits::{Component, HandleFocus},
         event::Event,
+        utils::strip_emoji_line,
     },
+    crossterm::event::{KeyCode, KeyModifiers},
     ratatui::{
         layout::{Alignment, Rect},
+        style::Style,
         text::{Line, Span},
         widgets::{block::Block, Borders, Paragraph, Wrap},
     },
-    std::sync::Arc,
+    std::{
+        collections::VecDeque,
+        sync::Arc,
+        time::{Duration, Instant},
+    },
     tokio::sync::mpsc::UnboundedSender,
 };
 
+/// The maximum number of notifications shown at once in the status bar, the
+/// oldest one is dropped when a new one arrives.
+const MAX_NOTIFICATIONS: usize = 3;
+
+/// `Notification` is a short message shown in the status bar until it
+/// expires.
+struct Notification {
+    /// The text of the notification.
+    message: String,
+    /// The severity of the notification.
+    level: NotifyLevel,
+    /// When the notification was received.
+    received_at: Instant,
+}
+
 /// `StatusBar` is a struct that represents a status bar.
 /// It is responsible for managing the layout and rendering of the status bar.
 pub struct StatusBar {
@@ -29,6 +52,12 @@ pub struct StatusBar {
     terminal_area: Rect,
     /// The last key pressed.
     last_key: Event,
+    /// The component focused in the core window, the key hints depend on it.
+    component_focused: Option<ComponentName>,
+    /// Indicates whether the mouse is captured by the application.
+    mouse_captured: bool,
+    /// The notifications to show in the status bar, the oldest first.
+    notifications: VecDeque<Notification>,
 }
 /// Implementation of `StatusBar` struct.
 impl StatusBar {
@@ -44,6 +73,9 @@ impl StatusBar {
         let name = "".to_string();
         let terminal_area = Rect::default();
         let last_key = Event::Unknown;
+        let component_focused = None;
+        let mouse_captured = app_context.app_config().mouse_support;
+        let notifications = VecDeque::new();
         let focused = false;
 
         StatusBar {
@@ -52,6 +84,9 @@ impl StatusBar {
             name,
             terminal_area,
             last_key,
+            component_focused,
+            mouse_captured,
+            notifications,
             focused,
         }
     }
@@ -66,6 +101,142 @@ impl StatusBar {
         self.name = name.as_ref().to_string();
         self
     }
+
+    /// Get the key bindings worth hinting in the current context, as pairs of
+    /// action and short description.
+    ///
+    /// # Returns
+    /// * `Vec<(Action, &'static str)>` - The hinted actions.
+    fn context_hints(&self) -> Vec<(Action, &'static str)> {
+        match self.component_focused {
+            Some(ComponentName::ChatList) => vec![
+                (Action::ChatListOpen, "open"),
+                (Action::FocusComponent(ComponentName::Prompt), "write"),
+                (Action::UnfocusComponent, "back"),
+                (Action::TryQuit, "quit"),
+            ],
+            Some(ComponentName::MessageTags) => vec![
+                (Action::ChatListOpen, "jump"),
+                (Action::ToggleMessageTags, "close"),
+                (Action::TryQuit, "quit"),
+            ],
+            Some(ComponentName::PinnedMessages) => vec![
+                (Action::ChatListOpen, "jump"),
+                (Action::FocusComponent(ComponentName::Chat), "close"),
+                (Action::TryQuit, "quit"),
+            ],
+            Some(ComponentName::BlockedUsers) => vec![
+                (Action::ChatListOpen, "unblock"),
+                (Action::FocusComponent(ComponentName::ChatList), "close"),
+                (Action::TryQuit, "quit"),
+            ],
+            Some(ComponentName::UserProfile) => vec![
+                (Action::ChatListOpen, "run"),
+                (Action::FocusComponent(ComponentName::Chat), "close"),
+                (Action::TryQuit, "quit"),
+            ],
+            Some(ComponentName::Stickers) => vec![
+                (Action::ToggleStickers, "close"),
+                (Action::UnfocusComponent, "back"),
+            ],
+            Some(ComponentName::OnlineContacts) => vec![
+                (Action::ChatListOpen, "open"),
+                (Action::ToggleOnlineContacts, "close"),
+                (Action::TryQuit, "quit"),
+            ],
+            Some(ComponentName::Chat) => vec![
+                (Action::ShowChatWindowReply, "reply"),
+                (Action::ChatWindowEdit, "edit"),
+                (Action::ChatWindowCopy, "copy"),
+                (Action::ChatWindowJumpToLatest, "latest"),
+                (Action::UnfocusComponent, "back"),
+                (Action::TryQuit, "quit"),
+            ],
+            // The core window does not quit from the prompt, it takes the
+            // letters.
+            Some(ComponentName::Prompt) => vec![
+                (Action::FocusComponent(ComponentName::Chat), "chat"),
+                (Action::UnfocusComponent, "back"),
+            ],
+            _ => vec![
+                (Action::FocusComponent(ComponentName::ChatList), "chats"),
+                (Action::FocusComponent(ComponentName::Chat), "chat"),
+                (Action::FocusComponent(ComponentName::Prompt), "prompt"),
+                (Action::TryQuit, "quit"),
+            ],
+        }
+    }
+
+    /// Get the shortest key bound to an action in the current context.
+    /// The bindings of the focused component are preferred over the ones of
+    /// the core window. In the prompt the single characters are typed, so
+    /// they are not hinted.
+    ///
+    /// # Arguments
+    /// * `action` - The action to find the key of.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The key, or `None` if the action is not bound.
+    fn key_of(&self, action: &Action) -> Option<String> {
+        let keymap_config = self.app_context.keymap_config();
+        let component = self.component_focused.unwrap_or(ComponentName::CoreWindow);
+        let mut keys = keymap_config.get_key_of_single_action(component, action.clone());
+        if keys.is_empty() {
+            keys =
+                keymap_config.get_key_of_single_action(ComponentName::CoreWindow, action.clone());
+        }
+        keys.into_iter()
+            .filter(|key| {
+                self.component_focused != Some(ComponentName::Prompt)
+                    || !matches!(
+                        key,
+                        Event::Key(KeyCode::Char(_), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                    )
+            })
+            .map(|key| key.to_string())
+            .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+    }
+
+    /// Add a notification, replacing an identical one still shown and
+    /// dropping the oldest one when there are too many.
+    ///
+    /// # Arguments
+    /// * `message` - The text of the notification.
+    /// * `level` - The severity of the notification.
+    fn notify(&mut self, message: String, level: NotifyLevel) {
+        self.notifications
+            .retain(|n| n.message != message || n.level != level);
+        if self.notifications.len() == MAX_NOTIFICATIONS {
+            self.notifications.pop_front();
+        }
+        self.notifications.push_back(Notification {
+            message,
+            level,
+            received_at: Instant::now(),
+        });
+    }
+
+    /// Remove the notifications older than the `notification_timeout`.
+    ///
+    /// # Arguments
+    /// * `now` - The current instant.
+    fn expire_notifications(&mut self, now: Instant) {
+        let timeout = Duration::from_secs(self.app_context.app_config().notification_timeout);
+        self.notifications
+            .retain(|n| now.duration_since(n.received_at) < timeout);
+    }
+
+    /// Get the style of a notification according to its level.
+    ///
+    /// # Arguments
+    /// * `level` - The severity of the notification.
+    fn notification_style(&self, level: NotifyLevel) -> Style {
+        match level {
+            NotifyLevel::Info => self.app_context.style_status_bar_status_message(),
+            NotifyLevel::Warn => self.app_context.style_status_bar_notify_warn(),
+            NotifyLevel::Error => self.app_context.style_status_bar_notify_error(),
+        }
+    }
 }
 
 /// Implement the `HandleFocus` trait for the `StatusBar` struct.
@@ -94,6 +265,11 @@ impl Component for StatusBar {
                 self.terminal_area = area;
             }
             Action::Key(key, modifiers) => self.last_key = Event::Key(key, modifiers.into()),
+            Action::FocusComponent(component_name) => self.component_focused = Some(component_name),
+            Action::UnfocusComponent => self.component_focused = None,
+            Action::ToggleMouseCapture => self.mouse_captured = !self.mouse_captured,
+            Action::StatusMessage(message) => self.notify(message, NotifyLevel::Info),
+            Action::Notify(message, level) => self.notify(message, level),
             _ => {}
         }
     }
@@ -104,21 +280,30 @@ impl Component for StatusBar {
             .tg_context()
             .name_of_open_chat_id()
             .unwrap_or_default();
-        let text = vec![Line::from(vec![
-            Span::styled(
-                "Press ",
-                self.app_context.style_status_bar_message_quit_text(),
-            ),
-            Span::styled("q ", self.app_context.style_status_bar_message_quit_key()),
-            Span::styled("or ", self.app_context.style_status_bar_message_quit_text()),
+        let focused = self
+            .component_focused
+            .map_or_else(|| "None".to_string(), |name| name.to_string());
+        let mut spans = vec![
             Span::styled(
-                "ctrl+c ",
-                self.app_context.style_status_bar_message_quit_key(),
-            ),
-            Span::styled(
-                "to quit",
-                self.app_context.style_status_bar_message_quit_text(),
+                "Focus: ",
+                self.app_context.style_status_bar_open_chat_text(),
             ),
+            Span::styled(focused, self.app_context.style_status_bar_open_chat_name()),
+        ];
+        for (action, description) in self.context_hints() {
+            if let Some(key) = self.key_of(&action) {
+                spans.push(Span::raw("   "));
+                spans.push(Span::styled(
+                    key,
+                    self.app_context.style_status_bar_message_quit_key(),
+                ));
+                spans.push(Span::styled(
+                    format!(" {}", description),
+                    self.app_context.style_status_bar_message_quit_text(),
+                ));
+            }
+        }
+        spans.extend([
             //
             Span::raw("     "),
             Span::styled(
@@ -151,7 +336,33 @@ impl Component for StatusBar {
                 self.terminal_area.height.to_string(),
                 self.app_context.style_status_bar_size_info_numbers(),
             ),
-        ])];
+        ]);
+        if !self.mouse_captured {
+            spans.push(Span::raw("     "));
+            spans.push(Span::styled(
+                "Mouse: off",
+                self.app_context.style_status_bar_press_key_text(),
+            ));
+        }
+        let mut text = vec![Line::from(spans)];
+        if let Some(connection_status) = self.app_context.tg_context().connection_status() {
+            text[0].spans.push(Span::raw("     "));
+            text[0].spans.push(Span::styled(
+                connection_status,
+                self.app_context.style_status_bar_connection_state(),
+            ));
+        }
+        self.expire_notifications(Instant::now());
+        for notification in self.notifications.iter() {
+            text[0].spans.push(Span::raw("     "));
+            text[0].spans.push(Span::styled(
+                notification.message.clone(),
+                self.notification_style(notification.level),
+            ));
+        }
+        if self.app_context.app_config().strip_emoji {
+            text.iter_mut().for_each(strip_emoji_line);
+        }
 
         let paragraph = Paragraph::new(text)
             .block(Block::new().title(self.name.as_str()).borders(Borders::ALL))
@@ -164,3 +375,58 @@ impl Component for StatusBar {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_stack_and_expire() {
+        let mut status_bar = StatusBar::new(Arc::new(AppContext::new_default()));
+        status_bar.update(Action::StatusMessage("one".to_string()));
+        status_bar.update(Action::Notify("two".to_string(), NotifyLevel::Warn));
+        status_bar.update(Action::Notify("one".to_string(), NotifyLevel::Info));
+        let messages: Vec<_> = status_bar
+            .notifications
+            .iter()
+            .map(|n| n.message.as_str())
+            .collect();
+        assert_eq!(messages, ["two", "one"]);
+
+        status_bar.update(Action::Notify("three".to_string(), NotifyLevel::Error));
+        status_bar.update(Action::Notify("four".to_string(), NotifyLevel::Error));
+        assert_eq!(status_bar.notifications.len(), MAX_NOTIFICATIONS);
+        assert_eq!(status_bar.notifications[0].message, "one");
+
+        let timeout = status_bar.app_context.app_config().notification_timeout;
+        status_bar.expire_notifications(Instant::now());
+        assert_eq!(status_bar.notifications.len(), MAX_NOTIFICATIONS);
+        status_bar.expire_notifications(Instant::now() + Duration::from_secs(timeout));
+        assert!(status_bar.notifications.is_empty());
+    }
+
+    #[test]
+    fn test_key_hints_follow_focus() {
+        let mut status_bar = StatusBar::new(Arc::new(AppContext::new_default()));
+        assert_eq!(status_bar.key_of(&Action::TryQuit).as_deref(), Some("q"));
+        status_bar.update(Action::FocusComponent(ComponentName::Chat));
+        assert_eq!(
+            status_bar.key_of(&Action::ShowChatWindowReply).as_deref(),
+            Some("r")
+        );
+        // The prompt and the stickers take the letters, quitting is not
+        // possible there.
+        status_bar.update(Action::FocusComponent(ComponentName::Prompt));
+        assert!(!status_bar
+            .context_hints()
+            .iter()
+            .any(|(action, _)| *action == Action::TryQuit));
+        status_bar.update(Action::FocusComponent(ComponentName::Stickers));
+        assert!(!status_bar
+            .context_hints()
+            .iter()
+            .any(|(action, _)| *action == Action::TryQuit));
+        status_bar.update(Action::UnfocusComponent);
+        assert_eq!(status_bar.component_focused, None);
+    }
+}
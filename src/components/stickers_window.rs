@@ -0,0 +1,408 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    component_name::ComponentName,
+    components::component_traits::{Component, HandleFocus},
+    event::Event,
+    tg::sticker_sets::{StickerEntry, StickerSetEntry},
+    utils::strip_emoji_line,
+};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+use unicode_width::UnicodeWidthStr;
+
+/// The width of a sticker in the grid, its emoji is centered in it.
+const STICKER_CELL_WIDTH: u16 = 4;
+
+/// `StickersWindow` is a struct that represents the grid of the stickers of
+/// the sticker sets installed by the user, shown in place of the chat.
+/// The stickers are selected with the arrow keys and sent to the open chat
+/// with Enter, typing searches the sticker sets by name.
+pub struct StickersWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `StickersWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The text searched in the titles and the names of the sticker sets.
+    query: String,
+    /// The selected sticker, as the index of its set among the sets matching
+    /// the search and its index in the set.
+    selected: (usize, usize),
+    /// The number of stickers in a row of the grid at the last draw.
+    columns: usize,
+    /// Indicates whether the sticker sets have been requested.
+    requested: bool,
+    /// Indicates whether the `StickersWindow` is focused or not.
+    focused: bool,
+}
+
+impl StickersWindow {
+    /// Create a new instance of the `StickersWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `StickersWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let name = "".to_string();
+        let action_tx = None;
+        let query = String::new();
+        let selected = (0, 0);
+        let columns = 1;
+        let requested = false;
+        let focused = false;
+        StickersWindow {
+            app_context,
+            name,
+            action_tx,
+            query,
+            selected,
+            columns,
+            requested,
+            focused,
+        }
+    }
+    /// Set the name of the `StickersWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `StickersWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `StickersWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Apply a function to the sticker sets matching the search, `None`
+    /// while they are loading. The sets are borrowed, not copied.
+    ///
+    /// # Arguments
+    /// * `f` - The function to apply to the sticker sets.
+    fn with_sticker_sets<R>(&self, f: impl FnOnce(Option<&[&StickerSetEntry]>) -> R) -> R {
+        let tg_context = self.app_context.tg_context();
+        let sticker_sets = tg_context.sticker_sets();
+        match sticker_sets.as_ref() {
+            Some(sets) => {
+                let sets = sets
+                    .iter()
+                    .filter(|set| !set.stickers.is_empty() && set.matches(&self.query))
+                    .collect::<Vec<_>>();
+                f(Some(&sets))
+            }
+            None => f(None),
+        }
+    }
+
+    /// Move the selection in the grid. The selection moves to the previous
+    /// or the next set past the first or the last row of a set.
+    ///
+    /// # Arguments
+    /// * `key` - The arrow key pressed.
+    fn move_selection(&mut self, key: KeyCode) {
+        let Some(lens) = self.with_sticker_sets(|sets| {
+            sets.map(|sets| {
+                sets.iter()
+                    .map(|set| set.stickers.len())
+                    .collect::<Vec<_>>()
+            })
+        }) else {
+            return;
+        };
+        if lens.is_empty() {
+            return;
+        }
+        let columns = self.columns.max(1);
+        let len = |set: usize| lens[set];
+        // The sets may have changed since the selection was made.
+        let set = self.selected.0.min(lens.len() - 1);
+        let sticker = self.selected.1.min(len(set) - 1);
+        self.selected = match key {
+            KeyCode::Right if sticker + 1 < len(set) => (set, sticker + 1),
+            KeyCode::Right if set + 1 < lens.len() => (set + 1, 0),
+            KeyCode::Left if sticker > 0 => (set, sticker - 1),
+            KeyCode::Left if set > 0 => (set - 1, len(set - 1) - 1),
+            KeyCode::Down if sticker + columns < len(set) => (set, sticker + columns),
+            // The last row of a set may be shorter than the others.
+            KeyCode::Down if sticker / columns < (len(set) - 1) / columns => (set, len(set) - 1),
+            KeyCode::Down if set + 1 < lens.len() => {
+                (set + 1, (sticker % columns).min(len(set + 1) - 1))
+            }
+            KeyCode::Up if sticker >= columns => (set, sticker - columns),
+            KeyCode::Up if set > 0 => {
+                let last_row = (len(set - 1) - 1) / columns * columns;
+                (
+                    set - 1,
+                    (last_row + sticker % columns).min(len(set - 1) - 1),
+                )
+            }
+            _ => (set, sticker),
+        };
+    }
+
+    /// Send the selected sticker to the open chat and go back to the prompt.
+    fn send_selected(&mut self) {
+        let Some(StickerEntry { file_id, emoji }) = self.with_sticker_sets(|sets| {
+            sets?
+                .get(self.selected.0)
+                .and_then(|set| set.stickers.get(self.selected.1))
+                .cloned()
+        }) else {
+            return;
+        };
+        if self.app_context.tg_context().open_chat_id() == 0 {
+            if let Some(action_tx) = self.action_tx.as_ref() {
+                action_tx
+                    .send(Action::StatusMessage(
+                        "Open a chat to send a sticker".to_string(),
+                    ))
+                    .unwrap();
+            }
+            return;
+        }
+        self.app_context
+            .tg_context()
+            .try_send_event(Event::SendSticker(file_id, emoji));
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::FocusComponent(ComponentName::Prompt))
+                .unwrap();
+        }
+    }
+
+    /// Get the lines of the grid, with the line of the selected sticker.
+    ///
+    /// # Arguments
+    /// * `sets` - The sticker sets matching the search.
+    fn grid_lines(&self, sets: &[&StickerSetEntry]) -> (Vec<Line<'static>>, usize) {
+        let columns = self.columns.max(1);
+        let strip_emoji = self.app_context.app_config().strip_emoji;
+        let mut lines = vec![];
+        let mut selected_line = 0;
+        for (i, set) in sets.iter().enumerate() {
+            let mut title = Line::from(Span::styled(
+                format!("{} ({})", set.title, set.name),
+                self.app_context.style_chat_list_group_header(),
+            ));
+            if strip_emoji {
+                strip_emoji_line(&mut title);
+            }
+            lines.push(title);
+            for (row, stickers) in set.stickers.chunks(columns).enumerate() {
+                if i == self.selected.0 && row == self.selected.1 / columns {
+                    selected_line = lines.len();
+                }
+                lines.push(Line::from(
+                    stickers
+                        .iter()
+                        .enumerate()
+                        .map(|(j, sticker)| {
+                            let padding = (STICKER_CELL_WIDTH as usize)
+                                .saturating_sub(sticker.emoji.width() + 1);
+                            // A sticker is only shown by its emoji, without
+                            // it the cell stays in place but blank.
+                            let emoji = if strip_emoji {
+                                " ".repeat(sticker.emoji.width())
+                            } else {
+                                sticker.emoji.clone()
+                            };
+                            let cell = format!(" {}{}", emoji, " ".repeat(padding));
+                            if self.focused && (i, row * columns + j) == self.selected {
+                                Span::styled(cell, self.app_context.style_chat_list_item_selected())
+                            } else {
+                                Span::raw(cell)
+                            }
+                        })
+                        .collect::<Vec<_>>(),
+                ));
+            }
+        }
+        (lines, selected_line)
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `StickersWindow` struct.
+/// This trait allows the `StickersWindow` to be focused or unfocused.
+impl HandleFocus for StickersWindow {
+    /// Set the `focused` flag for the `StickersWindow`, the sticker sets are
+    /// loaded the first time, and again after they failed to load.
+    fn focus(&mut self) {
+        self.focused = true;
+        let tg_context = self.app_context.tg_context();
+        if tg_context.sticker_sets_error().is_some() {
+            tg_context.set_sticker_sets_error(None);
+            self.requested = false;
+        }
+        if !self.requested && tg_context.sticker_sets().is_none() {
+            self.requested = tg_context.try_send_event(Event::LoadStickerSets);
+        }
+    }
+    /// Set the `focused` flag for the `StickersWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `StickersWindow` struct.
+impl Component for StickersWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        // The window has no key bindings, it takes the keys to search.
+        let Action::Key(key, modifiers) = action else {
+            return;
+        };
+        match key {
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+                self.move_selection(key)
+            }
+            KeyCode::Enter => self.send_selected(),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = (0, 0);
+            }
+            KeyCode::Char(c) if !modifiers.control && !modifiers.alt => {
+                self.query.push(c);
+                self.selected = (0, 0);
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            self.app_context.style_chat()
+        };
+        let title = if self.query.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} - {}", self.name, self.query)
+        };
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(style_border_focused)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .title(title)
+            .title_bottom(" Enter send, type to search ");
+        let inner = block.inner(area);
+        self.columns = usize::from(inner.width / STICKER_CELL_WIDTH).max(1);
+
+        let error = self.app_context.tg_context().sticker_sets_error();
+        let (lines, selected_line) = self.with_sticker_sets(|sets| match (sets, error) {
+            (None, Some(error)) => (
+                vec![Line::from(format!(
+                    "Failed to load the sticker sets: {}, open the stickers again to retry",
+                    error
+                ))],
+                0,
+            ),
+            (None, None) => (vec![Line::from("Loading the sticker sets…")], 0),
+            (Some([]), _) if self.query.is_empty() => {
+                (vec![Line::from("No sticker sets installed")], 0)
+            }
+            (Some([]), _) => (vec![Line::from("No sticker sets match the search")], 0),
+            (Some(sets), _) => self.grid_lines(sets),
+        });
+        // The grid scrolls to keep the selected row on screen.
+        let scroll = (selected_line + 1).saturating_sub(usize::from(inner.height)) as u16;
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .style(self.app_context.style_chat())
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Modifiers;
+    use crossterm::event::KeyModifiers;
+
+    fn sticker_set(id: i64, title: &str, count: i32) -> StickerSetEntry {
+        StickerSetEntry {
+            id,
+            title: title.to_string(),
+            name: title.to_lowercase(),
+            stickers: (0..count)
+                .map(|file_id| StickerEntry {
+                    file_id: id as i32 * 100 + file_id,
+                    emoji: "😀".to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_stickers_grid_navigation_and_search() {
+        let app_context = Arc::new(AppContext::new_default());
+        let tg_context = app_context.tg_context();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_sticker_sets(Some(vec![
+            sticker_set(1, "Cats", 5),
+            sticker_set(2, "Dogs", 2),
+        ]));
+        tg_context.set_open_chat_id(42);
+
+        let mut window = StickersWindow::new(Arc::clone(&app_context));
+        window.columns = 3;
+        let key = |code| Action::Key(code, Modifiers::from(KeyModifiers::NONE));
+        window.update(key(KeyCode::Down));
+        assert_eq!(window.selected, (0, 3));
+        // The last row of the first set is shorter.
+        window.update(key(KeyCode::Right));
+        window.update(key(KeyCode::Down));
+        assert_eq!(window.selected, (1, 1));
+        window.update(key(KeyCode::Up));
+        assert_eq!(window.selected, (0, 4));
+
+        for c in "dog".chars() {
+            window.update(key(KeyCode::Char(c)));
+        }
+        assert_eq!(window.selected, (0, 0));
+        window.update(key(KeyCode::Right));
+        window.update(key(KeyCode::Enter));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::SendSticker(201, ref emoji)) if emoji == "😀"
+        ));
+    }
+
+    #[test]
+    fn test_sticker_sets_loaded_again_after_failure() {
+        let app_context = Arc::new(AppContext::new_default());
+        let tg_context = app_context.tg_context();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+
+        let mut window = StickersWindow::new(Arc::clone(&app_context));
+        window.focus();
+        assert!(matches!(event_rx.try_recv(), Ok(Event::LoadStickerSets)));
+        // They are requested once while they load.
+        window.focus();
+        assert!(event_rx.try_recv().is_err());
+
+        tg_context.set_sticker_sets_error(Some("Timeout".to_string()));
+        window.focus();
+        assert!(matches!(event_rx.try_recv(), Ok(Event::LoadStickerSets)));
+        assert_eq!(tg_context.sticker_sets_error(), None);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{
+    action::Action,
+    app_context::AppContext,
+    components::component_traits::{Component, HandleFocus},
+    event::Event,
+    utils::strip_emoji_line,
+};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListDirection, ListState, Paragraph, Wrap},
+    Frame,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// `UserProfileWindow` is a struct that represents the read-only profile of
+/// a user, followed by the quick actions on the user.
+/// It uses the key bindings of the chat list to select a quick action and to
+/// run it.
+pub struct UserProfileWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `UserProfileWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    action_tx: Option<UnboundedSender<Action>>,
+    /// The lines of the profile of the user.
+    profile: Vec<Line<'static>>,
+    /// The label and the event of each quick action.
+    quick_actions: Vec<(&'static str, Event)>,
+    /// The state of the list of the quick actions.
+    quick_actions_state: ListState,
+    /// Indicates whether the `UserProfileWindow` is focused or not.
+    focused: bool,
+}
+
+impl UserProfileWindow {
+    /// Create a new instance of the `UserProfileWindow` struct.
+    ///
+    /// # Arguments
+    /// * `app_context` - An Arc wrapped AppContext struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `UserProfileWindow` struct.
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let name = "".to_string();
+        let action_tx = None;
+        let profile = vec![];
+        let quick_actions = vec![];
+        let quick_actions_state = ListState::default();
+        let focused = false;
+        UserProfileWindow {
+            app_context,
+            name,
+            action_tx,
+            profile,
+            quick_actions,
+            quick_actions_state,
+            focused,
+        }
+    }
+    /// Set the name of the `UserProfileWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `UserProfileWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `UserProfileWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Build the profile from the user and its full info cached, they are
+    /// kept up to date by TDLib, so a block or a mute shows at once.
+    fn refresh(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        let Some(user_id) = tg_context.profile_user_id() else {
+            self.profile.clear();
+            self.quick_actions.clear();
+            return;
+        };
+
+        let mut profile = vec![Line::from(Span::styled(
+            tg_context.sender_name(user_id),
+            self.app_context.style_chat_chat_name(),
+        ))];
+        if let Some(user) = tg_context.users().get(&user_id) {
+            let usernames = user
+                .usernames
+                .as_ref()
+                .map(|usernames| {
+                    usernames
+                        .active_usernames
+                        .iter()
+                        .map(|username| format!("@{username}"))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            if !usernames.is_empty() {
+                profile.push(Line::from(format!("Username: {}", usernames.join(", "))));
+            }
+            // The phone number is empty unless the user shares it.
+            if !user.phone_number.is_empty() {
+                profile.push(Line::from(format!("Phone: +{}", user.phone_number)));
+            }
+        }
+        let blocked = match tg_context.users_full_info().get(&user_id) {
+            Some(full_info) => {
+                if let Some(bio) = full_info.bio.as_ref().filter(|bio| !bio.text.is_empty()) {
+                    profile.push(Line::from(format!("Bio: {}", bio.text)));
+                }
+                profile.push(Line::from(format!(
+                    "Groups in common: {}",
+                    full_info.group_in_common_count
+                )));
+                full_info.block_list.is_some()
+            }
+            None => false,
+        };
+        if self.app_context.app_config().strip_emoji {
+            profile.iter_mut().for_each(strip_emoji_line);
+        }
+        self.profile = profile;
+
+        self.quick_actions = vec![
+            ("Start chat", Event::OpenPrivateChat(user_id)),
+            if blocked {
+                ("Unblock", Event::SetUserBlocked(user_id, false))
+            } else {
+                ("Block", Event::SetUserBlocked(user_id, true))
+            },
+        ];
+        // The private chat has the identifier of the user, it can only be
+        // muted once it exists.
+        if let Some(chat) = tg_context.chats().get(&user_id) {
+            self.quick_actions.push(
+                if tg_context.is_muted(&chat.notification_settings, &chat.r#type) {
+                    ("Unmute", Event::SetChatMuted(user_id, false))
+                } else {
+                    ("Mute", Event::SetChatMuted(user_id, true))
+                },
+            );
+        }
+        if let Some(i) = self.quick_actions_state.selected() {
+            self.quick_actions_state
+                .select(Some(i.min(self.quick_actions.len() - 1)));
+        }
+    }
+
+    /// Select the next quick action in the list.
+    fn next(&mut self) {
+        if self.quick_actions.is_empty() {
+            return;
+        }
+        let i = match self.quick_actions_state.selected() {
+            Some(i) if i + 1 < self.quick_actions.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.quick_actions_state.select(Some(i));
+    }
+
+    /// Select the previous quick action in the list.
+    fn previous(&mut self) {
+        if self.quick_actions.is_empty() {
+            return;
+        }
+        let i = match self.quick_actions_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.quick_actions_state.select(Some(i));
+    }
+
+    /// Unselect the quick action in the list.
+    fn unselect(&mut self) {
+        self.quick_actions_state.select(None);
+    }
+
+    /// Run the selected quick action.
+    fn run_selected(&mut self) {
+        let Some(event) = self
+            .quick_actions_state
+            .selected()
+            .and_then(|i| self.quick_actions.get(i))
+            .map(|(_, event)| event.clone())
+        else {
+            return;
+        };
+        self.app_context.tg_context().try_send_event(event);
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `UserProfileWindow` struct.
+/// This trait allows the `UserProfileWindow` to be focused or unfocused.
+impl HandleFocus for UserProfileWindow {
+    /// Set the `focused` flag for the `UserProfileWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.quick_actions_state.select(None);
+        self.refresh();
+    }
+    /// Set the `focused` flag for the `UserProfileWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `UserProfileWindow` struct.
+impl Component for UserProfileWindow {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> std::io::Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ChatListNext => self.next(),
+            Action::ChatListPrevious => self.previous(),
+            Action::ChatListUnselect => self.unselect(),
+            Action::ChatListOpen => self.run_selected(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> std::io::Result<()> {
+        self.refresh();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            self.app_context.style_chat_list()
+        };
+        let block = Block::default()
+            .border_set(PLAIN)
+            .border_style(style_border_focused)
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .title(self.name.as_str());
+        let inner = block.inner(area);
+        frame.render_widget(block.style(self.app_context.style_chat_list()), area);
+
+        // The profile takes the room left by the quick actions, a long bio
+        // wraps on more lines than the profile has. One more line separates
+        // the profile from the quick actions.
+        let [profile_area, quick_actions_area] = Layout::vertical([
+            Constraint::Min(self.profile.len() as u16 + 1),
+            Constraint::Length(self.quick_actions.len() as u16),
+        ])
+        .areas(inner);
+        let profile = Paragraph::new(self.profile.clone())
+            .style(self.app_context.style_chat_list())
+            .wrap(Wrap { trim: true });
+        frame.render_widget(profile, profile_area);
+
+        let items = self
+            .quick_actions
+            .iter()
+            .map(|(label, _)| Line::from(format!("▶ {label}")));
+        let list = List::new(items)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+        frame.render_stateful_widget(list, quick_actions_area, &mut self.quick_actions_state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_quick_action() {
+        let app_context = Arc::new(AppContext::new_default());
+        let tg_context = app_context.tg_context();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_profile_user_id(Some(7));
+
+        let mut window = UserProfileWindow::new(Arc::clone(&app_context));
+        window.focus();
+        // There is no private chat with the user to mute yet.
+        assert_eq!(
+            window
+                .quick_actions
+                .iter()
+                .map(|(label, _)| *label)
+                .collect::<Vec<_>>(),
+            vec!["Start chat", "Block"]
+        );
+
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListNext);
+        window.update(Action::ChatListOpen);
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok())
+            .any(|event| matches!(event, Event::SetUserBlocked(7, true))));
+
+        window.update(Action::ChatListPrevious);
+        window.update(Action::ChatListOpen);
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok())
+            .any(|event| matches!(event, Event::OpenPrivateChat(7))));
+    }
+}
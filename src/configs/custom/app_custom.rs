@@ -1,8 +1,123 @@
 use crate::{
     app_error::AppError,
+    component_name::ComponentName,
     configs::{self, config_file::ConfigFile, config_type::ConfigType, raw::app_raw::AppRaw},
+    tg::message_entry::MediaKind,
+    utils::parse_coordinates,
 };
-use std::path::Path;
+use chrono::FixedOffset;
+use crossterm::event::KeyModifiers;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The maximum number of messages returned by TDLib for a single request of
+/// the chat history.
+pub const MAX_HISTORY_BATCH_SIZE: i32 = 100;
+/// The maximum distance from the edge of a list at which more items are
+/// loaded.
+pub const MAX_LOAD_MORE_DISTANCE: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The automatic download policy of a kind of media.
+pub enum AutoDownloadPolicy {
+    /// The media are never downloaded automatically.
+    Never,
+    /// The media are downloaded automatically when the connection is not
+    /// metered.
+    Unmetered,
+    /// The media are always downloaded automatically.
+    Always,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The format of the time of the messages.
+pub enum TimestampFormat {
+    /// The time of the day, with the date if the message is older than
+    /// yesterday, for example "14:32".
+    Absolute,
+    /// The time elapsed since the message, for example "5m".
+    Relative,
+    /// The date and the time up to the seconds, for example
+    /// "2024-03-05 14:32:07". It is shown on demand for a message.
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The position of the prompt in the layout.
+pub enum PromptPosition {
+    /// The prompt is above the chat.
+    Top,
+    /// The prompt is below the chat.
+    Bottom,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The timezone of the dates and the times shown.
+pub enum Timezone {
+    /// The timezone of the system.
+    Local,
+    /// A fixed offset from UTC.
+    Fixed(FixedOffset),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// What Enter does when the prompt is empty.
+pub enum EmptyEnter {
+    /// Nothing happens.
+    Ignore,
+    /// The chat list is focused.
+    FocusChatList,
+    /// The open chat is marked as read.
+    MarkRead,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The shape of the cursor of the prompt.
+pub enum CursorShape {
+    /// A block over the character.
+    Block,
+    /// A vertical bar before the character.
+    Bar,
+    /// A line under the character.
+    Underline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How the step of the selection of the chat list grows while a navigation
+/// key is held.
+pub enum ScrollAcceleration {
+    /// The selection always moves by one chat.
+    Off,
+    /// The step grows by one chat every few moves in a row.
+    Linear,
+    /// The step doubles every few moves in a row.
+    Exponential,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The sound played when a message arrives in a chat.
+pub enum NotificationSound {
+    /// No sound.
+    None,
+    /// The bell of the terminal.
+    Bell,
+    /// A sound file, played with the audio player of the system.
+    File(PathBuf),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// The location sent from the prompt when no coordinates are typed.
+pub enum LocationSource {
+    /// No location, the coordinates must be typed.
+    None,
+    /// The latitude and the longitude in degrees.
+    Coordinates(f64, f64),
+    /// A file containing the coordinates, read when the location is sent.
+    File(PathBuf),
+}
 
 #[derive(Clone, Debug)]
 /// The application configuration.
@@ -25,6 +140,143 @@ pub struct AppConfig {
     pub take_api_id_from_telegram_config: bool,
     /// Take the API HASH from the Telegram configuration.
     pub take_api_hash_from_telegram_config: bool,
+    /// The order in which the components are focused when cycling the focus.
+    pub focus_order: Vec<ComponentName>,
+    /// Show the unread messages count in the terminal title.
+    pub show_unread_in_terminal_title: bool,
+    /// Send the message with Enter and insert a newline with Alt+Enter or
+    /// Ctrl+Enter, instead of the opposite.
+    pub prompt_send_on_enter: bool,
+    /// The modifier that, combined with Enter, performs the action that Enter
+    /// alone does not perform.
+    pub prompt_enter_modifier: KeyModifiers,
+    /// Remove the cached files that exceed the storage limits at startup.
+    pub optimize_storage_on_startup: bool,
+    /// The number of days after which a cached file not accessed is removed.
+    pub storage_max_age_days: i32,
+    /// The maximum size of the cached files in megabytes.
+    pub storage_max_size_mb: i64,
+    /// Group the chats of the chat list by category.
+    pub chat_list_group_by_category: bool,
+    /// The automatic download policy of the photos.
+    pub auto_download_photos: AutoDownloadPolicy,
+    /// The automatic download policy of the videos.
+    pub auto_download_videos: AutoDownloadPolicy,
+    /// The automatic download policy of the files.
+    pub auto_download_files: AutoDownloadPolicy,
+    /// The connection is metered, the media with the `unmetered` automatic
+    /// download policy are not downloaded.
+    pub metered_connection: bool,
+    /// The tags that can be given to the messages, the `chat_window_tag`
+    /// command cycles through them.
+    pub message_tags: Vec<String>,
+    /// The number of messages requested at once when loading the chat
+    /// history, between 1 and 100.
+    pub history_batch_size: i32,
+    /// The width of the chat list, as a percentage of the width of the
+    /// terminal.
+    pub layout_chat_list_size: u16,
+    /// The height of the prompt, in lines.
+    pub layout_prompt_size: u16,
+    /// Show the usernames of the users instead of their full names.
+    pub show_usernames: bool,
+    /// The number of seconds after which the notifications of the status
+    /// bar expire.
+    pub notification_timeout: u64,
+    /// The fixed width of the chat list in columns, `0` uses
+    /// `layout_chat_list_size` instead.
+    pub layout_chat_list_width: u16,
+    /// The terminal width in columns below which the chat list is hidden
+    /// automatically, `0` disables it.
+    pub chat_list_auto_hide_width: u16,
+    /// Briefly pulse the highlight of the chat selected in the chat list.
+    pub selection_pulse: bool,
+    /// Show a colored avatar with the initials of the chat in the chat
+    /// list.
+    pub chat_list_avatars: bool,
+    /// The minutes without any input after which tgt is locked behind the
+    /// passphrase prompt. If it is 0 tgt is never locked automatically.
+    pub lock_after_idle_minutes: u64,
+    /// The format of the time of the last message of the chats in the chat
+    /// list.
+    pub chat_list_timestamp_format: TimestampFormat,
+    /// The format of the time of the messages in the chat.
+    pub chat_timestamp_format: TimestampFormat,
+    /// The position of the prompt, above or below the chat.
+    pub layout_prompt_position: PromptPosition,
+    /// The maximum number of lines of text the prompt grows to, the longer
+    /// texts are scrolled inside the prompt.
+    pub prompt_max_lines: usize,
+    /// The time in milliseconds the selection of the chat list rests on an
+    /// unread chat before the chat is marked as read, 0 to never mark it.
+    pub chat_list_read_on_dwell_ms: u64,
+    /// Indicates whether the empty prompt shows a placeholder with the name of
+    /// the open chat.
+    pub prompt_placeholder: bool,
+    /// Indicates whether the prompt shows the number of characters of the
+    /// message, it turns red close to the length limit of the messages.
+    pub prompt_char_counter: bool,
+    /// The number of chats from the end of the chat list at which more chats
+    /// are loaded, between 1 and 100.
+    pub chat_list_load_more_distance: usize,
+    /// The number of messages from the oldest loaded message at which older
+    /// messages are loaded, between 1 and 100.
+    pub chat_load_more_distance: usize,
+    /// The keywords highlighted in the messages, matched as whole words
+    /// ignoring the case.
+    pub highlight_keywords: Vec<String>,
+    /// Indicates whether the chats with an unread incoming message that
+    /// matches the `highlight_keywords` are highlighted in the chat list.
+    pub chat_list_highlight_keywords: bool,
+    /// The sound played when a message arrives in a chat, by chat id.
+    pub notification_sounds: HashMap<i64, NotificationSound>,
+    /// Show each chat of the chat list on a single line, without the preview
+    /// of its last message.
+    pub chat_list_compact: bool,
+    /// Quit the application when the Telegram backend stops unexpectedly,
+    /// instead of staying open without updates.
+    pub quit_when_backend_stops: bool,
+    /// The behavior of Enter when the prompt is empty.
+    pub prompt_empty_enter: EmptyEnter,
+    /// The timezone of the dates and the times shown.
+    pub timezone: Timezone,
+    /// How the step of the selection of the chat list grows while a
+    /// navigation key is held.
+    pub chat_list_scroll_acceleration: ScrollAcceleration,
+    /// The largest number of chats the selection of the chat list moves by
+    /// at a time while accelerating, at least 1.
+    pub chat_list_scroll_acceleration_max_step: usize,
+    /// The reaction toggled on the selected message with a single key, empty
+    /// to disable it.
+    pub chat_quick_reaction: String,
+    /// The maximum size in megabytes of the media downloaded automatically, the
+    /// larger media are only downloaded on demand. 0 disables the limit.
+    pub auto_download_max_size_mb: i64,
+    /// Whether the messages of a chat are marked as read when it is opened, the
+    /// senders then see that they are read. Disabled, the chat header shows
+    /// "Receipts off" and the chats are only read on demand.
+    pub send_read_receipts: bool,
+    /// The maximum number of messages kept in memory for each chat visited
+    /// recently, the oldest are dropped beyond it once the chat is left and
+    /// loaded again from TDLib when the history is scrolled up to them. 0
+    /// disables the limit.
+    pub max_messages_per_chat: usize,
+    /// Whether the emoji are stripped from the interface, for the terminals that
+    /// cannot render them. The emoji of the messages that carry a meaning are
+    /// replaced by text, the others are removed.
+    pub strip_emoji: bool,
+    /// The shape of the cursor of the prompt.
+    pub prompt_cursor_shape: CursorShape,
+    /// Whether the cursor of the prompt blinks.
+    pub prompt_cursor_blink: bool,
+    /// The location sent from the prompt when no coordinates are typed.
+    pub location: LocationSource,
+    /// The delay in milliseconds before an opened chat is marked as read,
+    /// 0 to mark it as read at once.
+    pub read_on_open_delay_ms: u64,
+    /// Whether the messages longer than the limit of Telegram are split
+    /// in several messages, otherwise they are not sent.
+    pub prompt_split_long_messages: bool,
 }
 /// The application configuration implementation.
 impl AppConfig {
@@ -37,6 +289,322 @@ impl AppConfig {
             &configs::custom::default_config_app_file_path()?,
         ))
     }
+    /// Parse the focus order from a list of component names.
+    /// The component names that are not recognized are ignored.
+    ///
+    /// # Arguments
+    /// * `focus_order` - A vector of strings that represents the component
+    ///   names.
+    ///
+    /// # Returns
+    /// A vector of the recognized component names.
+    fn parse_focus_order(focus_order: Vec<String>) -> Vec<ComponentName> {
+        focus_order
+            .iter()
+            .filter_map(|name| match ComponentName::from_str(name) {
+                Ok(component_name) => Some(component_name),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+    /// Parse an automatic download policy.
+    /// If the policy is not recognized, the media are never downloaded.
+    ///
+    /// # Arguments
+    /// * `policy` - A string that represents the policy.
+    ///
+    /// # Returns
+    /// The parsed policy.
+    fn parse_auto_download_policy(policy: &str) -> AutoDownloadPolicy {
+        match policy {
+            "never" => AutoDownloadPolicy::Never,
+            "unmetered" => AutoDownloadPolicy::Unmetered,
+            "always" => AutoDownloadPolicy::Always,
+            _ => {
+                tracing::warn!("Invalid auto download policy: {}", policy);
+                AutoDownloadPolicy::Never
+            }
+        }
+    }
+    /// Parse a timestamp format.
+    /// If the format is not recognized, the absolute format is used.
+    ///
+    /// # Arguments
+    /// * `format` - A string that represents the format.
+    ///
+    /// # Returns
+    /// The parsed format.
+    fn parse_timestamp_format(format: &str) -> TimestampFormat {
+        match format {
+            "absolute" => TimestampFormat::Absolute,
+            "relative" => TimestampFormat::Relative,
+            _ => {
+                tracing::warn!("Invalid timestamp format: {}", format);
+                TimestampFormat::Absolute
+            }
+        }
+    }
+    /// Parse the position of the prompt.
+    /// If the position is not recognized, the prompt is below the chat.
+    ///
+    /// # Arguments
+    /// * `position` - A string that represents the position.
+    ///
+    /// # Returns
+    /// The parsed position.
+    fn parse_prompt_position(position: &str) -> PromptPosition {
+        match position {
+            "top" => PromptPosition::Top,
+            "bottom" => PromptPosition::Bottom,
+            _ => {
+                tracing::warn!("Invalid prompt position: {}", position);
+                PromptPosition::Bottom
+            }
+        }
+    }
+    /// Parse the behavior of Enter on an empty prompt.
+    /// If the behavior is not recognized, Enter is ignored.
+    ///
+    /// # Arguments
+    /// * `behavior` - A string that represents the behavior.
+    ///
+    /// # Returns
+    /// The parsed behavior.
+    fn parse_empty_enter(behavior: &str) -> EmptyEnter {
+        match behavior {
+            "ignore" => EmptyEnter::Ignore,
+            "focus_chat_list" => EmptyEnter::FocusChatList,
+            "mark_read" => EmptyEnter::MarkRead,
+            _ => {
+                tracing::warn!("Invalid behavior of Enter on an empty prompt: {}", behavior);
+                EmptyEnter::Ignore
+            }
+        }
+    }
+    /// Parse the shape of the cursor of the prompt.
+    /// If the shape is not recognized, the cursor is a bar.
+    ///
+    /// # Arguments
+    /// * `shape` - A string that represents the shape.
+    ///
+    /// # Returns
+    /// The parsed shape.
+    fn parse_cursor_shape(shape: &str) -> CursorShape {
+        match shape {
+            "block" => CursorShape::Block,
+            "bar" => CursorShape::Bar,
+            "underline" => CursorShape::Underline,
+            _ => {
+                tracing::warn!("Invalid cursor shape: {}", shape);
+                CursorShape::Bar
+            }
+        }
+    }
+    /// Parse the location sent from the prompt: coordinates, or the path of
+    /// a file containing them.
+    ///
+    /// # Arguments
+    /// * `location` - A string that represents the location.
+    ///
+    /// # Returns
+    /// The parsed location.
+    fn parse_location_source(location: &str) -> LocationSource {
+        let location = location.trim();
+        if location.is_empty() {
+            return LocationSource::None;
+        }
+        if let Some((latitude, longitude)) = parse_coordinates(location) {
+            return LocationSource::Coordinates(latitude, longitude);
+        }
+        match location.strip_prefix("~/").zip(dirs::home_dir()) {
+            Some((path, home)) => LocationSource::File(home.join(path)),
+            None => LocationSource::File(PathBuf::from(location)),
+        }
+    }
+    /// Parse the acceleration of the selection of the chat list.
+    /// If the acceleration is not recognized, it is turned off.
+    ///
+    /// # Arguments
+    /// * `acceleration` - A string that represents the acceleration.
+    ///
+    /// # Returns
+    /// The parsed acceleration.
+    fn parse_scroll_acceleration(acceleration: &str) -> ScrollAcceleration {
+        match acceleration {
+            "off" => ScrollAcceleration::Off,
+            "linear" => ScrollAcceleration::Linear,
+            "exponential" => ScrollAcceleration::Exponential,
+            _ => {
+                tracing::warn!("Invalid scroll acceleration: {}", acceleration);
+                ScrollAcceleration::Off
+            }
+        }
+    }
+    /// Parse the timezone of the dates and the times.
+    /// If the timezone is not recognized, the timezone of the system is used.
+    ///
+    /// # Arguments
+    /// * `timezone` - "local", "utc" or an offset from UTC like "+05:30".
+    ///
+    /// # Returns
+    /// The parsed timezone.
+    fn parse_timezone(timezone: &str) -> Timezone {
+        match timezone.trim().to_lowercase().as_str() {
+            "local" | "" => Timezone::Local,
+            "utc" => Timezone::Fixed(FixedOffset::east_opt(0).unwrap()),
+            offset => match FixedOffset::from_str(offset) {
+                Ok(offset) => Timezone::Fixed(offset),
+                Err(_) => {
+                    tracing::warn!("Invalid timezone: {}", timezone);
+                    Timezone::Local
+                }
+            },
+        }
+    }
+    /// Parse the notification sounds of the chats.
+    /// The entries whose key is not a chat id are ignored.
+    ///
+    /// # Arguments
+    /// * `sounds` - A map from the chat ids to the sounds, as strings.
+    ///
+    /// # Returns
+    /// The parsed notification sounds.
+    fn parse_notification_sounds(
+        sounds: HashMap<String, String>,
+    ) -> HashMap<i64, NotificationSound> {
+        sounds
+            .into_iter()
+            .filter_map(|(chat_id, sound)| {
+                let Ok(chat_id) = chat_id.trim().parse::<i64>() else {
+                    tracing::warn!("Invalid chat id for a notification sound: {}", chat_id);
+                    return None;
+                };
+                let sound = match sound.as_str() {
+                    "bell" => NotificationSound::Bell,
+                    "none" | "" => NotificationSound::None,
+                    path => match path.strip_prefix("~/").zip(dirs::home_dir()) {
+                        Some((path, home)) => NotificationSound::File(home.join(path)),
+                        None => NotificationSound::File(PathBuf::from(path)),
+                    },
+                };
+                Some((chat_id, sound))
+            })
+            .collect()
+    }
+    /// Check if a media of the given kind and size should be downloaded
+    /// automatically.
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of the media.
+    /// * `size` - The size of the media in bytes, 0 if it is unknown.
+    ///
+    /// # Returns
+    /// `true` if the media should be downloaded automatically.
+    pub fn should_auto_download(&self, kind: MediaKind, size: i64) -> bool {
+        if self.auto_download_max_size_mb > 0 && size > self.auto_download_max_size_mb * 1024 * 1024
+        {
+            return false;
+        }
+        let policy = match kind {
+            MediaKind::Photo => self.auto_download_photos,
+            MediaKind::Video => self.auto_download_videos,
+            MediaKind::File => self.auto_download_files,
+        };
+        match policy {
+            AutoDownloadPolicy::Never => false,
+            AutoDownloadPolicy::Unmetered => !self.metered_connection,
+            AutoDownloadPolicy::Always => true,
+        }
+    }
+    /// Clamp the history batch size to the range accepted by TDLib.
+    ///
+    /// # Arguments
+    /// * `size` - The configured history batch size.
+    ///
+    /// # Returns
+    /// The history batch size between 1 and 100.
+    fn clamp_history_batch_size(size: i32) -> i32 {
+        if !(1..=MAX_HISTORY_BATCH_SIZE).contains(&size) {
+            tracing::warn!(
+                "Invalid history batch size: {}, it must be between 1 and {}",
+                size,
+                MAX_HISTORY_BATCH_SIZE
+            );
+        }
+        size.clamp(1, MAX_HISTORY_BATCH_SIZE)
+    }
+    /// Clamp the largest step of the selection of the chat list.
+    ///
+    /// # Arguments
+    /// * `step` - The configured step.
+    ///
+    /// # Returns
+    /// The step, at least 1.
+    fn clamp_scroll_acceleration_max_step(step: usize) -> usize {
+        if step == 0 {
+            tracing::warn!("Invalid scroll acceleration max step: 0, it must be at least 1");
+        }
+        step.max(1)
+    }
+    /// Clamp the distance from the edge of a list at which more items are
+    /// loaded.
+    ///
+    /// # Arguments
+    /// * `distance` - The configured distance.
+    ///
+    /// # Returns
+    /// The distance between 1 and 100.
+    fn clamp_load_more_distance(distance: usize) -> usize {
+        if !(1..=MAX_LOAD_MORE_DISTANCE).contains(&distance) {
+            tracing::warn!(
+                "Invalid load more distance: {}, it must be between 1 and {}",
+                distance,
+                MAX_LOAD_MORE_DISTANCE
+            );
+        }
+        distance.clamp(1, MAX_LOAD_MORE_DISTANCE)
+    }
+    /// Get the tag that follows the current tag of a message in the
+    /// configured message tags, the last tag is followed by no tag.
+    ///
+    /// # Arguments
+    /// * `current` - The current tag of the message, if any.
+    ///
+    /// # Returns
+    /// The next tag, or `None` if the message should be untagged.
+    pub fn next_message_tag(&self, current: Option<&str>) -> Option<String> {
+        let next = match current.and_then(|tag| self.message_tags.iter().position(|t| t == tag)) {
+            Some(i) => i + 1,
+            None if current.is_some() => return None,
+            None => 0,
+        };
+        self.message_tags.get(next).cloned()
+    }
+    /// Parse the modifier combined with Enter in the prompt.
+    /// If the modifier is not recognized, the Alt modifier is used.
+    ///
+    /// # Arguments
+    /// * `modifier` - A string that represents the modifier.
+    ///
+    /// # Returns
+    /// The parsed modifier.
+    fn parse_enter_modifier(modifier: &str) -> KeyModifiers {
+        match modifier {
+            "alt" => KeyModifiers::ALT,
+            "ctrl" => KeyModifiers::CONTROL,
+            "shift" => KeyModifiers::SHIFT,
+            "super" => KeyModifiers::SUPER,
+            "meta" => KeyModifiers::META,
+            "hyper" => KeyModifiers::HYPER,
+            _ => {
+                tracing::warn!("Invalid prompt enter modifier: {}", modifier);
+                KeyModifiers::ALT
+            }
+        }
+    }
 }
 /// The implementation of the configuration file for the application.
 impl ConfigFile for AppConfig {
@@ -86,6 +654,173 @@ impl ConfigFile for AppConfig {
                 {
                     self.take_api_hash_from_telegram_config = take_api_hash_from_telegram_config;
                 }
+                if let Some(focus_order) = other.focus_order {
+                    self.focus_order = Self::parse_focus_order(focus_order);
+                }
+                if let Some(show_unread_in_terminal_title) = other.show_unread_in_terminal_title {
+                    self.show_unread_in_terminal_title = show_unread_in_terminal_title;
+                }
+                if let Some(prompt_send_on_enter) = other.prompt_send_on_enter {
+                    self.prompt_send_on_enter = prompt_send_on_enter;
+                }
+                if let Some(prompt_enter_modifier) = other.prompt_enter_modifier {
+                    self.prompt_enter_modifier = Self::parse_enter_modifier(&prompt_enter_modifier);
+                }
+                if let Some(optimize_storage_on_startup) = other.optimize_storage_on_startup {
+                    self.optimize_storage_on_startup = optimize_storage_on_startup;
+                }
+                if let Some(storage_max_age_days) = other.storage_max_age_days {
+                    self.storage_max_age_days = storage_max_age_days;
+                }
+                if let Some(storage_max_size_mb) = other.storage_max_size_mb {
+                    self.storage_max_size_mb = storage_max_size_mb;
+                }
+                if let Some(chat_list_group_by_category) = other.chat_list_group_by_category {
+                    self.chat_list_group_by_category = chat_list_group_by_category;
+                }
+                if let Some(auto_download_photos) = other.auto_download_photos {
+                    self.auto_download_photos =
+                        Self::parse_auto_download_policy(&auto_download_photos);
+                }
+                if let Some(auto_download_videos) = other.auto_download_videos {
+                    self.auto_download_videos =
+                        Self::parse_auto_download_policy(&auto_download_videos);
+                }
+                if let Some(auto_download_files) = other.auto_download_files {
+                    self.auto_download_files =
+                        Self::parse_auto_download_policy(&auto_download_files);
+                }
+                if let Some(metered_connection) = other.metered_connection {
+                    self.metered_connection = metered_connection;
+                }
+                if let Some(message_tags) = other.message_tags {
+                    self.message_tags = message_tags;
+                }
+                if let Some(history_batch_size) = other.history_batch_size {
+                    self.history_batch_size = Self::clamp_history_batch_size(history_batch_size);
+                }
+                if let Some(layout_chat_list_size) = other.layout_chat_list_size {
+                    self.layout_chat_list_size = layout_chat_list_size;
+                }
+                if let Some(layout_prompt_size) = other.layout_prompt_size {
+                    self.layout_prompt_size = layout_prompt_size;
+                }
+                if let Some(show_usernames) = other.show_usernames {
+                    self.show_usernames = show_usernames;
+                }
+                if let Some(notification_timeout) = other.notification_timeout {
+                    self.notification_timeout = notification_timeout;
+                }
+                if let Some(layout_chat_list_width) = other.layout_chat_list_width {
+                    self.layout_chat_list_width = layout_chat_list_width;
+                }
+                if let Some(chat_list_auto_hide_width) = other.chat_list_auto_hide_width {
+                    self.chat_list_auto_hide_width = chat_list_auto_hide_width;
+                }
+                if let Some(selection_pulse) = other.selection_pulse {
+                    self.selection_pulse = selection_pulse;
+                }
+                if let Some(chat_list_avatars) = other.chat_list_avatars {
+                    self.chat_list_avatars = chat_list_avatars;
+                }
+                if let Some(lock_after_idle_minutes) = other.lock_after_idle_minutes {
+                    self.lock_after_idle_minutes = lock_after_idle_minutes;
+                }
+                if let Some(chat_list_timestamp_format) = other.chat_list_timestamp_format {
+                    self.chat_list_timestamp_format =
+                        Self::parse_timestamp_format(&chat_list_timestamp_format);
+                }
+                if let Some(chat_timestamp_format) = other.chat_timestamp_format {
+                    self.chat_timestamp_format =
+                        Self::parse_timestamp_format(&chat_timestamp_format);
+                }
+                if let Some(layout_prompt_position) = other.layout_prompt_position {
+                    self.layout_prompt_position =
+                        Self::parse_prompt_position(&layout_prompt_position);
+                }
+                if let Some(prompt_max_lines) = other.prompt_max_lines {
+                    self.prompt_max_lines = prompt_max_lines;
+                }
+                if let Some(chat_list_read_on_dwell_ms) = other.chat_list_read_on_dwell_ms {
+                    self.chat_list_read_on_dwell_ms = chat_list_read_on_dwell_ms;
+                }
+                if let Some(prompt_placeholder) = other.prompt_placeholder {
+                    self.prompt_placeholder = prompt_placeholder;
+                }
+                if let Some(prompt_char_counter) = other.prompt_char_counter {
+                    self.prompt_char_counter = prompt_char_counter;
+                }
+                if let Some(chat_list_load_more_distance) = other.chat_list_load_more_distance {
+                    self.chat_list_load_more_distance =
+                        Self::clamp_load_more_distance(chat_list_load_more_distance);
+                }
+                if let Some(chat_load_more_distance) = other.chat_load_more_distance {
+                    self.chat_load_more_distance =
+                        Self::clamp_load_more_distance(chat_load_more_distance);
+                }
+                if let Some(highlight_keywords) = other.highlight_keywords {
+                    self.highlight_keywords = highlight_keywords;
+                }
+                if let Some(chat_list_highlight_keywords) = other.chat_list_highlight_keywords {
+                    self.chat_list_highlight_keywords = chat_list_highlight_keywords;
+                }
+                if let Some(notification_sounds) = other.notification_sounds {
+                    self.notification_sounds = Self::parse_notification_sounds(notification_sounds);
+                }
+                if let Some(chat_list_compact) = other.chat_list_compact {
+                    self.chat_list_compact = chat_list_compact;
+                }
+                if let Some(quit_when_backend_stops) = other.quit_when_backend_stops {
+                    self.quit_when_backend_stops = quit_when_backend_stops;
+                }
+                if let Some(prompt_empty_enter) = other.prompt_empty_enter {
+                    self.prompt_empty_enter = Self::parse_empty_enter(&prompt_empty_enter);
+                }
+                if let Some(timezone) = other.timezone {
+                    self.timezone = Self::parse_timezone(&timezone);
+                }
+                if let Some(chat_list_scroll_acceleration) = other.chat_list_scroll_acceleration {
+                    self.chat_list_scroll_acceleration =
+                        Self::parse_scroll_acceleration(&chat_list_scroll_acceleration);
+                }
+                if let Some(chat_list_scroll_acceleration_max_step) =
+                    other.chat_list_scroll_acceleration_max_step
+                {
+                    self.chat_list_scroll_acceleration_max_step =
+                        Self::clamp_scroll_acceleration_max_step(
+                            chat_list_scroll_acceleration_max_step,
+                        );
+                }
+                if let Some(chat_quick_reaction) = other.chat_quick_reaction {
+                    self.chat_quick_reaction = chat_quick_reaction;
+                }
+                if let Some(auto_download_max_size_mb) = other.auto_download_max_size_mb {
+                    self.auto_download_max_size_mb = auto_download_max_size_mb;
+                }
+                if let Some(send_read_receipts) = other.send_read_receipts {
+                    self.send_read_receipts = send_read_receipts;
+                }
+                if let Some(max_messages_per_chat) = other.max_messages_per_chat {
+                    self.max_messages_per_chat = max_messages_per_chat;
+                }
+                if let Some(strip_emoji) = other.strip_emoji {
+                    self.strip_emoji = strip_emoji;
+                }
+                if let Some(prompt_cursor_shape) = other.prompt_cursor_shape {
+                    self.prompt_cursor_shape = Self::parse_cursor_shape(&prompt_cursor_shape);
+                }
+                if let Some(prompt_cursor_blink) = other.prompt_cursor_blink {
+                    self.prompt_cursor_blink = prompt_cursor_blink;
+                }
+                if let Some(location) = other.location {
+                    self.location = Self::parse_location_source(&location);
+                }
+                if let Some(read_on_open_delay_ms) = other.read_on_open_delay_ms {
+                    self.read_on_open_delay_ms = read_on_open_delay_ms;
+                }
+                if let Some(prompt_split_long_messages) = other.prompt_split_long_messages {
+                    self.prompt_split_long_messages = prompt_split_long_messages;
+                }
                 self.clone()
             }
         }
@@ -111,15 +846,98 @@ impl From<AppRaw> for AppConfig {
             theme_filename: raw.theme_filename.unwrap(),
             take_api_id_from_telegram_config: raw.take_api_id_from_telegram_config.unwrap(),
             take_api_hash_from_telegram_config: raw.take_api_hash_from_telegram_config.unwrap(),
+            focus_order: Self::parse_focus_order(raw.focus_order.unwrap()),
+            show_unread_in_terminal_title: raw.show_unread_in_terminal_title.unwrap(),
+            prompt_send_on_enter: raw.prompt_send_on_enter.unwrap(),
+            prompt_enter_modifier: Self::parse_enter_modifier(&raw.prompt_enter_modifier.unwrap()),
+            optimize_storage_on_startup: raw.optimize_storage_on_startup.unwrap(),
+            storage_max_age_days: raw.storage_max_age_days.unwrap(),
+            storage_max_size_mb: raw.storage_max_size_mb.unwrap(),
+            chat_list_group_by_category: raw.chat_list_group_by_category.unwrap(),
+            auto_download_photos: Self::parse_auto_download_policy(
+                &raw.auto_download_photos.unwrap(),
+            ),
+            auto_download_videos: Self::parse_auto_download_policy(
+                &raw.auto_download_videos.unwrap(),
+            ),
+            auto_download_files: Self::parse_auto_download_policy(
+                &raw.auto_download_files.unwrap(),
+            ),
+            metered_connection: raw.metered_connection.unwrap(),
+            message_tags: raw.message_tags.unwrap(),
+            history_batch_size: Self::clamp_history_batch_size(raw.history_batch_size.unwrap()),
+            layout_chat_list_size: raw.layout_chat_list_size.unwrap(),
+            layout_prompt_size: raw.layout_prompt_size.unwrap(),
+            show_usernames: raw.show_usernames.unwrap(),
+            notification_timeout: raw.notification_timeout.unwrap(),
+            layout_chat_list_width: raw.layout_chat_list_width.unwrap(),
+            chat_list_auto_hide_width: raw.chat_list_auto_hide_width.unwrap(),
+            selection_pulse: raw.selection_pulse.unwrap(),
+            chat_list_avatars: raw.chat_list_avatars.unwrap(),
+            lock_after_idle_minutes: raw.lock_after_idle_minutes.unwrap(),
+            chat_list_timestamp_format: Self::parse_timestamp_format(
+                &raw.chat_list_timestamp_format.unwrap(),
+            ),
+            chat_timestamp_format: Self::parse_timestamp_format(
+                &raw.chat_timestamp_format.unwrap(),
+            ),
+            layout_prompt_position: Self::parse_prompt_position(
+                &raw.layout_prompt_position.unwrap(),
+            ),
+            prompt_max_lines: raw.prompt_max_lines.unwrap(),
+            chat_list_read_on_dwell_ms: raw.chat_list_read_on_dwell_ms.unwrap(),
+            prompt_placeholder: raw.prompt_placeholder.unwrap(),
+            prompt_char_counter: raw.prompt_char_counter.unwrap(),
+            chat_list_load_more_distance: Self::clamp_load_more_distance(
+                raw.chat_list_load_more_distance.unwrap(),
+            ),
+            chat_load_more_distance: Self::clamp_load_more_distance(
+                raw.chat_load_more_distance.unwrap(),
+            ),
+            highlight_keywords: raw.highlight_keywords.unwrap(),
+            chat_list_highlight_keywords: raw.chat_list_highlight_keywords.unwrap(),
+            notification_sounds: Self::parse_notification_sounds(raw.notification_sounds.unwrap()),
+            chat_list_compact: raw.chat_list_compact.unwrap(),
+            quit_when_backend_stops: raw.quit_when_backend_stops.unwrap(),
+            prompt_empty_enter: Self::parse_empty_enter(&raw.prompt_empty_enter.unwrap()),
+            timezone: Self::parse_timezone(&raw.timezone.unwrap()),
+            chat_list_scroll_acceleration: Self::parse_scroll_acceleration(
+                &raw.chat_list_scroll_acceleration.unwrap(),
+            ),
+            chat_list_scroll_acceleration_max_step: Self::clamp_scroll_acceleration_max_step(
+                raw.chat_list_scroll_acceleration_max_step.unwrap(),
+            ),
+            chat_quick_reaction: raw.chat_quick_reaction.unwrap(),
+            auto_download_max_size_mb: raw.auto_download_max_size_mb.unwrap(),
+            send_read_receipts: raw.send_read_receipts.unwrap(),
+            max_messages_per_chat: raw.max_messages_per_chat.unwrap(),
+            strip_emoji: raw.strip_emoji.unwrap(),
+            prompt_cursor_shape: Self::parse_cursor_shape(&raw.prompt_cursor_shape.unwrap()),
+            prompt_cursor_blink: raw.prompt_cursor_blink.unwrap(),
+            location: Self::parse_location_source(&raw.location.unwrap()),
+            read_on_open_delay_ms: raw.read_on_open_delay_ms.unwrap(),
+            prompt_split_long_messages: raw.prompt_split_long_messages.unwrap(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::configs::{
-        config_file::ConfigFile, custom::app_custom::AppConfig, raw::app_raw::AppRaw,
+    use crate::{
+        component_name::ComponentName,
+        configs::{
+            config_file::ConfigFile,
+            custom::app_custom::{
+                AppConfig, AutoDownloadPolicy, CursorShape, EmptyEnter, LocationSource,
+                NotificationSound, PromptPosition, ScrollAcceleration, TimestampFormat, Timezone,
+            },
+            raw::app_raw::AppRaw,
+        },
+        tg::message_entry::MediaKind,
     };
+    use chrono::FixedOffset;
+    use crossterm::event::KeyModifiers;
+    use std::{collections::HashMap, path::PathBuf};
 
     #[test]
     fn test_app_config_default() {
@@ -133,6 +951,71 @@ mod tests {
         assert_eq!(app_config.theme_filename, "theme.toml");
         assert!(app_config.take_api_id_from_telegram_config);
         assert!(app_config.take_api_hash_from_telegram_config);
+        // The media are only downloaded on demand unless enabled.
+        assert_eq!(app_config.auto_download_photos, AutoDownloadPolicy::Never);
+        assert_eq!(app_config.auto_download_videos, AutoDownloadPolicy::Never);
+        assert_eq!(app_config.auto_download_files, AutoDownloadPolicy::Never);
+        assert_eq!(
+            app_config.focus_order,
+            vec![
+                ComponentName::ChatList,
+                ComponentName::Chat,
+                ComponentName::Prompt
+            ]
+        );
+        assert!(app_config.show_unread_in_terminal_title);
+        assert!(!app_config.prompt_send_on_enter);
+        assert_eq!(app_config.prompt_enter_modifier, KeyModifiers::ALT);
+        assert!(!app_config.optimize_storage_on_startup);
+        assert_eq!(app_config.storage_max_age_days, 30);
+        assert_eq!(app_config.storage_max_size_mb, 1024);
+        assert!(!app_config.chat_list_group_by_category);
+        assert!(!app_config.metered_connection);
+        assert_eq!(app_config.message_tags, vec!["todo", "reference"]);
+        assert_eq!(app_config.history_batch_size, 50);
+        assert_eq!(app_config.layout_chat_list_size, 20);
+        assert_eq!(app_config.layout_prompt_size, 3);
+        assert!(!app_config.show_usernames);
+        assert_eq!(app_config.notification_timeout, 4);
+        assert_eq!(app_config.layout_chat_list_width, 0);
+        assert_eq!(app_config.chat_list_auto_hide_width, 80);
+        assert!(app_config.selection_pulse);
+        assert!(!app_config.chat_list_avatars);
+        assert_eq!(app_config.lock_after_idle_minutes, 0);
+        assert_eq!(
+            app_config.chat_list_timestamp_format,
+            TimestampFormat::Absolute
+        );
+        assert_eq!(app_config.chat_timestamp_format, TimestampFormat::Absolute);
+        assert_eq!(app_config.layout_prompt_position, PromptPosition::Bottom);
+        assert_eq!(app_config.prompt_max_lines, 5);
+        assert_eq!(app_config.chat_list_read_on_dwell_ms, 0);
+        assert!(app_config.prompt_placeholder);
+        assert!(!app_config.prompt_char_counter);
+        assert_eq!(app_config.chat_list_load_more_distance, 10);
+        assert_eq!(app_config.chat_load_more_distance, 10);
+        assert!(app_config.highlight_keywords.is_empty());
+        assert!(app_config.chat_list_highlight_keywords);
+        assert!(app_config.notification_sounds.is_empty());
+        assert!(!app_config.chat_list_compact);
+        assert!(app_config.quit_when_backend_stops);
+        assert_eq!(app_config.prompt_empty_enter, EmptyEnter::Ignore);
+        assert_eq!(app_config.timezone, Timezone::Local);
+        assert_eq!(
+            app_config.chat_list_scroll_acceleration,
+            ScrollAcceleration::Off
+        );
+        assert_eq!(app_config.chat_list_scroll_acceleration_max_step, 10);
+        assert_eq!(app_config.chat_quick_reaction, "👍");
+        assert_eq!(app_config.auto_download_max_size_mb, 10);
+        assert!(app_config.send_read_receipts);
+        assert_eq!(app_config.max_messages_per_chat, 1000);
+        assert!(!app_config.strip_emoji);
+        assert_eq!(app_config.prompt_cursor_shape, CursorShape::Bar);
+        assert!(app_config.prompt_cursor_blink);
+        assert_eq!(app_config.location, LocationSource::None);
+        assert_eq!(app_config.read_on_open_delay_ms, 0);
+        assert!(app_config.prompt_split_long_messages);
     }
 
     #[test]
@@ -147,6 +1030,61 @@ mod tests {
             theme_filename: Some("test".to_string()),
             take_api_id_from_telegram_config: Some(true),
             take_api_hash_from_telegram_config: Some(true),
+            focus_order: Some(vec![
+                "chat_list".to_string(),
+                "chat".to_string(),
+                "prompt".to_string(),
+            ]),
+            show_unread_in_terminal_title: Some(true),
+            prompt_send_on_enter: Some(true),
+            prompt_enter_modifier: Some("ctrl".to_string()),
+            optimize_storage_on_startup: Some(true),
+            storage_max_age_days: Some(7),
+            storage_max_size_mb: Some(512),
+            chat_list_group_by_category: Some(true),
+            auto_download_photos: Some("always".to_string()),
+            auto_download_videos: Some("always".to_string()),
+            auto_download_files: Some("always".to_string()),
+            metered_connection: Some(true),
+            message_tags: Some(vec!["todo".to_string()]),
+            history_batch_size: Some(100),
+            layout_chat_list_size: Some(25),
+            layout_prompt_size: Some(5),
+            show_usernames: Some(true),
+            notification_timeout: Some(10),
+            layout_chat_list_width: Some(30),
+            chat_list_auto_hide_width: Some(100),
+            selection_pulse: Some(false),
+            chat_list_avatars: Some(true),
+            lock_after_idle_minutes: Some(15),
+            chat_list_timestamp_format: Some("relative".to_string()),
+            chat_timestamp_format: Some("relative".to_string()),
+            layout_prompt_position: Some("top".to_string()),
+            prompt_max_lines: Some(8),
+            chat_list_read_on_dwell_ms: Some(1500),
+            prompt_placeholder: Some(false),
+            prompt_char_counter: Some(true),
+            chat_list_load_more_distance: Some(20),
+            chat_load_more_distance: Some(20),
+            highlight_keywords: Some(vec!["tgt".to_string()]),
+            chat_list_highlight_keywords: Some(false),
+            notification_sounds: Some(HashMap::from([("42".to_string(), "bell".to_string())])),
+            chat_list_compact: Some(true),
+            quit_when_backend_stops: Some(false),
+            prompt_empty_enter: Some("mark_read".to_string()),
+            timezone: Some("+05:30".to_string()),
+            chat_list_scroll_acceleration: Some("linear".to_string()),
+            chat_list_scroll_acceleration_max_step: Some(20),
+            chat_quick_reaction: Some("❤".to_string()),
+            auto_download_max_size_mb: Some(50),
+            send_read_receipts: Some(false),
+            max_messages_per_chat: Some(500),
+            strip_emoji: Some(true),
+            prompt_cursor_shape: Some("underline".to_string()),
+            prompt_cursor_blink: Some(false),
+            location: Some("48.8584, 2.2945".to_string()),
+            read_on_open_delay_ms: Some(2000),
+            prompt_split_long_messages: Some(false),
         };
         let app_config = AppConfig::from(app_raw);
         assert!(app_config.mouse_support);
@@ -170,6 +1108,61 @@ mod tests {
             theme_filename: Some("test".to_string()),
             take_api_id_from_telegram_config: Some(true),
             take_api_hash_from_telegram_config: Some(true),
+            focus_order: Some(vec![
+                "chat_list".to_string(),
+                "chat".to_string(),
+                "prompt".to_string(),
+            ]),
+            show_unread_in_terminal_title: Some(true),
+            prompt_send_on_enter: Some(true),
+            prompt_enter_modifier: Some("ctrl".to_string()),
+            optimize_storage_on_startup: Some(true),
+            storage_max_age_days: Some(7),
+            storage_max_size_mb: Some(512),
+            chat_list_group_by_category: Some(true),
+            auto_download_photos: Some("always".to_string()),
+            auto_download_videos: Some("always".to_string()),
+            auto_download_files: Some("always".to_string()),
+            metered_connection: Some(true),
+            message_tags: Some(vec!["todo".to_string()]),
+            history_batch_size: Some(100),
+            layout_chat_list_size: Some(25),
+            layout_prompt_size: Some(5),
+            show_usernames: Some(true),
+            notification_timeout: Some(10),
+            layout_chat_list_width: Some(30),
+            chat_list_auto_hide_width: Some(100),
+            selection_pulse: Some(false),
+            chat_list_avatars: Some(true),
+            lock_after_idle_minutes: Some(15),
+            chat_list_timestamp_format: Some("relative".to_string()),
+            chat_timestamp_format: Some("relative".to_string()),
+            layout_prompt_position: Some("top".to_string()),
+            prompt_max_lines: Some(8),
+            chat_list_read_on_dwell_ms: Some(1500),
+            prompt_placeholder: Some(false),
+            prompt_char_counter: Some(true),
+            chat_list_load_more_distance: Some(20),
+            chat_load_more_distance: Some(20),
+            highlight_keywords: Some(vec!["tgt".to_string()]),
+            chat_list_highlight_keywords: Some(false),
+            notification_sounds: Some(HashMap::from([("42".to_string(), "bell".to_string())])),
+            chat_list_compact: Some(true),
+            quit_when_backend_stops: Some(false),
+            prompt_empty_enter: Some("mark_read".to_string()),
+            timezone: Some("+05:30".to_string()),
+            chat_list_scroll_acceleration: Some("linear".to_string()),
+            chat_list_scroll_acceleration_max_step: Some(20),
+            chat_quick_reaction: Some("❤".to_string()),
+            auto_download_max_size_mb: Some(50),
+            send_read_receipts: Some(false),
+            max_messages_per_chat: Some(500),
+            strip_emoji: Some(true),
+            prompt_cursor_shape: Some("underline".to_string()),
+            prompt_cursor_blink: Some(false),
+            location: Some("48.8584, 2.2945".to_string()),
+            read_on_open_delay_ms: Some(2000),
+            prompt_split_long_messages: Some(false),
         });
         let app_raw = AppRaw {
             mouse_support: Some(false),
@@ -181,6 +1174,57 @@ mod tests {
             theme_filename: None,
             take_api_id_from_telegram_config: None,
             take_api_hash_from_telegram_config: None,
+            focus_order: None,
+            show_unread_in_terminal_title: None,
+            prompt_send_on_enter: Some(true),
+            prompt_enter_modifier: Some("ctrl".to_string()),
+            optimize_storage_on_startup: Some(true),
+            storage_max_age_days: Some(7),
+            storage_max_size_mb: Some(512),
+            chat_list_group_by_category: Some(true),
+            auto_download_photos: Some("always".to_string()),
+            auto_download_videos: Some("always".to_string()),
+            auto_download_files: Some("always".to_string()),
+            metered_connection: Some(true),
+            message_tags: Some(vec!["todo".to_string()]),
+            history_batch_size: Some(100),
+            layout_chat_list_size: Some(25),
+            layout_prompt_size: Some(5),
+            show_usernames: Some(true),
+            notification_timeout: Some(10),
+            layout_chat_list_width: Some(30),
+            chat_list_auto_hide_width: Some(100),
+            selection_pulse: Some(false),
+            chat_list_avatars: Some(true),
+            lock_after_idle_minutes: Some(15),
+            chat_list_timestamp_format: Some("relative".to_string()),
+            chat_timestamp_format: Some("relative".to_string()),
+            layout_prompt_position: Some("top".to_string()),
+            prompt_max_lines: Some(8),
+            chat_list_read_on_dwell_ms: Some(1500),
+            prompt_placeholder: Some(false),
+            prompt_char_counter: Some(true),
+            chat_list_load_more_distance: Some(20),
+            chat_load_more_distance: Some(20),
+            highlight_keywords: Some(vec!["tgt".to_string()]),
+            chat_list_highlight_keywords: Some(false),
+            notification_sounds: Some(HashMap::from([("42".to_string(), "bell".to_string())])),
+            chat_list_compact: Some(true),
+            quit_when_backend_stops: Some(false),
+            prompt_empty_enter: Some("mark_read".to_string()),
+            timezone: Some("+05:30".to_string()),
+            chat_list_scroll_acceleration: Some("linear".to_string()),
+            chat_list_scroll_acceleration_max_step: Some(20),
+            chat_quick_reaction: Some("❤".to_string()),
+            auto_download_max_size_mb: Some(50),
+            send_read_receipts: Some(false),
+            max_messages_per_chat: Some(500),
+            strip_emoji: Some(true),
+            prompt_cursor_shape: Some("underline".to_string()),
+            prompt_cursor_blink: Some(false),
+            location: Some("48.8584, 2.2945".to_string()),
+            read_on_open_delay_ms: Some(2000),
+            prompt_split_long_messages: Some(false),
         };
         app_config = app_config.merge(Some(app_raw));
         assert!(!app_config.mouse_support);
@@ -190,6 +1234,122 @@ mod tests {
         assert!(app_config.show_title_bar);
         assert!(app_config.theme_enable);
         assert_eq!(app_config.theme_filename, "test");
+        assert_eq!(app_config.prompt_empty_enter, EmptyEnter::MarkRead);
+        assert_eq!(app_config.prompt_cursor_shape, CursorShape::Underline);
+        assert_eq!(
+            app_config.location,
+            LocationSource::Coordinates(48.8584, 2.2945)
+        );
+        assert_eq!(app_config.read_on_open_delay_ms, 2000);
+        assert!(!app_config.prompt_split_long_messages);
+        assert!(!app_config.prompt_cursor_blink);
+        assert_eq!(
+            app_config.timezone,
+            Timezone::Fixed(FixedOffset::east_opt(5 * 3_600 + 30 * 60).unwrap())
+        );
+        assert_eq!(
+            app_config.chat_list_scroll_acceleration,
+            ScrollAcceleration::Linear
+        );
+        assert_eq!(app_config.chat_list_scroll_acceleration_max_step, 20);
+        assert_eq!(app_config.chat_quick_reaction, "❤");
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(AppConfig::parse_timezone("local"), Timezone::Local);
+        assert_eq!(
+            AppConfig::parse_timezone("UTC"),
+            Timezone::Fixed(FixedOffset::east_opt(0).unwrap())
+        );
+        assert_eq!(
+            AppConfig::parse_timezone("-03:00"),
+            Timezone::Fixed(FixedOffset::west_opt(3 * 3_600).unwrap())
+        );
+        assert_eq!(AppConfig::parse_timezone("Mars/Olympus"), Timezone::Local);
+    }
+
+    #[test]
+    fn test_parse_notification_sounds() {
+        let sounds = AppConfig::parse_notification_sounds(HashMap::from([
+            ("-1001234".to_string(), "bell".to_string()),
+            ("42".to_string(), "none".to_string()),
+            ("7".to_string(), "/tmp/ping.ogg".to_string()),
+            ("not an id".to_string(), "bell".to_string()),
+        ]));
+        assert_eq!(sounds.len(), 3);
+        assert_eq!(sounds[&-1001234], NotificationSound::Bell);
+        assert_eq!(sounds[&42], NotificationSound::None);
+        assert_eq!(
+            sounds[&7],
+            NotificationSound::File(PathBuf::from("/tmp/ping.ogg"))
+        );
+    }
+
+    #[test]
+    fn test_should_auto_download() {
+        let mut app_config = AppConfig::default();
+        assert!(!app_config.should_auto_download(MediaKind::Photo, 0));
+        assert!(!app_config.should_auto_download(MediaKind::Video, 0));
+        assert!(!app_config.should_auto_download(MediaKind::File, 0));
+        app_config.auto_download_photos = AppConfig::parse_auto_download_policy("unmetered");
+        assert!(app_config.should_auto_download(MediaKind::Photo, 0));
+        app_config.metered_connection = true;
+        assert!(!app_config.should_auto_download(MediaKind::Photo, 0));
+        app_config.auto_download_files = AppConfig::parse_auto_download_policy("always");
+        assert!(app_config.should_auto_download(MediaKind::File, 0));
+        // The media larger than the limit are not downloaded.
+        let limit = app_config.auto_download_max_size_mb * 1024 * 1024;
+        assert!(app_config.should_auto_download(MediaKind::File, limit));
+        assert!(!app_config.should_auto_download(MediaKind::File, limit + 1));
+        app_config.auto_download_max_size_mb = 0;
+        assert!(app_config.should_auto_download(MediaKind::File, limit + 1));
+        assert_eq!(
+            AppConfig::parse_auto_download_policy("invalid"),
+            AutoDownloadPolicy::Never
+        );
+    }
+
+    #[test]
+    fn test_clamp_history_batch_size() {
+        assert_eq!(AppConfig::clamp_history_batch_size(50), 50);
+        assert_eq!(AppConfig::clamp_history_batch_size(500), 100);
+        assert_eq!(AppConfig::clamp_history_batch_size(0), 1);
+    }
+
+    #[test]
+    fn test_clamp_load_more_distance() {
+        assert_eq!(AppConfig::clamp_load_more_distance(10), 10);
+        assert_eq!(AppConfig::clamp_load_more_distance(10_000), 100);
+        assert_eq!(AppConfig::clamp_load_more_distance(0), 1);
+    }
+
+    #[test]
+    fn test_next_message_tag() {
+        let app_config = AppConfig::default();
+        assert_eq!(app_config.next_message_tag(None), Some("todo".to_string()));
+        assert_eq!(
+            app_config.next_message_tag(Some("todo")),
+            Some("reference".to_string())
+        );
+        assert_eq!(app_config.next_message_tag(Some("reference")), None);
+        assert_eq!(app_config.next_message_tag(Some("removed")), None);
+    }
+
+    #[test]
+    fn test_parse_enter_modifier() {
+        assert_eq!(
+            AppConfig::parse_enter_modifier("ctrl"),
+            KeyModifiers::CONTROL
+        );
+        assert_eq!(
+            AppConfig::parse_enter_modifier("shift"),
+            KeyModifiers::SHIFT
+        );
+        assert_eq!(
+            AppConfig::parse_enter_modifier("invalid"),
+            KeyModifiers::ALT
+        );
     }
 
     #[test]
@@ -210,6 +1370,57 @@ mod tests {
             theme_filename: None,
             take_api_id_from_telegram_config: None,
             take_api_hash_from_telegram_config: None,
+            focus_order: None,
+            show_unread_in_terminal_title: None,
+            prompt_send_on_enter: None,
+            prompt_enter_modifier: None,
+            optimize_storage_on_startup: None,
+            storage_max_age_days: None,
+            storage_max_size_mb: None,
+            chat_list_group_by_category: None,
+            auto_download_photos: None,
+            auto_download_videos: None,
+            auto_download_files: None,
+            metered_connection: None,
+            message_tags: None,
+            history_batch_size: None,
+            layout_chat_list_size: None,
+            layout_prompt_size: None,
+            show_usernames: None,
+            notification_timeout: None,
+            layout_chat_list_width: None,
+            chat_list_auto_hide_width: None,
+            selection_pulse: None,
+            chat_list_avatars: None,
+            lock_after_idle_minutes: None,
+            chat_list_timestamp_format: None,
+            chat_timestamp_format: None,
+            layout_prompt_position: None,
+            prompt_max_lines: None,
+            chat_list_read_on_dwell_ms: None,
+            prompt_placeholder: None,
+            prompt_char_counter: None,
+            chat_list_load_more_distance: None,
+            chat_load_more_distance: None,
+            highlight_keywords: None,
+            chat_list_highlight_keywords: None,
+            notification_sounds: None,
+            chat_list_compact: None,
+            quit_when_backend_stops: None,
+            prompt_empty_enter: None,
+            timezone: None,
+            chat_list_scroll_acceleration: None,
+            chat_list_scroll_acceleration_max_step: None,
+            chat_quick_reaction: None,
+            auto_download_max_size_mb: None,
+            send_read_receipts: None,
+            max_messages_per_chat: None,
+            strip_emoji: None,
+            prompt_cursor_shape: None,
+            prompt_cursor_blink: None,
+            location: None,
+            read_on_open_delay_ms: None,
+            prompt_split_long_messages: None,
         };
         app_config = app_config.merge(Some(app_raw));
         assert!(app_config.mouse_support);
@@ -313,7 +313,14 @@ impl KeymapConfig {
     ) -> &HashMap<Event, ActionBinding> {
         match component_name {
             Some(componnt) => match componnt {
-                ComponentName::ChatList => &self.chat_list,
+                // The tags overview and the pinned messages are lists, they
+                // share the chat list key bindings.
+                ComponentName::ChatList
+                | ComponentName::MessageTags
+                | ComponentName::PinnedMessages
+                | ComponentName::OnlineContacts
+                | ComponentName::BlockedUsers
+                | ComponentName::UserProfile => &self.chat_list,
                 ComponentName::Chat => &self.chat,
                 ComponentName::Prompt => &self.prompt,
                 _ => &self.core_window,
@@ -438,9 +445,9 @@ mod tests {
     #[test]
     fn test_keymap_config_default() {
         let keymap_config = KeymapConfig::default();
-        assert_eq!(keymap_config.core_window.len(), 15);
-        assert_eq!(keymap_config.chat_list.len(), 5);
-        assert_eq!(keymap_config.chat.len(), 9);
+        assert_eq!(keymap_config.core_window.len(), 30);
+        assert_eq!(keymap_config.chat_list.len(), 16);
+        assert_eq!(keymap_config.chat.len(), 31);
         assert_eq!(keymap_config.prompt.len(), 0);
     }
 
@@ -540,9 +547,9 @@ mod tests {
             prompt: Some(KeymapMode { keymap: vec![] }),
         };
         keymap_config = keymap_config.merge(Some(keymap_raw));
-        assert_eq!(keymap_config.core_window.len(), 15);
-        assert_eq!(keymap_config.chat_list.len(), 5);
-        assert_eq!(keymap_config.chat.len(), 9);
+        assert_eq!(keymap_config.core_window.len(), 30);
+        assert_eq!(keymap_config.chat_list.len(), 16);
+        assert_eq!(keymap_config.chat.len(), 31);
         assert_eq!(keymap_config.prompt.len(), 0);
     }
 
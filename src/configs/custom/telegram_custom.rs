@@ -21,6 +21,9 @@ pub struct TelegramConfig {
     pub api_hash: String,
     /// The directory where the database is stored.
     pub database_dir: String,
+    /// The directory where the downloaded files are stored, if empty the
+    /// database directory is used.
+    pub files_dir: String,
     /// A flag that indicates if the user database should be used.
     pub use_file_database: bool,
     /// A flag that indicates if the chat info database should be used.
@@ -49,6 +52,26 @@ impl TelegramConfig {
             &configs::custom::default_config_telegram_file_path()?,
         ))
     }
+    /// Check that the database directory and the files directory are
+    /// writable, TDLib fails in a cryptic way if they are not.
+    ///
+    /// # Returns
+    /// An error that contains the first directory that is not writable.
+    pub fn check_directories(&self) -> Result<(), AppError<()>> {
+        for dir in [&self.database_dir, &self.files_dir] {
+            if dir.is_empty() {
+                continue;
+            }
+            let test_file = Path::new(dir).join(".tgt_write_test");
+            if let Err(e) = std::fs::create_dir_all(dir)
+                .and_then(|_| std::fs::write(&test_file, b""))
+                .and_then(|_| std::fs::remove_file(&test_file))
+            {
+                return Err(AppError::NotWritableDirectory(format!("{}: {}", dir, e)));
+            }
+        }
+        Ok(())
+    }
 }
 /// The implementation of the configuration file for telegram.
 impl ConfigFile for TelegramConfig {
@@ -73,12 +96,22 @@ impl ConfigFile for TelegramConfig {
                 if let Some(api_hash) = _other.api_hash {
                     self.api_hash = api_hash;
                 }
+                // The directories are created by `check_directories`, that
+                // reports an error if they are not writable.
                 if let Some(database_dir) = _other.database_dir {
-                    if !Path::new(&database_dir).exists() {
-                        std::fs::create_dir_all(&database_dir).unwrap();
-                    }
                     self.database_dir = database_dir;
                 }
+                if let Some(files_dir) = _other.files_dir {
+                    self.files_dir = if files_dir.is_empty() {
+                        files_dir
+                    } else {
+                        utils::tgt_dir()
+                            .unwrap()
+                            .join(files_dir)
+                            .to_string_lossy()
+                            .to_string()
+                    };
+                }
                 if let Some(use_file_database) = _other.use_file_database {
                     self.use_file_database = use_file_database;
                 }
@@ -127,15 +160,24 @@ impl From<TelegramRaw> for TelegramConfig {
             .join(raw.database_dir.unwrap())
             .to_string_lossy()
             .to_string();
+        let files_dir = raw.files_dir.unwrap();
+        let files_dir = if files_dir.is_empty() {
+            files_dir
+        } else {
+            utils::tgt_dir()
+                .unwrap()
+                .join(files_dir)
+                .to_string_lossy()
+                .to_string()
+        };
         let log_path = utils::tgt_dir()
             .unwrap()
             .join(raw.log_path.unwrap())
             .to_string_lossy()
             .to_string();
 
-        if !Path::new(&database_dir).exists() {
-            std::fs::create_dir_all(&database_dir).unwrap();
-        }
+        // The directories are created by `check_directories`, that reports an
+        // error if they are not writable.
         if !Path::new(&log_path).exists() {
             std::fs::create_dir_all(PathBuf::from(&log_path).parent().unwrap()).unwrap();
         }
@@ -144,6 +186,7 @@ impl From<TelegramRaw> for TelegramConfig {
             api_id: raw.api_id.unwrap(),
             api_hash: raw.api_hash.unwrap(),
             database_dir,
+            files_dir,
             use_file_database: raw.use_file_database.unwrap(),
             use_chat_info_database: raw.use_chat_info_database.unwrap(),
             use_message_database: raw.use_message_database.unwrap(),
@@ -179,6 +222,7 @@ mod tests {
             api_id: Some("api_id".to_string()),
             api_hash: Some("api_hash".to_string()),
             database_dir: Some(".data/tg".to_string()),
+            files_dir: Some("".to_string()),
             use_file_database: Some(true),
             use_chat_info_database: Some(true),
             use_message_database: Some(true),
@@ -214,6 +258,54 @@ mod tests {
                 .to_string()
         );
         assert!(telegram_config.redirect_stderr);
+        assert_eq!(telegram_config.files_dir, "");
+    }
+
+    #[test]
+    fn test_check_directories() {
+        let dir =
+            std::env::temp_dir().join(format!("tgt-check-directories-{}", std::process::id()));
+        let mut telegram_config = TelegramConfig {
+            api_id: String::new(),
+            api_hash: String::new(),
+            database_dir: dir.join("database").display().to_string(),
+            files_dir: String::new(),
+            use_file_database: false,
+            use_chat_info_database: false,
+            use_message_database: false,
+            system_language_code: String::new(),
+            device_model: String::new(),
+            verbosity_level: 0,
+            log_path: String::new(),
+            redirect_stderr: false,
+        };
+        assert!(telegram_config.check_directories().is_ok());
+        // A regular file can not be the parent of a directory, even for root.
+        let file = dir.join("file");
+        std::fs::write(&file, b"").unwrap();
+        telegram_config.files_dir = file.join("files").display().to_string();
+        assert!(telegram_config.check_directories().is_err());
+        // An uncreatable directory from the user config does not panic while
+        // merging, it is reported by `check_directories`.
+        telegram_config.files_dir = String::new();
+        let files_dir = file.join("merged").display().to_string();
+        telegram_config.merge(Some(TelegramRaw {
+            api_id: None,
+            api_hash: None,
+            database_dir: None,
+            files_dir: Some(files_dir.clone()),
+            use_file_database: None,
+            use_chat_info_database: None,
+            use_message_database: None,
+            system_language_code: None,
+            device_model: None,
+            verbosity_level: None,
+            log_path: None,
+            redirect_stderr: None,
+        }));
+        assert_eq!(telegram_config.files_dir, files_dir);
+        assert!(telegram_config.check_directories().is_err());
+        std::fs::remove_dir_all(dir).unwrap();
     }
 
     #[test]
@@ -222,6 +314,7 @@ mod tests {
             api_id: "api_id".to_string(),
             api_hash: "api_hash".to_string(),
             database_dir: ".data/tg".to_string(),
+            files_dir: "".to_string(),
             use_file_database: false,
             use_chat_info_database: false,
             use_message_database: false,
@@ -235,6 +328,7 @@ mod tests {
             api_id: Some("api_id_2".to_string()),
             api_hash: Some("api_hash_2".to_string()),
             database_dir: None,
+            files_dir: None,
             use_file_database: Some(true),
             use_chat_info_database: Some(true),
             use_message_database: Some(true),
@@ -267,6 +361,7 @@ mod tests {
             api_id: "api_id".to_string(),
             api_hash: "api_hash".to_string(),
             database_dir: ".data/tg".to_string(),
+            files_dir: "".to_string(),
             use_file_database: false,
             use_chat_info_database: false,
             use_message_database: false,
@@ -296,6 +391,7 @@ mod tests {
             api_id: "api_id".to_string(),
             api_hash: "api_hash".to_string(),
             database_dir: ".data/tg".to_string(),
+            files_dir: "".to_string(),
             use_file_database: false,
             use_chat_info_database: false,
             use_message_database: false,
@@ -309,6 +405,7 @@ mod tests {
             api_id: Some("api_id_2".to_string()),
             api_hash: None,
             database_dir: None,
+            files_dir: None,
             use_file_database: None,
             use_chat_info_database: None,
             use_message_database: None,
@@ -343,6 +440,7 @@ mod tests {
             api_id: "api_id".to_string(),
             api_hash: "api_hash".to_string(),
             database_dir: ".data/tg".to_string(),
+            files_dir: "".to_string(),
             use_file_database: false,
             use_chat_info_database: false,
             use_message_database: false,
@@ -356,6 +454,7 @@ mod tests {
             api_id: Some("api_id_2".to_string()),
             api_hash: Some("api_hash_2".to_string()),
             database_dir: None,
+            files_dir: None,
             use_file_database: Some(true),
             use_chat_info_database: Some(true),
             use_message_database: Some(true),
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Deserialize)]
 /// The raw application configuration.
@@ -26,4 +27,126 @@ pub struct AppRaw {
     /// A boolean flag that represents whether the API_HASH should be taken from
     /// the Telegram configuration or from environment variables `API_HASH`.
     pub take_api_hash_from_telegram_config: Option<bool>,
+    /// The order in which the components are focused when cycling the
+    /// focus with the `focus_next` and `focus_previous` commands.
+    pub focus_order: Option<Vec<String>>,
+    /// A boolean flag that represents whether the total unread messages count
+    /// should be shown in the terminal title or not.
+    pub show_unread_in_terminal_title: Option<bool>,
+    /// Send the message with Enter and insert a newline with Alt+Enter or
+    /// Ctrl+Enter, instead of the opposite.
+    pub prompt_send_on_enter: Option<bool>,
+    /// The modifier that, combined with Enter, performs the action that Enter
+    /// alone does not perform.
+    pub prompt_enter_modifier: Option<String>,
+    /// Remove the cached files that exceed the storage limits at startup.
+    pub optimize_storage_on_startup: Option<bool>,
+    /// The number of days after which a cached file not accessed is removed.
+    pub storage_max_age_days: Option<i32>,
+    /// The maximum size of the cached files in megabytes.
+    pub storage_max_size_mb: Option<i64>,
+    /// Group the chats of the chat list by category.
+    pub chat_list_group_by_category: Option<bool>,
+    /// The automatic download policy of the photos.
+    pub auto_download_photos: Option<String>,
+    /// The automatic download policy of the videos.
+    pub auto_download_videos: Option<String>,
+    /// The automatic download policy of the files.
+    pub auto_download_files: Option<String>,
+    /// The connection is metered, the media with the `unmetered` automatic
+    /// download policy are not downloaded.
+    pub metered_connection: Option<bool>,
+    /// The tags that can be given to the messages.
+    pub message_tags: Option<Vec<String>>,
+    /// The number of messages requested at once when loading the chat history.
+    pub history_batch_size: Option<i32>,
+    /// The width of the chat list, as a percentage of the width of the terminal.
+    pub layout_chat_list_size: Option<u16>,
+    /// The height of the prompt, in lines.
+    pub layout_prompt_size: Option<u16>,
+    /// Show the usernames of the users instead of their full names.
+    pub show_usernames: Option<bool>,
+    /// The number of seconds after which the notifications of the status
+    /// bar expire.
+    pub notification_timeout: Option<u64>,
+    /// The fixed width of the chat list in columns, `0` uses
+    /// `layout_chat_list_size` instead.
+    pub layout_chat_list_width: Option<u16>,
+    /// The terminal width in columns below which the chat list is hidden
+    /// automatically, `0` disables it.
+    pub chat_list_auto_hide_width: Option<u16>,
+    /// Briefly pulse the highlight of the chat selected in the chat list.
+    pub selection_pulse: Option<bool>,
+    /// Show a colored avatar with the initials of the chat in the chat
+    /// list.
+    pub chat_list_avatars: Option<bool>,
+    /// The minutes without any input after which tgt is locked behind the
+    /// passphrase prompt. If it is 0 tgt is never locked automatically.
+    pub lock_after_idle_minutes: Option<u64>,
+    /// The format of the time of the last message of the chats in the chat
+    /// list: "absolute" or "relative".
+    pub chat_list_timestamp_format: Option<String>,
+    /// The format of the time of the messages in the chat: "absolute" or
+    /// "relative".
+    pub chat_timestamp_format: Option<String>,
+    /// The position of the prompt: "top" or "bottom".
+    pub layout_prompt_position: Option<String>,
+    /// The maximum number of lines of text the prompt grows to.
+    pub prompt_max_lines: Option<usize>,
+    /// The time in milliseconds the selection rests on an unread chat before it is marked as read.
+    pub chat_list_read_on_dwell_ms: Option<u64>,
+    /// Indicates whether the empty prompt shows a placeholder with the name of the open chat.
+    pub prompt_placeholder: Option<bool>,
+    /// Indicates whether the prompt shows the number of characters of the message.
+    pub prompt_char_counter: Option<bool>,
+    /// The number of chats from the end of the chat list at which more chats are loaded.
+    pub chat_list_load_more_distance: Option<usize>,
+    /// The number of messages from the oldest loaded message at which older messages are loaded.
+    pub chat_load_more_distance: Option<usize>,
+    /// The keywords highlighted in the messages.
+    pub highlight_keywords: Option<Vec<String>>,
+    /// Indicates whether the chats with an unread message that matches the keywords are highlighted.
+    pub chat_list_highlight_keywords: Option<bool>,
+    /// The sound played when a message arrives in a chat, by chat id: "bell",
+    /// "none" or the path of a sound file.
+    pub notification_sounds: Option<HashMap<String, String>>,
+    /// A boolean flag that represents whether each chat of the chat list is
+    /// shown on a single line, without the preview of its last message.
+    pub chat_list_compact: Option<bool>,
+    /// A boolean flag that represents whether the application quits when the
+    /// Telegram backend stops unexpectedly.
+    pub quit_when_backend_stops: Option<bool>,
+    /// The behavior of Enter when the prompt is empty: "ignore",
+    /// "focus_chat_list" or "mark_read".
+    pub prompt_empty_enter: Option<String>,
+    /// The timezone of the dates and the times shown: "local", "utc" or an
+    /// offset from UTC like "+05:30".
+    pub timezone: Option<String>,
+    /// How the step of the selection of the chat list grows while a
+    /// navigation key is held: "off", "linear" or "exponential".
+    pub chat_list_scroll_acceleration: Option<String>,
+    /// The largest number of chats the selection of the chat list moves by
+    /// at a time while accelerating.
+    pub chat_list_scroll_acceleration_max_step: Option<usize>,
+    /// The reaction toggled on the selected message with a single key.
+    pub chat_quick_reaction: Option<String>,
+    /// The maximum size in megabytes of the media downloaded automatically.
+    pub auto_download_max_size_mb: Option<i64>,
+    /// Whether the messages of a chat are marked as read when it is opened.
+    pub send_read_receipts: Option<bool>,
+    /// The maximum number of messages of a chat kept in memory.
+    pub max_messages_per_chat: Option<usize>,
+    /// Whether the emoji are stripped from the interface.
+    pub strip_emoji: Option<bool>,
+    /// The shape of the cursor of the prompt: "block", "bar" or "underline".
+    pub prompt_cursor_shape: Option<String>,
+    /// Whether the cursor of the prompt blinks.
+    pub prompt_cursor_blink: Option<bool>,
+    /// The location sent from the prompt when no coordinates are typed:
+    /// `latitude, longitude` or the path of a file containing them.
+    pub location: Option<String>,
+    /// The delay in milliseconds before an opened chat is marked as read.
+    pub read_on_open_delay_ms: Option<u64>,
+    /// Whether the messages longer than the limit of Telegram are split.
+    pub prompt_split_long_messages: Option<bool>,
 }
@@ -13,6 +13,8 @@ pub struct TelegramRaw {
     pub api_hash: Option<String>,
     /// The directory where the database is stored.
     pub database_dir: Option<String>,
+    /// The directory where the downloaded files are stored.
+    pub files_dir: Option<String>,
     /// A flag that indicates if the user database should be used.
     pub use_file_database: Option<bool>,
     /// A flag that indicates if the chat info database should be used.
@@ -40,6 +40,20 @@ pub enum Event {
     /// This event is used to reply to a message.
     /// The first parameter is the `message_id` and the second parameter is the `text`.
     ReplyMessage(i64, String),
+    /// ReplyPrivately event.
+    /// It is sent to reply to a group message in the private chat with its
+    /// sender. The parameters are the `user_id` of the sender, the
+    /// `message_id` and the `text` of the message.
+    ReplyPrivately(i64, i64, String),
+    /// OpenPrivateChat event.
+    /// It opens the private chat with a user, creating it if needed, the
+    /// parameter is the `user_id`.
+    OpenPrivateChat(i64),
+    /// SetReaction event.
+    /// It adds or removes an emoji reaction on a message of the open chat.
+    /// The parameters are the `message_id`, the `emoji` and whether the
+    /// reaction is added.
+    SetReaction(i64, String, bool),
 
     /// GetMe event.
     GetMe,
@@ -53,15 +67,100 @@ pub enum Event {
     /// Send message edited event with a `i64` and a `String`.
     /// The first parameter is the `message_id` and the second parameter is the `text`.
     SendMessageEdited(i64, String),
+    /// Send an animated dice with the given emoji to the open chat.
+    SendDice(String),
+    /// Send a location to the open chat, the parameter is the location typed
+    /// in the prompt, see `utils::parse_location`.
+    SendLocation(String),
+    /// Load sticker sets event.
+    /// It is sent to load the sticker sets installed by the user.
+    LoadStickerSets,
+    /// Send sticker event.
+    /// It is sent to send a sticker to the open chat, the parameters are the
+    /// `file_id` and the `emoji` of the sticker.
+    SendSticker(i32, String),
     /// Get chat history event.
     GetChatHistory,
-    /// Delete messages event with a `Vec<i64>` and a `bool`.
-    /// The first parameter is the `message_ids` and the second parameter is the `revoke`.
-    /// If `revoke` is true, the message will be deleted for everyone.
-    /// If `revoke` is false, the message will be deleted only for the current user.
-    DeleteMessages(Vec<i64>, bool),
+    /// Get newer messages event.
+    /// It is sent to load the messages arrived in the open chat since its
+    /// cached messages were loaded.
+    GetNewerMessages,
     /// View all messages event.
     ViewAllMessages,
+    /// Get callback query answer event.
+    /// It is sent when an inline keyboard callback button is pressed.
+    /// The first parameter is the `message_id` and the second parameter is the
+    /// `data` of the button.
+    GetCallbackQueryAnswer(i64, String),
+    /// Open message thread event.
+    /// It is sent to open the comments of a channel post, the parameter is
+    /// the `message_id` of the post.
+    OpenMessageThread(i64),
+    /// Download file event.
+    /// It is sent to download the file of a message, the parameter is the
+    /// `file_id`.
+    DownloadFile(i32),
+    /// Open file event.
+    /// It is sent to open the file of a message with the default
+    /// application, the parameter is the `file_id`.
+    OpenFile(i32),
+    /// Delete chat draft event.
+    /// It is sent to remove the draft of a chat, the parameter is the
+    /// `chat_id`.
+    DeleteChatDraft(i64),
+    /// Get message edit history event.
+    /// It is sent to show the previous versions of a message of the open
+    /// chat, the parameter is the `message_id`.
+    GetMessageEditHistory(i64),
+    /// Get message viewers event.
+    /// It is sent to show the users who read an outgoing message of the open
+    /// chat, the parameter is the `message_id`.
+    GetMessageViewers(i64),
+    /// Check chat invite link event.
+    /// It is sent to preview the chat of an invite link before joining it,
+    /// the parameter is the invite link.
+    CheckChatInviteLink(String),
+    /// Get pinned messages event.
+    /// It is sent to show the list of the pinned messages of a chat, the
+    /// parameter is the `chat_id`.
+    GetPinnedMessages(i64),
+    /// Jump to next mention event.
+    /// It is sent to select the oldest unread mention of a chat and mark it
+    /// as read, the parameter is the `chat_id`.
+    JumpToNextMention(i64),
+    /// Set user blocked event.
+    /// It is sent to block or unblock a user, the parameters are the
+    /// `user_id` and whether the user is blocked.
+    SetUserBlocked(i64, bool),
+    /// Get blocked users event.
+    /// It is sent to show the list of the users blocked by the user.
+    GetBlockedUsers,
+    /// Read chat event.
+    /// It is sent to mark all the messages of a chat as read without opening
+    /// it, the parameter is the `chat_id`.
+    ReadChat(i64),
+    /// Set chat pinned event.
+    /// It is sent to pin or unpin a chat in the chat list, the parameters are
+    /// the `chat_id` and whether the chat is pinned.
+    SetChatPinned(i64, bool),
+    /// Set chat muted event.
+    /// It is sent to mute or unmute the notifications of a chat, the
+    /// parameters are the `chat_id` and whether the chat is muted.
+    SetChatMuted(i64, bool),
+    /// Mark chat as unread event.
+    /// It is sent to mark a chat as unread, the parameter is the `chat_id`.
+    MarkChatAsUnread(i64),
+    /// Archive chat event.
+    /// It is sent to move a chat to the archive, the parameter is the
+    /// `chat_id`.
+    ArchiveChat(i64),
+    /// Refresh chats event.
+    /// It is sent to reload the chat list from the start.
+    RefreshChats,
+    /// Get user event.
+    /// It is sent to fetch a user missing from the cache, the parameter is
+    /// the `user_id`.
+    GetUser(i64),
 }
 /// Implement the `Event` enum.
 impl Event {
@@ -184,24 +283,72 @@ impl Display for Event {
             Event::SendMessage(s, reply_to) => {
                 write!(f, "SendMessage({}, {:?})", s, reply_to)
             }
+            Event::SendDice(emoji) => write!(f, "SendDice({})", emoji),
+            Event::SendLocation(location) => write!(f, "SendLocation({})", location),
+            Event::LoadStickerSets => write!(f, "LoadStickerSets"),
+            Event::SendSticker(file_id, emoji) => write!(f, "SendSticker({}, {})", file_id, emoji),
             Event::SendMessageEdited(message_id, s) => {
                 write!(f, "SendMessageEdited({}, {})", message_id, s)
             }
             Event::GetChatHistory => {
                 write!(f, "GetChatHistory")
             }
-            Event::DeleteMessages(message_ids, revoke) => {
-                write!(f, "DeleteMessages({:?}, {})", message_ids, revoke)
-            }
+            Event::GetNewerMessages => write!(f, "GetNewerMessages"),
             Event::EditMessage(message_id, text) => {
                 write!(f, "EditMessage({}, {})", message_id, text)
             }
             Event::ReplyMessage(message_id, text) => {
                 write!(f, "ReplyMessage({}, {})", message_id, text)
             }
+            Event::ReplyPrivately(user_id, message_id, text) => {
+                write!(f, "ReplyPrivately({}, {}, {})", user_id, message_id, text)
+            }
+            Event::OpenPrivateChat(user_id) => write!(f, "OpenPrivateChat({})", user_id),
+            Event::SetReaction(message_id, emoji, chosen) => {
+                write!(f, "SetReaction({}, {}, {})", message_id, emoji, chosen)
+            }
             Event::ViewAllMessages => {
                 write!(f, "ViewAllMessages")
             }
+            Event::GetCallbackQueryAnswer(message_id, data) => {
+                write!(f, "GetCallbackQueryAnswer({}, {})", message_id, data)
+            }
+            Event::OpenMessageThread(message_id) => {
+                write!(f, "OpenMessageThread({})", message_id)
+            }
+            Event::DownloadFile(file_id) => {
+                write!(f, "DownloadFile({})", file_id)
+            }
+            Event::OpenFile(file_id) => write!(f, "OpenFile({})", file_id),
+            Event::DeleteChatDraft(chat_id) => {
+                write!(f, "DeleteChatDraft({})", chat_id)
+            }
+            Event::GetMessageEditHistory(message_id) => {
+                write!(f, "GetMessageEditHistory({})", message_id)
+            }
+            Event::GetMessageViewers(message_id) => {
+                write!(f, "GetMessageViewers({})", message_id)
+            }
+            Event::CheckChatInviteLink(invite_link) => {
+                write!(f, "CheckChatInviteLink({})", invite_link)
+            }
+            Event::GetPinnedMessages(chat_id) => write!(f, "GetPinnedMessages({})", chat_id),
+            Event::JumpToNextMention(chat_id) => write!(f, "JumpToNextMention({})", chat_id),
+            Event::SetUserBlocked(user_id, blocked) => {
+                write!(f, "SetUserBlocked({}, {})", user_id, blocked)
+            }
+            Event::GetBlockedUsers => write!(f, "GetBlockedUsers"),
+            Event::ReadChat(chat_id) => write!(f, "ReadChat({})", chat_id),
+            Event::SetChatPinned(chat_id, pinned) => {
+                write!(f, "SetChatPinned({}, {})", chat_id, pinned)
+            }
+            Event::SetChatMuted(chat_id, muted) => {
+                write!(f, "SetChatMuted({}, {})", chat_id, muted)
+            }
+            Event::MarkChatAsUnread(chat_id) => write!(f, "MarkChatAsUnread({})", chat_id),
+            Event::ArchiveChat(chat_id) => write!(f, "ArchiveChat({})", chat_id),
+            Event::RefreshChats => write!(f, "RefreshChats"),
+            Event::GetUser(user_id) => write!(f, "GetUser({})", user_id),
         }
     }
 }
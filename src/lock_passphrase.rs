@@ -0,0 +1,219 @@
+use sha2::Sha256;
+use std::{
+    collections::hash_map::RandomState,
+    fmt, fs,
+    hash::{BuildHasher, Hasher},
+    io,
+    path::PathBuf,
+};
+
+/// The path of the file where the hash of the lock passphrase is stored,
+/// relative to the `tgt` directory.
+pub const LOCK_PASSPHRASE_FILE: &str = ".data/lock_passphrase";
+/// The number of PBKDF2 rounds used to hash the passphrase.
+#[cfg(not(test))]
+const ROUNDS: u32 = 100_000;
+/// Fewer rounds in the tests, the hash is slow without optimizations.
+#[cfg(test)]
+const ROUNDS: u32 = 1_000;
+/// The length in bytes of the salt and of the hash.
+const LEN: usize = 16;
+
+/// `PassphraseHash` is the salted hash of a lock passphrase.
+/// The passphrase itself is never stored.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PassphraseHash {
+    /// The random salt of the hash.
+    salt: [u8; LEN],
+    /// The PBKDF2-HMAC-SHA256 hash of the passphrase.
+    hash: [u8; LEN],
+}
+
+impl PassphraseHash {
+    /// Hash a passphrase with a given salt.
+    ///
+    /// # Arguments
+    /// * `passphrase` - The passphrase to hash.
+    /// * `salt` - The salt of the hash.
+    fn with_salt(passphrase: &str, salt: [u8; LEN]) -> Self {
+        let mut hash = [0; LEN];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), &salt, ROUNDS, &mut hash);
+        Self { salt, hash }
+    }
+
+    /// Hash a passphrase with a new random salt.
+    ///
+    /// # Arguments
+    /// * `passphrase` - The passphrase to hash.
+    pub fn new(passphrase: &str) -> Self {
+        let mut salt = [0; LEN];
+        for chunk in salt.chunks_mut(8) {
+            // Every `RandomState` is seeded with random keys by the standard
+            // library, it is enough for a salt.
+            let random = RandomState::new().build_hasher().finish().to_le_bytes();
+            chunk.copy_from_slice(&random[..chunk.len()]);
+        }
+        Self::with_salt(passphrase, salt)
+    }
+
+    /// Check whether a passphrase matches the hash.
+    ///
+    /// # Arguments
+    /// * `passphrase` - The passphrase to check.
+    pub fn verify(&self, passphrase: &str) -> bool {
+        Self::with_salt(passphrase, self.salt) == *self
+    }
+
+    /// Parse the hash from a line of the passphrase file.
+    /// The salt and the hash are hex encoded and separated by a `$`.
+    ///
+    /// # Arguments
+    /// * `line` - The line of the passphrase file.
+    ///
+    /// # Returns
+    /// * `Option<PassphraseHash>` - The hash, or `None` if the line is not
+    ///   valid.
+    fn from_line(line: &str) -> Option<Self> {
+        let (salt, hash) = line.trim().split_once('$')?;
+        Some(Self {
+            salt: from_hex(salt)?,
+            hash: from_hex(hash)?,
+        })
+    }
+
+    /// Convert the hash to a line of the passphrase file.
+    fn to_line(&self) -> String {
+        format!("{}${}", to_hex(&self.salt), to_hex(&self.hash))
+    }
+}
+
+/// The hash is not written to the logs.
+impl fmt::Debug for PassphraseHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PassphraseHash(..)")
+    }
+}
+
+/// Encode bytes as a lowercase hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex string of exactly `LEN` bytes.
+fn from_hex(hex: &str) -> Option<[u8; LEN]> {
+    if hex.len() != LEN * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0; LEN];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// `LockPassphrase` is the passphrase that unlocks `tgt` once it has been
+/// locked, stored locally as a salted hash.
+#[derive(Debug, Default)]
+pub struct LockPassphrase {
+    /// The file where the hash is saved, if `None` the hash is not saved.
+    path: Option<PathBuf>,
+    /// The hash of the passphrase, `None` if no passphrase has been chosen.
+    hash: Option<PassphraseHash>,
+}
+
+impl LockPassphrase {
+    /// Load the hash of the passphrase from a file.
+    /// A missing file is not an error, no passphrase has been chosen yet.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the passphrase file.
+    ///
+    /// # Returns
+    /// * `LockPassphrase` - The loaded passphrase.
+    pub fn load(path: PathBuf) -> Self {
+        let hash = match fs::read_to_string(&path) {
+            Ok(content) => {
+                let hash = PassphraseHash::from_line(&content);
+                if hash.is_none() {
+                    tracing::error!("Invalid lock passphrase file {:?}", path);
+                }
+                hash
+            }
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    tracing::error!("Failed to read the lock passphrase {:?}: {e}", path);
+                }
+                None
+            }
+        };
+        Self {
+            path: Some(path),
+            hash,
+        }
+    }
+
+    /// Whether a passphrase has been chosen.
+    pub fn is_set(&self) -> bool {
+        self.hash.is_some()
+    }
+
+    /// Check whether a passphrase unlocks `tgt`.
+    ///
+    /// # Arguments
+    /// * `passphrase` - The passphrase to check.
+    pub fn verify(&self, passphrase: &str) -> bool {
+        self.hash.as_ref().is_some_and(|h| h.verify(passphrase))
+    }
+
+    /// Choose a new passphrase and save its hash.
+    ///
+    /// # Arguments
+    /// * `passphrase` - The new passphrase.
+    pub fn set(&mut self, passphrase: &str) {
+        let hash = PassphraseHash::new(passphrase);
+        if let Some(path) = self.path.as_ref() {
+            if let Err(e) = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(path, hash.to_line() + "\n"))
+            {
+                tracing::error!("Failed to save the lock passphrase {:?}: {e}", path);
+            }
+        }
+        self.hash = Some(hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_hash_verify() {
+        let hash = PassphraseHash::new("correct horse");
+        assert!(hash.verify("correct horse"));
+        assert!(!hash.verify("correct horse "));
+        assert!(!hash.verify(""));
+        assert_ne!(hash, PassphraseHash::new("correct horse"));
+    }
+
+    #[test]
+    fn test_passphrase_hash_line() {
+        let hash = PassphraseHash::new("secret");
+        let parsed = PassphraseHash::from_line(&(hash.to_line() + "\n")).unwrap();
+        assert!(parsed.verify("secret"));
+        assert_eq!(PassphraseHash::from_line("not a hash"), None);
+        assert_eq!(PassphraseHash::from_line("00$11"), None);
+    }
+
+    #[test]
+    fn test_lock_passphrase_set() {
+        let mut lock = LockPassphrase::default();
+        assert!(!lock.is_set());
+        assert!(!lock.verify(""));
+        lock.set("secret");
+        assert!(lock.is_set());
+        assert!(lock.verify("secret"));
+        assert!(!lock.verify("Secret"));
+    }
+}
@@ -1,10 +1,13 @@
 pub mod action;
+pub mod api_credentials;
 pub mod app_context;
 pub mod app_error;
 pub mod cli;
 pub mod component_name;
 pub mod event;
+pub mod lock_passphrase;
 pub mod logger;
+pub mod setup_wizard;
 pub mod tui;
 pub mod tui_backend;
 pub mod utils;
@@ -24,8 +27,13 @@ use crate::configs::{
         palette_custom::PaletteConfig, theme_custom::ThemeConfig,
     },
 };
+use crate::lock_passphrase::{LockPassphrase, LOCK_PASSPHRASE_FILE};
 use crate::logger::Logger;
-use crate::tg::{tg_backend::TgBackend, tg_context::TgContext};
+use crate::tg::{
+    message_tags::{MessageTags, MESSAGE_TAGS_FILE},
+    tg_backend::TgBackend,
+    tg_context::TgContext,
+};
 use crate::tui::Tui;
 use crate::tui_backend::TuiBackend;
 use clap::Parser;
@@ -86,8 +94,14 @@ async fn tokio_main() -> Result<(), AppError<()>> {
     // This is done to avoid that deleting a message other application in
     // a chats causes the `--send-message` to resend the messages that were deleted.
     telegram_config.use_message_database = std::env::args().count() <= 1;
+    if let Err(e) = telegram_config.check_directories() {
+        eprintln!("[ERROR]: {}", e);
+        return Err(e);
+    }
 
     let tg_context = TgContext::default();
+    tg_context.set_show_usernames(app_config.show_usernames);
+    tg_context.set_message_tags(MessageTags::load(utils::tgt_dir()?.join(MESSAGE_TAGS_FILE)));
     tracing::info!("Telegram context: {:?}", tg_context);
     let app_context = Arc::new(AppContext::new(
         app_config,
@@ -98,6 +112,9 @@ async fn tokio_main() -> Result<(), AppError<()>> {
         tg_context,
         cli_args,
     )?);
+    app_context.set_lock_passphrase(LockPassphrase::load(
+        utils::tgt_dir()?.join(LOCK_PASSPHRASE_FILE),
+    ));
     tracing::info!("App context: {:?}", app_context);
 
     let mut tui_backend = TuiBackend::new(Arc::clone(&app_context))?;
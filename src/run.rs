@@ -1,14 +1,29 @@
 use crate::component_name::ComponentName::Prompt;
 use crate::{
-    action::Action, app_context::AppContext, app_error::AppError,
-    configs::custom::keymap_custom::ActionBinding, event::Event, tg::tg_backend::TgBackend,
-    tui::Tui, tui_backend::TuiBackend,
+    action::{Action, NotifyLevel},
+    app_context::AppContext,
+    app_error::AppError,
+    configs::custom::{app_custom::NotificationSound, keymap_custom::ActionBinding},
+    event::Event,
+    tg::tg_backend::TgBackend,
+    tui::Tui,
+    tui_backend::TuiBackend,
+    utils::play_sound,
 };
 use ratatui::layout::Rect;
-use std::{collections::HashMap, io, sync::Arc, time::Instant};
+use std::{
+    collections::HashMap,
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tdlib_rs::enums::ChatList;
 use tokio::sync::mpsc::UnboundedSender;
 
+/// The time without input after which the user is no longer reading the open
+/// chat, then its notification sound is played again.
+const READING_IDLE_TIME: Duration = Duration::from_secs(60);
+
 /// Run the main event loop for the application.
 /// This function will process events and actions for the tui and the backend.
 ///
@@ -49,18 +64,27 @@ pub async fn run_app(
         }
         HandleCliOutcome::Continue => {}
     }
+    let chat_to_open = chat_from_cli(Arc::clone(&app_context), tg_backend).await;
 
     tg_backend.online().await;
     tg_backend.disable_animated_emoji(true).await;
+    if app_context.app_config().optimize_storage_on_startup {
+        tg_backend.optimize_storage().await;
+    }
 
     tui_backend.enter()?;
     tui.register_action_handler(app_context.action_tx().clone())?;
+    if let Some(chat_id) = chat_to_open {
+        app_context.action_tx().send(Action::OpenChat(chat_id))?;
+    }
 
     // Main loop
     while tg_backend.have_authorization {
         handle_tui_backend_events(Arc::clone(&app_context), tui, tui_backend).await?;
         handle_tg_backend_events(Arc::clone(&app_context), tg_backend).await?;
         handle_app_actions(Arc::clone(&app_context), tui, tui_backend, tg_backend).await?;
+        lock_if_idle(&app_context)?;
+        quit_if_backend_stopped(&app_context);
 
         if app_context.quit_acquire() {
             quit_tui(tg_backend, tui_backend).await;
@@ -96,6 +120,22 @@ async fn handle_tg_backend_events(
                     .action_tx()
                     .send(Action::SendMessage(message, reply_to))?;
             }
+            Event::SendDice(emoji) => {
+                app_context.action_tx().send(Action::SendDice(emoji))?;
+            }
+            Event::SendLocation(location) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SendLocation(location))?;
+            }
+            Event::LoadStickerSets => {
+                app_context.action_tx().send(Action::LoadStickerSets)?;
+            }
+            Event::SendSticker(file_id, emoji) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SendSticker(file_id, emoji))?;
+            }
             Event::SendMessageEdited(message_id, message) => {
                 app_context
                     .action_tx()
@@ -104,10 +144,8 @@ async fn handle_tg_backend_events(
             Event::GetChatHistory => {
                 app_context.action_tx().send(Action::GetChatHistory)?;
             }
-            Event::DeleteMessages(message_ids, revoke) => {
-                app_context
-                    .action_tx()
-                    .send(Action::DeleteMessages(message_ids, revoke))?;
+            Event::GetNewerMessages => {
+                app_context.action_tx().send(Action::GetNewerMessages)?;
             }
             Event::EditMessage(message_id, message) => {
                 // It is important to focus the prompt before editing the message.
@@ -129,9 +167,101 @@ async fn handle_tg_backend_events(
                     .action_tx()
                     .send(Action::ReplyMessage(message_id, message))?;
             }
+            Event::ReplyPrivately(user_id, message_id, message) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ReplyPrivately(user_id, message_id, message))?;
+            }
+            Event::OpenPrivateChat(user_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenPrivateChat(user_id))?;
+            }
+            Event::SetReaction(message_id, emoji, chosen) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetReaction(message_id, emoji, chosen))?;
+            }
             Event::ViewAllMessages => {
                 app_context.action_tx().send(Action::ViewAllMessages)?;
             }
+            Event::GetCallbackQueryAnswer(message_id, data) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetCallbackQueryAnswer(message_id, data))?;
+            }
+            Event::OpenMessageThread(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenMessageThread(message_id))?;
+            }
+            Event::DownloadFile(file_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::DownloadFile(file_id))?;
+            }
+            Event::OpenFile(file_id) => {
+                app_context.action_tx().send(Action::OpenFile(file_id))?;
+            }
+            Event::DeleteChatDraft(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::DeleteChatDraft(chat_id))?;
+            }
+            Event::GetMessageEditHistory(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetMessageEditHistory(message_id))?;
+            }
+            Event::GetMessageViewers(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetMessageViewers(message_id))?;
+            }
+            Event::CheckChatInviteLink(invite_link) => {
+                app_context
+                    .action_tx()
+                    .send(Action::CheckChatInviteLink(invite_link))?;
+            }
+            Event::GetPinnedMessages(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetPinnedMessages(chat_id))?;
+            }
+            Event::JumpToNextMention(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::JumpToNextMention(chat_id))?;
+            }
+            Event::ReadChat(chat_id) => {
+                app_context.action_tx().send(Action::ReadChat(chat_id))?;
+            }
+            Event::SetChatPinned(chat_id, pinned) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetChatPinned(chat_id, pinned))?;
+            }
+            Event::SetChatMuted(chat_id, muted) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetChatMuted(chat_id, muted))?;
+            }
+            Event::MarkChatAsUnread(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::MarkChatAsUnread(chat_id))?;
+            }
+            Event::ArchiveChat(chat_id) => {
+                app_context.action_tx().send(Action::ArchiveChat(chat_id))?;
+            }
+            Event::SetUserBlocked(user_id, blocked) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetUserBlocked(user_id, blocked))?;
+            }
+            Event::GetBlockedUsers => app_context.action_tx().send(Action::GetBlockedUsers)?,
+            Event::RefreshChats => app_context.action_tx().send(Action::RefreshChats)?,
+            Event::GetUser(user_id) => app_context.action_tx().send(Action::GetUser(user_id))?,
             _ => {}
         }
     }
@@ -155,6 +285,29 @@ async fn handle_tui_backend_events(
     tui_backend: &mut TuiBackend,
 ) -> Result<(), AppError<Action>> {
     if let Some(event) = tui_backend.next().await {
+        if matches!(event, Event::Key(..) | Event::Mouse(_) | Event::Paste(_)) {
+            app_context.touch_input();
+            // The core window is hidden by the loading screen, the input
+            // would act on it blindly, only quitting is allowed.
+            if tui.is_loading() {
+                if let Event::Key(key, modifiers) = event {
+                    let quit = matches!(
+                        app_context
+                            .keymap_config()
+                            .core_window
+                            .get(&Event::Key(key, modifiers)),
+                        Some(ActionBinding::Single {
+                            action: Action::TryQuit,
+                            ..
+                        })
+                    );
+                    if quit {
+                        app_context.action_tx().send(Action::TryQuit)?;
+                    }
+                }
+                return Ok(());
+            }
+        }
         match event {
             Event::Render => app_context.action_tx().send(Action::Render)?,
             Event::Resize(width, height) => app_context
@@ -166,6 +319,10 @@ async fn handle_tui_backend_events(
                     .send(Action::from_key_event(key, modifiers))?;
 
                 // Handle core_window key bindings.
+                // While locked every key goes to the passphrase prompt.
+                if app_context.locked_acquire() {
+                    return Ok(());
+                }
                 if let Some(action_binding) = app_context
                     .keymap_config()
                     .core_window
@@ -205,6 +362,80 @@ async fn handle_tui_backend_events(
     }
     Ok(())
 }
+/// Quit the application if the Telegram backend has stopped and the
+/// `quit_when_backend_stops` of the application configuration is set,
+/// otherwise the application stays open and the requests are dropped.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+fn quit_if_backend_stopped(app_context: &AppContext) {
+    if app_context.tg_context().backend_stopped()
+        && app_context.app_config().quit_when_backend_stops
+        && !app_context.quit_acquire()
+    {
+        tracing::error!("Quitting because the Telegram backend has stopped");
+        app_context.quit_store(true);
+    }
+}
+/// Lock the application if the user has not given any input for longer than
+/// the `lock_after_idle_minutes` of the application configuration.
+/// The application is locked automatically only once a passphrase has been
+/// chosen.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+fn lock_if_idle(app_context: &AppContext) -> Result<(), AppError<Action>> {
+    let lock_after_idle_minutes = app_context.app_config().lock_after_idle_minutes;
+    if lock_after_idle_minutes == 0
+        || app_context.locked_acquire()
+        || !app_context.lock_passphrase().is_set()
+    {
+        return Ok(());
+    }
+    if app_context.last_input().elapsed() >= Duration::from_secs(lock_after_idle_minutes * 60) {
+        tracing::info!("Locking after {} idle minutes", lock_after_idle_minutes);
+        // Reset the idle time, the lock action is sent only once.
+        app_context.touch_input();
+        app_context.action_tx().send(Action::Lock)?;
+    }
+    Ok(())
+}
+/// Play the notification sound of a chat where a message arrived, unless the
+/// user is reading the chat.
+///
+/// # Arguments
+/// * `app_context` - A reference to the AppContext struct.
+/// * `tui_backend` - A mutable reference to the TuiBackend struct.
+/// * `chat_id` - The identifier of the chat.
+fn play_notification_sound(app_context: &AppContext, tui_backend: &mut TuiBackend, chat_id: i64) {
+    let is_reading = !app_context.locked_acquire()
+        && app_context.tg_context().open_chat_id() == chat_id
+        && app_context.last_input().elapsed() < READING_IDLE_TIME;
+    if is_reading {
+        return;
+    }
+    let sound = app_context
+        .app_config()
+        .notification_sounds
+        .get(&chat_id)
+        .cloned();
+    match sound {
+        Some(NotificationSound::Bell) => {
+            if let Err(e) = tui_backend.ring_bell() {
+                tracing::error!("Failed to ring the bell: {e}");
+            }
+        }
+        Some(NotificationSound::File(path)) => {
+            if let Err(e) = play_sound(&path) {
+                tracing::error!("Failed to play {}: {e}", path.display());
+            }
+        }
+        Some(NotificationSound::None) | None => {}
+    }
+}
 /// Consume events until a single action is produced.
 /// This function is used to consume events until a single action is produced
 /// from a map of events to actions.
@@ -257,7 +488,42 @@ pub async fn handle_app_actions(
                 tui_backend.terminal.draw(|f| {
                     tui.draw(f, f.area()).unwrap();
                 })?;
+                if app_context.app_config().show_unread_in_terminal_title {
+                    let unread_count = app_context.tg_context().total_unread_count();
+                    let title = app_context.tg_context().unread_counts().title(unread_count);
+                    tui_backend.set_title(&title)?;
+                }
+            }
+            Action::NotificationSound(chat_id) => {
+                play_notification_sound(&app_context, tui_backend, chat_id)
             }
+            Action::CycleTitleUnreadCount => {
+                let shown = app_context.tg_context().unread_counts().cycle_shown();
+                app_context.action_tx().send(Action::StatusMessage(format!(
+                    "The title counts first the unread messages of {shown}"
+                )))?;
+            }
+            Action::ToggleMouseCapture => match tui_backend.toggle_mouse() {
+                Ok(mouse) => {
+                    app_context.action_tx().send(Action::StatusMessage(
+                        if mouse {
+                            "Mouse capture enabled"
+                        } else {
+                            "Mouse capture disabled, the terminal can select text"
+                        }
+                        .to_string(),
+                    ))?;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to toggle the mouse capture: {e}");
+                    app_context.action_tx().send(Action::Notify(
+                        format!("Failed to toggle the mouse capture: {e}"),
+                        NotifyLevel::Error,
+                    ))?;
+                    // The components keep the previous state of the mouse.
+                    continue;
+                }
+            },
             Action::Resize(width, height) => {
                 tui_backend
                     .terminal
@@ -273,6 +539,7 @@ pub async fn handle_app_actions(
             }
             Action::LoadChats(chat_list, limit) => {
                 tg_backend.load_chats(chat_list.into(), limit).await;
+                app_context.tg_context().set_loading_chats(false);
             }
             Action::SendMessage(ref message, ref reply_to) => {
                 let _ = tg_backend
@@ -283,6 +550,28 @@ pub async fn handle_app_actions(
                     )
                     .await;
             }
+            Action::SendDice(ref emoji) => {
+                tg_backend
+                    .send_dice(emoji.to_string(), app_context.tg_context().open_chat_id())
+                    .await;
+            }
+            Action::SendLocation(ref location) => {
+                tg_backend
+                    .send_location(location, app_context.tg_context().open_chat_id())
+                    .await;
+            }
+            Action::LoadStickerSets => {
+                tg_backend.load_sticker_sets().await;
+            }
+            Action::SendSticker(file_id, ref emoji) => {
+                tg_backend
+                    .send_sticker(
+                        file_id,
+                        emoji.to_string(),
+                        app_context.tg_context().open_chat_id(),
+                    )
+                    .await;
+            }
             Action::SendMessageEdited(message_id, ref message) => {
                 tg_backend
                     .send_message_edited(message_id, message.to_string())
@@ -292,8 +581,18 @@ pub async fn handle_app_actions(
                 tg_backend
                     .get_chat_history(app_context.tg_context().open_chat_id())
                     .await;
+                app_context.tg_context().end_loading_history();
+            }
+            Action::GetNewerMessages => {
+                tg_backend
+                    .get_newer_messages(app_context.tg_context().open_chat_id())
+                    .await;
             }
             Action::DeleteMessages(ref message_ids, revoke) => {
+                // The messages disappear at once, without waiting for TDLib.
+                for message_id in message_ids {
+                    app_context.tg_context().delete_message(*message_id);
+                }
                 tg_backend
                     .delete_messages(
                         app_context.tg_context().open_chat_id(),
@@ -302,6 +601,37 @@ pub async fn handle_app_actions(
                     )
                     .await;
             }
+            Action::ForceReply(message_id, ref message, _) => {
+                app_context
+                    .tg_context()
+                    .set_reply_message(message_id, message.to_string());
+                app_context
+                    .action_tx()
+                    .send(Action::FocusComponent(Prompt))?;
+            }
+            Action::ReplyPrivately(user_id, message_id, ref message) => {
+                tg_backend
+                    .reply_privately(
+                        user_id,
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        message.to_string(),
+                    )
+                    .await;
+            }
+            Action::OpenPrivateChat(user_id) => {
+                tg_backend.open_private_chat(user_id).await;
+            }
+            Action::SetReaction(message_id, ref emoji, chosen) => {
+                tg_backend
+                    .set_reaction(message_id, emoji.clone(), chosen)
+                    .await;
+            }
+            Action::ReplyInOtherChat(_, message_id, ref message) => {
+                app_context
+                    .tg_context()
+                    .set_reply_message(message_id, message.to_string());
+            }
             Action::ReplyMessage(message_id, ref message) => {
                 app_context
                     .tg_context()
@@ -310,6 +640,104 @@ pub async fn handle_app_actions(
             Action::ViewAllMessages => {
                 tg_backend.view_all_messages().await;
             }
+            Action::GetCallbackQueryAnswer(message_id, ref data) => {
+                tg_backend
+                    .get_callback_query_answer(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        data.to_string(),
+                    )
+                    .await;
+            }
+            Action::OpenMessageThread(message_id) => {
+                tg_backend
+                    .open_message_thread(app_context.tg_context().open_chat_id(), message_id)
+                    .await;
+            }
+            Action::DownloadFile(file_id) => {
+                tg_backend.download_file(file_id).await;
+            }
+            Action::OpenFile(file_id) => {
+                tg_backend.open_file(file_id).await;
+            }
+            Action::DeleteChatDraft(chat_id) => {
+                tg_backend.delete_chat_draft(chat_id).await;
+            }
+            Action::GetMessageEditHistory(message_id) => {
+                tg_backend.get_message_edit_history(message_id).await;
+            }
+            Action::GetMessageViewers(message_id) => {
+                tg_backend.get_message_viewers(message_id).await;
+            }
+            Action::CheckChatInviteLink(ref invite_link) => {
+                tg_backend
+                    .check_chat_invite_link(invite_link.to_string())
+                    .await;
+            }
+            Action::JoinChatByInviteLink(ref invite_link) => {
+                tg_backend
+                    .join_chat_by_invite_link(invite_link.to_string())
+                    .await;
+            }
+            Action::GetPinnedMessages(chat_id) => {
+                tg_backend.get_pinned_messages(chat_id).await;
+            }
+            Action::JumpToNextMention(chat_id) => {
+                tg_backend.jump_to_next_mention(chat_id).await;
+            }
+            Action::SetUserBlocked(user_id, blocked) => {
+                tg_backend.set_user_blocked(user_id, blocked).await;
+            }
+            Action::GetBlockedUsers => {
+                tg_backend.get_blocked_users().await;
+            }
+            Action::GetUserProfile(user_id) => {
+                tg_backend.get_user_profile(user_id).await;
+            }
+            Action::ReadChat(chat_id) => {
+                tg_backend.read_chat(chat_id).await;
+            }
+            Action::SetChatPinned(chat_id, pinned) => {
+                tg_backend.set_chat_pinned(chat_id, pinned).await;
+            }
+            Action::SetChatMuted(chat_id, muted) => {
+                tg_backend.set_chat_muted(chat_id, muted).await;
+            }
+            Action::MarkChatAsUnread(chat_id) => {
+                tg_backend.mark_chat_as_unread(chat_id).await;
+            }
+            Action::ArchiveChat(chat_id) => {
+                tg_backend.archive_chat(chat_id).await;
+            }
+            Action::DeleteChat(chat_id) => {
+                tg_backend.delete_chat(chat_id).await;
+            }
+            Action::ReportChat(chat_id, ref message_ids, reason) => {
+                tg_backend
+                    .report_chat(chat_id, message_ids.clone(), reason)
+                    .await;
+            }
+            Action::ReadChats(ref chat_ids) => {
+                tg_backend.read_chats(chat_ids.clone()).await;
+            }
+            Action::ReadChatList => {
+                tg_backend.read_chat_list().await;
+            }
+            Action::RefreshChats => {
+                tg_backend.refresh_chats().await;
+            }
+            Action::GetUser(user_id) => {
+                tg_backend.get_user(user_id).await;
+            }
+            Action::Reconnect => {
+                tg_backend.reconnect().await;
+            }
+            Action::OptimizeStorage => {
+                tg_backend.optimize_storage().await;
+            }
+            Action::ShowStorageStatistics => {
+                tg_backend.show_storage_statistics().await;
+            }
             _ => {}
         }
 
@@ -399,6 +827,44 @@ async fn handle_cli(app_context: Arc<AppContext>, tg_backend: &mut TgBackend) ->
     HandleCliOutcome::Continue
 }
 
+/// Find the chat to open at launch, given with the `--chat` or the
+/// `--chat-id` argument. When the chat is not found the error is reported and
+/// the application starts on the chat list.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+/// * `tg_backend` - A reference to the TgBackend struct.
+///
+/// # Returns
+/// * `Option<i64>` - The identifier of the chat to open, if any.
+async fn chat_from_cli(app_context: Arc<AppContext>, tg_backend: &TgBackend) -> Option<i64> {
+    let (chat, chat_id) = {
+        let cli_args = app_context.cli_args();
+        let telegram_cli = cli_args.telegram_cli();
+        (
+            telegram_cli.chat().map(str::to_string),
+            telegram_cli.chat_id(),
+        )
+    };
+    let result = match (chat, chat_id) {
+        (Some(chat), _) => tg_backend.find_chat(&chat).await,
+        (None, Some(chat_id)) => tg_backend.find_chat_id(chat_id).await,
+        (None, None) => return None,
+    };
+    match result {
+        Ok(chat_id) => Some(chat_id),
+        Err(message) => {
+            tracing::error!("{}", message);
+            println!("{}", message);
+            app_context
+                .action_tx()
+                .send(Action::Notify(message, NotifyLevel::Error))
+                .unwrap();
+            None
+        }
+    }
+}
+
 /// Quit the tui.
 ///
 /// # Arguments
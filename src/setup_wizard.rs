@@ -0,0 +1,364 @@
+use crossterm::{
+    event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Modifier, Style},
+    symbols::border::PLAIN,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::io::{self, Stderr};
+
+/// The width of the setup wizard.
+const WIZARD_WIDTH: u16 = 64;
+
+/// `FieldOutcome` is what a key pressed in the field of the wizard does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldOutcome {
+    /// The answer is still being typed.
+    Editing,
+    /// The answer is submitted, the parameter is the answer.
+    Submit(String),
+    /// The user quits `tgt`.
+    Quit,
+}
+
+/// `Field` is the answer to the question asked by the wizard.
+#[derive(Debug, Default)]
+struct Field {
+    /// The answer typed so far.
+    input: String,
+    /// Indicates whether the answer is hidden while it is typed.
+    secret: bool,
+}
+
+impl Field {
+    /// Handle a key pressed while the question is asked.
+    ///
+    /// # Arguments
+    /// * `key` - The key pressed.
+    fn handle_key(&mut self, key: KeyEvent) -> FieldOutcome {
+        match key.code {
+            KeyCode::Enter => FieldOutcome::Submit(std::mem::take(&mut self.input).trim().into()),
+            KeyCode::Esc => FieldOutcome::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                FieldOutcome::Quit
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                FieldOutcome::Editing
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                FieldOutcome::Editing
+            }
+            _ => FieldOutcome::Editing,
+        }
+    }
+
+    /// Get the answer as it is shown, a secret answer is masked.
+    fn shown(&self) -> String {
+        if self.secret {
+            "•".repeat(self.input.chars().count())
+        } else {
+            self.input.clone()
+        }
+    }
+}
+
+/// `SetupWizard` is the guided flow shown on the first run, before the user
+/// interface of `tgt`. It asks one question at a time, like the API
+/// credentials or the authentication code, and lists the steps already done.
+/// The terminal is taken only once something is asked, so the wizard is never
+/// shown once the user is logged in. Without a terminal the questions are
+/// asked on the standard input.
+#[derive(Default)]
+pub struct SetupWizard {
+    /// The terminal where the wizard is drawn, once it is shown.
+    terminal: Option<Terminal<CrosstermBackend<Stderr>>>,
+    /// The steps already done, in order.
+    done: Vec<String>,
+    /// The message shown above the question and whether it is an error.
+    notice: Option<(String, bool)>,
+}
+
+impl SetupWizard {
+    /// Add a step to the steps done, the message shown is cleared.
+    ///
+    /// # Arguments
+    /// * `step` - The description of the step.
+    pub fn step_done(&mut self, step: impl Into<String>) {
+        self.done.push(step.into());
+        self.notice = None;
+    }
+
+    /// Show a message above the next question, or right away if no question
+    /// follows.
+    ///
+    /// # Arguments
+    /// * `message` - The message.
+    pub fn notice(&mut self, message: impl Into<String>) {
+        self.set_notice(message.into(), false);
+    }
+
+    /// Show an error above the next question, like the reason why the last
+    /// answer was refused.
+    ///
+    /// # Arguments
+    /// * `message` - The error.
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.set_notice(message.into(), true);
+    }
+
+    /// Ask a question and wait for the answer. Quitting the wizard quits
+    /// `tgt`.
+    ///
+    /// # Arguments
+    /// * `question` - The question.
+    /// * `secret` - Whether the answer is hidden while it is typed.
+    ///
+    /// # Returns
+    /// * `String` - The answer, trimmed.
+    pub fn ask(&mut self, question: &str, secret: bool) -> String {
+        if let Err(e) = self.enter() {
+            tracing::error!("Failed to show the setup wizard: {e}");
+            return self.ask_stdin(question);
+        }
+        let mut field = Field {
+            secret,
+            ..Default::default()
+        };
+        loop {
+            self.draw(question, Some(&field));
+            let key = match crossterm::event::read() {
+                Ok(CrosstermEvent::Key(key)) if key.kind == KeyEventKind::Press => key,
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::error!("Failed to read the terminal events: {e}");
+                    continue;
+                }
+            };
+            match field.handle_key(key) {
+                FieldOutcome::Editing => {}
+                FieldOutcome::Submit(answer) => {
+                    self.notice = None;
+                    return answer;
+                }
+                FieldOutcome::Quit => {
+                    self.leave();
+                    std::process::exit(0);
+                }
+            }
+        }
+    }
+
+    /// Give the terminal back, the user interface of `tgt` takes it.
+    pub fn leave(&mut self) {
+        if self.terminal.take().is_none() {
+            return;
+        }
+        if let Err(e) = crossterm::execute!(io::stderr(), LeaveAlternateScreen) {
+            tracing::error!("Failed to leave the setup wizard: {e}");
+        }
+        if let Err(e) = crossterm::terminal::disable_raw_mode() {
+            tracing::error!("Failed to disable the raw mode: {e}");
+        }
+    }
+
+    /// Take the terminal to show the wizard, if it is not shown yet.
+    fn enter(&mut self) -> io::Result<()> {
+        if self.terminal.is_some() {
+            return Ok(());
+        }
+        crossterm::terminal::enable_raw_mode()?;
+        crossterm::execute!(io::stderr(), EnterAlternateScreen)?;
+        self.terminal = Some(Terminal::new(CrosstermBackend::new(io::stderr()))?);
+        Ok(())
+    }
+
+    /// Set the message shown and show it right away, when a question is
+    /// already shown it stays until the next one.
+    ///
+    /// # Arguments
+    /// * `message` - The message.
+    /// * `is_error` - Whether the message is an error.
+    fn set_notice(&mut self, message: String, is_error: bool) {
+        tracing::info!("Setup wizard: {message}");
+        if self.enter().is_err() {
+            println!("{message}");
+        }
+        self.notice = Some((message, is_error));
+        self.draw("", None);
+    }
+
+    /// Ask a question on the standard input, when the wizard can not be
+    /// shown.
+    ///
+    /// # Arguments
+    /// * `question` - The question.
+    fn ask_stdin(&mut self, question: &str) -> String {
+        println!("{question}:");
+        let mut input = String::new();
+        if let Err(e) = io::stdin().read_line(&mut input) {
+            tracing::error!("Failed to read the standard input: {e}");
+        }
+        input.trim().to_string()
+    }
+
+    /// Draw the wizard with a question and its answer, if any.
+    ///
+    /// # Arguments
+    /// * `question` - The question.
+    /// * `field` - The answer typed so far, `None` if nothing is asked.
+    fn draw(&mut self, question: &str, field: Option<&Field>) {
+        let lines = self.lines(question, field);
+        let Some(terminal) = self.terminal.as_mut() else {
+            return;
+        };
+        if let Err(e) = terminal.draw(|frame| Self::render(frame, lines)) {
+            tracing::error!("Failed to draw the setup wizard: {e}");
+        }
+    }
+
+    /// Get the lines of the wizard: the greeting, the steps done, the
+    /// message and the question.
+    ///
+    /// # Arguments
+    /// * `question` - The question.
+    /// * `field` - The answer typed so far, `None` if nothing is asked.
+    fn lines(&self, question: &str, field: Option<&Field>) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from("Welcome to tgt! Log in to Telegram to start."),
+            Line::from(""),
+        ];
+        lines.extend(self.done.iter().map(|step| {
+            Line::from(vec![
+                Span::styled("✓ ", Style::default().fg(Color::Green)),
+                Span::raw(step.clone()),
+            ])
+        }));
+        if let Some((message, is_error)) = &self.notice {
+            let style = if *is_error {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(message.clone(), style)));
+        }
+        if let Some(field) = field {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                question.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(vec![
+                Span::raw("> "),
+                Span::raw(field.shown()),
+                Span::styled("█", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+            ]));
+        }
+        lines
+    }
+
+    /// Render the lines of the wizard centered in the frame.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to render to.
+    /// * `lines` - The lines of the wizard.
+    fn render(frame: &mut Frame<'_>, lines: Vec<Line<'static>>) {
+        let width = WIZARD_WIDTH.min(frame.area().width);
+        // The borders take two lines, and the long lines are wrapped.
+        let inner_width = usize::from(width.saturating_sub(2)).max(1);
+        let height = lines
+            .iter()
+            .map(|line| line.width().div_ceil(inner_width).max(1))
+            .sum::<usize>()
+            .saturating_add(2)
+            .min(usize::from(frame.area().height)) as u16;
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .border_set(PLAIN)
+                .borders(Borders::ALL)
+                .title(" tgt setup ")
+                .title_bottom(" Enter confirm, Esc quit "),
+        );
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        frame.render_widget(paragraph, area);
+    }
+}
+
+/// The terminal is given back even if the wizard is interrupted.
+impl Drop for SetupWizard {
+    fn drop(&mut self) {
+        self.leave();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_handle_key() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut field = Field {
+            secret: true,
+            ..Default::default()
+        };
+        for c in "12ab".chars() {
+            assert_eq!(
+                field.handle_key(key(KeyCode::Char(c))),
+                FieldOutcome::Editing
+            );
+        }
+        field.handle_key(key(KeyCode::Backspace));
+        assert_eq!(field.shown(), "•••");
+        assert_eq!(
+            field.handle_key(key(KeyCode::Enter)),
+            FieldOutcome::Submit("12a".to_string())
+        );
+        assert!(field.input.is_empty());
+        assert_eq!(field.handle_key(key(KeyCode::Esc)), FieldOutcome::Quit);
+        assert_eq!(
+            field.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            FieldOutcome::Quit
+        );
+    }
+
+    #[test]
+    fn test_lines_list_the_steps_done() {
+        let mut wizard = SetupWizard::default();
+        wizard.done.push("Phone number +100".to_string());
+        wizard.notice = Some(("PHONE_CODE_INVALID".to_string(), true));
+        let field = Field {
+            input: "1234".to_string(),
+            secret: false,
+        };
+        let text = wizard
+            .lines("Verification code", Some(&field))
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            text[2..],
+            [
+                "✓ Phone number +100",
+                "",
+                "PHONE_CODE_INVALID",
+                "",
+                "Verification code",
+                "> 1234█",
+            ]
+        );
+    }
+}
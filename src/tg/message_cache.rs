@@ -0,0 +1,130 @@
+use super::message_entry::MessageEntry;
+use std::collections::VecDeque;
+
+/// The maximum number of chats whose messages are kept.
+const MAX_CACHED_CHATS: usize = 20;
+
+/// `CachedChat` is the history of a chat loaded while it was open.
+#[derive(Debug, Clone)]
+pub struct CachedChat {
+    /// The identifier of the chat.
+    pub chat_id: i64,
+    /// The loaded messages of the chat, the newest first.
+    pub messages: Vec<MessageEntry>,
+    /// Indicates whether the oldest message of the chat is loaded.
+    pub at_oldest: bool,
+}
+
+/// `MessageCache` keeps the messages of the chats visited recently, so a chat
+/// opened again shows its history at once, where it was left, instead of
+/// loading it from the newest message.
+/// Only the main history of the chats is kept, not the message threads.
+#[derive(Debug, Default)]
+pub struct MessageCache {
+    /// The cached chats, the most recently left last.
+    chats: VecDeque<CachedChat>,
+}
+
+impl MessageCache {
+    /// Keep the messages of a chat that is left, the chats left the longest
+    /// time ago are forgotten.
+    ///
+    /// # Arguments
+    /// * `chat` - The history of the chat.
+    pub fn store(&mut self, chat: CachedChat) {
+        self.chats.retain(|c| c.chat_id != chat.chat_id);
+        if chat.messages.is_empty() {
+            return;
+        }
+        self.chats.push_back(chat);
+        while self.chats.len() > MAX_CACHED_CHATS {
+            self.chats.pop_front();
+        }
+    }
+
+    /// Take the messages of a chat out of the cache, when it is opened again.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub fn take(&mut self, chat_id: i64) -> Option<CachedChat> {
+        let index = self.chats.iter().position(|c| c.chat_id == chat_id)?;
+        self.chats.remove(index)
+    }
+
+    /// Drop the oldest cached messages of a chat beyond a number, they are
+    /// loaded again when the history is scrolled up to them.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `max_messages` - The number of messages kept, 0 for no limit.
+    pub fn trim(&mut self, chat_id: i64, max_messages: usize) {
+        let Some(chat) = self.chats.iter_mut().find(|c| c.chat_id == chat_id) else {
+            return;
+        };
+        if max_messages != 0 && chat.messages.len() > max_messages {
+            chat.messages.truncate(max_messages);
+            chat.at_oldest = false;
+        }
+    }
+
+    /// Get the cached messages of a chat, to keep them up to date with the
+    /// updates received while the chat is not open.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub fn messages_mut(&mut self, chat_id: i64) -> Option<&mut Vec<MessageEntry>> {
+        self.chats
+            .iter_mut()
+            .find(|c| c.chat_id == chat_id)
+            .map(|c| &mut c.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat(chat_id: i64) -> CachedChat {
+        CachedChat {
+            chat_id,
+            messages: vec![MessageEntry::new_text(1, "hello", 0)],
+            at_oldest: false,
+        }
+    }
+
+    #[test]
+    fn test_message_cache() {
+        let mut cache = MessageCache::default();
+        for chat_id in 0..=MAX_CACHED_CHATS as i64 {
+            cache.store(chat(chat_id));
+        }
+        // The chat left the longest time ago is forgotten.
+        assert!(cache.take(0).is_none());
+        assert!(cache.messages_mut(1).is_some());
+        assert_eq!(cache.take(1).map(|c| c.chat_id), Some(1));
+        assert!(cache.take(1).is_none());
+
+        // A chat left without messages is not kept.
+        cache.store(CachedChat {
+            messages: vec![],
+            ..chat(2)
+        });
+        assert!(cache.messages_mut(2).is_none());
+
+        // The oldest messages beyond the limit are dropped.
+        cache.store(CachedChat {
+            messages: vec![
+                MessageEntry::new_text(2, "newest", 1),
+                MessageEntry::new_text(1, "oldest", 0),
+            ],
+            at_oldest: true,
+            ..chat(3)
+        });
+        cache.trim(3, 0);
+        assert_eq!(cache.messages_mut(3).map(|m| m.len()), Some(2));
+        cache.trim(3, 1);
+        let chat = cache.take(3).unwrap();
+        assert_eq!(chat.messages.len(), 1);
+        assert!(!chat.at_oldest);
+    }
+}
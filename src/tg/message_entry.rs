@@ -1,33 +1,172 @@
 use crate::app_context::AppContext;
-use chrono::{DateTime, Local};
+use crate::configs::custom::app_custom::{TimestampFormat, Timezone};
+use crate::utils::{human_readable_size, strip_emoji_line};
+use chrono::{DateTime, FixedOffset, Local};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use std::time::{Duration, UNIX_EPOCH};
-use tdlib_rs::enums::{MessageContent, MessageReplyTo, MessageSender};
-use tdlib_rs::types::FormattedText;
+use tdlib_rs::enums::{
+    MessageContent, MessageReplyTo, MessageSender, MessageSendingState, ReactionType, ReplyMarkup,
+    TextEntityType,
+};
+use tdlib_rs::types::{
+    File, FormattedText, InlineKeyboardButton, Location, MessageInteractionInfo,
+};
+use unicode_width::UnicodeWidthChar;
 
-use super::td_enums::{TdMessageReplyTo, TdMessageSender};
+use super::td_enums::{TdMessageOrigin, TdMessageReplyTo, TdMessageSender};
+
+/// The character that replaces the characters of a spoiler.
+const SPOILER_CHAR: char = '▓';
+/// The text that replaces a media with a spoiler.
+const SPOILER_MEDIA: &str = "▓▓▓▓▓▓ (spoiler)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of a media, it is used to choose its automatic download policy.
+pub enum MediaKind {
+    /// A photo.
+    Photo,
+    /// A video or an animation.
+    Video,
+    /// A document, an audio or a voice note.
+    File,
+}
+
+#[derive(Debug, Clone, Copy)]
+/// The downloadable file of a message.
+pub struct MediaFile {
+    /// The kind of the media.
+    pub kind: MediaKind,
+    /// The identifier of the file.
+    pub file_id: i32,
+    /// Indicates whether the file is already downloaded.
+    pub is_downloaded: bool,
+    /// The size of the file in bytes, 0 if it is unknown.
+    pub size: i64,
+}
+impl From<(MediaKind, &File)> for MediaFile {
+    fn from((kind, file): (MediaKind, &File)) -> Self {
+        Self {
+            kind,
+            file_id: file.id,
+            is_downloaded: file.local.is_downloading_completed,
+            // The exact size may be unknown until the file is downloaded.
+            size: file.size.max(file.expected_size),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How a message can be deleted, as told by TDLib.
+pub enum RevokeWindow {
+    /// The message can be deleted for everyone or for the user only, with no
+    /// known time limit.
+    Unlimited,
+    /// The message can be deleted for everyone for this number of seconds,
+    /// or for the user only.
+    Left(i64),
+    /// The message can only be deleted for everyone, as in the supergroups
+    /// and the channels.
+    EveryoneOnly,
+    /// The message can only be deleted for the user, it can no longer be
+    /// deleted for everyone.
+    SelfOnly,
+    /// The message can not be deleted.
+    Forbidden,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An emoji reaction to a message, the custom emoji reactions are not shown.
+pub struct ReactionEntry {
+    /// The emoji of the reaction.
+    pub emoji: String,
+    /// The number of times the reaction was added.
+    pub count: i32,
+    /// Indicates whether the reaction was added by the user.
+    pub is_chosen: bool,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct DateTimeEntry {
     pub timestamp: i32,
 }
 impl DateTimeEntry {
-    pub fn convert_time(timestamp: i32) -> String {
-        let d = UNIX_EPOCH + Duration::from_secs(timestamp as u64);
-        let datetime = DateTime::<Local>::from(d);
-        if datetime.date_naive() == Local::now().date_naive() {
+    /// Convert a timestamp to a date and time in the given timezone.
+    ///
+    /// # Arguments
+    /// * `timestamp` - The unix timestamp to convert.
+    /// * `timezone` - The timezone of the date and time.
+    pub fn datetime(timestamp: i64, timezone: Timezone) -> DateTime<FixedOffset> {
+        let datetime = DateTime::from_timestamp(timestamp, 0).unwrap_or_default();
+        match timezone {
+            Timezone::Local => datetime.with_timezone(&Local).fixed_offset(),
+            Timezone::Fixed(offset) => datetime.with_timezone(&offset),
+        }
+    }
+
+    pub fn convert_time(timestamp: i32, timezone: Timezone) -> String {
+        let datetime = Self::datetime(timestamp as i64, timezone);
+        let now = Self::datetime(Local::now().timestamp(), timezone);
+        if datetime.date_naive() == now.date_naive() {
             return datetime.format("%H:%M").to_string();
         }
-        if datetime.date_naive() == (Local::now() - chrono::Duration::days(1)).date_naive() {
+        if datetime.date_naive() == (now - chrono::Duration::days(1)).date_naive() {
             return datetime.format("Yesterday %H:%M").to_string();
         }
         datetime.format("%Y-%m-%d %H:%M").to_string() // :%S
     }
 
-    pub fn get_span_styled(&self, app_context: &AppContext) -> Span {
+    /// Convert a timestamp to the time elapsed since it, for example "5m".
+    /// After four weeks the date is shown instead.
+    ///
+    /// # Arguments
+    /// * `timestamp` - The unix timestamp to convert.
+    /// * `now` - The current unix timestamp.
+    /// * `timezone` - The timezone of the date.
+    pub fn convert_time_relative(timestamp: i32, now: i64, timezone: Timezone) -> String {
+        let elapsed = now - timestamp as i64;
+        match elapsed {
+            ..60 => "now".to_string(),
+            60..3_600 => format!("{}m", elapsed / 60),
+            3_600..86_400 => format!("{}h", elapsed / 3_600),
+            86_400..604_800 => format!("{}d", elapsed / 86_400),
+            604_800..2_419_200 => format!("{}w", elapsed / 604_800),
+            _ => Self::datetime(timestamp as i64, timezone)
+                .format("%Y-%m-%d")
+                .to_string(),
+        }
+    }
+
+    /// Convert a timestamp to the full date and time, up to the seconds.
+    ///
+    /// # Arguments
+    /// * `timestamp` - The unix timestamp to convert.
+    /// * `timezone` - The timezone of the date and time.
+    pub fn convert_time_full(timestamp: i32, timezone: Timezone) -> String {
+        Self::datetime(timestamp as i64, timezone)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+
+    /// Convert a timestamp to a string in the given format.
+    ///
+    /// # Arguments
+    /// * `timestamp` - The unix timestamp to convert.
+    /// * `format` - The format of the timestamp.
+    /// * `timezone` - The timezone of the date and time.
+    pub fn format_time(timestamp: i32, format: TimestampFormat, timezone: Timezone) -> String {
+        match format {
+            TimestampFormat::Absolute => Self::convert_time(timestamp, timezone),
+            TimestampFormat::Relative => {
+                Self::convert_time_relative(timestamp, Local::now().timestamp(), timezone)
+            }
+            TimestampFormat::Full => Self::convert_time_full(timestamp, timezone),
+        }
+    }
+
+    pub fn get_span_styled(&self, app_context: &AppContext, format: TimestampFormat) -> Span<'_> {
+        let timezone = app_context.app_config().timezone;
         Span::styled(
-            Self::convert_time(self.timestamp),
+            Self::format_time(self.timestamp, format, timezone),
             app_context.style_timestamp(),
         )
     }
@@ -38,12 +177,95 @@ pub struct MessageEntry {
     id: i64,
     sender_id: TdMessageSender,
     message_content: Vec<Line<'static>>,
+    /// The content of the message with the spoilers obscured, if the message
+    /// contains spoilers.
+    obscured_content: Option<Vec<Line<'static>>>,
+    /// Indicates whether the spoilers of the message are revealed.
+    spoiler_revealed: bool,
+    /// Indicates whether the full date and time of the message are shown.
+    full_timestamp: bool,
+    formatted_text: Option<FormattedText>,
+    media_file: Option<MediaFile>,
     reply_to: Option<TdMessageReplyTo>,
+    /// The origin of the message, if it has been forwarded.
+    forward_origin: Option<TdMessageOrigin>,
+    /// The identifier of the inline bot the message was sent via, if any.
+    via_bot_user_id: Option<i64>,
     timestamp: DateTimeEntry,
-    is_edited: bool,
+    /// The date of the last edit of the message, if it has been edited.
+    edit_date: Option<DateTimeEntry>,
+    inline_keyboard: Vec<Vec<InlineKeyboardButton>>,
+    /// The emoji reactions to the message.
+    reactions: Vec<ReactionEntry>,
+    can_get_message_thread: bool,
+    /// The number of comments, if the message is a channel post with a
+    /// discussion group.
+    reply_count: Option<i32>,
+    /// Indicates whether the message has not reached the server yet, it is
+    /// pending in TDLib.
+    is_sending: bool,
+    /// The identifier of the album the message belongs to, 0 if none.
+    media_album_id: i64,
+    /// The caption of the media of the message, if any.
+    caption: Option<FormattedText>,
+    /// The messages of the album shown by this entry, the oldest first,
+    /// empty if the entry is a single message.
+    album: Vec<MessageEntry>,
+    /// Indicates whether the message can be deleted for all the users.
+    can_be_deleted_for_all_users: bool,
+    /// Indicates whether the message can be deleted only for the user, the
+    /// other users keep it.
+    can_be_deleted_only_for_self: bool,
 }
 
 impl MessageEntry {
+    /// Create a text message, it is used to test the rendering of the
+    /// components without building a whole TDLib message.
+    ///
+    /// # Arguments
+    /// * `id` - The identifier of the message.
+    /// * `text` - The text of the message.
+    /// * `timestamp` - The date of the message, as a unix timestamp.
+    #[cfg(test)]
+    pub fn new_text(id: i64, text: &str, timestamp: i32) -> Self {
+        Self {
+            id,
+            sender_id: TdMessageSender::User(0),
+            message_content: vec![Line::from(text.to_string())],
+            obscured_content: None,
+            spoiler_revealed: false,
+            full_timestamp: false,
+            formatted_text: None,
+            media_file: None,
+            reply_to: None,
+            forward_origin: None,
+            via_bot_user_id: None,
+            timestamp: DateTimeEntry { timestamp },
+            edit_date: None,
+            inline_keyboard: vec![],
+            reactions: vec![],
+            can_get_message_thread: false,
+            reply_count: None,
+            is_sending: false,
+            media_album_id: 0,
+            caption: None,
+            album: vec![],
+            can_be_deleted_for_all_users: true,
+            can_be_deleted_only_for_self: true,
+        }
+    }
+
+    /// Set how the message can be deleted.
+    ///
+    /// # Arguments
+    /// * `for_all_users` - Whether it can be deleted for all the users.
+    /// * `only_for_self` - Whether it can be deleted only for the user.
+    #[cfg(test)]
+    pub fn set_can_be_deleted(&mut self, for_all_users: bool, only_for_self: bool) {
+        self.can_be_deleted_for_all_users = for_all_users;
+        self.can_be_deleted_only_for_self = only_for_self;
+    }
+
     pub fn id(&self) -> i64 {
         self.id
     }
@@ -52,6 +274,81 @@ impl MessageEntry {
         &self.timestamp
     }
 
+    /// Whether the message has been forwarded from another chat.
+    pub fn is_forwarded(&self) -> bool {
+        self.forward_origin.is_some()
+    }
+
+    /// Set the origin of the message, as if it had been forwarded.
+    ///
+    /// # Arguments
+    /// * `forward_origin` - The origin of the message.
+    #[cfg(test)]
+    pub fn set_forward_origin(&mut self, forward_origin: TdMessageOrigin) {
+        self.forward_origin = Some(forward_origin);
+    }
+
+    /// Get the name of the origin of a forwarded message, as shown after
+    /// "Forwarded from". The users hidden by their privacy settings are
+    /// shown with the name they chose, if any.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The name, or `None` if the message has not been
+    ///   forwarded.
+    pub fn forward_origin_name(&self, app_context: &AppContext) -> Option<String> {
+        Some(match self.forward_origin.as_ref()? {
+            TdMessageOrigin::User(user_id) => app_context.tg_context().sender_name(*user_id),
+            TdMessageOrigin::HiddenUser(name) if name.is_empty() => "a hidden user".to_string(),
+            TdMessageOrigin::HiddenUser(name) => format!("{} (hidden user)", name),
+            TdMessageOrigin::Chat(chat_id) | TdMessageOrigin::Channel(chat_id) => app_context
+                .tg_context()
+                .name_from_chats(*chat_id)
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| "a hidden chat".to_string()),
+        })
+    }
+
+    /// Get the identifier of the inline bot the message was sent via, if
+    /// any.
+    pub fn via_bot_user_id(&self) -> Option<i64> {
+        self.via_bot_user_id
+    }
+
+    /// Set the inline bot the message was sent via.
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The identifier of the bot.
+    #[cfg(test)]
+    pub fn set_via_bot_user_id(&mut self, bot_user_id: i64) {
+        self.via_bot_user_id = Some(bot_user_id);
+    }
+
+    /// Get the name of the inline bot the message was sent via, as shown
+    /// after "via". The bots are named by their @username, as they are
+    /// mentioned to be used inline.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The name, or `None` if the message was not sent
+    ///   via a bot.
+    pub fn via_bot_name(&self, app_context: &AppContext) -> Option<String> {
+        let bot_user_id = self.via_bot_user_id?;
+        let tg_context = app_context.tg_context();
+        let username = tg_context
+            .users()
+            .get(&bot_user_id)
+            .and_then(|bot| bot.usernames.as_ref()?.active_usernames.first().cloned());
+        Some(match username {
+            Some(username) => format!("@{}", username),
+            None => tg_context.sender_name(bot_user_id),
+        })
+    }
+
     pub fn sender_id(&self) -> i64 {
         match self.sender_id {
             TdMessageSender::User(user_id) => user_id,
@@ -59,6 +356,15 @@ impl MessageEntry {
         }
     }
 
+    /// Get the identifier of the sender of the message, if it is a user and
+    /// not a chat.
+    pub fn sender_user_id(&self) -> Option<i64> {
+        match self.sender_id {
+            TdMessageSender::User(user_id) => Some(user_id),
+            TdMessageSender::Chat(_) => None,
+        }
+    }
+
     pub fn message_content_to_string(&self) -> String {
         self.message_content
             .iter()
@@ -67,14 +373,472 @@ impl MessageEntry {
             .join("\n")
     }
 
+    /// Get the text of the message without the formatting entities.
+    pub fn message_content_to_plain_text(&self) -> String {
+        match &self.formatted_text {
+            Some(formatted_text) => formatted_text.text.clone(),
+            None => self.message_content_to_string(),
+        }
+    }
+
+    /// Get the text of the message with the formatting entities converted to
+    /// markdown.
+    pub fn message_content_to_markdown(&self) -> String {
+        match &self.formatted_text {
+            Some(formatted_text) => Self::format_markdown(formatted_text),
+            None => self.message_content_to_string(),
+        }
+    }
+
+    /// Get the code blocks of the message, that are the texts of its `pre`
+    /// and `code` entities, in the order of the message.
+    pub fn code_blocks(&self) -> Vec<String> {
+        self.formatted_text
+            .as_ref()
+            .map(Self::code_blocks_of)
+            .unwrap_or_default()
+    }
+
+    pub fn media_file(&self) -> Option<MediaFile> {
+        self.media_file
+    }
+
+    pub fn has_spoiler(&self) -> bool {
+        self.obscured_content.is_some()
+    }
+
+    pub fn set_spoiler_revealed(&mut self, spoiler_revealed: bool) {
+        self.spoiler_revealed = spoiler_revealed;
+    }
+
+    pub fn set_full_timestamp(&mut self, full_timestamp: bool) {
+        self.full_timestamp = full_timestamp;
+    }
+
+    /// Get the lines of the content to display, the spoilers are obscured
+    /// unless they are revealed.
+    fn content_lines(&self) -> &Vec<Line<'static>> {
+        match &self.obscured_content {
+            Some(obscured_content) if !self.spoiler_revealed => obscured_content,
+            _ => &self.message_content,
+        }
+    }
+
+    /// Find the keywords in a text, as whole words and ignoring the case.
+    /// When keywords overlap, the one that starts first is kept, and the
+    /// longest one if they start at the same character.
+    ///
+    /// # Arguments
+    /// * `text` - The text to search.
+    /// * `keywords` - The keywords to find.
+    ///
+    /// # Returns
+    /// * `Vec<(usize, usize)>` - The byte ranges of the keywords in the text.
+    pub fn keyword_ranges(text: &str, keywords: &[String]) -> Vec<(usize, usize)> {
+        let chars: Vec<(usize, char)> = text
+            .char_indices()
+            .map(|(i, c)| (i, c.to_lowercase().next().unwrap_or(c)))
+            .collect();
+        let keywords: Vec<Vec<char>> = keywords
+            .iter()
+            .map(|k| {
+                k.chars()
+                    .map(|c| c.to_lowercase().next().unwrap_or(c))
+                    .collect::<Vec<char>>()
+            })
+            .filter(|k| !k.is_empty())
+            .collect();
+        let is_word = |i: usize| chars.get(i).is_some_and(|(_, c)| c.is_alphanumeric());
+        let mut ranges = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            let at_boundary = i == 0 || !is_word(i - 1);
+            let found = keywords
+                .iter()
+                .filter(|k| {
+                    at_boundary
+                        && chars.len() - i >= k.len()
+                        && chars[i..i + k.len()].iter().map(|(_, c)| c).eq(k.iter())
+                        && !is_word(i + k.len())
+                })
+                .map(|k| k.len())
+                .max();
+            match found {
+                Some(len) => {
+                    let end = chars.get(i + len).map_or(text.len(), |(j, _)| *j);
+                    ranges.push((chars[i].0, end));
+                    i += len;
+                }
+                None => i += 1,
+            }
+        }
+        ranges
+    }
+
+    /// Whether the text of the message contains one of the keywords.
+    ///
+    /// # Arguments
+    /// * `keywords` - The keywords to find.
+    pub fn matches_keywords(&self, keywords: &[String]) -> bool {
+        !keywords.is_empty()
+            && self.content_lines().iter().any(|line| {
+                line.spans
+                    .iter()
+                    .any(|span| !Self::keyword_ranges(&span.content, keywords).is_empty())
+            })
+    }
+
+    /// Highlight the keywords in some lines, the style of the keywords is
+    /// patched over the style of their spans.
+    ///
+    /// # Arguments
+    /// * `lines` - The lines to highlight.
+    /// * `keywords` - The keywords to highlight.
+    /// * `keyword_style` - The style of the keywords.
+    pub fn highlight_keywords(
+        lines: Vec<Line<'static>>,
+        keywords: &[String],
+        keyword_style: Style,
+    ) -> Vec<Line<'static>> {
+        if keywords.is_empty() {
+            return lines;
+        }
+        lines
+            .into_iter()
+            .map(|line| {
+                let mut spans = vec![];
+                for span in line.spans {
+                    let ranges = Self::keyword_ranges(&span.content, keywords);
+                    if ranges.is_empty() {
+                        spans.push(span);
+                        continue;
+                    }
+                    let mut last = 0;
+                    for (start, end) in ranges {
+                        if start > last {
+                            spans.push(Span::styled(
+                                span.content[last..start].to_string(),
+                                span.style,
+                            ));
+                        }
+                        spans.push(Span::styled(
+                            span.content[start..end].to_string(),
+                            span.style.patch(keyword_style),
+                        ));
+                        last = end;
+                    }
+                    if last < span.content.len() {
+                        spans.push(Span::styled(span.content[last..].to_string(), span.style));
+                    }
+                }
+                Line { spans, ..line }
+            })
+            .collect()
+    }
+
     pub fn set_message_content(&mut self, content: &MessageContent) {
         self.message_content = Self::message_content_lines(content);
+        self.obscured_content = Self::message_obscured_lines(content);
+        self.media_file = Self::message_media_file(content);
+        self.formatted_text = Self::message_formatted_text(content);
+        self.caption = Self::message_caption(content);
     }
 
-    pub fn set_is_edited(&mut self, is_edited: bool) {
-        self.is_edited = is_edited;
+    /// Group the consecutive messages of the same album into a single entry,
+    /// that shows the number of media of the album and its caption. The
+    /// entry is the message with the caption, or the oldest one, and it keeps
+    /// the messages of the album to act on each of them.
+    ///
+    /// # Arguments
+    /// * `messages` - The messages, the newest first.
+    pub fn group_albums(messages: Vec<MessageEntry>) -> Vec<MessageEntry> {
+        let mut runs: Vec<Vec<MessageEntry>> = vec![];
+        for message in messages {
+            match runs.last_mut() {
+                Some(run)
+                    if message.media_album_id != 0
+                        && run[0].media_album_id == message.media_album_id =>
+                {
+                    run.push(message)
+                }
+                _ => runs.push(vec![message]),
+            }
+        }
+        runs.into_iter()
+            .map(|mut run| {
+                if run.len() == 1 {
+                    return run.remove(0);
+                }
+                run.reverse();
+                let index = run.iter().position(|m| m.caption.is_some()).unwrap_or(0);
+                let mut entry = run[index].clone();
+                let mut content = vec![Line::from(Self::album_text(&run))];
+                if let Some(caption) = &entry.caption {
+                    content.extend(Self::format_message_content(caption));
+                }
+                entry.message_content = content;
+                entry.obscured_content = None;
+                entry.formatted_text = entry.caption.clone();
+                entry.album = run;
+                entry
+            })
+            .collect()
+    }
+
+    /// Get the text of an album, with the number of its media of each kind.
+    ///
+    /// # Arguments
+    /// * `album` - The messages of the album.
+    fn album_text(album: &[MessageEntry]) -> String {
+        let count = |kind: MediaKind| {
+            album
+                .iter()
+                .filter(|m| m.media_file.is_some_and(|f| f.kind == kind))
+                .count()
+        };
+        let counts = [
+            (count(MediaKind::Photo), "photo"),
+            (count(MediaKind::Video), "video"),
+            (count(MediaKind::File), "file"),
+        ];
+        let details = counts
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, name)| {
+                format!("{} {}{}", count, name, if *count == 1 { "" } else { "s" })
+            })
+            .collect::<Vec<_>>();
+        if details.is_empty() {
+            format!("🖼 Album: {} items", album.len())
+        } else {
+            format!("🖼 Album: {}", details.join(", "))
+        }
+    }
+
+    /// Get the number of messages of the album shown by the entry, 0 if it is
+    /// a single message.
+    pub fn album_len(&self) -> usize {
+        self.album.len()
+    }
+
+    /// Get a message of the album shown by the entry.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the message, the oldest first.
+    pub fn album_item(&self, index: usize) -> Option<&MessageEntry> {
+        self.album.get(index)
+    }
+
+    /// Whether the entry is the message, or shows it in its album.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    pub fn includes(&self, message_id: i64) -> bool {
+        self.id == message_id || self.album.iter().any(|m| m.id == message_id)
+    }
+
+    /// Get the identifiers of the messages shown by the entry, the messages
+    /// of its album or the message itself.
+    pub fn message_ids(&self) -> Vec<i64> {
+        if self.album.is_empty() {
+            vec![self.id]
+        } else {
+            self.album.iter().map(|m| m.id).collect()
+        }
+    }
+
+    /// Get how the message can be deleted, from what TDLib tells about the
+    /// message, or all the messages of its album. The time left to delete it
+    /// for everyone is only known when the user can choose.
+    ///
+    /// # Arguments
+    /// * `revoke_time_limit` - The time in seconds the messages of the chat
+    ///   can be deleted for everyone after they are sent, 0 if it is unknown.
+    /// * `now` - The current timestamp in seconds.
+    pub fn revoke_window(&self, revoke_time_limit: i64, now: i64) -> RevokeWindow {
+        let messages = if self.album.is_empty() {
+            std::slice::from_ref(self)
+        } else {
+            &self.album
+        };
+        let for_all_users = messages.iter().all(|m| m.can_be_deleted_for_all_users);
+        let only_for_self = messages.iter().all(|m| m.can_be_deleted_only_for_self);
+        match (for_all_users, only_for_self) {
+            (true, true) => {
+                let left = self.timestamp.timestamp as i64 + revoke_time_limit - now;
+                // The administrators can delete the messages for everyone past
+                // the limit.
+                if revoke_time_limit > 0 && left > 0 {
+                    RevokeWindow::Left(left)
+                } else {
+                    RevokeWindow::Unlimited
+                }
+            }
+            (true, false) => RevokeWindow::EveryoneOnly,
+            (false, true) => RevokeWindow::SelfOnly,
+            (false, false) => RevokeWindow::Forbidden,
+        }
+    }
+
+    /// Get the line of the media of the album shown by the entry, the
+    /// selected one is highlighted.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    /// * `selected_item` - The index of the selected media, if any.
+    fn get_album_line(
+        &self,
+        app_context: &AppContext,
+        selected_item: Option<usize>,
+    ) -> Option<Line<'static>> {
+        if self.album.is_empty() {
+            return None;
+        }
+        let spans = self.album.iter().enumerate().flat_map(|(i, m)| {
+            let icon = match m.media_file.map(|f| f.kind) {
+                Some(MediaKind::Photo) => "📷",
+                Some(MediaKind::Video) => "🎥",
+                Some(MediaKind::File) => "📄",
+                None => "·",
+            };
+            let style = if selected_item == Some(i) {
+                app_context.style_chat_message_inline_button_selected()
+            } else {
+                app_context.style_chat_message_inline_button()
+            };
+            [
+                Span::styled(format!("[ {} {} ]", icon, i + 1), style),
+                Span::raw(" "),
+            ]
+        });
+        Some(Line::from(spans.collect::<Vec<_>>()))
+    }
+
+    pub fn can_get_message_thread(&self) -> bool {
+        self.can_get_message_thread
+    }
+
+    pub fn is_sending(&self) -> bool {
+        self.is_sending
+    }
+
+    pub fn is_edited(&self) -> bool {
+        self.edit_date.is_some()
+    }
+
+    /// Set the date of the last edit of the message, 0 if it has not been
+    /// edited.
+    pub fn set_edit_date(&mut self, edit_date: i32) {
+        self.edit_date = (edit_date != 0).then_some(DateTimeEntry {
+            timestamp: edit_date,
+        });
+    }
+
+    /// Get the inline keyboard button at the given index, the buttons are
+    /// counted row by row.
+    pub fn inline_keyboard_button(&self, index: usize) -> Option<&InlineKeyboardButton> {
+        self.inline_keyboard.iter().flatten().nth(index)
+    }
+
+    pub fn inline_keyboard_len(&self) -> usize {
+        self.inline_keyboard.iter().map(|row| row.len()).sum()
     }
 
+    /// Whether the user added a reaction to the message.
+    ///
+    /// # Arguments
+    /// * `emoji` - The emoji of the reaction.
+    pub fn is_reaction_chosen(&self, emoji: &str) -> bool {
+        self.reactions
+            .iter()
+            .any(|reaction| reaction.is_chosen && reaction.emoji == emoji)
+    }
+
+    pub fn set_reactions(&mut self, interaction_info: Option<&MessageInteractionInfo>) {
+        self.reactions = Self::reactions_of(interaction_info);
+    }
+
+    fn reactions_of(interaction_info: Option<&MessageInteractionInfo>) -> Vec<ReactionEntry> {
+        interaction_info
+            .and_then(|info| info.reactions.as_ref())
+            .map_or_else(Vec::new, |reactions| {
+                reactions
+                    .reactions
+                    .iter()
+                    .filter_map(|reaction| match &reaction.r#type {
+                        ReactionType::Emoji(emoji) => Some(ReactionEntry {
+                            emoji: emoji.emoji.clone(),
+                            count: reaction.total_count,
+                            is_chosen: reaction.is_chosen,
+                        }),
+                        ReactionType::CustomEmoji(_) => None,
+                    })
+                    .collect()
+            })
+    }
+
+    /// Get the line of the reactions, the reactions added by the user are
+    /// highlighted.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    fn get_reactions_line(&self, app_context: &AppContext) -> Option<Line<'static>> {
+        if self.reactions.is_empty() {
+            return None;
+        }
+        let spans = self.reactions.iter().flat_map(|reaction| {
+            let style = if reaction.is_chosen {
+                app_context.style_chat_message_inline_button_selected()
+            } else {
+                app_context.style_chat_message_inline_button()
+            };
+            [
+                Span::styled(format!(" {} {} ", reaction.emoji, reaction.count), style),
+                Span::raw(" "),
+            ]
+        });
+        Some(Line::from(spans.collect::<Vec<_>>()))
+    }
+
+    pub fn set_inline_keyboard(&mut self, reply_markup: Option<&ReplyMarkup>) {
+        self.inline_keyboard = Self::inline_keyboard_rows(reply_markup);
+    }
+
+    fn inline_keyboard_rows(reply_markup: Option<&ReplyMarkup>) -> Vec<Vec<InlineKeyboardButton>> {
+        match reply_markup {
+            Some(ReplyMarkup::InlineKeyboard(keyboard)) => keyboard.rows.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_inline_keyboard_lines(
+        &self,
+        app_context: &AppContext,
+        selected_button: Option<usize>,
+    ) -> Vec<Line<'static>> {
+        let mut index = 0;
+        self.inline_keyboard
+            .iter()
+            .map(|row| {
+                let mut spans = Vec::new();
+                for button in row {
+                    if !spans.is_empty() {
+                        spans.push(Span::raw(" "));
+                    }
+                    let style = if selected_button == Some(index) {
+                        app_context.style_chat_message_inline_button_selected()
+                    } else {
+                        app_context.style_chat_message_inline_button()
+                    };
+                    spans.push(Span::styled(format!("[ {} ]", button.text), style));
+                    index += 1;
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn get_text_styled(
         &self,
         myself: bool,
@@ -83,7 +847,8 @@ impl MessageEntry {
         name_style: Style,
         content_style: Style,
         wrap_width: i32,
-    ) -> Text {
+        selected_button: Option<usize>,
+    ) -> Text<'_> {
         let (message_reply_name, message_reply_content) = if myself {
             (
                 app_context.style_chat_message_myself_reply_name(),
@@ -107,20 +872,17 @@ impl MessageEntry {
                                 app_context.style_chat_message_reply_text(),
                             ),
                             Span::styled(
-                                app_context
-                                    .tg_context()
-                                    .try_name_from_chats_or_users(
-                                        match app_context
-                                            .tg_context()
-                                            .open_chat_messages()
-                                            .iter()
-                                            .find(|m| m.id() == message.message_id)
-                                        {
-                                            Some(m) => m.sender_id(),
-                                            None => -1,
-                                        },
-                                    )
-                                    .unwrap_or_default(),
+                                app_context.tg_context().sender_name(
+                                    match app_context
+                                        .tg_context()
+                                        .open_chat_messages()
+                                        .iter()
+                                        .find(|m| m.id() == message.message_id)
+                                    {
+                                        Some(m) => m.sender_id(),
+                                        None => -1,
+                                    },
+                                ),
                                 message_reply_name,
                             ),
                         ])]);
@@ -154,14 +916,16 @@ impl MessageEntry {
             None => None,
         };
 
+        let timestamp_format = if self.full_timestamp {
+            TimestampFormat::Full
+        } else {
+            app_context.app_config().chat_timestamp_format
+        };
         let mut entry = Text::default();
         entry.extend(vec![Line::from(vec![
             Span::styled(
                 match self.sender_id {
-                    TdMessageSender::User(user_id) => app_context
-                        .tg_context()
-                        .try_name_from_chats_or_users(user_id)
-                        .unwrap_or_default(),
+                    TdMessageSender::User(user_id) => app_context.tg_context().sender_name(user_id),
                     TdMessageSender::Chat(chat_id) => app_context
                         .tg_context()
                         .name_from_chats(chat_id)
@@ -169,8 +933,13 @@ impl MessageEntry {
                 },
                 name_style,
             ),
-            Span::raw(" "),
-            Span::raw(if self.is_edited { "✏️" } else { "" }),
+            Span::styled(
+                match self.via_bot_name(app_context) {
+                    Some(name) => format!(" via {}", name),
+                    None => String::new(),
+                },
+                app_context.style_chat_message_via_bot(),
+            ),
             Span::raw(" "),
             Span::raw(match myself {
                 true => {
@@ -183,10 +952,52 @@ impl MessageEntry {
                 false => "",
             }),
             Span::raw(" "),
-            self.timestamp.get_span_styled(app_context),
+            self.timestamp
+                .get_span_styled(app_context, timestamp_format),
+            Span::styled(
+                match &self.edit_date {
+                    Some(edit_date) => format!(
+                        " (edited {})",
+                        DateTimeEntry::format_time(
+                            edit_date.timestamp,
+                            timestamp_format,
+                            app_context.app_config().timezone
+                        )
+                    ),
+                    None => String::new(),
+                },
+                app_context.style_timestamp(),
+            ),
+            Span::styled(
+                if self.is_sending { " sending…" } else { "" },
+                app_context.style_timestamp(),
+            ),
         ])]);
         entry.extend(reply_text.unwrap_or_default());
-        entry.extend(self.get_lines_styled_with_style(content_style, wrap_width));
+        if let Some(name) = self.forward_origin_name(app_context) {
+            entry.extend(vec![Line::from(Span::styled(
+                format!("↪ Forwarded from {}", name),
+                app_context.style_chat_message_forward_origin(),
+            ))]);
+        }
+        let keywords = app_context.app_config().highlight_keywords.clone();
+        entry.extend(Self::highlight_keywords(
+            self.get_lines_styled_with_style(content_style, wrap_width),
+            &keywords,
+            app_context.style_chat_message_keyword(),
+        ));
+        entry.extend(self.get_album_line(app_context, selected_button));
+        entry.extend(self.get_reactions_line(app_context));
+        entry.extend(self.get_inline_keyboard_lines(app_context, selected_button));
+        if let Some(reply_count) = self.reply_count {
+            entry.extend(vec![Line::from(Span::styled(
+                format!("💬 {} comments", reply_count),
+                app_context.style_chat_message_reply_text(),
+            ))]);
+        }
+        if app_context.app_config().strip_emoji {
+            entry.lines.iter_mut().for_each(strip_emoji_line);
+        }
         entry
     }
 
@@ -197,13 +1008,252 @@ impl MessageEntry {
             MessageContent::MessagePhoto(_) => vec![Line::from("📷 Photo")],
             MessageContent::MessageSticker(_) => vec![Line::from("🎨 Sticker")],
             MessageContent::MessageVideo(_) => vec![Line::from("🎥 Video")],
-            MessageContent::MessageAnimation(_) => vec![Line::from("🎞️ Animation")],
+            MessageContent::MessageAnimation(m) => vec![Line::from(Self::animation_text(
+                m.animation.duration,
+                m.animation
+                    .animation
+                    .size
+                    .max(m.animation.animation.expected_size),
+            ))],
             MessageContent::MessageVoiceNote(_) => vec![Line::from("🎤 Voice Note")],
             MessageContent::MessageDocument(_) => vec![Line::from("📄 Document")],
+            MessageContent::MessageDice(m) => vec![Line::from(Self::dice_text(&m.emoji, m.value))],
+            MessageContent::MessageLocation(m) => {
+                let live = (m.live_period > 0).then_some(m.expires_in > 0);
+                vec![Line::from(Self::location_text(&m.location, live))]
+            }
+            MessageContent::MessageVenue(m) => vec![
+                Line::from(format!("📍 {}, {}", m.venue.title, m.venue.address)),
+                Line::from(Self::location_text(&m.venue.location, None)),
+            ],
             _ => vec![Line::from("")],
         }
     }
 
+    /// Get the text of an animation, with its duration and its size when
+    /// they are known.
+    ///
+    /// # Arguments
+    /// * `duration` - The duration of the animation in seconds.
+    /// * `size` - The size of the file of the animation in bytes.
+    fn animation_text(duration: i32, size: i64) -> String {
+        let mut details = vec![];
+        if duration > 0 {
+            details.push(format!("{}:{:02}", duration / 60, duration % 60));
+        }
+        if size > 0 {
+            details.push(human_readable_size(size));
+        }
+        if details.is_empty() {
+            "🎞 GIF".to_string()
+        } else {
+            format!("🎞 GIF {}", details.join(", "))
+        }
+    }
+
+    /// Get the text of a dice, with its value once it is rolled.
+    ///
+    /// # Arguments
+    /// * `emoji` - The emoji of the dice.
+    /// * `value` - The value of the dice, 0 while it is still rolling.
+    fn dice_text(emoji: &str, value: i32) -> String {
+        if value == 0 {
+            format!("{} → …", emoji)
+        } else {
+            format!("{} → {}", emoji, value)
+        }
+    }
+
+    /// Get the text of a location, with its coordinates and the link to
+    /// see it on a map.
+    ///
+    /// # Arguments
+    /// * `location` - The location.
+    /// * `live` - For a live location, whether it is still shared.
+    fn location_text(location: &Location, live: Option<bool>) -> String {
+        let kind = match live {
+            None => "Location",
+            Some(true) => "Live location",
+            Some(false) => "Live location (ended)",
+        };
+        format!(
+            "📍 {} {:.5}, {:.5} https://www.openstreetmap.org/?mlat={}&mlon={}",
+            kind, location.latitude, location.longitude, location.latitude, location.longitude
+        )
+    }
+
+    /// Get the lines of the content with the spoilers obscured, if the
+    /// content contains spoilers.
+    fn message_obscured_lines(content: &MessageContent) -> Option<Vec<Line<'static>>> {
+        match content {
+            MessageContent::MessageText(m) => {
+                if !m
+                    .text
+                    .entities
+                    .iter()
+                    .any(|e| matches!(e.r#type, TextEntityType::Spoiler))
+                {
+                    return None;
+                }
+                // The offsets of the entities are in UTF-16 code units, the
+                // characters outside the BMP are replaced by two blocks to
+                // keep the offsets of the following entities valid.
+                let mut text = String::with_capacity(m.text.text.len());
+                let mut offset = 0;
+                for c in m.text.text.chars() {
+                    let len = c.len_utf16() as i32;
+                    let is_spoiler = m.text.entities.iter().any(|e| {
+                        matches!(e.r#type, TextEntityType::Spoiler)
+                            && offset >= e.offset
+                            && offset < e.offset + e.length
+                    });
+                    if is_spoiler && !c.is_whitespace() {
+                        (0..len).for_each(|_| text.push(SPOILER_CHAR));
+                    } else {
+                        text.push(c);
+                    }
+                    offset += len;
+                }
+                Some(Self::format_message_content(&FormattedText {
+                    text,
+                    entities: m.text.entities.clone(),
+                }))
+            }
+            MessageContent::MessagePhoto(m) if m.has_spoiler => {
+                Some(vec![Line::from(format!("📷 {}", SPOILER_MEDIA))])
+            }
+            MessageContent::MessageVideo(m) if m.has_spoiler => {
+                Some(vec![Line::from(format!("🎥 {}", SPOILER_MEDIA))])
+            }
+            MessageContent::MessageAnimation(m) if m.has_spoiler => {
+                Some(vec![Line::from(format!("🎞️ {}", SPOILER_MEDIA))])
+            }
+            _ => None,
+        }
+    }
+
+    fn message_media_file(content: &MessageContent) -> Option<MediaFile> {
+        match content {
+            MessageContent::MessagePhoto(m) => m
+                .photo
+                .sizes
+                .last()
+                .map(|size| (MediaKind::Photo, &size.photo).into()),
+            MessageContent::MessageVideo(m) => Some((MediaKind::Video, &m.video.video).into()),
+            MessageContent::MessageAnimation(m) => {
+                Some((MediaKind::Video, &m.animation.animation).into())
+            }
+            MessageContent::MessageDocument(m) => {
+                Some((MediaKind::File, &m.document.document).into())
+            }
+            MessageContent::MessageAudio(m) => Some((MediaKind::File, &m.audio.audio).into()),
+            MessageContent::MessageVoiceNote(m) => {
+                Some((MediaKind::File, &m.voice_note.voice).into())
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the caption of the media of a message, if it is not empty.
+    fn message_caption(content: &MessageContent) -> Option<FormattedText> {
+        let caption = match content {
+            MessageContent::MessagePhoto(m) => &m.caption,
+            MessageContent::MessageVideo(m) => &m.caption,
+            MessageContent::MessageAnimation(m) => &m.caption,
+            MessageContent::MessageDocument(m) => &m.caption,
+            MessageContent::MessageAudio(m) => &m.caption,
+            _ => return None,
+        };
+        (!caption.text.is_empty()).then(|| caption.clone())
+    }
+
+    fn message_formatted_text(content: &MessageContent) -> Option<FormattedText> {
+        match content {
+            MessageContent::MessageText(m) => Some(m.text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get the markdown markers that open and close a text entity.
+    fn markdown_markers(entity_type: &TextEntityType) -> Option<(String, String)> {
+        match entity_type {
+            TextEntityType::Bold => Some(("**".to_string(), "**".to_string())),
+            TextEntityType::Italic => Some(("_".to_string(), "_".to_string())),
+            TextEntityType::Strikethrough => Some(("~~".to_string(), "~~".to_string())),
+            TextEntityType::Code => Some(("`".to_string(), "`".to_string())),
+            TextEntityType::Pre => Some(("```\n".to_string(), "\n```".to_string())),
+            TextEntityType::PreCode(pre_code) => {
+                Some((format!("```{}\n", pre_code.language), "\n```".to_string()))
+            }
+            TextEntityType::TextUrl(text_url) => {
+                Some(("[".to_string(), format!("]({})", text_url.url)))
+            }
+            TextEntityType::MentionName(mention_name) => Some((
+                "[".to_string(),
+                format!("](tg://user?id={})", mention_name.user_id),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Get the texts of the `pre` and `code` entities of a formatted text.
+    /// The offsets and the lengths of the entities are in UTF-16 code units.
+    fn code_blocks_of(message: &FormattedText) -> Vec<String> {
+        let text = message.text.encode_utf16().collect::<Vec<u16>>();
+        let mut entities = message
+            .entities
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e.r#type,
+                    TextEntityType::Pre | TextEntityType::PreCode(_) | TextEntityType::Code
+                )
+            })
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|e| e.offset);
+        entities
+            .into_iter()
+            .map(|e| {
+                let start = (e.offset as usize).min(text.len());
+                let end = ((e.offset + e.length) as usize).min(text.len());
+                String::from_utf16_lossy(&text[start..end])
+            })
+            .collect()
+    }
+
+    /// Convert a formatted text to markdown.
+    /// The offsets and the lengths of the entities are in UTF-16 code units.
+    fn format_markdown(message: &FormattedText) -> String {
+        let text = message.text.encode_utf16().collect::<Vec<u16>>();
+        // The markers to insert, sorted by position. At the same position the
+        // closing markers come first, in the reverse order of the opening
+        // ones, so that nested entities stay well formed.
+        let mut markers = message
+            .entities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| {
+                Self::markdown_markers(&e.r#type).map(|(open, close)| {
+                    let start = (e.offset as usize).min(text.len());
+                    let end = ((e.offset + e.length) as usize).min(text.len());
+                    [((start, 1, i as i64), open), ((end, 0, -(i as i64)), close)]
+                })
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        markers.sort_by_key(|(key, _)| *key);
+
+        let mut markdown = String::new();
+        let mut last = 0;
+        for ((position, ..), marker) in markers {
+            markdown.push_str(&String::from_utf16_lossy(&text[last..position]));
+            markdown.push_str(&marker);
+            last = position;
+        }
+        markdown.push_str(&String::from_utf16_lossy(&text[last..]));
+        markdown
+    }
+
     fn from_span_to_lines(span: Span) -> Vec<Line<'static>> {
         span.content
             .split('\n')
@@ -241,7 +1291,7 @@ impl MessageEntry {
     ) -> Vec<Line<'static>> {
         if wrap_width == -1 {
             // No wrap
-            self.message_content
+            self.content_lines()
                 .iter()
                 .map(|l| {
                     l.iter()
@@ -260,9 +1310,17 @@ impl MessageEntry {
             let mut current_line = Line::default();
             let mut current_line_length = 0;
             // for span in self.message_content.iter().flat_map(|l| l.iter()) {
-            for span in self.message_content.iter().flat_map(|l| l.iter()) {
+            for span in self.content_lines().iter().flat_map(|l| l.iter()) {
                 for c in span.content.chars() {
-                    if c == ' ' && current_line_length >= wrap_width {
+                    // The lines are broken at the spaces, but the wide
+                    // characters, like the CJK ones, are often written
+                    // without spaces, then the line is broken before them.
+                    let width = c.width().unwrap_or(0) as i32;
+                    if (c == ' ' && current_line_length >= wrap_width)
+                        || (width > 1
+                            && current_line_length > 0
+                            && current_line_length + width > wrap_width)
+                    {
                         lines.push(current_line);
                         current_line = Line::default();
                         current_line_length = 0;
@@ -271,7 +1329,7 @@ impl MessageEntry {
                         c.to_string(),
                         Self::merge_two_style(span.style, content_style),
                     ));
-                    current_line_length += 1;
+                    current_line_length += width;
                 }
                 lines.push(current_line);
                 current_line = Line::default();
@@ -281,132 +1339,76 @@ impl MessageEntry {
         }
     }
 
+    /// Format a text with its entities, in a single pass over the text.
+    /// The offsets and the lengths of the entities are in UTF-16 code units,
+    /// the entities overlapping a previous one are ignored.
     fn format_message_content(message: &FormattedText) -> Vec<Line<'static>> {
-        let text = &message.text;
-        let entities = &message.entities;
-
-        if entities.is_empty() {
-            return Self::from_span_to_lines(Span::raw(text));
+        if message.entities.is_empty() {
+            return Self::from_span_to_lines(Span::raw(message.text.clone()));
         }
 
+        let text = message.text.encode_utf16().collect::<Vec<u16>>();
+        let mut entities = message.entities.iter().collect::<Vec<_>>();
+        entities.sort_by_key(|e| e.offset);
+
         let mut message_vec = Vec::new();
-        entities.iter().for_each(|e| {
-            let offset = e.offset as usize;
-            let length = e.length as usize;
-            message_vec.push(Span::raw(text.chars().take(offset).collect::<String>()));
-            match &e.r#type {
-                tdlib_rs::enums::TextEntityType::Italic => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::ITALIC),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Bold => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Underline => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::UNDERLINED),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Strikethrough => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::CROSSED_OUT),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Url => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::UNDERLINED),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::TextUrl(text_url) => {
-                    message_vec.push(Span::styled(
-                        text_url.url.clone(),
-                        Style::default().add_modifier(Modifier::UNDERLINED),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::EmailAddress => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::UNDERLINED),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Mention => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Hashtag => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::PhoneNumber => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::UNDERLINED),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::MentionName(mention_name) => {
-                    message_vec.push(Span::styled(
-                        // TODO: Fix from user_id to username
-                        mention_name.user_id.to_string(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Code => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::DIM),
-                    ));
+        let mut last = 0;
+        for e in entities {
+            let start = (e.offset as usize).min(text.len());
+            let end = ((e.offset + e.length) as usize).min(text.len());
+            if start < last {
+                continue;
+            }
+            message_vec.push(Span::raw(String::from_utf16_lossy(&text[last..start])));
+            let content = String::from_utf16_lossy(&text[start..end]);
+            let span = match &e.r#type {
+                TextEntityType::Italic => {
+                    Span::styled(content, Style::default().add_modifier(Modifier::ITALIC))
                 }
-                tdlib_rs::enums::TextEntityType::Pre => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::DIM),
-                    ));
+                TextEntityType::Bold
+                | TextEntityType::Mention
+                | TextEntityType::Hashtag
+                | TextEntityType::Cashtag => {
+                    Span::styled(content, Style::default().add_modifier(Modifier::BOLD))
                 }
-                tdlib_rs::enums::TextEntityType::PreCode(_pre_code) => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::DIM),
-                    ));
+                TextEntityType::Underline
+                | TextEntityType::Url
+                | TextEntityType::EmailAddress
+                | TextEntityType::PhoneNumber
+                | TextEntityType::BankCardNumber => {
+                    Span::styled(content, Style::default().add_modifier(Modifier::UNDERLINED))
                 }
-                tdlib_rs::enums::TextEntityType::Cashtag => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ));
+                TextEntityType::Strikethrough => Span::styled(
+                    content,
+                    Style::default().add_modifier(Modifier::CROSSED_OUT),
+                ),
+                TextEntityType::TextUrl(text_url) => Span::styled(
+                    text_url.url.clone(),
+                    Style::default().add_modifier(Modifier::UNDERLINED),
+                ),
+                TextEntityType::MentionName(mention_name) => Span::styled(
+                    // TODO: Fix from user_id to username
+                    mention_name.user_id.to_string(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                TextEntityType::Code
+                | TextEntityType::Pre
+                | TextEntityType::PreCode(_)
+                | TextEntityType::BlockQuote => {
+                    Span::styled(content, Style::default().add_modifier(Modifier::DIM))
                 }
-                tdlib_rs::enums::TextEntityType::BankCardNumber => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::UNDERLINED),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::BlockQuote => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::DIM),
-                    ));
-                }
-                tdlib_rs::enums::TextEntityType::Spoiler => {}
-                tdlib_rs::enums::TextEntityType::MediaTimestamp(_) => {}
-                tdlib_rs::enums::TextEntityType::CustomEmoji(_) => {}
-                tdlib_rs::enums::TextEntityType::BotCommand => {}
-            }
-            message_vec.push(Span::raw(
-                text.chars().skip(offset + length).collect::<String>(),
-            ));
-        });
+                // The text of a custom emoji entity is its fallback unicode
+                // emoji, the custom emoji itself can not be displayed in the
+                // terminal.
+                TextEntityType::Spoiler
+                | TextEntityType::CustomEmoji(_)
+                | TextEntityType::MediaTimestamp(_)
+                | TextEntityType::BotCommand => Span::raw(content),
+            };
+            message_vec.push(span);
+            last = end;
+        }
+        message_vec.push(Span::raw(String::from_utf16_lossy(&text[last..])));
 
         Self::from_spans_to_lines(message_vec)
     }
@@ -420,6 +1422,11 @@ impl From<&tdlib_rs::types::Message> for MessageEntry {
                 MessageSender::Chat(chat) => TdMessageSender::Chat(chat.chat_id),
             },
             message_content: Self::message_content_lines(&message.content),
+            obscured_content: Self::message_obscured_lines(&message.content),
+            spoiler_revealed: false,
+            full_timestamp: false,
+            formatted_text: Self::message_formatted_text(&message.content),
+            media_file: Self::message_media_file(&message.content),
             reply_to: match &message.reply_to {
                 Some(reply) => match reply {
                     MessageReplyTo::Message(message) => {
@@ -429,10 +1436,515 @@ impl From<&tdlib_rs::types::Message> for MessageEntry {
                 },
                 None => None,
             },
+            forward_origin: message
+                .forward_info
+                .as_ref()
+                .map(|info| (&info.origin).into()),
+            via_bot_user_id: (message.via_bot_user_id != 0).then_some(message.via_bot_user_id),
             timestamp: DateTimeEntry {
                 timestamp: message.date,
             },
-            is_edited: message.edit_date != 0,
+            edit_date: (message.edit_date != 0).then_some(DateTimeEntry {
+                timestamp: message.edit_date,
+            }),
+            inline_keyboard: Self::inline_keyboard_rows(message.reply_markup.as_ref()),
+            reactions: Self::reactions_of(message.interaction_info.as_ref()),
+            can_get_message_thread: message.can_get_message_thread,
+            can_be_deleted_for_all_users: message.can_be_deleted_for_all_users,
+            can_be_deleted_only_for_self: message.can_be_deleted_only_for_self,
+            reply_count: if message.is_channel_post {
+                message
+                    .interaction_info
+                    .as_ref()
+                    .and_then(|info| info.reply_info.as_ref())
+                    .map(|reply_info| reply_info.reply_count)
+            } else {
+                None
+            },
+            is_sending: matches!(message.sending_state, Some(MessageSendingState::Pending(_))),
+            media_album_id: message.media_album_id,
+            caption: Self::message_caption(&message.content),
+            album: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdlib_rs::types::{MessageText, TextEntity, TextEntityTypeCustomEmoji};
+
+    fn text_content(text: &str, entities: Vec<TextEntity>) -> MessageContent {
+        MessageContent::MessageText(MessageText {
+            text: FormattedText {
+                text: text.to_string(),
+                entities,
+            },
+            web_page: None,
+            link_preview_options: None,
+        })
+    }
+
+    fn spoiler(offset: i32, length: i32) -> TextEntity {
+        TextEntity {
+            offset,
+            length,
+            r#type: TextEntityType::Spoiler,
+        }
+    }
+
+    #[test]
+    fn test_convert_time_relative() {
+        let now = 1_700_000_000;
+        let relative = |elapsed: i64| {
+            DateTimeEntry::convert_time_relative((now - elapsed) as i32, now, Timezone::Local)
+        };
+        assert_eq!(relative(-30), "now");
+        assert_eq!(relative(59), "now");
+        assert_eq!(relative(5 * 60), "5m");
+        assert_eq!(relative(3 * 3_600 + 59), "3h");
+        assert_eq!(relative(2 * 86_400), "2d");
+        assert_eq!(relative(15 * 86_400), "2w");
+        assert_eq!(
+            relative(40 * 86_400),
+            DateTimeEntry::convert_time((now - 40 * 86_400) as i32, Timezone::Local)[..10]
+        );
+    }
+
+    #[test]
+    fn test_datetime_in_timezone() {
+        let timestamp = 1_700_000_000;
+        let utc = DateTimeEntry::datetime(
+            timestamp,
+            Timezone::Fixed(FixedOffset::east_opt(0).unwrap()),
+        );
+        assert_eq!(utc.format("%Y-%m-%d %H:%M").to_string(), "2023-11-14 22:13");
+        let offset = FixedOffset::east_opt(5 * 3_600 + 30 * 60).unwrap();
+        let shifted = DateTimeEntry::datetime(timestamp, Timezone::Fixed(offset));
+        assert_eq!(
+            shifted.format("%Y-%m-%d %H:%M").to_string(),
+            "2023-11-15 03:43"
+        );
+        assert_eq!(shifted.timestamp(), timestamp);
+
+        // The timezone is read from the configuration of the app context.
+        let app_context = AppContext::new_default();
+        app_context.app_config().timezone = Timezone::Fixed(offset);
+        let entry = DateTimeEntry {
+            timestamp: timestamp as i32,
+        };
+        assert_eq!(
+            entry
+                .get_span_styled(&app_context, TimestampFormat::Full)
+                .content,
+            "2023-11-15 03:43:20"
+        );
+    }
+
+    #[test]
+    fn test_full_timestamp() {
+        let app_context = AppContext::new_default();
+        let timestamp = 1_700_000_007;
+        let full = DateTimeEntry::convert_time_full(timestamp, Timezone::Local);
+        assert_eq!(full.len(), "2023-11-14 22:13:27".len());
+        assert!(full.ends_with(":27"));
+
+        let mut message = MessageEntry::new_text(1, "Hello", timestamp);
+        let header = |message: &MessageEntry| {
+            message
+                .get_text_styled(
+                    false,
+                    &app_context,
+                    false,
+                    Style::default(),
+                    Style::default(),
+                    80,
+                    None,
+                )
+                .lines[0]
+                .to_string()
+        };
+        assert!(!header(&message).contains(&full));
+        message.set_full_timestamp(true);
+        assert!(header(&message).contains(&full));
+    }
+
+    #[test]
+    fn test_message_obscured_lines() {
+        let content = text_content("no spoiler here", vec![]);
+        assert!(MessageEntry::message_obscured_lines(&content).is_none());
+
+        let content = text_content("the end is near", vec![spoiler(4, 6)]);
+        let lines = MessageEntry::message_obscured_lines(&content).unwrap();
+        let text: String = lines.iter().map(|line| line.to_string()).collect();
+        assert!(text.contains("▓▓▓ ▓▓"));
+        assert!(!text.contains("end"));
+    }
+
+    #[test]
+    fn test_message_obscured_lines_utf16() {
+        // The emoji takes two UTF-16 code units, so the spoiler starts at 3.
+        let content = text_content("😀 secret", vec![spoiler(3, 6)]);
+        let lines = MessageEntry::message_obscured_lines(&content).unwrap();
+        let text: String = lines.iter().map(|line| line.to_string()).collect();
+        assert!(text.contains("▓▓▓▓▓▓"));
+        assert!(text.contains('😀'));
+        assert!(!text.contains("secret"));
+    }
+
+    fn custom_emoji(offset: i32, length: i32) -> TextEntity {
+        TextEntity {
+            offset,
+            length,
+            r#type: TextEntityType::CustomEmoji(TextEntityTypeCustomEmoji { custom_emoji_id: 1 }),
         }
     }
+
+    fn content_text(lines: &[Line]) -> String {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_format_custom_emojis() {
+        let text = FormattedText {
+            text: "😀😃 hi".to_string(),
+            entities: vec![custom_emoji(0, 2), custom_emoji(2, 2)],
+        };
+        let lines = MessageEntry::format_message_content(&text);
+        assert_eq!(content_text(&lines), "😀😃 hi");
+    }
+
+    #[test]
+    fn test_format_custom_emoji_then_bold() {
+        // The emoji takes two UTF-16 code units, so the bold starts at 3.
+        let text = FormattedText {
+            text: "😀 bold end".to_string(),
+            entities: vec![
+                custom_emoji(0, 2),
+                TextEntity {
+                    offset: 3,
+                    length: 4,
+                    r#type: TextEntityType::Bold,
+                },
+            ],
+        };
+        let lines = MessageEntry::format_message_content(&text);
+        assert_eq!(content_text(&lines), "😀 bold end");
+        let bold = lines
+            .iter()
+            .flat_map(|line| line.iter())
+            .filter(|span| span.style.add_modifier.contains(Modifier::BOLD))
+            .map(|span| span.content.to_string())
+            .collect::<String>();
+        assert_eq!(bold, "bold");
+    }
+
+    #[test]
+    fn test_wrap_wide_characters() {
+        // Written without spaces, the line is broken before a wide character.
+        let message = MessageEntry::new_text(1, "東京の友達と週末に会いましょう", 0);
+        let lines = message.get_lines_styled_with_style(Style::default(), 10);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.width() <= 10));
+
+        let message = MessageEntry::new_text(1, "東京の友達と 🎉🎉 hello world", 0);
+        let lines = message.get_lines_styled_with_style(Style::default(), 10);
+        let text = lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<String>();
+        assert_eq!(text, message.message_content_to_string());
+    }
+
+    #[test]
+    fn test_animation_text() {
+        assert_eq!(
+            MessageEntry::animation_text(5, 1536 * 1024),
+            "🎞 GIF 0:05, 1.5 MB"
+        );
+        assert_eq!(MessageEntry::animation_text(0, 0), "🎞 GIF");
+    }
+
+    #[test]
+    fn test_group_albums() {
+        let media = |id: i64, album_id: i64, kind: MediaKind, caption: &str| {
+            let mut message = MessageEntry::new_text(id, "", id as i32);
+            message.media_album_id = album_id;
+            message.media_file = Some(MediaFile {
+                kind,
+                file_id: id as i32,
+                is_downloaded: false,
+                size: 0,
+            });
+            message.caption = (!caption.is_empty()).then(|| FormattedText {
+                text: caption.to_string(),
+                entities: vec![],
+            });
+            message
+        };
+        let messages = vec![
+            MessageEntry::new_text(6, "after", 6),
+            media(5, 7, MediaKind::Video, ""),
+            media(4, 7, MediaKind::Photo, "Holidays"),
+            media(3, 7, MediaKind::Photo, ""),
+            media(2, 8, MediaKind::Photo, ""),
+            MessageEntry::new_text(1, "before", 1),
+        ];
+        let grouped = MessageEntry::group_albums(messages);
+        let ids: Vec<i64> = grouped.iter().map(|m| m.id()).collect();
+        // A message alone in its album is not grouped.
+        assert_eq!(ids, [6, 4, 2, 1]);
+
+        let album = &grouped[1];
+        assert_eq!(album.album_len(), 3);
+        assert_eq!(album.album_item(0).map(|m| m.id()), Some(3));
+        assert_eq!(album.message_ids(), [3, 4, 5]);
+        assert!(album.includes(5) && !album.includes(2));
+        assert_eq!(
+            album.message_content_to_string(),
+            "🖼 Album: 2 photos, 1 video\nHolidays"
+        );
+        assert_eq!(album.message_content_to_plain_text(), "Holidays");
+        assert_eq!(grouped[2].album_len(), 0);
+    }
+
+    #[test]
+    fn test_dice_text() {
+        assert_eq!(MessageEntry::dice_text("🎲", 4), "🎲 → 4");
+        assert_eq!(MessageEntry::dice_text("🎯", 0), "🎯 → …");
+    }
+
+    #[test]
+    fn test_revoke_window() {
+        let limit = 48 * 60 * 60;
+        let now = 1000 + limit - 60;
+        let mut message = MessageEntry::new_text(1, "hello", 1000);
+        assert_eq!(message.revoke_window(limit, now), RevokeWindow::Left(60));
+        // Past the limit, TDLib still allows the administrators to delete it.
+        assert_eq!(
+            message.revoke_window(limit, now + 60),
+            RevokeWindow::Unlimited
+        );
+        assert_eq!(message.revoke_window(0, now), RevokeWindow::Unlimited);
+
+        message.set_can_be_deleted(true, false);
+        assert_eq!(
+            message.revoke_window(limit, now),
+            RevokeWindow::EveryoneOnly
+        );
+        message.set_can_be_deleted(false, true);
+        assert_eq!(message.revoke_window(limit, now), RevokeWindow::SelfOnly);
+        message.set_can_be_deleted(false, false);
+        assert_eq!(message.revoke_window(limit, now), RevokeWindow::Forbidden);
+    }
+
+    #[test]
+    fn test_location_text() {
+        let location = Location {
+            latitude: 48.8584,
+            longitude: 2.2945,
+            horizontal_accuracy: 0.0,
+        };
+        assert_eq!(
+            MessageEntry::location_text(&location, None),
+            "📍 Location 48.85840, 2.29450 https://www.openstreetmap.org/?mlat=48.8584&mlon=2.2945"
+        );
+        assert!(
+            MessageEntry::location_text(&location, Some(true)).starts_with("📍 Live location 48")
+        );
+        assert!(MessageEntry::location_text(&location, Some(false))
+            .starts_with("📍 Live location (ended) 48"));
+    }
+
+    #[test]
+    fn test_code_blocks_of() {
+        let entity = |offset, length, r#type| TextEntity {
+            offset,
+            length,
+            r#type,
+        };
+        let text = FormattedText {
+            text: "Run `cargo test` then:\nfn main() {}\n😀 done".to_string(),
+            entities: vec![
+                entity(23, 12, TextEntityType::Pre),
+                entity(5, 10, TextEntityType::Code),
+                entity(0, 3, TextEntityType::Bold),
+            ],
+        };
+        assert_eq!(
+            MessageEntry::code_blocks_of(&text),
+            vec!["cargo test", "fn main() {}"]
+        );
+        assert!(MessageEntry::code_blocks_of(&FormattedText::default()).is_empty());
+    }
+
+    #[test]
+    fn test_forward_origin_name() {
+        let app_context = AppContext::new_default();
+        let mut message = MessageEntry::new_text(1, "Hello", 0);
+        assert!(!message.is_forwarded());
+        assert_eq!(message.forward_origin_name(&app_context), None);
+
+        message.set_forward_origin(TdMessageOrigin::HiddenUser(String::new()));
+        assert!(message.is_forwarded());
+        assert_eq!(
+            message.forward_origin_name(&app_context).unwrap(),
+            "a hidden user"
+        );
+        message.set_forward_origin(TdMessageOrigin::HiddenUser("Bob".to_string()));
+        assert_eq!(
+            message.forward_origin_name(&app_context).unwrap(),
+            "Bob (hidden user)"
+        );
+        message.set_forward_origin(TdMessageOrigin::Channel(-100));
+        assert_eq!(
+            message.forward_origin_name(&app_context).unwrap(),
+            "a hidden chat"
+        );
+    }
+
+    #[test]
+    fn test_via_bot_name() {
+        use crate::{event::Event, tg::tg_context::UNKNOWN_USER_NAME};
+
+        let app_context = AppContext::new_default();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut message = MessageEntry::new_text(1, "Hello", 0);
+        assert_eq!(message.via_bot_user_id(), None);
+        assert_eq!(message.via_bot_name(&app_context), None);
+
+        // The bot is requested while its username is not known.
+        message.set_via_bot_user_id(42);
+        assert_eq!(
+            message.via_bot_name(&app_context).unwrap(),
+            UNKNOWN_USER_NAME
+        );
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok())
+            .any(|event| matches!(event, Event::GetUser(42))));
+        let header = message
+            .get_text_styled(
+                false,
+                &app_context,
+                false,
+                Style::default(),
+                Style::default(),
+                80,
+                None,
+            )
+            .lines[0]
+            .to_string();
+        assert!(header.contains(&format!(" via {}", UNKNOWN_USER_NAME)));
+    }
+
+    #[test]
+    fn test_strip_emoji() {
+        let app_context = AppContext::new_default();
+        let message = MessageEntry::new_text(1, "📷 Photo of the 🏔️ mountains", 0);
+        let content = |app_context: &AppContext| {
+            message
+                .get_text_styled(
+                    true,
+                    app_context,
+                    true,
+                    Style::default(),
+                    Style::default(),
+                    80,
+                    None,
+                )
+                .lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert!(content(&app_context)[0].contains("📤"));
+
+        app_context.app_config().strip_emoji = true;
+        let lines = content(&app_context);
+        // The markers are replaced by text, the other emoji are removed.
+        assert!(lines[0].contains("[sent]"));
+        assert_eq!(lines[1], "Photo of the mountains");
+        assert_eq!(crate::utils::strip_emoji("ok 👍\n🎉 done"), "ok \ndone");
+        assert_eq!(crate::utils::strip_emoji("🟢 Name ✅"), "* Name [v]");
+    }
+
+    #[test]
+    fn test_reactions() {
+        use tdlib_rs::types::{MessageReaction, MessageReactions, ReactionTypeEmoji};
+
+        let reaction = |r#type, total_count, is_chosen| MessageReaction {
+            r#type,
+            total_count,
+            is_chosen,
+            used_sender_id: None,
+            recent_sender_ids: vec![],
+        };
+        let emoji = |emoji: &str| {
+            ReactionType::Emoji(ReactionTypeEmoji {
+                emoji: emoji.to_string(),
+            })
+        };
+        let info = MessageInteractionInfo {
+            reactions: Some(MessageReactions {
+                reactions: vec![
+                    reaction(emoji("👍"), 3, true),
+                    reaction(ReactionType::CustomEmoji(Default::default()), 2, true),
+                    reaction(emoji("🔥"), 1, false),
+                ],
+                are_tags: false,
+            }),
+            ..Default::default()
+        };
+        let app_context = AppContext::new_default();
+        let mut message = MessageEntry::new_text(1, "Hello", 0);
+        assert!(message.get_reactions_line(&app_context).is_none());
+
+        message.set_reactions(Some(&info));
+        assert!(message.is_reaction_chosen("👍"));
+        assert!(!message.is_reaction_chosen("🔥"));
+        assert_eq!(
+            message
+                .get_reactions_line(&app_context)
+                .unwrap()
+                .to_string(),
+            " 👍 3   🔥 1  "
+        );
+        message.set_reactions(None);
+        assert!(!message.is_reaction_chosen("👍"));
+    }
+
+    #[test]
+    fn test_keyword_ranges() {
+        let keywords = vec!["tgt".to_string(), "rust lang".to_string()];
+        assert_eq!(
+            MessageEntry::keyword_ranges("TGT and tgt, not tgts", &keywords),
+            vec![(0, 3), (8, 11)]
+        );
+        assert_eq!(
+            MessageEntry::keyword_ranges("I like Rust Lang!", &keywords),
+            vec![(7, 16)]
+        );
+        assert!(MessageEntry::keyword_ranges("atgt", &keywords).is_empty());
+        assert!(MessageEntry::keyword_ranges("tgt", &[String::new()]).is_empty());
+        // The byte offsets follow the multi-byte characters.
+        assert_eq!(
+            MessageEntry::keyword_ranges("é tgt", &keywords),
+            vec![(3, 6)]
+        );
+    }
+
+    #[test]
+    fn test_highlight_keywords() {
+        let keyword_style = Style::default().add_modifier(Modifier::BOLD);
+        let lines = MessageEntry::highlight_keywords(
+            vec![Line::from("about tgt here")],
+            &["tgt".to_string()],
+            keyword_style,
+        );
+        let spans: Vec<&str> = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(spans, vec!["about ", "tgt", " here"]);
+        assert_eq!(lines[0].spans[1].style, keyword_style);
+        assert!(MessageEntry::new_text(1, "see TGT", 0).matches_keywords(&["tgt".to_string()]));
+        assert!(!MessageEntry::new_text(1, "see TGT", 0).matches_keywords(&[]));
+    }
 }
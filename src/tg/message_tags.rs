@@ -0,0 +1,245 @@
+use std::{fs, io, path::PathBuf};
+
+/// The path of the file where the message tags are stored, relative to the
+/// `tgt` directory.
+pub const MESSAGE_TAGS_FILE: &str = ".data/message_tags";
+/// The maximum number of characters of a message kept as preview of a
+/// tagged message.
+const PREVIEW_LEN: usize = 60;
+
+/// `TaggedMessage` is a message tagged locally by the user.
+/// The tags are not sent to Telegram, they only live in the local file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedMessage {
+    /// The identifier of the chat of the message.
+    pub chat_id: i64,
+    /// The identifier of the message.
+    pub message_id: i64,
+    /// The tag of the message, for example "todo".
+    pub tag: String,
+    /// The beginning of the text of the message, it is shown in the tags
+    /// overview without loading the message.
+    pub preview: String,
+}
+
+impl TaggedMessage {
+    /// Parse a tagged message from a line of the tags file.
+    /// The fields are separated by tabs: chat id, message id, tag and preview.
+    /// The tabs, the newlines and the backslashes of the tag and of the
+    /// preview are escaped.
+    ///
+    /// # Arguments
+    /// * `line` - The line of the tags file.
+    ///
+    /// # Returns
+    /// * `Option<TaggedMessage>` - The tagged message, or `None` if the line is
+    ///   not valid.
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        let chat_id = fields.next()?.parse().ok()?;
+        let message_id = fields.next()?.parse().ok()?;
+        let tag = unescape(fields.next()?);
+        if tag.is_empty() {
+            return None;
+        }
+        let preview = unescape(fields.next().unwrap_or_default());
+        Some(Self {
+            chat_id,
+            message_id,
+            tag,
+            preview,
+        })
+    }
+
+    /// Convert the tagged message to a line of the tags file.
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.chat_id,
+            self.message_id,
+            escape(&self.tag),
+            escape(&self.preview)
+        )
+    }
+}
+
+/// Escape the tabs, the newlines and the backslashes of a field of the tags
+/// file.
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverse the escaping of a field of the tags file.
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// `MessageTags` is the collection of the messages tagged locally by the
+/// user, across all the chats.
+/// The tags are saved to a file every time they change.
+#[derive(Debug, Default)]
+pub struct MessageTags {
+    /// The file where the tags are saved, if `None` the tags are not saved.
+    path: Option<PathBuf>,
+    /// The tagged messages, in the order they were tagged.
+    entries: Vec<TaggedMessage>,
+}
+
+impl MessageTags {
+    /// Load the message tags from a file.
+    /// A missing file is not an error, there are simply no tags yet.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the tags file.
+    ///
+    /// # Returns
+    /// * `MessageTags` - The loaded message tags.
+    pub fn load(path: PathBuf) -> Self {
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .filter_map(TaggedMessage::from_line)
+                .collect(),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    tracing::error!("Failed to read the message tags {:?}: {e}", path);
+                }
+                Vec::new()
+            }
+        };
+        Self {
+            path: Some(path),
+            entries,
+        }
+    }
+
+    /// Get the tagged messages, in the order they were tagged.
+    pub fn entries(&self) -> &[TaggedMessage] {
+        &self.entries
+    }
+
+    /// Get the tag of a message.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat of the message.
+    /// * `message_id` - The identifier of the message.
+    pub fn tag_of(&self, chat_id: i64, message_id: i64) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.chat_id == chat_id && e.message_id == message_id)
+            .map(|e| e.tag.as_str())
+    }
+
+    /// Set the tag of a message, replacing its previous tag, and save the
+    /// tags.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat of the message.
+    /// * `message_id` - The identifier of the message.
+    /// * `tag` - The new tag of the message, `None` to untag it.
+    /// * `text` - The text of the message, its beginning is kept as preview.
+    pub fn set_tag(&mut self, chat_id: i64, message_id: i64, tag: Option<String>, text: &str) {
+        self.entries
+            .retain(|e| e.chat_id != chat_id || e.message_id != message_id);
+        if let Some(tag) = tag {
+            let preview = text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .chars()
+                .take(PREVIEW_LEN)
+                .collect();
+            self.entries.push(TaggedMessage {
+                chat_id,
+                message_id,
+                tag,
+                preview,
+            });
+        }
+        self.save();
+    }
+
+    /// Save the tags to the file, if any.
+    fn save(&self) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let content: String = self.entries.iter().map(|e| e.to_line() + "\n").collect();
+        if let Err(e) = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, content))
+        {
+            tracing::error!("Failed to save the message tags {:?}: {e}", path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tagged_message_line() {
+        let tagged = TaggedMessage {
+            chat_id: -100123,
+            message_id: 42,
+            tag: "to\tdo\\".to_string(),
+            preview: "buy\tmilk\nand \\n eggs".to_string(),
+        };
+        let line = tagged.to_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(line.matches('\t').count(), 3);
+        assert_eq!(TaggedMessage::from_line(&line), Some(tagged));
+        assert_eq!(TaggedMessage::from_line("1\t2\t\tpreview"), None);
+        assert_eq!(TaggedMessage::from_line("not a tag"), None);
+    }
+
+    #[test]
+    fn test_message_tags_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("tgt-message-tags-{}", std::process::id()));
+        let path = dir.join("message_tags");
+        let mut tags = MessageTags::load(path.clone());
+        tags.set_tag(1, 2, Some("to\tdo".to_string()), "first");
+        tags.set_tag(3, 4, Some("later".to_string()), "second");
+        assert_eq!(MessageTags::load(path).entries(), tags.entries());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_message_tags_set_tag() {
+        let mut tags = MessageTags::default();
+        tags.set_tag(1, 2, Some("todo".to_string()), "first\nline   second");
+        assert_eq!(tags.tag_of(1, 2), Some("todo"));
+        assert_eq!(tags.entries()[0].preview, "first line second");
+        tags.set_tag(1, 2, Some("reference".to_string()), "");
+        assert_eq!(tags.entries().len(), 1);
+        assert_eq!(tags.tag_of(1, 2), Some("reference"));
+        tags.set_tag(1, 2, None, "");
+        assert_eq!(tags.tag_of(1, 2), None);
+        assert!(tags.entries().is_empty());
+    }
+}
@@ -1,5 +1,10 @@
+pub mod message_cache;
 pub mod message_entry;
+pub mod message_tags;
+pub mod navigation_history;
 pub mod ordered_chat;
+pub mod sticker_sets;
 pub mod td_enums;
 pub mod tg_backend;
 pub mod tg_context;
+pub mod unread_counts;
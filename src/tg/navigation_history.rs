@@ -0,0 +1,111 @@
+/// The maximum number of positions kept in each direction of the history.
+const MAX_HISTORY_LEN: usize = 100;
+
+/// `Position` is a place visited by the user: a chat, and the message of the
+/// chat that was jumped to, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The identifier of the chat.
+    pub chat_id: i64,
+    /// The identifier of the message jumped to, if `None` the chat is opened
+    /// where it was left.
+    pub message_id: Option<i64>,
+}
+
+/// `NavigationHistory` is the history of the chats and the messages visited
+/// by the user, it works like the history of a web browser.
+/// Visiting a new position forgets the positions that could be reached going
+/// forward.
+#[derive(Debug, Default)]
+pub struct NavigationHistory {
+    /// The positions visited before the current one, the most recent last.
+    back: Vec<Position>,
+    /// The positions left going back, the most recent last.
+    forward: Vec<Position>,
+    /// The current position.
+    current: Option<Position>,
+}
+
+impl NavigationHistory {
+    /// Record the visit of a position.
+    ///
+    /// # Arguments
+    /// * `position` - The visited position.
+    pub fn visit(&mut self, position: Position) {
+        if self.current == Some(position) {
+            return;
+        }
+        if let Some(current) = self.current.replace(position) {
+            self.back.push(current);
+            if self.back.len() > MAX_HISTORY_LEN {
+                self.back.remove(0);
+            }
+        }
+        self.forward.clear();
+    }
+
+    /// Go back to the previous position.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The previous position, or `None` if there is no
+    ///   position before the current one.
+    pub fn back(&mut self) -> Option<Position> {
+        let position = self.back.pop()?;
+        if let Some(current) = self.current.replace(position) {
+            self.forward.push(current);
+        }
+        Some(position)
+    }
+
+    /// Go forward to the position left going back.
+    ///
+    /// # Returns
+    /// * `Option<Position>` - The next position, or `None` if there is no
+    ///   position after the current one.
+    pub fn forward(&mut self) -> Option<Position> {
+        let position = self.forward.pop()?;
+        if let Some(current) = self.current.replace(position) {
+            self.back.push(current);
+        }
+        Some(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat(chat_id: i64) -> Position {
+        Position {
+            chat_id,
+            message_id: None,
+        }
+    }
+
+    #[test]
+    fn test_navigation_history() {
+        let mut history = NavigationHistory::default();
+        assert_eq!(history.back(), None);
+
+        history.visit(chat(1));
+        history.visit(chat(1));
+        history.visit(chat(2));
+        let message = Position {
+            chat_id: 3,
+            message_id: Some(42),
+        };
+        history.visit(message);
+        assert_eq!(history.back(), Some(chat(2)));
+        assert_eq!(history.back(), Some(chat(1)));
+        assert_eq!(history.back(), None);
+        assert_eq!(history.forward(), Some(chat(2)));
+        assert_eq!(history.forward(), Some(message));
+        assert_eq!(history.forward(), None);
+
+        // Visiting a new position forgets the forward positions.
+        history.back();
+        history.visit(chat(4));
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some(chat(2)));
+    }
+}
@@ -0,0 +1,75 @@
+use tdlib_rs::types::StickerSet;
+
+/// `StickerEntry` is a sticker of an installed sticker set. The terminal can
+/// not show the image of the sticker, it is shown as its emoji.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StickerEntry {
+    /// The identifier of the file of the sticker, used to send it.
+    pub file_id: i32,
+    /// The emoji of the sticker.
+    pub emoji: String,
+}
+
+/// `StickerSetEntry` is a sticker set installed by the user, with its
+/// stickers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StickerSetEntry {
+    /// The identifier of the sticker set.
+    pub id: i64,
+    /// The title of the sticker set.
+    pub title: String,
+    /// The name of the sticker set, as in its link.
+    pub name: String,
+    /// The stickers of the sticker set, in order.
+    pub stickers: Vec<StickerEntry>,
+}
+
+impl StickerSetEntry {
+    /// Check whether the sticker set matches a search, by its title or its
+    /// name. The search is case insensitive and an empty search matches all
+    /// the sticker sets.
+    ///
+    /// # Arguments
+    /// * `query` - The text searched.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.title.to_lowercase().contains(&query) || self.name.to_lowercase().contains(&query)
+    }
+}
+
+impl From<&StickerSet> for StickerSetEntry {
+    fn from(sticker_set: &StickerSet) -> Self {
+        Self {
+            id: sticker_set.id,
+            title: sticker_set.title.clone(),
+            name: sticker_set.name.clone(),
+            stickers: sticker_set
+                .stickers
+                .iter()
+                .map(|sticker| StickerEntry {
+                    file_id: sticker.sticker.id,
+                    emoji: sticker.emoji.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sticker_set_matches() {
+        let sticker_set = StickerSetEntry {
+            id: 1,
+            title: "Hot Cherry".to_string(),
+            name: "HotCherry_pack".to_string(),
+            stickers: vec![],
+        };
+        assert!(sticker_set.matches(""));
+        assert!(sticker_set.matches("cherry"));
+        assert!(sticker_set.matches("_PACK"));
+        assert!(!sticker_set.matches("duck"));
+    }
+}
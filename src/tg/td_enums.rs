@@ -8,6 +8,33 @@ pub enum TdMessageSender {
     Chat(i64),
 }
 
+/// The origin of a forwarded message.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdMessageOrigin {
+    /// A known user, with the identifier of the user.
+    User(i64),
+    /// A user hidden by their privacy settings, with the name of the user.
+    HiddenUser(String),
+    /// A chat the message was sent on behalf of, with the identifier of the
+    /// chat.
+    Chat(i64),
+    /// A channel the message was posted in, with the identifier of the chat.
+    Channel(i64),
+}
+
+impl From<&tdlib_rs::enums::MessageOrigin> for TdMessageOrigin {
+    fn from(origin: &tdlib_rs::enums::MessageOrigin) -> Self {
+        match origin {
+            tdlib_rs::enums::MessageOrigin::User(o) => TdMessageOrigin::User(o.sender_user_id),
+            tdlib_rs::enums::MessageOrigin::HiddenUser(o) => {
+                TdMessageOrigin::HiddenUser(o.sender_name.clone())
+            }
+            tdlib_rs::enums::MessageOrigin::Chat(o) => TdMessageOrigin::Chat(o.sender_chat_id),
+            tdlib_rs::enums::MessageOrigin::Channel(o) => TdMessageOrigin::Channel(o.chat_id),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TdChatList {
     Main,
@@ -95,3 +122,64 @@ impl From<TdChatList> for ChatList {
         }
     }
 }
+
+/// The reason of a report of a chat or of messages to the moderators of
+/// Telegram.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdReportReason {
+    Spam,
+    Violence,
+    Pornography,
+    ChildAbuse,
+    Copyright,
+    Fake,
+    IllegalDrugs,
+    PersonalDetails,
+    Other,
+}
+
+impl TdReportReason {
+    /// The reasons offered, in order.
+    pub const ALL: [TdReportReason; 9] = [
+        TdReportReason::Spam,
+        TdReportReason::Violence,
+        TdReportReason::Pornography,
+        TdReportReason::ChildAbuse,
+        TdReportReason::Copyright,
+        TdReportReason::Fake,
+        TdReportReason::IllegalDrugs,
+        TdReportReason::PersonalDetails,
+        TdReportReason::Other,
+    ];
+
+    /// Get the label of the reason, shown to choose it.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TdReportReason::Spam => "Spam",
+            TdReportReason::Violence => "Violence",
+            TdReportReason::Pornography => "Pornography",
+            TdReportReason::ChildAbuse => "Child abuse",
+            TdReportReason::Copyright => "Copyright",
+            TdReportReason::Fake => "Fake account",
+            TdReportReason::IllegalDrugs => "Illegal drugs",
+            TdReportReason::PersonalDetails => "Personal details",
+            TdReportReason::Other => "Other",
+        }
+    }
+}
+
+impl From<TdReportReason> for tdlib_rs::enums::ReportReason {
+    fn from(reason: TdReportReason) -> Self {
+        match reason {
+            TdReportReason::Spam => tdlib_rs::enums::ReportReason::Spam,
+            TdReportReason::Violence => tdlib_rs::enums::ReportReason::Violence,
+            TdReportReason::Pornography => tdlib_rs::enums::ReportReason::Pornography,
+            TdReportReason::ChildAbuse => tdlib_rs::enums::ReportReason::ChildAbuse,
+            TdReportReason::Copyright => tdlib_rs::enums::ReportReason::Copyright,
+            TdReportReason::Fake => tdlib_rs::enums::ReportReason::Fake,
+            TdReportReason::IllegalDrugs => tdlib_rs::enums::ReportReason::IllegalDrugs,
+            TdReportReason::PersonalDetails => tdlib_rs::enums::ReportReason::PersonalDetails,
+            TdReportReason::Other => tdlib_rs::enums::ReportReason::Custom,
+        }
+    }
+}
@@ -1,20 +1,45 @@
-use crate::action::Action;
+use crate::action::{Action, NotifyLevel};
+use crate::api_credentials::{ApiCredentials, API_CREDENTIALS_FILE};
 use crate::event::Event;
-use crate::{app_context::AppContext, tg::ordered_chat::OrderedChat};
+use crate::setup_wizard::SetupWizard;
+use crate::utils::{
+    human_readable_size, online_symbol, open_url, parse_location, read_coordinates, tgt_dir,
+};
+use crate::{
+    app_context::AppContext,
+    component_name::ComponentName,
+    tg::{
+        ordered_chat::OrderedChat,
+        td_enums::{TdChatList, TdReportReason},
+    },
+};
 use std::collections::{BTreeSet, VecDeque};
+use std::future::Future;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 use tdlib_rs::enums::{
-    self, AuthorizationState, ChatList, InputMessageContent, InputMessageReplyTo, LogStream,
-    Messages, OptionValue, Update, User,
+    self, AuthorizationState, BlockList, CallbackQueryAnswer, CallbackQueryPayload,
+    CanSendMessageToUserResult, ChatEventAction, ChatEvents, ChatList, File, FoundChatMessages,
+    InputFile, InputMessageContent, InputMessageReplyTo, LogStream, MessageContent, MessageReplyTo,
+    MessageSender, MessageSenders, MessageThreadInfo, MessageViewers, Messages, NetworkType,
+    OptionValue, ReactionType, ReplyMarkup, SearchMessagesFilter, StickerType, StorageStatistics,
+    StorageStatisticsFast, TextEntityType, Update, User,
 };
 use tdlib_rs::functions;
-use tdlib_rs::types::{Chat, ChatPosition, InputMessageText, LogStreamFile, OptionValueBoolean};
+use tdlib_rs::types::{
+    CallbackQueryPayloadData, Chat, ChatEventLogFilters, ChatPosition, Error, InputFileId,
+    InputMessageDice, InputMessageLocation, InputMessageSticker, InputMessageText, Location,
+    LogStreamFile, Message, MessageSenderUser, OptionValueBoolean, ReactionTypeEmoji,
+};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
 
-use super::message_entry::MessageEntry;
+use super::message_entry::{DateTimeEntry, MessageEntry};
+use super::navigation_history::Position;
+use super::sticker_sets::StickerSetEntry;
 use super::td_enums::TdMessageReplyToMessage;
+use super::tg_context::{HistoryLoad, LiveLocation, TgContext};
 
 pub struct TgBackend {
     pub handle_updates: JoinHandle<()>,
@@ -27,6 +52,9 @@ pub struct TgBackend {
     pub can_quit: Arc<AtomicBool>,
     pub app_context: Arc<AppContext>,
     full_chats_list: bool,
+    /// The chat whose mentions are being viewed, the number of mentions
+    /// viewed and their total, to show the progress in the status bar.
+    mention_jump: Arc<Mutex<Option<(i64, usize, usize)>>>,
 }
 
 impl TgBackend {
@@ -39,6 +67,7 @@ impl TgBackend {
         let have_authorization = false;
         let can_quit = Arc::new(AtomicBool::new(false));
         let full_chats_list = false;
+        let mention_jump = Arc::new(Mutex::new(None));
         app_context.tg_context().set_event_tx(event_tx.clone());
         tracing::info!("Created TDLib client with client_id: {}", client_id);
 
@@ -53,6 +82,7 @@ impl TgBackend {
             can_quit,
             app_context,
             full_chats_list,
+            mention_jump,
         })
     }
 
@@ -74,6 +104,9 @@ impl TgBackend {
             tracing::error!("Failed to load chats: {e:?}");
             self.full_chats_list = true;
         }
+        // Also when loading fails, for example because there are no chats at
+        // all, so the loading screen does not stay forever.
+        self.app_context.tg_context().set_chats_loaded(true);
     }
 
     pub async fn load_all_chats(&mut self) {
@@ -82,6 +115,58 @@ impl TgBackend {
         }
     }
 
+    /// Reload the main chat list from the start and rebuild the chats index
+    /// from the chats returned by TDLib. It is an escape hatch for the chats
+    /// that go out of sync, for example when updates are missed while the
+    /// computer sleeps.
+    pub async fn refresh_chats(&mut self) {
+        self.full_chats_list = false;
+        let limit = self.app_context.tg_context().chats_index().len().max(30) as i32;
+        let chat_ids = match functions::get_chats(Some(ChatList::Main), limit, self.client_id).await
+        {
+            Ok(enums::Chats::Chats(chats)) => chats.chat_ids,
+            Err(e) => {
+                tracing::error!("Failed to refresh the chats: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+                return;
+            }
+        };
+        for chat_id in chat_ids {
+            match functions::get_chat(chat_id, self.client_id).await {
+                Ok(enums::Chat::Chat(chat)) => {
+                    self.app_context.tg_context().chats().insert(chat.id, chat);
+                }
+                Err(e) => tracing::error!("Failed to refresh the chat {chat_id}: {e:?}"),
+            }
+        }
+
+        // The index is rebuilt from the positions of the chats, so the
+        // updates received later find the positions they replace.
+        let tg_context = self.app_context.tg_context();
+        let chats = tg_context.chats();
+        let mut chats_index = tg_context.chats_index();
+        chats_index.clear();
+        for chat in chats.values() {
+            for position in &chat.positions {
+                if let enums::ChatList::Main = position.list {
+                    chats_index.insert(OrderedChat {
+                        position: position.clone(),
+                        chat_id: chat.id,
+                    });
+                }
+            }
+        }
+        drop(chats_index);
+        drop(chats);
+        self.app_context
+            .action_tx()
+            .send(Action::StatusMessage("Chat list refreshed".to_string()))
+            .unwrap();
+    }
+
     pub async fn search_chats(
         &self,
         username: String,
@@ -95,6 +180,54 @@ impl TgBackend {
         }
     }
 
+    /// Find the chat to open at launch.
+    /// An @username is resolved as a public chat, any other name must match
+    /// exactly one chat.
+    ///
+    /// # Arguments
+    /// * `chat` - The @username or the name of the chat.
+    ///
+    /// # Returns
+    /// * `Result<i64, String>` - The identifier of the chat, or the reason it
+    ///   was not found.
+    pub async fn find_chat(&self, chat: &str) -> Result<i64, String> {
+        if let Some(username) = chat.strip_prefix('@') {
+            return match functions::search_public_chat(username.to_string(), self.client_id).await {
+                Ok(tdlib_rs::enums::Chat::Chat(chat)) => Ok(chat.id),
+                Err(e) => Err(format!(
+                    "No chat found with the username {}: {}",
+                    chat, e.message
+                )),
+            };
+        }
+        match self.search_chats(chat.to_string()).await {
+            Ok(chats) if chats.chat_ids.len() == 1 => Ok(chats.chat_ids[0]),
+            Ok(chats) if chats.chat_ids.is_empty() => {
+                Err(format!("No chat found with the name: {}", chat))
+            }
+            Ok(_) => Err(format!("Multiple chats found with the name: {}", chat)),
+            Err(e) => Err(format!("Error searching for chat: {}", e.message)),
+        }
+    }
+
+    /// Check that a chat exists and load it, to open it at launch.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    ///
+    /// # Returns
+    /// * `Result<i64, String>` - The identifier of the chat, or the reason it
+    ///   was not found.
+    pub async fn find_chat_id(&self, chat_id: i64) -> Result<i64, String> {
+        match functions::get_chat(chat_id, self.client_id).await {
+            Ok(tdlib_rs::enums::Chat::Chat(chat)) => Ok(chat.id),
+            Err(e) => Err(format!(
+                "No chat found with the id {}: {}",
+                chat_id, e.message
+            )),
+        }
+    }
+
     pub async fn close(&self) {
         match functions::close(self.client_id).await {
             Ok(me) => tracing::info!("TDLib client closed: {:?}", me),
@@ -131,102 +264,1730 @@ impl TgBackend {
         let mut mut_open_chat_messages_len =
             self.app_context.tg_context().open_chat_messages().len();
         let win_size = 100;
+        let batch_size = self.app_context.app_config().history_batch_size;
+        if self.app_context.tg_context().at_oldest() {
+            return;
+        }
 
         while mut_open_chat_messages_len < start_open_chat_messages_len + win_size {
             let from_message_id = self.app_context.tg_context().from_message_id();
-            match functions::get_chat_history(
+            let thread_id = self.app_context.tg_context().open_thread_id();
+            let messages = if thread_id != 0 {
+                functions::get_message_thread_history(
+                    chat_id,
+                    thread_id,
+                    from_message_id,
+                    0,
+                    batch_size,
+                    self.client_id,
+                )
+                .await
+            } else {
+                functions::get_chat_history(
+                    chat_id,
+                    from_message_id,
+                    0,
+                    batch_size,
+                    false,
+                    self.client_id,
+                )
+                .await
+            };
+            // The user may have switched to another chat or thread while the
+            // messages were loading, they must not be shown in the new one.
+            if self.app_context.tg_context().open_chat_id() != chat_id
+                || self.app_context.tg_context().open_thread_id() != thread_id
+            {
+                tracing::info!("Chat switched, dropping the history of chat {chat_id}");
+                break;
+            }
+            match messages {
+                Ok(Messages::Messages(messages)) => {
+                    // TDLib may return fewer messages than requested, even a
+                    // single one, only an empty batch means that the
+                    // beginning of the chat is reached.
+                    if messages.messages.is_empty() {
+                        tracing::info!("No more messages to get");
+                        self.app_context.tg_context().set_at_oldest(true);
+                        break;
+                    }
+
+                    let message_flatten = messages.messages.into_iter().flatten();
+                    for message in message_flatten.clone() {
+                        let message_entry = MessageEntry::from(&message);
+                        auto_download(&self.app_context, &message_entry, self.client_id);
+                        self.app_context
+                            .tg_context()
+                            .open_chat_messages()
+                            .push(message_entry);
+                        mut_open_chat_messages_len += 1;
+                    }
+                    if let Some(message) = message_flatten.last() {
+                        self.app_context
+                            .tg_context()
+                            .set_from_message_id(message.id);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to get chat history: {e:?}");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Load the messages arrived in the open chat since its cached messages
+    /// were loaded, they are added before them. If too many messages arrived,
+    /// the cached messages are dropped and the history is loaded again from
+    /// the newest message, so there is no gap in the history.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn get_newer_messages(&mut self, chat_id: i64) {
+        let newest_id = self
+            .app_context
+            .tg_context()
+            .open_chat_messages()
+            .first()
+            .map(|m| m.id());
+        let Some(newest_id) = newest_id else {
+            self.get_chat_history(chat_id).await;
+            return;
+        };
+        let batch_size = self.app_context.app_config().history_batch_size;
+        let mut newer_messages = vec![];
+        let mut from_message_id = 0;
+        let mut reached = false;
+        while !reached && newer_messages.len() < MAX_NEWER_MESSAGES {
+            let messages = functions::get_chat_history(
                 chat_id,
                 from_message_id,
                 0,
-                50,
+                batch_size,
                 false,
                 self.client_id,
             )
+            .await;
+            if self.app_context.tg_context().open_chat_id() != chat_id
+                || self.app_context.tg_context().open_thread_id() != 0
+            {
+                tracing::info!("Chat switched, dropping the newer messages of chat {chat_id}");
+                return;
+            }
+            match messages {
+                Ok(Messages::Messages(messages)) => {
+                    if messages.messages.is_empty() {
+                        break;
+                    }
+                    for message in messages.messages.into_iter().flatten() {
+                        if message.id <= newest_id {
+                            reached = true;
+                            break;
+                        }
+                        from_message_id = message.id;
+                        let message_entry = MessageEntry::from(&message);
+                        auto_download(&self.app_context, &message_entry, self.client_id);
+                        newer_messages.push(message_entry);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to get the newer messages: {e:?}");
+                    return;
+                }
+            }
+        }
+        if !reached && !newer_messages.is_empty() {
+            tracing::info!("Too many new messages in chat {chat_id}, reloading its history");
+            self.app_context.tg_context().clear_open_chat_messages();
+            self.app_context.tg_context().set_from_message_id(0);
+            self.get_chat_history(chat_id).await;
+            return;
+        }
+        let tg_context = self.app_context.tg_context();
+        {
+            let mut open_chat_messages = tg_context.open_chat_messages();
+            // The messages may have arrived as updates while they were loading.
+            newer_messages.retain(|m| open_chat_messages.iter().all(|o| o.id() != m.id()));
+            open_chat_messages.splice(0..0, newer_messages);
+        }
+    }
+
+    #[allow(clippy::await_holding_lock)]
+    pub async fn send_message(
+        &mut self,
+        message: String,
+        chat_id: i64,
+        reply_to: Option<TdMessageReplyToMessage>,
+    ) -> Result<tdlib_rs::types::Message, tdlib_rs::types::Error> {
+        self.app_context
+            .tg_context()
+            .set_reply_message(-1, "".to_string());
+
+        self.app_context
+            .action_tx()
+            .send(Action::HideChatWindowReply)
+            .unwrap();
+
+        let thread_id = self.app_context.tg_context().open_thread_id();
+        self.send_text(chat_id, thread_id, message, reply_to.as_ref())
+            .await
+    }
+
+    /// Hand a text message to TDLib, that sends it once the connection is
+    /// ready.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `thread_id` - The identifier of the thread, 0 for the chat.
+    /// * `message` - The text of the message.
+    /// * `reply_to` - The message replied to, if any.
+    async fn send_text(
+        &self,
+        chat_id: i64,
+        thread_id: i64,
+        message: String,
+        reply_to: Option<&TdMessageReplyToMessage>,
+    ) -> Result<tdlib_rs::types::Message, tdlib_rs::types::Error> {
+        let text = InputMessageContent::InputMessageText(InputMessageText {
+            text: tdlib_rs::types::FormattedText {
+                text: message,
+                entities: vec![], // TODO: Add entities
+            },
+            link_preview_options: None,
+            clear_draft: true,
+        });
+        let reply_to: Option<InputMessageReplyTo> =
+            reply_to.map(|reply_to| InputMessageReplyTo::Message(reply_to.into()));
+        match functions::send_message(chat_id, thread_id, reply_to, None, text, self.client_id)
+            .await
+        {
+            Ok(tdlib_rs::enums::Message::Message(message)) => Ok(message),
+            Err(e) => {
+                tracing::error!("Failed to send message: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    /// Send an animated dice, Telegram chooses its value.
+    ///
+    /// # Arguments
+    /// * `emoji` - The emoji of the dice.
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn send_dice(&self, emoji: String, chat_id: i64) {
+        let dice = InputMessageContent::InputMessageDice(InputMessageDice {
+            emoji,
+            clear_draft: false,
+        });
+        let thread_id = self.app_context.tg_context().open_thread_id();
+        if let Err(e) =
+            functions::send_message(chat_id, thread_id, None, None, dice, self.client_id).await
+        {
+            tracing::error!("Failed to send dice: {e:?}");
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(e.message, NotifyLevel::Error))
+                .unwrap();
+        }
+    }
+
+    /// Send a location to a chat, in the open thread. A live location read
+    /// from the configured location file is updated from the file every
+    /// minute, in the background, while it is shared.
+    ///
+    /// # Arguments
+    /// * `text` - The location typed in the prompt, see `parse_location`.
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn send_location(&self, text: &str, chat_id: i64) {
+        let location = parse_location(text, &self.app_context.app_config().location);
+        let location = match location {
+            Ok(location) => location,
+            Err(message) => {
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(message, NotifyLevel::Warn))
+                    .unwrap();
+                return;
+            }
+        };
+        let content = InputMessageContent::InputMessageLocation(InputMessageLocation {
+            location: Location {
+                latitude: location.latitude,
+                longitude: location.longitude,
+                horizontal_accuracy: 0.0,
+            },
+            live_period: location.live_period,
+            heading: 0,
+            proximity_alert_radius: 0,
+        });
+        let thread_id = self.app_context.tg_context().open_thread_id();
+        let message =
+            match functions::send_message(chat_id, thread_id, None, None, content, self.client_id)
+                .await
+            {
+                Ok(enums::Message::Message(message)) => message,
+                Err(e) => {
+                    tracing::error!("Failed to send the location: {e:?}");
+                    self.app_context
+                        .action_tx()
+                        .send(Action::Notify(e.message, NotifyLevel::Error))
+                        .unwrap();
+                    return;
+                }
+            };
+        let Some(source) = location.source.filter(|_| location.live_period > 0) else {
+            return;
+        };
+        let live_location = LiveLocation {
+            chat_id,
+            sent_message_id: message.id,
+            message_id: message.id,
+        };
+        *self.app_context.tg_context().live_location() = Some(live_location);
+        let app_context = Arc::clone(&self.app_context);
+        let client_id = self.client_id;
+        let mut coordinates = (location.latitude, location.longitude);
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now();
+            let end = start + Duration::from_secs(location.live_period as u64);
+            let mut interval =
+                tokio::time::interval_at(start + LIVE_LOCATION_INTERVAL, LIVE_LOCATION_INTERVAL);
+            while interval.tick().await < end {
+                // Another live location shared since replaces this one.
+                let Some(live_location) = *app_context.tg_context().live_location() else {
+                    break;
+                };
+                if live_location.sent_message_id != message.id {
+                    break;
+                }
+                let new_coordinates = match read_coordinates(&source) {
+                    Ok(new_coordinates) => new_coordinates,
+                    Err(message) => {
+                        tracing::warn!("Failed to update the live location: {message}");
+                        continue;
+                    }
+                };
+                if new_coordinates == coordinates {
+                    continue;
+                }
+                let location = Location {
+                    latitude: new_coordinates.0,
+                    longitude: new_coordinates.1,
+                    horizontal_accuracy: 0.0,
+                };
+                match functions::edit_message_live_location(
+                    live_location.chat_id,
+                    live_location.message_id,
+                    Some(location),
+                    0,
+                    0,
+                    0,
+                    client_id,
+                )
+                .await
+                {
+                    Ok(_) => coordinates = new_coordinates,
+                    Err(e) => tracing::error!("Failed to update the live location: {e:?}"),
+                }
+            }
+            let tg_context = app_context.tg_context();
+            let mut live_location = tg_context.live_location();
+            if live_location.is_some_and(|l| l.sent_message_id == message.id) {
+                *live_location = None;
+            }
+        });
+    }
+
+    /// Load the sticker sets installed by the user with their stickers,
+    /// they are kept for the session. The sets are loaded in the background,
+    /// one request for each set, not to hold the other actions. When they
+    /// can not be loaded the error is kept to be shown instead.
+    pub async fn load_sticker_sets(&self) {
+        let app_context = Arc::clone(&self.app_context);
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            let sets = match functions::get_installed_sticker_sets(StickerType::Regular, client_id)
+                .await
+            {
+                Ok(enums::StickerSets::StickerSets(sets)) => sets.sets,
+                Err(e) => {
+                    tracing::error!("Failed to get the installed sticker sets: {e:?}");
+                    app_context
+                        .tg_context()
+                        .set_sticker_sets_error(Some(e.message));
+                    return;
+                }
+            };
+            // The sets only come with a few of their stickers, the full sets
+            // are loaded one by one.
+            let mut sticker_sets = Vec::with_capacity(sets.len());
+            for set in sets {
+                match functions::get_sticker_set(set.id, client_id).await {
+                    Ok(enums::StickerSet::StickerSet(sticker_set)) => {
+                        sticker_sets.push(StickerSetEntry::from(&sticker_set));
+                    }
+                    Err(e) => tracing::error!("Failed to get the sticker set {}: {e:?}", set.id),
+                }
+            }
+            app_context
+                .tg_context()
+                .set_sticker_sets(Some(sticker_sets));
+        });
+    }
+
+    /// Send a sticker to a chat, in the open thread.
+    ///
+    /// # Arguments
+    /// * `file_id` - The identifier of the file of the sticker.
+    /// * `emoji` - The emoji of the sticker.
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn send_sticker(&self, file_id: i32, emoji: String, chat_id: i64) {
+        let sticker = InputMessageContent::InputMessageSticker(InputMessageSticker {
+            sticker: InputFile::Id(InputFileId { id: file_id }),
+            thumbnail: None,
+            width: 0,
+            height: 0,
+            emoji,
+        });
+        let thread_id = self.app_context.tg_context().open_thread_id();
+        if let Err(e) =
+            functions::send_message(chat_id, thread_id, None, None, sticker, self.client_id).await
+        {
+            tracing::error!("Failed to send the sticker: {e:?}");
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(e.message, NotifyLevel::Error))
+                .unwrap();
+        }
+    }
+
+    pub async fn send_message_edited(&self, message_id: i64, message: String) {
+        let text = InputMessageContent::InputMessageText(InputMessageText {
+            text: tdlib_rs::types::FormattedText {
+                text: message,
+                entities: vec![],
+            },
+            link_preview_options: None,
+            clear_draft: true,
+        });
+        match functions::edit_message_text(
+            self.app_context.tg_context().open_chat_id(),
+            message_id,
+            text,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Message edited"),
+            Err(e) => tracing::error!("Failed to edit message: {e:?}"),
+        }
+    }
+
+    pub async fn get_callback_query_answer(&self, chat_id: i64, message_id: i64, data: String) {
+        let payload = CallbackQueryPayload::Data(CallbackQueryPayloadData { data });
+        match functions::get_callback_query_answer(chat_id, message_id, payload, self.client_id)
             .await
+        {
+            Ok(CallbackQueryAnswer::CallbackQueryAnswer(answer)) => {
+                if !answer.text.is_empty() {
+                    self.app_context
+                        .action_tx()
+                        .send(Action::StatusMessage(answer.text))
+                        .unwrap();
+                }
+                if !answer.url.is_empty() {
+                    if let Err(e) = open_url(&answer.url) {
+                        tracing::error!("Failed to open url {}: {e:?}", answer.url);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to get callback query answer: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Open the comments of a channel post in the chat window, the chat of
+    /// the discussion group becomes the open chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the channel.
+    /// * `message_id` - The identifier of the post.
+    pub async fn open_message_thread(&mut self, chat_id: i64, message_id: i64) {
+        match functions::get_message_thread(chat_id, message_id, self.client_id).await {
+            Ok(MessageThreadInfo::MessageThreadInfo(info)) => {
+                let tg_context = self.app_context.tg_context();
+                tg_context.cache_open_chat_messages();
+                tg_context.set_open_chat_user(None);
+                tg_context.set_open_chat_id(info.chat_id);
+                tg_context.set_open_thread_id(info.message_thread_id);
+                tg_context.clear_open_chat_messages();
+                tg_context.set_from_message_id(0);
+                self.get_chat_history(info.chat_id).await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to get message thread: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        format!("Comments are not available: {}", e.message),
+                        NotifyLevel::Error,
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Download the file of a message and report where it was saved.
+    /// The download runs in the background to keep the user interface
+    /// responsive with large files.
+    ///
+    /// # Arguments
+    /// * `file_id` - The identifier of the file.
+    pub async fn download_file(&self, file_id: i32) {
+        let app_context = Arc::clone(&self.app_context);
+        let client_id = self.client_id;
+        app_context
+            .action_tx()
+            .send(Action::StatusMessage("Downloading...".to_string()))
+            .unwrap();
+        tokio::spawn(async move {
+            let (message, level) =
+                match functions::download_file(file_id, 32, 0, 0, true, client_id).await {
+                    Ok(File::File(file)) => (
+                        format!("Downloaded to {}", file.local.path),
+                        NotifyLevel::Info,
+                    ),
+                    Err(e) => {
+                        tracing::error!("Failed to download file: {e:?}");
+                        (e.message, NotifyLevel::Error)
+                    }
+                };
+            app_context
+                .action_tx()
+                .send(Action::Notify(message, level))
+                .unwrap();
+        });
+    }
+
+    /// Open a file with the default application of the system, it is
+    /// downloaded first if needed, in the background.
+    ///
+    /// # Arguments
+    /// * `file_id` - The identifier of the file.
+    pub async fn open_file(&self, file_id: i32) {
+        let app_context = Arc::clone(&self.app_context);
+        let client_id = self.client_id;
+        app_context
+            .action_tx()
+            .send(Action::StatusMessage("Opening...".to_string()))
+            .unwrap();
+        tokio::spawn(async move {
+            let result = match functions::download_file(file_id, 32, 0, 0, true, client_id).await {
+                Ok(File::File(file)) => open_url(&file.local.path).map_err(|e| e.to_string()),
+                Err(e) => Err(e.message),
+            };
+            if let Err(message) = result {
+                tracing::error!("Failed to open file {file_id}: {message}");
+                app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        format!("Failed to open the file: {}", message),
+                        NotifyLevel::Error,
+                    ))
+                    .unwrap();
+            }
+        });
+    }
+
+    /// Reply privately to a group message: open the private chat with its
+    /// sender, creating it if needed, and reply there to the group message.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the sender of the message.
+    /// * `chat_id` - The identifier of the group.
+    /// * `message_id` - The identifier of the message.
+    /// * `text` - The text of the message, shown while replying.
+    pub async fn reply_privately(&self, user_id: i64, chat_id: i64, message_id: i64, text: String) {
+        let refusal =
+            match functions::can_send_message_to_user(user_id, false, self.client_id).await {
+                Ok(CanSendMessageToUserResult::Ok) => None,
+                Ok(CanSendMessageToUserResult::UserIsDeleted) => {
+                    Some("This user is deleted, they cannot be messaged".to_string())
+                }
+                Ok(CanSendMessageToUserResult::UserRestrictsNewChats) => {
+                    Some("This user only accepts new chats from their contacts".to_string())
+                }
+                Err(e) => Some(e.message),
+            };
+        if let Some(refusal) = refusal {
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(refusal, NotifyLevel::Warn))
+                .unwrap();
+            return;
+        }
+        match functions::create_private_chat(user_id, false, self.client_id).await {
+            Ok(tdlib_rs::enums::Chat::Chat(chat)) => {
+                let action_tx = self.app_context.action_tx();
+                action_tx.send(Action::OpenChat(chat.id)).unwrap();
+                action_tx
+                    .send(Action::ReplyInOtherChat(chat_id, message_id, text))
+                    .unwrap();
+            }
+            Err(e) => {
+                tracing::error!("Failed to create the private chat: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Open the private chat with a user, creating it if needed.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    pub async fn open_private_chat(&self, user_id: i64) {
+        match functions::create_private_chat(user_id, false, self.client_id).await {
+            Ok(tdlib_rs::enums::Chat::Chat(chat)) => {
+                self.app_context
+                    .action_tx()
+                    .send(Action::OpenChat(chat.id))
+                    .unwrap();
+            }
+            Err(e) => {
+                tracing::error!("Failed to create the private chat: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Add or remove an emoji reaction on a message of the open chat. When
+    /// the reaction can not be added, the reactions allowed on the message
+    /// are offered instead.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    /// * `emoji` - The emoji of the reaction.
+    /// * `chosen` - Whether the reaction is added or removed.
+    pub async fn set_reaction(&self, message_id: i64, emoji: String, chosen: bool) {
+        let chat_id = self.app_context.tg_context().open_chat_id();
+        let reaction_type = ReactionType::Emoji(ReactionTypeEmoji {
+            emoji: emoji.clone(),
+        });
+        if !chosen {
+            if let Err(e) = functions::remove_message_reaction(
+                chat_id,
+                message_id,
+                reaction_type,
+                self.client_id,
+            )
+            .await
+            {
+                tracing::error!("Failed to remove the reaction: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+            }
+            return;
+        }
+        let available = match functions::get_message_available_reactions(
+            chat_id,
+            message_id,
+            REACTIONS_ROW_SIZE,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(enums::AvailableReactions::AvailableReactions(available)) => available,
+            Err(e) => {
+                tracing::error!("Failed to get the available reactions: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+                return;
+            }
+        };
+        let mut emojis: Vec<String> = vec![];
+        for reaction in available
+            .top_reactions
+            .iter()
+            .chain(&available.recent_reactions)
+            .chain(&available.popular_reactions)
+            .filter(|reaction| !reaction.needs_premium)
+        {
+            if let ReactionType::Emoji(ReactionTypeEmoji { emoji }) = &reaction.r#type {
+                if !emojis.contains(emoji) {
+                    emojis.push(emoji.clone());
+                }
+            }
+        }
+        if emojis.contains(&emoji) {
+            if let Err(e) = functions::add_message_reaction(
+                chat_id,
+                message_id,
+                reaction_type,
+                false,
+                true,
+                self.client_id,
+            )
+            .await
+            {
+                tracing::error!("Failed to add the reaction: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+            }
+        } else if emojis.is_empty() {
+            self.app_context
+                .action_tx()
+                .send(Action::Notify(
+                    "The reactions are not allowed in this chat".to_string(),
+                    NotifyLevel::Warn,
+                ))
+                .unwrap();
+        } else {
+            self.app_context
+                .action_tx()
+                .send(Action::ChooseReaction(message_id, emoji, emojis))
+                .unwrap();
+        }
+    }
+
+    /// Remove the draft of a chat, the draft is also cleared locally so the
+    /// chat list stops showing it without waiting for the update.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn delete_chat_draft(&self, chat_id: i64) {
+        match functions::set_chat_draft_message(chat_id, 0, None, self.client_id).await {
+            Ok(_) => {
+                if let Some(chat) = self.app_context.tg_context().chats().get_mut(&chat_id) {
+                    chat.draft_message = None;
+                }
+                self.app_context
+                    .action_tx()
+                    .send(Action::StatusMessage("Draft deleted".to_string()))
+                    .unwrap();
+            }
+            Err(e) => {
+                tracing::error!("Failed to delete the draft: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Pin or unpin a chat in the chat list it is shown in.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `pinned` - Whether the chat is pinned.
+    pub async fn set_chat_pinned(&self, chat_id: i64, pinned: bool) {
+        let chat_list = self
+            .app_context
+            .tg_context()
+            .chats_index()
+            .iter()
+            .find(|ordered_chat| ordered_chat.chat_id == chat_id)
+            .map_or(ChatList::Main, |ordered_chat| {
+                ordered_chat.position.list.clone()
+            });
+        let result =
+            functions::toggle_chat_is_pinned(chat_list, chat_id, pinned, self.client_id).await;
+        self.report_chat_operation(chat_id, result, if pinned { "Pinned" } else { "Unpinned" });
+    }
+
+    /// Mute the notifications of a chat until they are unmuted, or unmute
+    /// them.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `muted` - Whether the chat is muted.
+    pub async fn set_chat_muted(&self, chat_id: i64, muted: bool) {
+        let Some(mut settings) = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .map(|chat| chat.notification_settings.clone())
+        else {
+            return;
+        };
+        settings.use_default_mute_for = false;
+        settings.mute_for = if muted { MUTE_FOREVER } else { 0 };
+        let result =
+            functions::set_chat_notification_settings(chat_id, settings, self.client_id).await;
+        self.report_chat_operation(chat_id, result, if muted { "Muted" } else { "Unmuted" });
+    }
+
+    /// Mark a chat as unread, it is read again once it is opened.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn mark_chat_as_unread(&self, chat_id: i64) {
+        let result =
+            functions::toggle_chat_is_marked_as_unread(chat_id, true, self.client_id).await;
+        self.report_chat_operation(chat_id, result, "Marked as unread");
+    }
+
+    /// Move a chat to the archive, it leaves the chat list.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn archive_chat(&self, chat_id: i64) {
+        let result = functions::add_chat_to_list(chat_id, ChatList::Archive, self.client_id).await;
+        self.report_chat_operation(chat_id, result, "Archived");
+    }
+
+    /// Delete a chat: the groups and the channels are left, the history of
+    /// the other chats is deleted for the user only, and the chat leaves the
+    /// chat list.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn delete_chat(&self, chat_id: i64) {
+        let chat_type = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .map(|chat| chat.r#type.clone());
+        let result = match chat_type {
+            Some(enums::ChatType::Supergroup(_)) => {
+                functions::leave_chat(chat_id, self.client_id).await
+            }
+            Some(enums::ChatType::BasicGroup(_)) => {
+                match functions::leave_chat(chat_id, self.client_id).await {
+                    Ok(()) => {
+                        functions::delete_chat_history(chat_id, true, false, self.client_id).await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            _ => functions::delete_chat_history(chat_id, true, false, self.client_id).await,
+        };
+        self.report_chat_operation(chat_id, result, "Deleted");
+    }
+
+    /// Report a chat, or some of its messages, to the moderators of Telegram.
+    /// The spam in a supergroup administered by the user is reported to the
+    /// supergroup instead.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `message_ids` - The identifiers of the messages, empty to report the
+    ///   chat.
+    /// * `reason` - The reason of the report.
+    pub async fn report_chat(&self, chat_id: i64, message_ids: Vec<i64>, reason: TdReportReason) {
+        let chat_type = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .map(|chat| chat.r#type.clone());
+        let administered_supergroup = match chat_type {
+            Some(enums::ChatType::Supergroup(sg)) => self
+                .app_context
+                .tg_context()
+                .supergroups()
+                .get(&sg.supergroup_id)
+                .filter(|supergroup| {
+                    matches!(
+                        supergroup.status,
+                        enums::ChatMemberStatus::Creator(_)
+                            | enums::ChatMemberStatus::Administrator(_)
+                    )
+                })
+                .map(|supergroup| supergroup.id),
+            _ => None,
+        };
+        let reported = if message_ids.is_empty() {
+            "The chat has been reported"
+        } else {
+            "The message has been reported"
+        };
+        let result = match administered_supergroup {
+            Some(supergroup_id) if reason == TdReportReason::Spam && !message_ids.is_empty() => {
+                functions::report_supergroup_spam(supergroup_id, message_ids, self.client_id).await
+            }
+            _ => {
+                functions::report_chat(
+                    chat_id,
+                    message_ids,
+                    reason.into(),
+                    String::new(),
+                    self.client_id,
+                )
+                .await
+            }
+        };
+        let action = match result {
+            Ok(()) => Action::Notify(reported.to_string(), NotifyLevel::Info),
+            Err(e) => {
+                tracing::error!("Failed to report the chat {chat_id}: {e:?}");
+                Action::Notify(
+                    format!("Failed to report: {}", e.message),
+                    NotifyLevel::Error,
+                )
+            }
+        };
+        self.app_context.action_tx().send(action).unwrap();
+    }
+
+    /// Report the result of an operation on a chat in the status bar, or as
+    /// an error notification if it failed.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `result` - The result of the operation.
+    /// * `done` - What was done to the chat, like `Archived`.
+    fn report_chat_operation(&self, chat_id: i64, result: Result<(), Error>, done: &str) {
+        let title = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .map(|chat| chat.title.clone())
+            .unwrap_or_default();
+        let action = match result {
+            Ok(()) => Action::StatusMessage(format!("{done} {title}")),
+            Err(e) => {
+                tracing::error!("Failed to change the chat {chat_id} ({done}): {e:?}");
+                Action::Notify(
+                    format!("Failed to change {title}: {}", e.message),
+                    NotifyLevel::Error,
+                )
+            }
+        };
+        self.app_context.action_tx().send(action).unwrap();
+    }
+
+    /// Fetch a user missing from the cache, for example the sender of an old
+    /// message, and add it to the cache.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    pub async fn get_user(&self, user_id: i64) {
+        match functions::get_user(user_id, self.client_id).await {
+            Ok(enums::User::User(user)) => {
+                self.app_context.tg_context().users().insert(user.id, user);
+            }
+            Err(e) => tracing::error!("Failed to get the user {user_id}: {e:?}"),
+        }
+    }
+
+    /// Mark all the messages of a chat as read without opening it, viewing
+    /// its last message reads the whole chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn read_chat(&self, chat_id: i64) {
+        let Some(unread_state) = self.unread_state(chat_id) else {
+            return;
+        };
+        Self::view_chat(chat_id, unread_state, self.client_id).await;
+    }
+
+    /// Mark all the messages of some chats as read. The chats are shown as
+    /// read right away, then they are read one after the other with a pause
+    /// in between to stay within the rate limits of Telegram.
+    ///
+    /// # Arguments
+    /// * `chat_ids` - The identifiers of the chats.
+    pub async fn read_chats(&self, chat_ids: Vec<i64>) {
+        let chats = chat_ids
+            .iter()
+            .filter_map(|chat_id| {
+                self.unread_state(*chat_id)
+                    .map(|unread_state| (*chat_id, unread_state))
+            })
+            .collect::<Vec<_>>();
+        self.app_context.tg_context().mark_chats_read(&chat_ids);
+        let app_context = Arc::clone(&self.app_context);
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            let total = chats.len();
+            let mut failed = 0;
+            for (i, (chat_id, unread_state)) in chats.into_iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(READ_CHATS_INTERVAL).await;
+                }
+                if !Self::view_chat(chat_id, unread_state, client_id).await {
+                    failed += 1;
+                }
+            }
+            let action = if failed == 0 {
+                Action::StatusMessage(format!("Marked {} chats as read", total))
+            } else {
+                Action::Notify(
+                    format!("Failed to mark {} of {} chats as read", failed, total),
+                    NotifyLevel::Error,
+                )
+            };
+            app_context.action_tx().send(action).unwrap();
+        });
+    }
+
+    /// Mark all the chats of the main chat list as read. The chats are shown
+    /// as read right away, TDLib reads them with a single request.
+    pub async fn read_chat_list(&self) {
+        let tg_context = self.app_context.tg_context();
+        let chat_ids = tg_context
+            .chats_index()
+            .iter()
+            .map(|chat| chat.chat_id)
+            .collect::<Vec<_>>();
+        tg_context.mark_chats_read(&chat_ids);
+        let action =
+            match with_flood_wait(|| functions::read_chat_list(ChatList::Main, self.client_id))
+                .await
+            {
+                Ok(()) => Action::StatusMessage("Marked all the chats as read".to_string()),
+                Err(e) => {
+                    tracing::error!("Failed to read the chat list: {e:?}");
+                    Action::Notify(
+                        format!("Failed to mark all the chats as read: {}", e.message),
+                        NotifyLevel::Error,
+                    )
+                }
+            };
+        self.app_context.action_tx().send(action).unwrap();
+    }
+
+    /// Get what is needed to read a chat, if it is known.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    ///
+    /// # Returns
+    /// * `Option<(Option<i64>, bool, bool)>` - The identifier of the last
+    ///   message of the chat, if any, whether the chat is marked as unread and
+    ///   whether it has unread mentions.
+    fn unread_state(&self, chat_id: i64) -> Option<(Option<i64>, bool, bool)> {
+        self.app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .map(|chat| {
+                (
+                    chat.last_message.as_ref().map(|m| m.id),
+                    chat.is_marked_as_unread,
+                    chat.unread_mention_count > 0,
+                )
+            })
+    }
+
+    /// View the last message of a chat, read its mentions and remove its
+    /// unread mark.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `unread_state` - The identifier of the last message of the chat,
+    ///   whether the chat is marked as unread and whether it has unread
+    ///   mentions, as given by `unread_state`.
+    /// * `client_id` - The identifier of the TDLib client.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the chat is read.
+    async fn view_chat(
+        chat_id: i64,
+        (last_message_id, is_marked_as_unread, has_unread_mentions): (Option<i64>, bool, bool),
+        client_id: i32,
+    ) -> bool {
+        let mut read = true;
+        if let Some(message_id) = last_message_id {
+            if let Err(e) = with_flood_wait(|| {
+                functions::view_messages(chat_id, vec![message_id], None, true, client_id)
+            })
+            .await
+            {
+                tracing::error!("Failed to read the chat {chat_id}: {e:?}");
+                read = false;
+            }
+        }
+        // Viewing the messages does not read the mentions, their badge would
+        // come back with the next update of the chat.
+        if has_unread_mentions {
+            if let Err(e) =
+                with_flood_wait(|| functions::read_all_chat_mentions(chat_id, client_id)).await
+            {
+                tracing::error!("Failed to read the mentions of the chat {chat_id}: {e:?}");
+                read = false;
+            }
+        }
+        if is_marked_as_unread {
+            if let Err(e) = with_flood_wait(|| {
+                functions::toggle_chat_is_marked_as_unread(chat_id, false, client_id)
+            })
+            .await
+            {
+                tracing::error!("Failed to unmark the chat {chat_id} as unread: {e:?}");
+                read = false;
+            }
+        }
+        read
+    }
+
+    /// Show the previous versions of a message of the open chat in a popup.
+    /// Telegram does not give the edit history of the messages to the
+    /// clients, the previous versions are taken from the recent actions of
+    /// the chat, that are kept for 48 hours and are available only to the
+    /// administrators of supergroups and channels.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    pub async fn get_message_edit_history(&self, message_id: i64) {
+        let chat_id = self.app_context.tg_context().open_chat_id();
+        let filters = ChatEventLogFilters {
+            message_edits: true,
+            ..Default::default()
+        };
+        let lines = match functions::get_chat_event_log(
+            chat_id,
+            String::new(),
+            0,
+            100,
+            Some(filters),
+            vec![],
+            self.client_id,
+        )
+        .await
+        {
+            Ok(ChatEvents::ChatEvents(chat_events)) => {
+                let mut versions: Vec<Message> = chat_events
+                    .events
+                    .into_iter()
+                    .filter_map(|event| match event.action {
+                        ChatEventAction::ChatEventMessageEdited(edited)
+                            if edited.new_message.id == message_id =>
+                        {
+                            Some(edited.old_message)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                // The first version has no edit date, it is the oldest.
+                versions.sort_by_key(|m| m.edit_date);
+                let timezone = self.app_context.app_config().timezone;
+                if versions.is_empty() {
+                    vec![
+                        "No previous versions in the recent actions of the chat.".to_string(),
+                        "They are kept for 48 hours.".to_string(),
+                    ]
+                } else {
+                    versions
+                        .iter()
+                        .flat_map(|m| {
+                            let date = if m.edit_date != 0 {
+                                m.edit_date
+                            } else {
+                                m.date
+                            };
+                            [
+                                DateTimeEntry::convert_time(date, timezone),
+                                MessageEntry::from(m).message_content_to_plain_text(),
+                                String::new(),
+                            ]
+                        })
+                        .collect()
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to get the edit history: {e:?}");
+                vec![
+                    "The edit history is not available for this chat.".to_string(),
+                    "Telegram shows the previous versions of the messages only in the recent \
+                     actions of supergroups and channels, to their administrators."
+                        .to_string(),
+                    String::new(),
+                    e.message,
+                ]
+            }
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::ShowPopup("Edit history".to_string(), lines))
+            .unwrap();
+    }
+
+    /// Show the chat of an invite link and ask to join it. The chats the user
+    /// is already a member of are opened.
+    ///
+    /// # Arguments
+    /// * `invite_link` - The invite link.
+    pub async fn check_chat_invite_link(&self, invite_link: String) {
+        let info =
+            match functions::check_chat_invite_link(invite_link.clone(), self.client_id).await {
+                Ok(enums::ChatInviteLinkInfo::ChatInviteLinkInfo(info)) => info,
+                Err(e) => {
+                    tracing::error!("Failed to check the invite link {invite_link}: {e:?}");
+                    self.app_context
+                        .action_tx()
+                        .send(Action::Notify(
+                            format!("The invite link is invalid or expired: {}", e.message),
+                            NotifyLevel::Error,
+                        ))
+                        .unwrap();
+                    return;
+                }
+            };
+        let is_member = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&info.chat_id)
+            .is_some_and(|chat| !chat.positions.is_empty());
+        let action_tx = self.app_context.action_tx();
+        if is_member {
+            action_tx
+                .send(Action::StatusMessage(format!(
+                    "You are already a member of {}",
+                    info.title
+                )))
+                .unwrap();
+            action_tx.send(Action::OpenChat(info.chat_id)).unwrap();
+            return;
+        }
+        let kind = match info.r#type {
+            enums::InviteLinkChatType::BasicGroup | enums::InviteLinkChatType::Supergroup => {
+                "Group"
+            }
+            enums::InviteLinkChatType::Channel => "Channel",
+        };
+        let members = if info.member_count == 1 {
+            "member"
+        } else {
+            "members"
+        };
+        let mut lines = vec![
+            info.title.clone(),
+            format!("{kind}, {} {members}", info.member_count),
+        ];
+        if info.is_scam || info.is_fake {
+            lines.push("⚠ Telegram flagged this chat as a scam or a fake".to_string());
+        }
+        if !info.description.is_empty() {
+            lines.push(String::new());
+            lines.extend(info.description.lines().map(str::to_string));
+        }
+        lines.push(String::new());
+        lines.push(if info.creates_join_request {
+            "Send a request to join, the administrators must approve it?".to_string()
+        } else {
+            format!("Join this {}?", kind.to_lowercase())
+        });
+        action_tx
+            .send(Action::ShowConfirm(
+                "Join chat".to_string(),
+                lines,
+                Box::new(Action::JoinChatByInviteLink(invite_link)),
+            ))
+            .unwrap();
+    }
+
+    /// Join the chat of an invite link and open it.
+    ///
+    /// # Arguments
+    /// * `invite_link` - The invite link.
+    pub async fn join_chat_by_invite_link(&self, invite_link: String) {
+        let result = functions::join_chat_by_invite_link(invite_link.clone(), self.client_id).await;
+        let action_tx = self.app_context.action_tx();
+        match result {
+            Ok(enums::Chat::Chat(chat)) => {
+                action_tx
+                    .send(Action::StatusMessage(format!("Joined {}", chat.title)))
+                    .unwrap();
+                action_tx.send(Action::OpenChat(chat.id)).unwrap();
+            }
+            // The chats that need an approval are joined once it is given.
+            Err(e) if e.message == "INVITE_REQUEST_SENT" => {
+                action_tx
+                    .send(Action::Notify(
+                        "The request to join was sent to the administrators".to_string(),
+                        NotifyLevel::Info,
+                    ))
+                    .unwrap();
+            }
+            Err(e) => {
+                tracing::error!("Failed to join the chat of {invite_link}: {e:?}");
+                action_tx
+                    .send(Action::Notify(
+                        format!("Failed to join the chat: {}", e.message),
+                        NotifyLevel::Error,
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Show the users who read an outgoing message of the open chat in a
+    /// popup, the most recent first. Telegram tells it only in the small
+    /// groups, for the recent messages.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    pub async fn get_message_viewers(&self, message_id: i64) {
+        let chat_id = self.app_context.tg_context().open_chat_id();
+        let lines = match functions::get_message_viewers(chat_id, message_id, self.client_id).await
+        {
+            Ok(MessageViewers::MessageViewers(message_viewers)) => {
+                let mut viewers = message_viewers.viewers;
+                viewers.sort_by_key(|viewer| -viewer.view_date);
+                for viewer in viewers.iter() {
+                    // The users must be known to show their names.
+                    let known = self
+                        .app_context
+                        .tg_context()
+                        .users()
+                        .contains_key(&viewer.user_id);
+                    if !known {
+                        self.get_user(viewer.user_id).await;
+                    }
+                }
+                if viewers.is_empty() {
+                    vec!["Nobody has read the message yet.".to_string()]
+                } else {
+                    let tg_context = self.app_context.tg_context();
+                    let timezone = self.app_context.app_config().timezone;
+                    viewers
+                        .iter()
+                        .map(|viewer| {
+                            let online = tg_context
+                                .users()
+                                .get(&viewer.user_id)
+                                .map(|user| online_symbol(&user.status))
+                                .unwrap_or_default();
+                            format!(
+                                "{}{} - {}",
+                                online,
+                                tg_context.sender_name(viewer.user_id),
+                                DateTimeEntry::convert_time(viewer.view_date, timezone)
+                            )
+                        })
+                        .collect()
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to get the message viewers: {e:?}");
+                vec![
+                    "The readers of this message are not available.".to_string(),
+                    "Telegram tells who read a message only in the groups with up to 100 \
+                     members, for a week after it was sent, and not the users who hide it."
+                        .to_string(),
+                    String::new(),
+                    e.message,
+                ]
+            }
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::ShowPopup("Read by".to_string(), lines))
+            .unwrap();
+    }
+
+    /// Load the pinned messages of a chat and show them in a list, the most
+    /// recent first.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn get_pinned_messages(&self, chat_id: i64) {
+        let pinned_messages = match functions::search_chat_messages(
+            chat_id,
+            String::new(),
+            None,
+            0,
+            0,
+            100,
+            Some(SearchMessagesFilter::Pinned),
+            0,
+            0,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(FoundChatMessages::FoundChatMessages(found)) => found
+                .messages
+                .iter()
+                .map(MessageEntry::from)
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                tracing::error!("Failed to get the pinned messages: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        format!("Failed to get the pinned messages: {}", e.message),
+                        NotifyLevel::Error,
+                    ))
+                    .unwrap();
+                return;
+            }
+        };
+        // The chat may have been closed while the messages were loading.
+        if self.app_context.tg_context().open_chat_id() != chat_id {
+            return;
+        }
+        let action = if pinned_messages.is_empty() {
+            Action::Notify(
+                "There are no pinned messages in this chat".to_string(),
+                NotifyLevel::Info,
+            )
+        } else {
+            Action::FocusComponent(ComponentName::PinnedMessages)
+        };
+        self.app_context
+            .tg_context()
+            .set_pinned_messages(pinned_messages);
+        self.app_context.action_tx().send(action).unwrap();
+    }
+
+    /// Select the oldest unread mention of a chat, loading the history around
+    /// it, and mark it as read. The progress through the mentions is shown
+    /// in the status bar.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn jump_to_next_mention(&mut self, chat_id: i64) {
+        let found = match functions::search_chat_messages(
+            chat_id,
+            String::new(),
+            None,
+            0,
+            0,
+            100,
+            Some(SearchMessagesFilter::UnreadMention),
+            0,
+            0,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(FoundChatMessages::FoundChatMessages(found)) => found,
+            Err(e) => {
+                tracing::error!("Failed to get the unread mentions: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(
+                        format!("Failed to get the unread mentions: {}", e.message),
+                        NotifyLevel::Error,
+                    ))
+                    .unwrap();
+                return;
+            }
+        };
+        // The messages are found from the newest one.
+        let Some(message_id) = found.messages.last().map(|message| message.id) else {
+            *self.mention_jump.lock().unwrap() = None;
+            if let Some(chat) = self.app_context.tg_context().chats().get_mut(&chat_id) {
+                chat.unread_mention_count = 0;
+            }
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(
+                    "No unread mentions in this chat".to_string(),
+                ))
+                .unwrap();
+            return;
+        };
+        let remaining = (found.total_count.max(0) as usize).max(found.messages.len());
+        let viewed = {
+            let mut mention_jump = self.mention_jump.lock().unwrap();
+            let viewed = match *mention_jump {
+                Some((id, viewed, _)) if id == chat_id => viewed,
+                _ => 0,
+            };
+            *mention_jump = Some((chat_id, viewed + 1, viewed + remaining));
+            viewed
+        };
+
+        let position = Position {
+            chat_id,
+            message_id: Some(message_id),
+        };
+        self.app_context
+            .tg_context()
+            .navigation_history()
+            .visit(position);
+        let app_context = Arc::clone(&self.app_context);
+        let mention_jump = Arc::clone(&self.mention_jump);
+        let client_id = self.client_id;
+        // The history is requested through the actions, so it is waited for
+        // in the background.
+        tokio::spawn(async move {
+            let tg_context = app_context.tg_context();
+            if tg_context.open_chat_id() != chat_id || tg_context.open_thread_id() != 0 {
+                tg_context.open_position(position);
+            } else {
+                // In the open chat, the history is loaded down to the mention
+                // so the messages after it stay around, instead of loading it
+                // again from the mention.
+                let load = tg_context
+                    .load_history_until(chat_id, Some(message_id), LOAD_HISTORY_TIMEOUT)
+                    .await;
+                let found = tg_context
+                    .open_chat_messages()
+                    .iter()
+                    .any(|m| m.id() == message_id);
+                if found {
+                    tg_context.set_jump_to_message_id(Some(message_id));
+                } else if load == HistoryLoad::Complete {
+                    tg_context.open_position(position);
+                } else {
+                    // The messages loaded are kept, the next jump continues
+                    // from them.
+                    *mention_jump.lock().unwrap() = Some((chat_id, viewed, viewed + remaining));
+                    app_context
+                        .action_tx()
+                        .send(Action::Notify(
+                            "The history is not loaded down to the mention yet, jump again to continue"
+                                .to_string(),
+                            NotifyLevel::Warn,
+                        ))
+                        .unwrap();
+                    return;
+                }
+            }
+            if let Err(e) =
+                functions::view_messages(chat_id, vec![message_id], None, true, client_id).await
             {
-                Ok(Messages::Messages(messages)) => {
-                    if messages.messages.is_empty() {
-                        tracing::info!("No more messages to get");
-                        break;
-                    }
+                tracing::error!("Failed to read the mention {message_id}: {e:?}");
+            } else if let Some(chat) = tg_context.chats().get_mut(&chat_id) {
+                chat.unread_mention_count = (remaining - 1) as i32;
+            }
+            app_context
+                .action_tx()
+                .send(Action::FocusComponent(ComponentName::Chat))
+                .unwrap();
+            app_context
+                .action_tx()
+                .send(Action::StatusMessage(format!(
+                    "Mention {} of {}",
+                    viewed + 1,
+                    viewed + remaining
+                )))
+                .unwrap();
+        });
+    }
 
-                    let message_flatten = messages.messages.into_iter().flatten();
-                    for message in message_flatten.clone() {
-                        self.app_context
-                            .tg_context()
-                            .open_chat_messages()
-                            .push(MessageEntry::from(&message));
-                        mut_open_chat_messages_len += 1;
-                    }
-                    if let Some(message) = message_flatten.last() {
-                        self.app_context
-                            .tg_context()
-                            .set_from_message_id(message.id);
+    /// Block or unblock a user. The chats with the user are updated by TDLib
+    /// with their new block list.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    /// * `blocked` - A boolean that indicates whether the user is blocked,
+    ///   otherwise the user is unblocked.
+    pub async fn set_user_blocked(&self, user_id: i64, blocked: bool) {
+        let sender_id = MessageSender::User(MessageSenderUser { user_id });
+        let block_list = if blocked { Some(BlockList::Main) } else { None };
+        let name = self.app_context.tg_context().sender_name(user_id);
+        let action =
+            match functions::set_message_sender_block_list(sender_id, block_list, self.client_id)
+                .await
+            {
+                Ok(()) => {
+                    let tg_context = self.app_context.tg_context();
+                    let mut blocked_users = tg_context.blocked_users();
+                    blocked_users.retain(|id| *id != user_id);
+                    if blocked {
+                        blocked_users.insert(0, user_id);
                     }
+                    Action::StatusMessage(if blocked {
+                        format!("Blocked {}", name)
+                    } else {
+                        format!("Unblocked {}", name)
+                    })
                 }
                 Err(e) => {
-                    tracing::error!("Failed to get chat history: {e:?}");
-                    break;
+                    tracing::error!("Failed to change the block list of {user_id}: {e:?}");
+                    Action::Notify(
+                        format!(
+                            "Failed to {} {}: {}",
+                            if blocked { "block" } else { "unblock" },
+                            name,
+                            e.message
+                        ),
+                        NotifyLevel::Error,
+                    )
+                }
+            };
+        self.app_context.action_tx().send(action).unwrap();
+    }
+
+    /// Load the users blocked by the user and show them in a list, the most
+    /// recently blocked first.
+    pub async fn get_blocked_users(&self) {
+        let blocked_users =
+            match functions::get_blocked_message_senders(BlockList::Main, 0, 100, self.client_id)
+                .await
+            {
+                Ok(MessageSenders::MessageSenders(senders)) => senders
+                    .senders
+                    .iter()
+                    .filter_map(|sender| match sender {
+                        MessageSender::User(user) => Some(user.user_id),
+                        MessageSender::Chat(_) => None,
+                    })
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    tracing::error!("Failed to get the blocked users: {e:?}");
+                    self.app_context
+                        .action_tx()
+                        .send(Action::Notify(
+                            format!("Failed to get the blocked users: {}", e.message),
+                            NotifyLevel::Error,
+                        ))
+                        .unwrap();
+                    return;
                 }
+            };
+        let action = if blocked_users.is_empty() {
+            Action::Notify("There are no blocked users".to_string(), NotifyLevel::Info)
+        } else {
+            Action::FocusComponent(ComponentName::BlockedUsers)
+        };
+        let tg_context = self.app_context.tg_context();
+        for user_id in blocked_users.iter() {
+            if !tg_context.users().contains_key(user_id) {
+                tg_context.request_user(*user_id);
             }
         }
+        tg_context.set_blocked_users(blocked_users);
+        self.app_context.action_tx().send(action).unwrap();
     }
 
-    #[allow(clippy::await_holding_lock)]
-    pub async fn send_message(
-        &mut self,
-        message: String,
-        chat_id: i64,
-        reply_to: Option<TdMessageReplyToMessage>,
-    ) -> Result<tdlib_rs::types::Message, tdlib_rs::types::Error> {
-        self.app_context
-            .tg_context()
-            .set_reply_message(-1, "".to_string());
-
+    /// Load the full info of a user and show its profile. The cached info,
+    /// kept up to date by TDLib, is shown when it can not be loaded.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    pub async fn get_user_profile(&self, user_id: i64) {
+        let tg_context = self.app_context.tg_context();
+        match functions::get_user_full_info(user_id, self.client_id).await {
+            Ok(enums::UserFullInfo::UserFullInfo(user_full_info)) => {
+                tg_context.users_full_info().insert(user_id, user_full_info);
+            }
+            Err(e) => {
+                tracing::error!("Failed to get the user full info: {e:?}");
+                if !tg_context.users_full_info().contains_key(&user_id) {
+                    self.app_context
+                        .action_tx()
+                        .send(Action::Notify(
+                            format!("Failed to get the user profile: {}", e.message),
+                            NotifyLevel::Error,
+                        ))
+                        .unwrap();
+                    return;
+                }
+            }
+        }
+        if !tg_context.users().contains_key(&user_id) {
+            tg_context.request_user(user_id);
+        }
+        tg_context.set_profile_user_id(Some(user_id));
         self.app_context
             .action_tx()
-            .send(Action::HideChatWindowReply)
+            .send(Action::FocusComponent(ComponentName::UserProfile))
             .unwrap();
+    }
 
-        let text = InputMessageContent::InputMessageText(InputMessageText {
-            text: tdlib_rs::types::FormattedText {
-                text: message,
-                entities: vec![], // TODO: Add entities
-            },
-            link_preview_options: None,
-            clear_draft: true,
-        });
-        let reply_to: Option<InputMessageReplyTo> =
-            reply_to.map(|reply_to| InputMessageReplyTo::Message((&reply_to).into()));
-        match functions::send_message(chat_id, 0, reply_to, None, text, self.client_id).await {
-            Ok(tdlib_rs::enums::Message::Message(message)) => Ok(message),
+    /// Force TDLib to reconnect immediately instead of waiting for the next
+    /// retry, setting the network type makes TDLib drop the current
+    /// connection attempts.
+    pub async fn reconnect(&self) {
+        match functions::set_network_type(Some(NetworkType::Other), self.client_id).await {
+            Ok(_) => tracing::info!("Reconnecting"),
             Err(e) => {
-                tracing::error!("Failed to send message: {e:?}");
-                Err(e)
+                tracing::error!("Failed to reconnect: {e:?}");
+                self.app_context
+                    .action_tx()
+                    .send(Action::Notify(e.message, NotifyLevel::Error))
+                    .unwrap();
             }
         }
     }
 
-    pub async fn send_message_edited(&self, message_id: i64, message: String) {
-        let text = InputMessageContent::InputMessageText(InputMessageText {
-            text: tdlib_rs::types::FormattedText {
-                text: message,
-                entities: vec![],
-            },
-            link_preview_options: None,
-            clear_draft: true,
-        });
-        match functions::edit_message_text(
-            self.app_context.tg_context().open_chat_id(),
-            message_id,
-            text,
+    /// Remove the cached files that exceed the storage limits of the
+    /// application configuration and report how much space was freed.
+    pub async fn optimize_storage(&self) {
+        // The large limits saturate instead of overflowing.
+        let (size, ttl) = {
+            let app_config = self.app_context.app_config();
+            (
+                app_config.storage_max_size_mb.saturating_mul(1024 * 1024),
+                app_config.storage_max_age_days.saturating_mul(24 * 60 * 60),
+            )
+        };
+        let (message, level) = match functions::optimize_storage(
+            size,
+            ttl,
+            -1,
+            -1,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            true,
+            0,
             self.client_id,
         )
         .await
         {
-            Ok(_) => tracing::info!("Message edited"),
-            Err(e) => tracing::error!("Failed to edit message: {e:?}"),
-        }
+            Ok(StorageStatistics::StorageStatistics(deleted)) => {
+                tracing::info!("Storage optimized: {:?}", deleted);
+                (
+                    format!(
+                        "Freed {} ({} files)",
+                        human_readable_size(deleted.size),
+                        deleted.count
+                    ),
+                    NotifyLevel::Info,
+                )
+            }
+            Err(e) => {
+                tracing::error!("Failed to optimize storage: {e:?}");
+                (e.message, NotifyLevel::Error)
+            }
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::Notify(message, level))
+            .unwrap();
+    }
+
+    /// Report the approximate size of the cached files and of the database.
+    pub async fn show_storage_statistics(&self) {
+        let (message, level) = match functions::get_storage_statistics_fast(self.client_id).await {
+            Ok(StorageStatisticsFast::StorageStatisticsFast(statistics)) => (
+                format!(
+                    "Cache: {} ({} files), database: {}",
+                    human_readable_size(statistics.files_size),
+                    statistics.file_count,
+                    human_readable_size(statistics.database_size)
+                ),
+                NotifyLevel::Info,
+            ),
+            Err(e) => {
+                tracing::error!("Failed to get storage statistics: {e:?}");
+                (e.message, NotifyLevel::Error)
+            }
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::Notify(message, level))
+            .unwrap();
     }
 
     pub async fn delete_messages(&self, chat_id: i64, message_ids: Vec<i64>, revoke: bool) {
@@ -301,58 +2062,204 @@ impl TgBackend {
         }
     }
 
-    #[allow(clippy::await_holding_lock)]
-    pub async fn handle_authorization_state(&mut self) {
-        tracing::info!("Handling authorization state");
-        let telegram_config = self.app_context.telegram_config();
-        let api_id: i32 = {
-            if !self
-                .app_context
-                .app_config()
-                .take_api_id_from_telegram_config
-            {
-                // `env!("API_ID").parse().unwrap()` generates a compile time error
-                if let Ok(api_id) = std::env::var("API_ID") {
-                    api_id.parse().unwrap()
-                } else {
-                    tracing::error!("API_ID not found in environment");
-                    "-1".parse().unwrap() // This will throw the tdlib-rs error message
+    /// Get the credentials to access the Telegram API. They are taken from
+    /// the configuration or from the environment, as chosen in `app.toml`,
+    /// otherwise from the ones saved by the setup wizard. If there are none,
+    /// the wizard asks for them and saves them for the next runs.
+    ///
+    /// # Arguments
+    /// * `wizard` - The setup wizard.
+    ///
+    /// # Returns
+    /// * `ApiCredentials` - The API credentials.
+    fn api_credentials(&self, wizard: &mut SetupWizard) -> ApiCredentials {
+        let api_id = if self
+            .app_context
+            .app_config()
+            .take_api_id_from_telegram_config
+        {
+            Some(self.app_context.telegram_config().api_id.clone())
+        } else {
+            std::env::var("API_ID").ok()
+        };
+        let api_hash = if self
+            .app_context
+            .app_config()
+            .take_api_hash_from_telegram_config
+        {
+            Some(self.app_context.telegram_config().api_hash.clone())
+        } else {
+            std::env::var("API_HASH").ok()
+        };
+        if let Some(credentials) = api_id
+            .zip(api_hash)
+            .and_then(|(api_id, api_hash)| ApiCredentials::parse(&api_id, &api_hash))
+        {
+            return credentials;
+        }
+        tracing::warn!("No valid API_ID and API_HASH in the configuration or in the environment");
+
+        let path = match tgt_dir() {
+            Ok(dir) => Some(dir.join(API_CREDENTIALS_FILE)),
+            Err(e) => {
+                tracing::error!("Failed to get the tgt directory: {e}");
+                None
+            }
+        };
+        if let Some(credentials) = path.as_deref().and_then(ApiCredentials::load) {
+            return credentials;
+        }
+
+        wizard.notice(
+            "Create an application at https://my.telegram.org, in \"API development \
+             tools\", to get its API ID and API hash.",
+        );
+        let api_id = loop {
+            match wizard.ask("API ID", false).parse::<i32>() {
+                Ok(api_id) if api_id > 0 => break api_id,
+                _ => wizard.error("The API ID is a positive number"),
+            }
+        };
+        let api_hash = loop {
+            let api_hash = wizard.ask("API hash", true).to_lowercase();
+            if ApiCredentials::is_valid_hash(&api_hash) {
+                break api_hash;
+            }
+            wizard.error("The API hash is made of 32 hex digits");
+        };
+        let credentials = ApiCredentials { api_id, api_hash };
+        match path.map(|path| credentials.save(&path)) {
+            Some(Ok(())) => wizard.step_done("API credentials saved"),
+            Some(Err(e)) => {
+                tracing::error!("Failed to save the API credentials: {e}");
+                wizard.step_done("API credentials, they could not be saved");
+            }
+            None => wizard.step_done("API credentials, they could not be saved"),
+        }
+        credentials
+    }
+
+    /// Ask for the two-step verification password until it is right, the
+    /// hint is shown after a wrong password. If a recovery email address is
+    /// set up, an empty password sends a recovery code to it instead, TDLib
+    /// then asks for the password again with the recovery in progress.
+    ///
+    /// # Arguments
+    /// * `wizard` - The setup wizard.
+    /// * `state` - The state sent by TDLib.
+    async fn ask_password(
+        &self,
+        wizard: &mut SetupWizard,
+        state: &tdlib_rs::types::AuthorizationStateWaitPassword,
+    ) {
+        let hint = if state.password_hint.is_empty() {
+            String::new()
+        } else {
+            format!(" (hint: {})", state.password_hint)
+        };
+        wizard.notice(format!("Two-step verification is enabled{hint}"));
+        let question = if state.has_recovery_email_address {
+            "Password, or nothing to recover it by email"
+        } else {
+            "Password"
+        };
+        loop {
+            let password = wizard.ask(question, true);
+            if password.is_empty() && state.has_recovery_email_address {
+                match functions::request_authentication_password_recovery(self.client_id).await {
+                    Ok(_) => break,
+                    Err(e) => wizard.error(e.message),
                 }
-            } else {
-                telegram_config.api_id.parse().unwrap()
+                continue;
+            }
+            match functions::check_authentication_password(password, self.client_id).await {
+                Ok(_) => {
+                    wizard.step_done("Password");
+                    break;
+                }
+                Err(e) if e.message == "PASSWORD_HASH_INVALID" => {
+                    wizard.error(format!("Wrong password{hint}"));
+                }
+                Err(e) => wizard.error(e.message),
+            }
+        }
+    }
+
+    /// Ask for the recovery code sent by email and for a new password, an
+    /// empty new password removes the two-step verification.
+    ///
+    /// # Arguments
+    /// * `wizard` - The setup wizard.
+    /// * `state` - The state sent by TDLib.
+    async fn recover_password(
+        &self,
+        wizard: &mut SetupWizard,
+        state: &tdlib_rs::types::AuthorizationStateWaitPassword,
+    ) {
+        wizard.notice(format!(
+            "A recovery code was sent to {}",
+            state.recovery_email_address_pattern
+        ));
+        let code = loop {
+            let code = wizard.ask("Recovery code", false);
+            match functions::check_authentication_password_recovery_code(
+                code.clone(),
+                self.client_id,
+            )
+            .await
+            {
+                Ok(_) => break code,
+                Err(e) => wizard.error(e.message),
             }
         };
-        let api_hash: String = {
-            if !self
-                .app_context
-                .app_config()
-                .take_api_hash_from_telegram_config
+        wizard.step_done("Recovery code");
+        loop {
+            let new_password = wizard.ask("New password, or nothing to disable it", true);
+            let new_hint = if new_password.is_empty() {
+                String::new()
+            } else {
+                wizard.ask("New password hint, optional", false)
+            };
+            match functions::recover_authentication_password(
+                code.clone(),
+                new_password,
+                new_hint,
+                self.client_id,
+            )
+            .await
             {
-                // `env!("API_HASH").into()` generates a compile time error
-                if let Ok(api_hash) = std::env::var("API_HASH") {
-                    api_hash
-                } else {
-                    tracing::error!("API_HASH not found in environment");
-                    "".into() // This will throw the tdlib-rs error message
+                Ok(_) => {
+                    wizard.step_done("Password recovered");
+                    break;
                 }
-            } else {
-                telegram_config.api_hash.clone()
+                Err(e) => wizard.error(e.message),
             }
-        };
-        let database_dir = telegram_config.database_dir.clone();
+        }
+    }
+
+    /// Follow the authorization states sent by TDLib until the user is
+    /// logged in. Whatever TDLib needs from the user is asked in the setup
+    /// wizard, that is shown only when something must be asked.
+    pub async fn handle_authorization_state(&mut self) {
+        tracing::info!("Handling authorization state");
+        let mut wizard = SetupWizard::default();
+        let ApiCredentials { api_id, api_hash } = self.api_credentials(&mut wizard);
+        let telegram_config = self.app_context.telegram_config().clone();
+        let database_dir = telegram_config.database_dir;
+        let files_dir = telegram_config.files_dir;
         let use_file_database = telegram_config.use_file_database;
         let use_chat_info_database = telegram_config.use_chat_info_database;
         let use_message_database = telegram_config.use_message_database;
-        let system_language_code = telegram_config.system_language_code.clone();
-        let device_model = telegram_config.device_model.clone();
+        let system_language_code = telegram_config.system_language_code;
+        let device_model = telegram_config.device_model;
 
         while let Some(state) = self.auth_rx.recv().await {
             match state {
-                AuthorizationState::WaitTdlibParameters => {
+                AuthorizationState::WaitTdlibParameters => loop {
                     let response = functions::set_tdlib_parameters(
                         false,
                         database_dir.clone(),
-                        String::new(),
+                        files_dir.clone(),
                         String::new(),
                         use_file_database,
                         use_chat_info_database,
@@ -368,42 +2275,72 @@ impl TgBackend {
                     )
                     .await;
 
-                    if let Err(error) = response {
-                        println!("{}", error.message);
+                    match response {
+                        Ok(_) => break,
+                        Err(error) if is_database_locked(&error.message) => {
+                            tracing::error!("Database in use: {}", error.message);
+                            wizard.error(format!(
+                                "Database in use by another instance: {}. Close the other tgt \
+                                 instance or Telegram client using this directory, or change \
+                                 `database_dir` in telegram.toml.",
+                                database_dir
+                            ));
+                            let answer =
+                                wizard.ask("Press Enter to retry or type \"q\" to quit", false);
+                            if answer == "q" {
+                                wizard.leave();
+                                std::process::exit(1);
+                            }
+                        }
+                        // The wizard would drop the error with its screen,
+                        // it is printed once the screen is left.
+                        Err(error) => {
+                            wizard.leave();
+                            println!("{}", error.message);
+                            break;
+                        }
                     }
-                }
+                },
                 AuthorizationState::WaitPhoneNumber => loop {
                     let phone_number =
-                        ask_user("Enter your phone number (include the country calling code):");
+                        wizard.ask("Phone number, with the country calling code", false);
                     let response = functions::set_authentication_phone_number(
-                        phone_number,
+                        phone_number.clone(),
                         None,
                         self.client_id,
                     )
                     .await;
                     match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
+                        Ok(_) => {
+                            wizard.step_done(format!("Phone number {}", phone_number));
+                            break;
+                        }
+                        Err(e) => wizard.error(e.message),
                     }
                 },
                 AuthorizationState::WaitOtherDeviceConfirmation(x) => {
-                    println!(
+                    wizard.notice(format!(
                         "Please confirm this login link on another device: {}",
                         x.link
-                    );
+                    ));
                 }
-                AuthorizationState::WaitEmailAddress(_x) => {
-                    let email_address = ask_user("Please enter email address: ");
-                    let response =
-                        functions::set_authentication_email_address(email_address, self.client_id)
-                            .await;
+                AuthorizationState::WaitEmailAddress(_x) => loop {
+                    let email_address = wizard.ask("Email address", false);
+                    let response = functions::set_authentication_email_address(
+                        email_address.clone(),
+                        self.client_id,
+                    )
+                    .await;
                     match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
+                        Ok(_) => {
+                            wizard.step_done(format!("Email address {}", email_address));
+                            break;
+                        }
+                        Err(e) => wizard.error(e.message),
                     }
-                }
-                AuthorizationState::WaitEmailCode(_x) => {
-                    let code = ask_user("Please enter email authentication code: ");
+                },
+                AuthorizationState::WaitEmailCode(_x) => loop {
+                    let code = wizard.ask("Email authentication code", false);
                     let response = functions::check_authentication_email_code(
                         enums::EmailAddressAuthentication::Code(
                             tdlib_rs::types::EmailAddressAuthenticationCode { code },
@@ -412,35 +2349,50 @@ impl TgBackend {
                     )
                     .await;
                     match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
+                        Ok(_) => {
+                            wizard.step_done("Email authentication code");
+                            break;
+                        }
+                        Err(e) => wizard.error(e.message),
                     }
-                }
+                },
                 AuthorizationState::WaitCode(_x) => loop {
                     // x contains info about verification code
-                    let code = ask_user("Enter the verification code:");
+                    let code = wizard.ask("Verification code", false);
                     let response = functions::check_authentication_code(code, self.client_id).await;
                     match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
+                        Ok(_) => {
+                            wizard.step_done("Verification code");
+                            break;
+                        }
+                        Err(e) => wizard.error(e.message),
                     }
                 },
-                AuthorizationState::WaitRegistration(_x) => {
+                AuthorizationState::WaitRegistration(_x) => loop {
                     // x useless but contains the TOS if we want to show it
-                    let first_name = ask_user("Please enter your first name: ");
-                    let last_name = ask_user("Please enter your last name: ");
-                    functions::register_user(first_name, last_name, false, self.client_id)
-                        .await
-                        .unwrap();
-                }
-                AuthorizationState::WaitPassword(_x) => {
-                    let password = ask_user("Please enter password: ");
-                    functions::check_authentication_password(password, self.client_id)
-                        .await
-                        .unwrap();
+                    let first_name = wizard.ask("First name", false);
+                    let last_name = wizard.ask("Last name", false);
+                    let response =
+                        functions::register_user(first_name, last_name, false, self.client_id)
+                            .await;
+                    match response {
+                        Ok(_) => {
+                            wizard.step_done("Registration");
+                            break;
+                        }
+                        Err(e) => wizard.error(e.message),
+                    }
+                },
+                AuthorizationState::WaitPassword(x) => {
+                    if x.recovery_email_address_pattern.is_empty() {
+                        self.ask_password(&mut wizard, &x).await;
+                    } else {
+                        self.recover_password(&mut wizard, &x).await;
+                    }
                 }
                 AuthorizationState::Ready => {
                     // Maybe block all until this state is reached
+                    wizard.leave();
                     self.have_authorization = true;
                     break;
                 }
@@ -524,6 +2476,8 @@ impl TgBackend {
         let auth_tx = self.auth_tx.clone();
         let can_quit = self.can_quit.clone();
         let tg_context = self.app_context.tg_context();
+        let app_context = Arc::clone(&self.app_context);
+        let client_id = self.client_id;
 
         self.handle_updates = tokio::spawn(async move {
             tracing::info!("Starting handling updates from TDLib");
@@ -535,6 +2489,44 @@ impl TgBackend {
                     match update.clone() {
                         Update::MessageSendSucceeded(update_message) => {
                             tracing::info!("Message sent: {:?}", update_message);
+                            if let Some(live_location) = tg_context
+                                .live_location()
+                                .as_mut()
+                                .filter(|l| l.message_id == update_message.old_message_id)
+                            {
+                                live_location.message_id = update_message.message.id;
+                            }
+                            // The pending message is replaced by the sent one.
+                            tg_context.update_chat_messages(
+                                update_message.message.chat_id,
+                                |messages| {
+                                    for m in messages.iter_mut() {
+                                        if m.id() == update_message.old_message_id {
+                                            *m = MessageEntry::from(&update_message.message);
+                                        }
+                                    }
+                                },
+                            );
+                        }
+                        Update::MessageSendFailed(update_message) => {
+                            tracing::error!("Failed to send message: {:?}", update_message);
+                            if tg_context.open_chat_id() == update_message.message.chat_id {
+                                for m in tg_context.open_chat_messages().iter_mut() {
+                                    if m.id() == update_message.old_message_id {
+                                        *m = MessageEntry::from(&update_message.message);
+                                    }
+                                }
+                            }
+                            app_context
+                                .action_tx()
+                                .send(Action::Notify(
+                                    format!(
+                                        "Failed to send message: {}",
+                                        update_message.error.message
+                                    ),
+                                    NotifyLevel::Error,
+                                ))
+                                .unwrap();
                         }
                         Update::MessageSendAcknowledged(update_message) => {
                             tg_context.set_last_acknowledged_message_id(update_message.message_id);
@@ -542,10 +2534,14 @@ impl TgBackend {
                         Update::AuthorizationState(update) => {
                             auth_tx.send(update.authorization_state).unwrap();
                         }
+                        Update::ConnectionState(update_connection_state) => {
+                            tracing::info!("Connection state: {:?}", update_connection_state.state);
+                            tg_context.set_connection_state(update_connection_state.state);
+                        }
                         Update::User(update_user) => {
-                            tg_context
-                                .users()
-                                .insert(update_user.user.id, update_user.user);
+                            let user_id = update_user.user.id;
+                            tg_context.users().insert(user_id, update_user.user);
+                            tg_context.refresh_open_chat_user(user_id);
                         }
                         Update::UserStatus(update_user) => {
                             match tg_context.users().get_mut(&update_user.user_id) {
@@ -554,6 +2550,7 @@ impl TgBackend {
                                 }
                                 None => update_dequeue.push_back(update),
                             }
+                            tg_context.refresh_open_chat_user(update_user.user_id);
                         }
                         Update::BasicGroup(update_basic_group) => {
                             tg_context.basic_groups().insert(
@@ -792,6 +2789,21 @@ impl TgBackend {
                                 None => update_dequeue.push_back(update),
                             }
                         }
+                        Update::ChatFolders(update_chat_folders) => {
+                            tg_context.unread_counts().set_folders(
+                                update_chat_folders
+                                    .chat_folders
+                                    .into_iter()
+                                    .map(|folder| (folder.id, folder.title))
+                                    .collect(),
+                            );
+                        }
+                        Update::UnreadMessageCount(update_unread) => {
+                            tg_context.unread_counts().set_count(
+                                TdChatList::from(update_unread.chat_list),
+                                update_unread.unread_count,
+                            );
+                        }
                         Update::ChatIsMarkedAsUnread(update_chat) => {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
                                 Some(chat) => {
@@ -818,7 +2830,7 @@ impl TgBackend {
                         Update::MessageUnreadReactions(update_chat) => {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
                                 Some(chat) => {
-                                    chat.unread_mention_count = update_chat.unread_reaction_count;
+                                    chat.unread_reaction_count = update_chat.unread_reaction_count;
                                 }
                                 None => update_dequeue.push_back(update),
                             }
@@ -846,41 +2858,108 @@ impl TgBackend {
                             // in real time the message displayed
                             let message = update_new_message.message;
                             let chat_id = message.chat_id;
-                            if tg_context.open_chat_id() == chat_id {
-                                tg_context
-                                    .open_chat_messages()
-                                    .insert(0, MessageEntry::from(&message));
+                            let has_sound = app_context
+                                .app_config()
+                                .notification_sounds
+                                .contains_key(&chat_id);
+                            let is_muted = tg_context.chats().get(&chat_id).is_some_and(|chat| {
+                                tg_context.is_muted(&chat.notification_settings, &chat.r#type)
+                            });
+                            if has_sound && !is_muted && !message.is_outgoing {
+                                app_context
+                                    .action_tx()
+                                    .send(Action::NotificationSound(chat_id))
+                                    .unwrap();
+                            }
+                            let thread_id = tg_context.open_thread_id();
+                            if tg_context.open_chat_id() == chat_id
+                                && (thread_id == 0 || message.message_thread_id == thread_id)
+                            {
+                                let message_entry = MessageEntry::from(&message);
+                                auto_download(&app_context, &message_entry, client_id);
+                                if let Some(ReplyMarkup::ForceReply(force_reply)) =
+                                    &message.reply_markup
+                                {
+                                    if !message.is_outgoing
+                                        && (!force_reply.is_personal
+                                            || force_reply_targets_me(&tg_context, &message))
+                                    {
+                                        app_context
+                                            .action_tx()
+                                            .send(Action::ForceReply(
+                                                message.id,
+                                                message_entry.message_content_to_string(),
+                                                force_reply.input_field_placeholder.clone(),
+                                            ))
+                                            .unwrap();
+                                    }
+                                }
+                                tg_context.open_chat_messages().insert(0, message_entry);
+                            } else if tg_context.open_chat_id() != chat_id {
+                                // The cached chats are kept up to date.
+                                tg_context.update_chat_messages(chat_id, |messages| {
+                                    messages.insert(0, MessageEntry::from(&message))
+                                });
+                                tg_context.trim_cached_chat_messages(chat_id);
                             }
                         }
-                        Update::MessageEdited(_) => {}
+                        Update::MessageEdited(message) => {
+                            tg_context.update_chat_messages(message.chat_id, |messages| {
+                                for m in messages.iter_mut() {
+                                    if m.id() == message.message_id {
+                                        m.set_inline_keyboard(message.reply_markup.as_ref());
+                                        m.set_edit_date(message.edit_date);
+                                    }
+                                }
+                            });
+                        }
+                        Update::MessageInteractionInfo(update) => {
+                            tg_context.update_chat_messages(update.chat_id, |messages| {
+                                for m in messages.iter_mut() {
+                                    if m.id() == update.message_id {
+                                        m.set_reactions(update.interaction_info.as_ref());
+                                    }
+                                }
+                            });
+                        }
                         Update::MessageContent(message) => {
-                            if tg_context.open_chat_id() == message.chat_id {
-                                for m in tg_context.open_chat_messages().iter_mut() {
+                            tg_context.update_chat_messages(message.chat_id, |messages| {
+                                for m in messages.iter_mut() {
                                     if m.id() == message.message_id {
                                         m.set_message_content(&message.new_content);
-                                        m.set_is_edited(true);
                                     }
                                 }
-                            }
+                            });
                         }
                         Update::DeleteMessages(update_delete_messages) => {
-                            if tg_context.open_chat_id() == update_delete_messages.chat_id {
-                                let mut i = 0;
-                                while i < tg_context.open_chat_messages().len() {
-                                    if update_delete_messages
-                                        .message_ids
-                                        .contains(&tg_context.open_chat_messages()[i].id())
-                                    {
-                                        tg_context.open_chat_messages().remove(i);
-                                    } else {
-                                        i += 1;
+                            tg_context.update_chat_messages(
+                                update_delete_messages.chat_id,
+                                |messages| {
+                                    messages.retain(|m| {
+                                        !update_delete_messages.message_ids.contains(&m.id())
+                                    })
+                                },
+                            );
+                        }
+                        Update::ScopeNotificationSettings(update_scope) => {
+                            tg_context.set_scope_mute_for(
+                                &update_scope.scope,
+                                update_scope.notification_settings.mute_for,
+                            );
+                        }
+                        Update::Option(option) => {
+                            if let OptionValue::Integer(value) = option.value {
+                                match option.name.as_str() {
+                                    "revoke_time_limit" => {
+                                        tg_context.set_revoke_time_limit(false, value.value)
+                                    }
+                                    "revoke_pm_time_limit" => {
+                                        tg_context.set_revoke_time_limit(true, value.value)
                                     }
+                                    _ => {}
                                 }
                             }
                         }
-                        // Update::Option(option) => {
-                        //     tracing::info!("{:?}", option);
-                        // }
                         // _ => eprintln!("[HANDLE UPDATE]: {update:?}"),
                         _ => {
                             // tracing::info!("Unhandled update: {:?}", update);
@@ -892,9 +2971,132 @@ impl TgBackend {
     }
 }
 
-fn ask_user(string: &str) -> String {
-    println!("{}", string);
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input.trim().to_string()
+/// Download the file of a message in the background if the automatic
+/// download policy of its kind of media allows it and it is not larger than
+/// `auto_download_max_size_mb`.
+///
+/// # Arguments
+/// * `app_context` - The application context.
+/// * `message` - The message that may contain a file.
+/// * `client_id` - The identifier of the TDLib client.
+fn auto_download(app_context: &AppContext, message: &MessageEntry, client_id: i32) {
+    let Some(media_file) = message.media_file() else {
+        return;
+    };
+    if media_file.is_downloaded
+        || !app_context
+            .app_config()
+            .should_auto_download(media_file.kind, media_file.size)
+    {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(e) =
+            functions::download_file(media_file.file_id, 1, 0, 0, false, client_id).await
+        {
+            tracing::error!("Failed to download file automatically: {e:?}");
+        }
+    });
+}
+
+/// Check if a personal force reply targets the current user, that is if the
+/// message replies to a message of the user or mentions them.
+///
+/// # Arguments
+/// * `tg_context` - The Telegram context.
+/// * `message` - The message with the force reply markup.
+fn force_reply_targets_me(tg_context: &TgContext, message: &Message) -> bool {
+    let me = tg_context.me();
+    if let Some(MessageReplyTo::Message(reply_to)) = &message.reply_to {
+        if tg_context
+            .open_chat_messages()
+            .iter()
+            .any(|m| m.id() == reply_to.message_id && m.sender_id() == me)
+        {
+            return true;
+        }
+    }
+    match &message.content {
+        MessageContent::MessageText(text) => text.text.entities.iter().any(|entity| {
+            matches!(&entity.r#type, TextEntityType::MentionName(mention) if mention.user_id == me)
+        }),
+        _ => false,
+    }
+}
+
+/// Check if an error returned by TDLib is caused by the database being
+/// locked by another instance of the application.
+fn is_database_locked(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("can't lock file") || message.contains("already in use")
+}
+
+/// The number of reactions per row asked to TDLib, the reactions offered
+/// when the quick reaction is not allowed are chosen with the digits.
+const REACTIONS_ROW_SIZE: i32 = 9;
+
+/// The time a chat is muted for when it is muted from the chat list, TDLib
+/// mutes the chat forever for the durations longer than a year.
+const MUTE_FOREVER: i32 = i32::MAX;
+
+/// The pause between two chats read in a row.
+const READ_CHATS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The maximum number of messages loaded when a cached chat is opened again,
+/// if more messages arrived since, its history is loaded again.
+const MAX_NEWER_MESSAGES: usize = 100;
+
+/// The maximum time waited for the history of a chat to be loaded until a
+/// message.
+const LOAD_HISTORY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The interval between two reads of the location file while a live
+/// location is shared.
+const LIVE_LOCATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Send a request to TDLib, and send it once more after the time asked by
+/// Telegram if it is refused because of too many requests.
+///
+/// # Arguments
+/// * `request` - The function that sends the request.
+///
+/// # Returns
+/// * `Result<(), Error>` - The result of the last request sent.
+async fn with_flood_wait<F, Fut>(mut request: F) -> Result<(), Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), Error>>,
+{
+    match request().await {
+        Err(e) => match flood_wait(&e) {
+            Some(wait) => {
+                tracing::warn!("Too many requests, retrying in {}s", wait.as_secs());
+                tokio::time::sleep(wait).await;
+                request().await
+            }
+            None => Err(e),
+        },
+        result => result,
+    }
+}
+
+/// Get the time to wait asked by Telegram in an error, the message of the
+/// error ends with the number of seconds, like "Too Many Requests: retry
+/// after 10".
+///
+/// # Arguments
+/// * `error` - The error returned by TDLib.
+///
+/// # Returns
+/// * `Option<Duration>` - The time to wait, if the error is a flood wait.
+fn flood_wait(error: &Error) -> Option<Duration> {
+    if error.code != 429 {
+        return None;
+    }
+    error
+        .message
+        .rsplit(' ')
+        .next()
+        .and_then(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs)
 }
@@ -1,26 +1,108 @@
+use super::message_cache::{CachedChat, MessageCache};
 use super::message_entry::MessageEntry;
+use super::message_tags::MessageTags;
+use super::navigation_history::{NavigationHistory, Position};
+use super::sticker_sets::StickerSetEntry;
+use super::unread_counts::UnreadCounts;
 use crate::tg::message_entry::DateTimeEntry;
 use crate::{
-    app_error::AppError, components::chat_list_window::ChatListEntry, event::Event,
+    app_error::AppError,
+    components::chat_list_window::{ChatCategory, ChatListEntry},
+    configs::custom::app_custom::Timezone,
+    event::Event,
     tg::ordered_chat::OrderedChat,
 };
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicUsize, Ordering};
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
 };
 use tdlib_rs::{
-    enums::ChatType,
+    enums::{ChatType, ConnectionState, InputMessageContent, NotificationSettingsScope, UserType},
     types::{
-        BasicGroup, BasicGroupFullInfo, Chat, SecretChat, Supergroup, SupergroupFullInfo, User,
-        UserFullInfo,
+        BasicGroup, BasicGroupFullInfo, Chat, ChatNotificationSettings, SecretChat, Supergroup,
+        SupergroupFullInfo, User, UserFullInfo, Usernames,
     },
 };
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{
+    mpsc::{error::SendError, UnboundedSender},
+    Notify,
+};
+
+/// The name shown for a user that is not known yet, or has no name.
+pub const UNKNOWN_USER_NAME: &str = "Unknown";
+/// The name shown for a user whose account has been deleted.
+pub const DELETED_ACCOUNT_NAME: &str = "Deleted Account";
+
+/// Build a regular user for the tests, with only a name and a status.
+///
+/// # Arguments
+/// * `id` - The identifier of the user.
+/// * `first_name` - The first name of the user.
+/// * `status` - The status of the user.
+#[cfg(test)]
+pub fn test_user(id: i64, first_name: &str, status: tdlib_rs::enums::UserStatus) -> User {
+    User {
+        id,
+        first_name: first_name.to_string(),
+        last_name: String::new(),
+        usernames: None,
+        phone_number: String::new(),
+        status,
+        profile_photo: None,
+        accent_color_id: 0,
+        background_custom_emoji_id: 0,
+        profile_accent_color_id: 0,
+        profile_background_custom_emoji_id: 0,
+        emoji_status: None,
+        is_contact: false,
+        is_mutual_contact: false,
+        is_close_friend: false,
+        is_verified: false,
+        is_premium: false,
+        is_support: false,
+        restriction_reason: String::new(),
+        is_scam: false,
+        is_fake: false,
+        has_active_stories: false,
+        has_unread_active_stories: false,
+        restricts_new_chats: false,
+        have_access: true,
+        r#type: UserType::Regular,
+        language_code: String::new(),
+        added_to_attachment_menu: false,
+    }
+}
+
+/// A live location updated from the configured location file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiveLocation {
+    /// The identifier of the chat.
+    pub chat_id: i64,
+    /// The temporary identifier of the message given when it was sent,
+    /// it identifies the live location.
+    pub sent_message_id: i64,
+    /// The identifier of the message, the temporary one until it is sent.
+    pub message_id: i64,
+}
+
+/// The outcome of loading the history of a chat until a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryLoad {
+    /// The history is loaded until the message, or the beginning of the chat.
+    Complete,
+    /// The loading stopped before, because the timeout elapsed, a request
+    /// failed or the chat was switched. The messages loaded are kept.
+    Partial,
+}
 
 #[derive(Debug, Default)]
 pub struct TgContext {
     users: Mutex<HashMap<i64, User>>,
+    /// The users missing from `users` that have already been requested, so
+    /// they are requested only once.
+    requested_users: Mutex<HashSet<i64>>,
     basic_groups: Mutex<HashMap<i64, BasicGroup>>,
     supergroups: Mutex<HashMap<i64, Supergroup>>,
     secret_chats: Mutex<HashMap<i32, SecretChat>>,
@@ -35,21 +117,98 @@ pub struct TgContext {
 
     event_tx: Mutex<Option<UnboundedSender<Event>>>,
     me: AtomicI64,
+    /// The time in seconds the messages of the groups can be deleted for
+    /// everyone after they are sent, as told by TDLib, 0 until it is known.
+    revoke_time_limit: AtomicI64,
+    /// The time in seconds the messages of the private chats can be deleted
+    /// for everyone after they are sent, 0 until it is known.
+    revoke_pm_time_limit: AtomicI64,
+    /// The default time in seconds the private chats are muted for, used by
+    /// the chats that keep the default notification settings.
+    private_chats_mute_for: AtomicI32,
+    /// The default time in seconds the groups are muted for.
+    group_chats_mute_for: AtomicI32,
+    /// The default time in seconds the channels are muted for.
+    channel_chats_mute_for: AtomicI32,
     open_chat_id: AtomicI64,
+    /// The identifier of the message thread open in the chat window, 0 if
+    /// the whole chat is open.
+    open_thread_id: AtomicI64,
     // This is the chat messages that are currently being displayed
     // in the chat window.
     open_chat_messages: Mutex<Vec<MessageEntry>>,
+    /// The messages of the chats visited recently, shown again at once when
+    /// they are reopened.
+    message_cache: Mutex<MessageCache>,
+    /// The maximum number of messages of a chat kept in memory, 0 for no
+    /// limit.
+    max_chat_messages: AtomicUsize,
     open_chat_user: Mutex<Option<User>>,
 
     last_acknowledged_message_id: AtomicI64,
 
     /// The message id from which to start loading the chat history.
     from_message_id: AtomicI64,
+    /// Indicates whether the oldest message of the open chat is loaded, then
+    /// there is no older history to request.
+    at_oldest: AtomicBool,
+    /// Indicates whether the older messages of the open chat are being
+    /// loaded, they are not requested again until they arrive.
+    loading_history: AtomicBool,
+    /// Notified each time a batch of the history of the open chat arrived.
+    history_loaded: Notify,
+    /// Indicates whether more chats are being loaded, they are not requested
+    /// again until they arrive.
+    loading_chats: AtomicBool,
 
     /// reply message id
     reply_message_id: AtomicI64,
     /// reply message text
     reply_message_text: Mutex<String>,
+
+    /// The last connection state received from TDLib.
+    connection_state: Mutex<Option<ConnectionState>>,
+    /// The instant the connection entered its current state.
+    connection_state_since: Mutex<Option<Instant>>,
+    /// Indicates whether the first batch of chats has been loaded.
+    chats_loaded: AtomicBool,
+
+    /// Indicates whether the users are named by their username instead of
+    /// their full name.
+    show_usernames: AtomicBool,
+
+    /// The messages tagged locally by the user.
+    message_tags: Mutex<MessageTags>,
+    /// The number of unread messages of each chat list, shown in the title of
+    /// the terminal.
+    unread_counts: Mutex<UnreadCounts>,
+    /// The history of the chats and the messages visited by the user.
+    navigation_history: Mutex<NavigationHistory>,
+    /// The identifier of the message to select once the open chat is loaded,
+    /// used to jump to a message from outside the chat window.
+    jump_to_message_id: Mutex<Option<i64>>,
+    /// The pinned messages of the open chat, the most recent first, loaded
+    /// when the user asks for the list of the pinned messages.
+    pinned_messages: Mutex<Vec<MessageEntry>>,
+    /// The identifiers of the users blocked by the user, loaded when the user
+    /// asks for the list of the blocked users.
+    blocked_users: Mutex<Vec<i64>>,
+    /// The user whose profile is shown, set once its full info is loaded.
+    profile_user_id: Mutex<Option<i64>>,
+    /// The sticker sets installed by the user, with their stickers, loaded
+    /// the first time the stickers are shown. `None` until they are loaded.
+    sticker_sets: Mutex<Option<Vec<StickerSetEntry>>>,
+    /// The error of the last loading of the sticker sets, if it failed.
+    sticker_sets_error: Mutex<Option<String>>,
+    /// The live location being updated from the configured location file,
+    /// only the last one shared is updated.
+    live_location: Mutex<Option<LiveLocation>>,
+    /// The chat to mark as read and when, it was opened while
+    /// `read_on_open_delay_ms` delays the read.
+    pending_read: Mutex<Option<(i64, Instant)>>,
+    /// Indicates whether the Telegram backend has stopped, then the events
+    /// sent to it are dropped.
+    backend_stopped: AtomicBool,
 }
 
 impl TgContext {
@@ -83,24 +242,99 @@ impl TgContext {
     pub fn open_chat_id(&self) -> i64 {
         self.open_chat_id.load(Ordering::Relaxed)
     }
+    pub fn open_thread_id(&self) -> i64 {
+        self.open_thread_id.load(Ordering::Relaxed)
+    }
     pub fn open_chat_messages(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
         self.open_chat_messages.lock().unwrap()
     }
-    pub fn event_tx(&self) -> MutexGuard<'_, Option<UnboundedSender<Event>>> {
+    pub fn message_cache(&self) -> MutexGuard<'_, MessageCache> {
+        self.message_cache.lock().unwrap()
+    }
+    fn event_tx(&self) -> MutexGuard<'_, Option<UnboundedSender<Event>>> {
         self.event_tx.lock().unwrap()
     }
     pub fn me(&self) -> i64 {
         self.me.load(Ordering::Relaxed)
     }
+    /// Get the time in seconds the messages can be deleted for everyone after
+    /// they are sent, 0 if it is unknown.
+    ///
+    /// # Arguments
+    /// * `private` - Whether the messages are the ones of a private chat.
+    pub fn revoke_time_limit(&self, private: bool) -> i64 {
+        if private {
+            self.revoke_pm_time_limit.load(Ordering::Relaxed)
+        } else {
+            self.revoke_time_limit.load(Ordering::Relaxed)
+        }
+    }
+    /// Get whether a chat is muted, the chats that keep the default
+    /// notification settings follow the ones of their scope.
+    ///
+    /// # Arguments
+    /// * `settings` - The notification settings of the chat.
+    /// * `chat_type` - The type of the chat, it gives the scope.
+    pub fn is_muted(&self, settings: &ChatNotificationSettings, chat_type: &ChatType) -> bool {
+        let mute_for = if !settings.use_default_mute_for {
+            settings.mute_for
+        } else {
+            match chat_type {
+                ChatType::Private(_) | ChatType::Secret(_) => &self.private_chats_mute_for,
+                ChatType::Supergroup(supergroup) if supergroup.is_channel => {
+                    &self.channel_chats_mute_for
+                }
+                ChatType::BasicGroup(_) | ChatType::Supergroup(_) => &self.group_chats_mute_for,
+            }
+            .load(Ordering::Relaxed)
+        };
+        mute_for > 0
+    }
     pub fn from_message_id(&self) -> i64 {
         self.from_message_id.load(Ordering::Relaxed)
     }
+    pub fn show_usernames(&self) -> bool {
+        self.show_usernames.load(Ordering::Relaxed)
+    }
+    pub fn at_oldest(&self) -> bool {
+        self.at_oldest.load(Ordering::Relaxed)
+    }
+    pub fn max_chat_messages(&self) -> usize {
+        self.max_chat_messages.load(Ordering::Relaxed)
+    }
     pub fn open_chat_user(&self) -> MutexGuard<'_, Option<User>> {
         self.open_chat_user.lock().unwrap()
     }
+    pub fn message_tags(&self) -> MutexGuard<'_, MessageTags> {
+        self.message_tags.lock().unwrap()
+    }
+    pub fn unread_counts(&self) -> MutexGuard<'_, UnreadCounts> {
+        self.unread_counts.lock().unwrap()
+    }
+    pub fn pinned_messages(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
+        self.pinned_messages.lock().unwrap()
+    }
+    pub fn blocked_users(&self) -> MutexGuard<'_, Vec<i64>> {
+        self.blocked_users.lock().unwrap()
+    }
+    pub fn profile_user_id(&self) -> Option<i64> {
+        *self.profile_user_id.lock().unwrap()
+    }
+    pub fn sticker_sets(&self) -> MutexGuard<'_, Option<Vec<StickerSetEntry>>> {
+        self.sticker_sets.lock().unwrap()
+    }
+    pub fn live_location(&self) -> MutexGuard<'_, Option<LiveLocation>> {
+        self.live_location.lock().unwrap()
+    }
+    pub fn navigation_history(&self) -> MutexGuard<'_, NavigationHistory> {
+        self.navigation_history.lock().unwrap()
+    }
     pub fn last_acknowledged_message_id(&self) -> i64 {
         self.last_acknowledged_message_id.load(Ordering::Relaxed)
     }
+    pub fn chats_loaded(&self) -> bool {
+        self.chats_loaded.load(Ordering::Relaxed)
+    }
 
     pub fn set_open_chat_user(&self, user: Option<User>) {
         *self.open_chat_user() = user;
@@ -110,8 +344,183 @@ impl TgContext {
         self.open_chat_id.store(chat_id, Ordering::Relaxed);
     }
 
+    pub fn set_open_thread_id(&self, thread_id: i64) {
+        self.open_thread_id.store(thread_id, Ordering::Relaxed);
+    }
+
+    /// Clear the messages of the open chat, it is done when another chat is
+    /// opened so the history of the new chat is not at its beginning yet.
     pub fn clear_open_chat_messages(&self) {
         *self.open_chat_messages() = Vec::new();
+        self.set_at_oldest(false);
+    }
+
+    /// Keep the messages of the open chat in the cache before another chat
+    /// is opened, only the main history of the chat is kept.
+    pub fn cache_open_chat_messages(&self) {
+        let chat_id = self.open_chat_id();
+        if chat_id == 0 || self.open_thread_id() != 0 {
+            return;
+        }
+        let messages = self.open_chat_messages().clone();
+        self.message_cache().store(CachedChat {
+            chat_id,
+            messages,
+            at_oldest: self.at_oldest(),
+        });
+        self.message_cache().trim(chat_id, self.max_chat_messages());
+    }
+
+    /// Show the cached messages of a chat that is opened again, the history
+    /// continues from the oldest of them.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the chat was cached, otherwise the messages are
+    ///   cleared and the history must be loaded from the newest message.
+    pub fn restore_cached_chat_messages(&self, chat_id: i64) -> bool {
+        let cached = self.message_cache().take(chat_id);
+        let Some(cached) = cached else {
+            self.clear_open_chat_messages();
+            self.set_from_message_id(0);
+            return false;
+        };
+        let oldest_id = cached.messages.last().map(|m| m.id()).unwrap_or_default();
+        *self.open_chat_messages() = cached.messages;
+        self.set_at_oldest(cached.at_oldest);
+        self.set_from_message_id(oldest_id);
+        true
+    }
+
+    /// Apply a change to the loaded messages of a chat, the open chat or a
+    /// chat of the cache, so the cached chats stay up to date.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `change` - The change to apply to the messages, the newest first.
+    pub fn update_chat_messages(&self, chat_id: i64, change: impl FnOnce(&mut Vec<MessageEntry>)) {
+        if self.open_chat_id() == chat_id {
+            change(&mut self.open_chat_messages());
+        } else if let Some(messages) = self.message_cache().messages_mut(chat_id) {
+            change(messages);
+        }
+    }
+
+    /// Drop the oldest messages of a cached chat beyond `max_chat_messages`.
+    /// The open chat is never trimmed, the messages scrolled up to stay
+    /// loaded until it is left, then it is trimmed when it is cached.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub fn trim_cached_chat_messages(&self, chat_id: i64) {
+        if self.open_chat_id() == chat_id {
+            return;
+        }
+        self.message_cache().trim(chat_id, self.max_chat_messages());
+    }
+
+    pub fn set_show_usernames(&self, show_usernames: bool) {
+        self.show_usernames.store(show_usernames, Ordering::Relaxed);
+    }
+
+    pub fn set_max_chat_messages(&self, max_chat_messages: usize) {
+        self.max_chat_messages
+            .store(max_chat_messages, Ordering::Relaxed);
+    }
+
+    pub fn set_at_oldest(&self, at_oldest: bool) {
+        self.at_oldest.store(at_oldest, Ordering::Relaxed);
+    }
+
+    /// Mark the older messages of the open chat as being loaded.
+    ///
+    /// # Returns
+    /// * `bool` - `true` if they were not being loaded already, then they
+    ///   have to be requested.
+    pub fn start_loading_history(&self) -> bool {
+        !self.loading_history.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn set_loading_history(&self, loading_history: bool) {
+        self.loading_history
+            .store(loading_history, Ordering::Release);
+    }
+
+    /// Mark the older messages of the open chat as loaded, the callers of
+    /// `load_history_until` waiting for them are woken up.
+    pub fn end_loading_history(&self) {
+        self.set_loading_history(false);
+        self.history_loaded.notify_waiters();
+    }
+
+    /// Load the history of the open chat until a message, or until the
+    /// beginning of the chat, and wait for it. The batches are requested one
+    /// after the other with `GetChatHistory`, so it must not be awaited from
+    /// the loop handling the actions. The loading is bounded by a timeout,
+    /// the messages loaded before it elapses are kept in the chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `message_id` - The identifier of the oldest message to load, `None`
+    ///   to load the whole history.
+    /// * `timeout` - The maximum time waited for the history.
+    pub async fn load_history_until(
+        &self,
+        chat_id: i64,
+        message_id: Option<i64>,
+        timeout: Duration,
+    ) -> HistoryLoad {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if self.open_chat_id() != chat_id {
+                return HistoryLoad::Partial;
+            }
+            let loaded = self.open_chat_messages().len();
+            let reached = message_id.is_some_and(|message_id| {
+                self.open_chat_messages()
+                    .last()
+                    .is_some_and(|m| m.id() <= message_id)
+            });
+            if reached || self.at_oldest() {
+                return HistoryLoad::Complete;
+            }
+            // Registered before the request, so its response is not missed.
+            let history_loaded = self.history_loaded.notified();
+            tokio::pin!(history_loaded);
+            history_loaded.as_mut().enable();
+            // A batch already being loaded is waited for instead.
+            if self.start_loading_history() && !self.try_send_event(Event::GetChatHistory) {
+                self.set_loading_history(false);
+                return HistoryLoad::Partial;
+            }
+            if tokio::time::timeout_at(deadline, history_loaded)
+                .await
+                .is_err()
+            {
+                tracing::info!("Timed out loading the history of chat {chat_id}");
+                return HistoryLoad::Partial;
+            }
+            // Nothing loaded without reaching the beginning of the chat means
+            // that the request failed.
+            if self.open_chat_messages().len() == loaded && !self.at_oldest() {
+                return HistoryLoad::Partial;
+            }
+        }
+    }
+
+    /// Mark more chats as being loaded.
+    ///
+    /// # Returns
+    /// * `bool` - `true` if they were not being loaded already, then they
+    ///   have to be requested.
+    pub fn start_loading_chats(&self) -> bool {
+        !self.loading_chats.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn set_loading_chats(&self, loading_chats: bool) {
+        self.loading_chats.store(loading_chats, Ordering::Release);
     }
 
     pub fn set_from_message_id(&self, from_message_id: i64) {
@@ -123,15 +532,215 @@ impl TgContext {
         self.me.store(me, Ordering::Relaxed);
     }
 
+    pub fn set_revoke_time_limit(&self, private: bool, seconds: i64) {
+        if private {
+            self.revoke_pm_time_limit.store(seconds, Ordering::Relaxed);
+        } else {
+            self.revoke_time_limit.store(seconds, Ordering::Relaxed);
+        }
+    }
+
+    pub fn set_scope_mute_for(&self, scope: &NotificationSettingsScope, mute_for: i32) {
+        match scope {
+            NotificationSettingsScope::PrivateChats => &self.private_chats_mute_for,
+            NotificationSettingsScope::GroupChats => &self.group_chats_mute_for,
+            NotificationSettingsScope::ChannelChats => &self.channel_chats_mute_for,
+        }
+        .store(mute_for, Ordering::Relaxed);
+    }
+
     pub fn set_last_acknowledged_message_id(&self, message_id: i64) {
         self.last_acknowledged_message_id
             .store(message_id, Ordering::Relaxed);
     }
 
+    pub fn set_chats_loaded(&self, chats_loaded: bool) {
+        self.chats_loaded.store(chats_loaded, Ordering::Relaxed);
+    }
+
+    /// Check whether the startup is complete, that is the first chats are
+    /// loaded. Until then the loading screen is shown instead of an empty chat
+    /// list. The chats cached by TDLib are loaded while offline too, the
+    /// connection state is then shown in the status bar.
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the startup is complete.
+    pub fn startup_complete(&self) -> bool {
+        self.chats_loaded() || !self.chats_index().is_empty()
+    }
+
+    pub fn set_connection_state(&self, state: ConnectionState) {
+        let mut connection_state = self.connection_state.lock().unwrap();
+        if connection_state.as_ref() != Some(&state) {
+            *self.connection_state_since.lock().unwrap() = Some(Instant::now());
+        }
+        *connection_state = Some(state);
+    }
+
+    /// Get a description of the connection state to show to the user, with
+    /// how long it has lasted. TDLib does not tell when it retries, so no
+    /// retry is announced.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The description, or `None` if the connection is
+    ///   ready.
+    pub fn connection_status(&self) -> Option<String> {
+        let state = match self.connection_state.lock().unwrap().as_ref() {
+            Some(ConnectionState::WaitingForNetwork) => "Waiting for network",
+            Some(ConnectionState::ConnectingToProxy) => "Connecting to proxy",
+            Some(ConnectionState::Connecting) => "Connecting",
+            Some(ConnectionState::Updating) => "Updating",
+            Some(ConnectionState::Ready) | None => return None,
+        };
+        match *self.connection_state_since.lock().unwrap() {
+            Some(since) if since.elapsed().as_secs() > 0 => {
+                Some(format!("{} (for {}s)", state, since.elapsed().as_secs()))
+            }
+            _ => Some(state.to_string()),
+        }
+    }
+
+    pub fn set_message_tags(&self, message_tags: MessageTags) {
+        *self.message_tags() = message_tags;
+    }
+
+    pub fn set_jump_to_message_id(&self, message_id: Option<i64>) {
+        *self.jump_to_message_id.lock().unwrap() = message_id;
+    }
+
+    pub fn take_jump_to_message_id(&self) -> Option<i64> {
+        self.jump_to_message_id.lock().unwrap().take()
+    }
+
+    pub fn set_pinned_messages(&self, pinned_messages: Vec<MessageEntry>) {
+        *self.pinned_messages() = pinned_messages;
+    }
+
+    /// Mark some chats as read before TDLib confirms it, their unread
+    /// messages and mentions are cleared.
+    ///
+    /// # Arguments
+    /// * `chat_ids` - The identifiers of the chats.
+    pub fn mark_chats_read(&self, chat_ids: &[i64]) {
+        let mut chats = self.chats();
+        for chat_id in chat_ids {
+            if let Some(chat) = chats.get_mut(chat_id) {
+                chat.unread_count = 0;
+                chat.unread_mention_count = 0;
+                chat.is_marked_as_unread = false;
+            }
+        }
+    }
+
+    pub fn set_blocked_users(&self, blocked_users: Vec<i64>) {
+        *self.blocked_users() = blocked_users;
+    }
+
+    pub fn set_profile_user_id(&self, user_id: Option<i64>) {
+        *self.profile_user_id.lock().unwrap() = user_id;
+    }
+
+    pub fn set_sticker_sets(&self, sticker_sets: Option<Vec<StickerSetEntry>>) {
+        *self.sticker_sets() = sticker_sets;
+    }
+
+    pub fn sticker_sets_error(&self) -> Option<String> {
+        self.sticker_sets_error.lock().unwrap().clone()
+    }
+
+    pub fn set_sticker_sets_error(&self, error: Option<String>) {
+        *self.sticker_sets_error.lock().unwrap() = error;
+    }
+
+    pub fn set_pending_read(&self, pending_read: Option<(i64, Instant)>) {
+        *self.pending_read.lock().unwrap() = pending_read;
+    }
+
+    /// Take the pending read of the open chat once it is due, or at once
+    /// when `now` is `None`. The pending read of a chat that was left is
+    /// dropped, the chat is not read.
+    ///
+    /// # Arguments
+    /// * `now` - The current instant, `None` to read the chat at once.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the open chat must be marked as read.
+    pub fn take_pending_read(&self, now: Option<Instant>) -> bool {
+        let mut pending_read = self.pending_read.lock().unwrap();
+        let Some((chat_id, read_at)) = *pending_read else {
+            return false;
+        };
+        if chat_id != self.open_chat_id() {
+            *pending_read = None;
+            return false;
+        }
+        if now.is_some_and(|now| now < read_at) {
+            return false;
+        }
+        *pending_read = None;
+        true
+    }
+
+    /// Open the chat of a position and load its history starting from the
+    /// message of the position, that is selected once loaded. Without a
+    /// message, the history is loaded from the newest message.
+    /// The position is not recorded in the navigation history.
+    ///
+    /// # Arguments
+    /// * `position` - The position to open.
+    pub fn open_position(&self, position: Position) {
+        self.cache_open_chat_messages();
+        self.set_open_chat_user(self.user_of_private_chat(position.chat_id));
+        self.set_open_chat_id(position.chat_id);
+        self.set_open_thread_id(0);
+        self.set_jump_to_message_id(position.message_id);
+        if let Some(message_id) = position.message_id {
+            self.clear_open_chat_messages();
+            self.set_from_message_id(message_id);
+            self.try_send_event(Event::GetChatHistory);
+        } else if self.restore_cached_chat_messages(position.chat_id) {
+            self.try_send_event(Event::GetNewerMessages);
+        } else {
+            self.try_send_event(Event::GetChatHistory);
+        }
+    }
+
     pub fn set_event_tx(&self, event_tx: UnboundedSender<Event>) {
         *self.event_tx() = Some(event_tx);
     }
 
+    pub fn backend_stopped(&self) -> bool {
+        self.backend_stopped.load(Ordering::Relaxed)
+    }
+
+    /// Send an event to the Telegram backend, it is the only way the
+    /// components and the context send events, so none of them panics if the
+    /// backend has stopped.
+    /// The event is dropped if the backend is not started yet. If the
+    /// backend has stopped, the failure is logged once and the next events
+    /// are dropped too.
+    ///
+    /// # Arguments
+    /// * `event` - The event to send.
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the event has been sent.
+    pub fn try_send_event(&self, event: Event) -> bool {
+        let mut event_tx = self.event_tx();
+        let Some(tx) = event_tx.as_ref() else {
+            return false;
+        };
+        match tx.send(event) {
+            Ok(()) => true,
+            Err(SendError(event)) => {
+                tracing::error!("The Telegram backend has stopped, dropping {event}");
+                *event_tx = None;
+                self.backend_stopped.store(true, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+
     // This is used to know if a message is being replied to.
     pub fn set_reply_message(&self, message_id: i64, text: String) {
         self.reply_message_id.store(message_id, Ordering::Relaxed);
@@ -143,7 +752,11 @@ impl TgContext {
         open_chat_messages.retain(|message| message.id() != message_id);
     }
 
-    pub fn open_chat_user_status(&self) -> String {
+    /// Get the status of the user of the open private chat.
+    ///
+    /// # Arguments
+    /// * `timezone` - The timezone of the time the user was last seen.
+    pub fn open_chat_user_status(&self, timezone: Timezone) -> String {
         if let Some(user) = self.open_chat_user().as_ref() {
             return match &user.status {
                 tdlib_rs::enums::UserStatus::Empty => "Empty".to_string(),
@@ -151,7 +764,7 @@ impl TgContext {
                 tdlib_rs::enums::UserStatus::Offline(offline) => {
                     format!(
                         "Last seen {}",
-                        DateTimeEntry::convert_time(offline.was_online)
+                        DateTimeEntry::convert_time(offline.was_online, timezone)
                     )
                 }
                 tdlib_rs::enums::UserStatus::Recently(_) => "Last seen recently ".to_string(),
@@ -173,6 +786,17 @@ impl TgContext {
         unread_messages
     }
 
+    /// Get the total number of unread messages of the chats in the main chat
+    /// list.
+    pub fn total_unread_count(&self) -> i32 {
+        let chats = self.chats();
+        self.chats_index()
+            .iter()
+            .filter_map(|ord_chat| chats.get(&ord_chat.chat_id))
+            .map(|chat| chat.unread_count)
+            .sum()
+    }
+
     pub fn last_read_inbox_message_id(&self) -> i64 {
         let opened_chat = self.chats().get(&self.open_chat_id()).cloned();
         if let Some(opened_chat) = opened_chat {
@@ -189,7 +813,28 @@ impl TgContext {
         -1
     }
 
+    /// Get the @username of a user, if the users are named by their
+    /// username and the user has one.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    pub fn username_of_user(&self, user_id: i64) -> Option<String> {
+        if !self.show_usernames() {
+            return None;
+        }
+        self.users()
+            .get(&user_id)?
+            .usernames
+            .as_ref()?
+            .active_usernames
+            .first()
+            .map(|username| format!("@{}", username))
+    }
+
     pub fn try_name_from_chats_or_users(&self, user_id: i64) -> Option<String> {
+        if let Some(username) = self.username_of_user(user_id) {
+            return Some(username);
+        }
         if self.name_from_chats(user_id).is_some() {
             return self.name_from_chats(user_id);
         }
@@ -208,6 +853,90 @@ impl TgContext {
         None
     }
 
+    /// Get the name of the sender of a message, it is never empty.
+    /// When the user is not known yet it is requested, and it is named
+    /// `Unknown` until it arrives.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    pub fn sender_name(&self, user_id: i64) -> String {
+        if let Some(name) = self
+            .try_name_from_chats_or_users(user_id)
+            .filter(|name| !name.is_empty())
+        {
+            return name;
+        }
+        let name = self.users().get(&user_id).map(|user| {
+            if let UserType::Deleted = user.r#type {
+                DELETED_ACCOUNT_NAME.to_string()
+            } else {
+                format!("{} {}", user.first_name, user.last_name)
+                    .trim()
+                    .to_string()
+            }
+        });
+        match name {
+            Some(name) if !name.is_empty() => name,
+            Some(_) => UNKNOWN_USER_NAME.to_string(),
+            None => {
+                self.request_user(user_id);
+                UNKNOWN_USER_NAME.to_string()
+            }
+        }
+    }
+
+    /// Request a user missing from the cache, the user is added to the cache
+    /// by the update that follows. Each user is requested only once.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    pub fn request_user(&self, user_id: i64) {
+        if user_id <= 0 || !self.requested_users.lock().unwrap().insert(user_id) {
+            return;
+        }
+        self.try_send_event(Event::GetUser(user_id));
+    }
+
+    /// Refresh the user of the open chat, cached when the chat is opened,
+    /// after TDLib sends a newer version of a user. The header of the chat
+    /// then shows the current name and status of the user.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the updated user.
+    pub fn refresh_open_chat_user(&self, user_id: i64) {
+        let is_open = matches!(
+            self.chats().get(&self.open_chat_id()).map(|chat| &chat.r#type),
+            Some(ChatType::Private(p)) if p.user_id == user_id
+        );
+        if is_open {
+            let user = self.users().get(&user_id).cloned();
+            self.set_open_chat_user(user);
+        }
+    }
+
+    /// Get the user of a private chat, it is shown in the header of the chat
+    /// window.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub fn user_of_private_chat(&self, chat_id: i64) -> Option<User> {
+        match self.chats().get(&chat_id).map(|chat| &chat.r#type) {
+            Some(ChatType::Private(p)) => self.users().get(&p.user_id).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Check whether a chat is in a block list, then its user or its
+    /// supergroup cannot write to the user.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub fn is_chat_blocked(&self, chat_id: i64) -> bool {
+        self.chats()
+            .get(&chat_id)
+            .is_some_and(|chat| chat.block_list.is_some())
+    }
+
     pub fn name_from_chats(&self, chat_id: i64) -> Option<String> {
         if let Some(chat) = self.chats().get(&chat_id) {
             return Some(chat.title.clone());
@@ -224,10 +953,17 @@ impl TgContext {
     }
 
     pub fn name_of_open_chat_id(&self) -> Option<String> {
-        if let Some(chat) = self.chats().get(&self.open_chat_id()) {
-            return Some(chat.title.clone());
+        let chat = self.chats().get(&self.open_chat_id()).cloned()?;
+        match &chat.r#type {
+            ChatType::Private(p) => {
+                if let Some(username) = self.username_of_user(p.user_id) {
+                    return Some(username);
+                }
+            }
+            ChatType::Secret(_) => return Some(format!("🔒 {}", chat.title)),
+            _ => {}
         }
-        None
+        Some(chat.title)
     }
 
     pub fn get_chats_index(&self) -> Result<Option<Vec<ChatListEntry>>, AppError<Event>> {
@@ -238,33 +974,70 @@ impl TgContext {
             let mut chat_list_item = ChatListEntry::new();
             chat_list_item.set_chat_id(ord_chat.chat_id);
             if let Some(chat) = chats.get(&ord_chat.chat_id) {
-                chat_list_item.set_is_marked_as_unread(chat.unread_count > 0);
+                chat_list_item
+                    .set_is_marked_as_unread(chat.unread_count > 0 || chat.is_marked_as_unread);
+                chat_list_item.set_is_pinned(ord_chat.position.is_pinned);
+                chat_list_item
+                    .set_is_muted(self.is_muted(&chat.notification_settings, &chat.r#type));
                 chat_list_item.set_chat_name(chat.title.clone());
                 chat_list_item.set_last_read_inbox_message_id(chat.last_read_inbox_message_id);
                 chat_list_item.set_last_read_outbox_message_id(chat.last_read_outbox_message_id);
                 chat_list_item.set_unread_count(chat.unread_count);
+                chat_list_item.set_unread_mention_count(chat.unread_mention_count);
+                chat_list_item.set_is_blocked(chat.block_list.is_some());
                 if let Some(chat_message) = &chat.last_message {
                     chat_list_item.set_last_message(MessageEntry::from(chat_message));
                 }
+                if let Some(draft) = &chat.draft_message {
+                    if let InputMessageContent::InputMessageText(text) = &draft.input_message_text {
+                        chat_list_item.set_draft(text.text.text.clone());
+                    }
+                }
                 match &chat.r#type {
                     ChatType::Private(p) => {
                         if let Some(user) = self.users().get(&p.user_id) {
+                            if let UserType::Bot(_) = user.r#type {
+                                chat_list_item.set_category(ChatCategory::Bot);
+                            }
+                            if let Some(username) = active_username(&user.usernames) {
+                                chat_list_item.set_username(username);
+                            }
                             chat_list_item.set_user(user.clone());
+                        } else {
+                            self.request_user(p.user_id);
+                        }
+                        if let Some(username) = self.username_of_user(p.user_id) {
+                            chat_list_item.set_chat_name(username);
                         }
                     }
                     ChatType::BasicGroup(bg) => {
+                        chat_list_item.set_category(ChatCategory::Group);
                         if let Some(_basic_group) = self.basic_groups().get(&bg.basic_group_id) {
                             chat_list_item.set_chat_name(chat.title.clone());
                         }
                     }
                     ChatType::Supergroup(sg) => {
-                        if let Some(_supergroup) = self.supergroups().get(&sg.supergroup_id) {
+                        chat_list_item.set_category(if sg.is_channel {
+                            ChatCategory::Channel
+                        } else {
+                            ChatCategory::Group
+                        });
+                        if let Some(supergroup) = self.supergroups().get(&sg.supergroup_id) {
                             chat_list_item.set_chat_name(chat.title.clone());
+                            if let Some(username) = active_username(&supergroup.usernames) {
+                                chat_list_item.set_username(username);
+                            }
                         }
                     }
                     ChatType::Secret(s) => {
-                        if let Some(_secret_chat) = self.secret_chats().get(&s.secret_chat_id) {
-                            chat_list_item.set_chat_name(chat.title.clone());
+                        if let Some(secret_chat) = self.secret_chats().get(&s.secret_chat_id) {
+                            chat_list_item.set_secret_chat_state(secret_chat.state.clone());
+                        }
+                        if let Some(user) = self.users().get(&s.user_id) {
+                            if let Some(username) = active_username(&user.usernames) {
+                                chat_list_item.set_username(username);
+                            }
+                            chat_list_item.set_user(user.clone());
                         }
                     }
                 }
@@ -276,3 +1049,204 @@ impl TgContext {
         Ok(Some(chat_list))
     }
 }
+
+/// Get the first active username, the one shown by Telegram.
+///
+/// # Arguments
+/// * `usernames` - The usernames of a user or a supergroup.
+///
+/// # Returns
+/// * `Option<String>` - The username without the `@`, if any.
+fn active_username(usernames: &Option<Usernames>) -> Option<String> {
+    usernames.as_ref()?.active_usernames.first().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_muted() {
+        let tg_context = TgContext::default();
+        let private = ChatType::Private(tdlib_rs::types::ChatTypePrivate::default());
+        let mut settings = ChatNotificationSettings {
+            use_default_mute_for: true,
+            ..Default::default()
+        };
+        assert!(!tg_context.is_muted(&settings, &private));
+        tg_context.set_scope_mute_for(&NotificationSettingsScope::PrivateChats, 3600);
+        assert!(tg_context.is_muted(&settings, &private));
+        let group = ChatType::BasicGroup(tdlib_rs::types::ChatTypeBasicGroup::default());
+        assert!(!tg_context.is_muted(&settings, &group));
+
+        // The settings of the chat win over the defaults.
+        settings.use_default_mute_for = false;
+        assert!(!tg_context.is_muted(&settings, &private));
+        settings.mute_for = 60;
+        assert!(tg_context.is_muted(&settings, &group));
+    }
+
+    #[test]
+    fn test_try_send_event_receiver_dropped() {
+        let tg_context = TgContext::default();
+        // The backend is not started yet.
+        assert!(!tg_context.try_send_event(Event::RefreshChats));
+        assert!(!tg_context.backend_stopped());
+
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        assert!(tg_context.try_send_event(Event::RefreshChats));
+
+        drop(event_rx);
+        assert!(!tg_context.try_send_event(Event::GetChatHistory));
+        assert!(tg_context.backend_stopped());
+        assert!(!tg_context.try_send_event(Event::GetUser(1)));
+        tg_context.request_user(2);
+    }
+
+    #[test]
+    fn test_reopen_cached_chat() {
+        let tg_context = TgContext::default();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(1);
+        *tg_context.open_chat_messages() = vec![
+            MessageEntry::new_text(20, "newest", 200),
+            MessageEntry::new_text(10, "oldest", 100),
+        ];
+
+        tg_context.open_position(Position {
+            chat_id: 2,
+            message_id: None,
+        });
+        assert!(tg_context.open_chat_messages().is_empty());
+        assert!(matches!(event_rx.try_recv(), Ok(Event::GetChatHistory)));
+        // The cached chat is kept up to date while it is not open.
+        tg_context.update_chat_messages(1, |messages| messages.retain(|m| m.id() != 20));
+
+        tg_context.open_position(Position {
+            chat_id: 1,
+            message_id: None,
+        });
+        let ids: Vec<i64> = tg_context
+            .open_chat_messages()
+            .iter()
+            .map(|m| m.id())
+            .collect();
+        assert_eq!(ids, [10]);
+        assert_eq!(tg_context.from_message_id(), 10);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::GetNewerMessages)));
+    }
+
+    #[test]
+    fn test_pending_read() {
+        let tg_context = TgContext::default();
+        let now = Instant::now();
+        let read_at = now + Duration::from_secs(2);
+        tg_context.set_open_chat_id(1);
+        tg_context.set_pending_read(Some((1, read_at)));
+        assert!(!tg_context.take_pending_read(Some(now)));
+        assert!(tg_context.take_pending_read(Some(read_at)));
+        // The chat is read only once.
+        assert!(!tg_context.take_pending_read(Some(read_at)));
+
+        // Scrolling reads the chat before the delay.
+        tg_context.set_pending_read(Some((1, read_at)));
+        assert!(tg_context.take_pending_read(None));
+
+        // The chat left before the delay is not read.
+        tg_context.set_pending_read(Some((1, read_at)));
+        tg_context.set_open_chat_id(2);
+        assert!(!tg_context.take_pending_read(Some(read_at)));
+        tg_context.set_open_chat_id(1);
+        assert!(!tg_context.take_pending_read(None));
+    }
+
+    #[test]
+    fn test_loading_history() {
+        let tg_context = TgContext::default();
+        assert!(tg_context.start_loading_history());
+        // The history is not requested again while it loads.
+        assert!(!tg_context.start_loading_history());
+        tg_context.set_loading_history(false);
+        assert!(tg_context.start_loading_history());
+    }
+
+    #[tokio::test]
+    async fn test_load_history_until() {
+        let tg_context = std::sync::Arc::new(TgContext::default());
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(1);
+        *tg_context.open_chat_messages() = vec![
+            MessageEntry::new_text(5, "message", 5),
+            MessageEntry::new_text(4, "message", 4),
+        ];
+        // It stands for the backend, each batch is one older message.
+        let backend = std::sync::Arc::clone(&tg_context);
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                if event == Event::GetChatHistory {
+                    {
+                        let mut messages = backend.open_chat_messages();
+                        let id = messages.last().unwrap().id() - 1;
+                        messages.push(MessageEntry::new_text(id, "message", id as i32));
+                    }
+                    backend.end_loading_history();
+                }
+            }
+        });
+        let timeout = Duration::from_secs(5);
+        assert_eq!(
+            tg_context.load_history_until(1, Some(2), timeout).await,
+            HistoryLoad::Complete
+        );
+        assert_eq!(tg_context.open_chat_messages().len(), 4);
+
+        // The messages loaded are kept when the history does not arrive.
+        let (event_tx, _event_rx) = tokio::sync::mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        assert_eq!(
+            tg_context
+                .load_history_until(1, Some(1), Duration::from_millis(50))
+                .await,
+            HistoryLoad::Partial
+        );
+        assert_eq!(tg_context.open_chat_messages().len(), 4);
+    }
+
+    #[test]
+    fn test_trim_chat_messages() {
+        let tg_context = TgContext::default();
+        tg_context.set_max_chat_messages(3);
+        tg_context.set_open_chat_id(1);
+        *tg_context.open_chat_messages() = (1..=5)
+            .rev()
+            .map(|id| MessageEntry::new_text(id, "message", id as i32))
+            .collect();
+        tg_context.set_at_oldest(true);
+        // The open chat keeps the messages scrolled up to.
+        tg_context.trim_cached_chat_messages(1);
+        assert_eq!(tg_context.open_chat_messages().len(), 5);
+
+        // It is trimmed once it is left.
+        tg_context.cache_open_chat_messages();
+        tg_context.set_open_chat_id(2);
+        tg_context
+            .message_cache()
+            .messages_mut(1)
+            .unwrap()
+            .insert(0, MessageEntry::new_text(6, "message", 6));
+        tg_context.trim_cached_chat_messages(1);
+        assert!(tg_context.restore_cached_chat_messages(1));
+        let ids: Vec<i64> = tg_context
+            .open_chat_messages()
+            .iter()
+            .map(|m| m.id())
+            .collect();
+        assert_eq!(ids, [6, 5, 4]);
+        // The dropped messages are loaded again from the oldest one kept.
+        assert!(!tg_context.at_oldest());
+        assert_eq!(tg_context.from_message_id(), 4);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::tg::td_enums::TdChatList;
+use std::collections::HashMap;
+
+/// `UnreadCounts` is the number of unread messages of each chat list, as sent
+/// by TDLib, with the titles of the chat folders.
+/// One of the chat lists is the aggregate shown first in the terminal title,
+/// the user can cycle through them.
+#[derive(Debug, Default)]
+pub struct UnreadCounts {
+    /// The chat folders, with their identifier and title, in the order of
+    /// the user.
+    folders: Vec<(i32, String)>,
+    /// The number of unread messages of the chat lists loaded so far.
+    counts: HashMap<TdChatList, i32>,
+    /// The chat list whose unread count is the aggregate of the title, the
+    /// main chat list if `None`.
+    shown: Option<TdChatList>,
+}
+
+impl UnreadCounts {
+    /// Set the chat folders of the user.
+    /// If the folder whose total was shown is deleted, the main chat list is
+    /// shown again.
+    ///
+    /// # Arguments
+    /// * `folders` - The identifier and the title of each folder.
+    pub fn set_folders(&mut self, folders: Vec<(i32, String)>) {
+        self.counts.retain(|list, _| match list {
+            TdChatList::Folder(id) => folders.iter().any(|(f, _)| f == id),
+            _ => true,
+        });
+        self.folders = folders;
+        if let Some(TdChatList::Folder(id)) = self.shown {
+            if !self.folders.iter().any(|(f, _)| *f == id) {
+                self.shown = None;
+            }
+        }
+    }
+
+    /// Set the number of unread messages of a chat list.
+    ///
+    /// # Arguments
+    /// * `chat_list` - The chat list.
+    /// * `count` - The number of unread messages.
+    pub fn set_count(&mut self, chat_list: TdChatList, count: i32) {
+        self.counts.insert(chat_list, count);
+    }
+
+    /// Get the name of a chat list as shown to the user.
+    ///
+    /// # Arguments
+    /// * `chat_list` - The chat list.
+    ///
+    /// # Returns
+    /// * `String` - The name of the chat list.
+    fn name_of(&self, chat_list: TdChatList) -> String {
+        match chat_list {
+            TdChatList::Main => "All chats".to_string(),
+            TdChatList::Archive => "Archive".to_string(),
+            TdChatList::Folder(id) => self
+                .folders
+                .iter()
+                .find(|(f, _)| *f == id)
+                .map_or_else(|| format!("Folder {id}"), |(_, title)| title.clone()),
+        }
+    }
+
+    /// Get the chat lists that can be shown as aggregate, that are the main
+    /// chat list and the other chat lists whose count is known.
+    fn cycle_order(&self) -> Vec<TdChatList> {
+        let mut order = vec![TdChatList::Main];
+        if self.counts.contains_key(&TdChatList::Archive) {
+            order.push(TdChatList::Archive);
+        }
+        order.extend(
+            self.folders
+                .iter()
+                .map(|(id, _)| TdChatList::Folder(*id))
+                .filter(|list| self.counts.contains_key(list)),
+        );
+        order
+    }
+
+    /// Show the next chat list as aggregate of the title.
+    ///
+    /// # Returns
+    /// * `String` - The name of the chat list now shown.
+    pub fn cycle_shown(&mut self) -> String {
+        let order = self.cycle_order();
+        let current = self.shown.unwrap_or(TdChatList::Main);
+        let next = order
+            .iter()
+            .position(|list| *list == current)
+            .map_or(TdChatList::Main, |i| order[(i + 1) % order.len()]);
+        self.shown = Some(next);
+        self.name_of(next)
+    }
+
+    /// Get the title of the terminal window.
+    /// The unread count of the shown chat list comes first, then the unread
+    /// count of each folder with unread messages, for example
+    /// `tgt (12) | Work 3 · Friends 9`.
+    ///
+    /// # Arguments
+    /// * `main_fallback` - The unread count of the main chat list, used until
+    ///   TDLib sends it.
+    ///
+    /// # Returns
+    /// * `String` - The title.
+    pub fn title(&self, main_fallback: i32) -> String {
+        let shown = self.shown.unwrap_or(TdChatList::Main);
+        let count = match shown {
+            TdChatList::Main => self
+                .counts
+                .get(&TdChatList::Main)
+                .copied()
+                .unwrap_or(main_fallback),
+            list => self.counts.get(&list).copied().unwrap_or_default(),
+        };
+        let mut title = match (shown, count) {
+            (TdChatList::Main, 0) => "tgt".to_string(),
+            (TdChatList::Main, _) => format!("tgt ({count})"),
+            (list, _) => format!("tgt ({} {count})", self.name_of(list)),
+        };
+        let folders = self
+            .folders
+            .iter()
+            .filter_map(
+                |(id, title)| match self.counts.get(&TdChatList::Folder(*id)) {
+                    Some(count) if *count > 0 => Some(format!("{title} {count}")),
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+        if !folders.is_empty() {
+            title.push_str(" | ");
+            title.push_str(&folders.join(" · "));
+        }
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unread_counts_title() {
+        let mut unread_counts = UnreadCounts::default();
+        assert_eq!(unread_counts.title(0), "tgt");
+        assert_eq!(unread_counts.title(4), "tgt (4)");
+
+        unread_counts.set_folders(vec![(1, "Work".to_string()), (2, "Friends".to_string())]);
+        unread_counts.set_count(TdChatList::Main, 12);
+        unread_counts.set_count(TdChatList::Folder(1), 3);
+        unread_counts.set_count(TdChatList::Folder(2), 0);
+        assert_eq!(unread_counts.title(4), "tgt (12) | Work 3");
+
+        assert_eq!(unread_counts.cycle_shown(), "Work");
+        assert_eq!(unread_counts.title(4), "tgt (Work 3) | Work 3");
+        assert_eq!(unread_counts.cycle_shown(), "Friends");
+        assert_eq!(unread_counts.cycle_shown(), "All chats");
+
+        unread_counts.cycle_shown();
+        unread_counts.set_folders(vec![(2, "Friends".to_string())]);
+        assert_eq!(unread_counts.title(4), "tgt (12)");
+    }
+}
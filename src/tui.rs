@@ -4,8 +4,9 @@ use crate::{
     app_error::AppError,
     component_name::ComponentName,
     components::{
-        component_traits::Component, core_window::CoreWindow, status_bar::StatusBar,
-        title_bar::TitleBar, SMALL_AREA_HEIGHT, SMALL_AREA_WIDTH,
+        component_traits::Component, core_window::CoreWindow, loading_screen::LoadingScreen,
+        lock_screen::LockScreen, status_bar::StatusBar, title_bar::TitleBar, SMALL_AREA_HEIGHT,
+        SMALL_AREA_WIDTH,
     },
     event::Event,
 };
@@ -24,6 +25,9 @@ pub struct Tui {
     action_tx: Option<UnboundedSender<Action>>,
     /// A hashmap of components that make up the user interface.
     components: HashMap<ComponentName, Box<dyn Component>>,
+    /// The overlay shown on startup until the chats are loaded, it is kept
+    /// apart from the other components because it is dismissed for good.
+    loading_screen: LoadingScreen,
 }
 /// Implement the `Tui` struct.
 impl Tui {
@@ -54,14 +58,23 @@ impl Tui {
                     .with_name("Status Bar")
                     .new_boxed(),
             ),
+            (
+                ComponentName::LockScreen,
+                LockScreen::new(Arc::clone(&app_context))
+                    .with_name("tgt is locked")
+                    .new_boxed(),
+            ),
         ];
         let action_tx = None;
         let components: HashMap<ComponentName, Box<dyn Component>> =
             components_iter.into_iter().collect();
 
+        let loading_screen = LoadingScreen::new(Arc::clone(&app_context)).with_name("tgt");
+
         Tui {
             action_tx,
             components,
+            loading_screen,
             app_context,
         }
     }
@@ -82,8 +95,17 @@ impl Tui {
         self.components
             .iter_mut()
             .try_for_each(|(_, component)| component.register_action_handler(tx.clone()))?;
+        self.loading_screen.register_action_handler(tx)?;
         Ok(())
     }
+    /// Check whether the loading screen is shown in place of the core window,
+    /// then the input is ignored.
+    ///
+    /// # Returns
+    /// * `bool` - `true` until the startup is complete.
+    pub fn is_loading(&mut self) -> bool {
+        self.loading_screen.is_visible()
+    }
     /// Handle incoming events and produce actions if necessary.
     ///
     /// # Arguments
@@ -96,6 +118,11 @@ impl Tui {
         &mut self,
         event: Option<Event>,
     ) -> Result<Option<Action>, AppError<Action>> {
+        // While locked the input only goes to the passphrase prompt, that
+        // receives it as actions.
+        if self.app_context.locked_acquire() {
+            return Ok(None);
+        }
         self.components
             .get_mut(&ComponentName::CoreWindow)
             .unwrap()
@@ -107,6 +134,14 @@ impl Tui {
     ///
     /// * `action` - An action that may modify the state of the component.
     pub fn update(&mut self, action: Action) {
+        if self.app_context.locked_acquire() && matches!(action, Action::Key(..) | Action::Paste(_))
+        {
+            self.components
+                .get_mut(&ComponentName::LockScreen)
+                .unwrap()
+                .update(action);
+            return;
+        }
         // We can not send the action only to the `CoreWindow` component because
         // the `StatusBar` component needs to know the area to render the size.
         self.components
@@ -133,6 +168,25 @@ impl Tui {
             core_window.with_small_area(area.width < SMALL_AREA_WIDTH);
         }
 
+        // While locked nothing but the passphrase prompt is drawn.
+        if self.app_context.locked_acquire() {
+            self.components
+                .get_mut(&ComponentName::LockScreen)
+                .unwrap_or_else(|| {
+                    tracing::error!("Failed to get component: {}", ComponentName::LockScreen);
+                    panic!("Failed to get component: {}", ComponentName::LockScreen)
+                })
+                .draw(frame, area)?;
+            return Ok(());
+        }
+
+        // On startup an empty chat list looks broken, so the loading screen
+        // covers everything until the first chats arrive.
+        if self.loading_screen.is_visible() {
+            self.loading_screen.draw(frame, area)?;
+            return Ok(());
+        }
+
         let main_layout = Layout::new(
             Direction::Vertical,
             [
@@ -1,12 +1,12 @@
 use {
-    crate::{app_context::AppContext, event::Event},
+    crate::{app_context::AppContext, configs::custom::app_custom::CursorShape, event::Event},
     crossterm::{
-        cursor,
+        cursor::{self, SetCursorStyle},
         event::{
             DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
             Event as CrosstermEvent, EventStream, KeyEventKind,
         },
-        terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
     },
     futures::{future::Fuse, stream::Next, FutureExt, StreamExt},
     ratatui::{backend::CrosstermBackend, Terminal},
@@ -43,6 +43,10 @@ pub struct TuiBackend {
     /// A boolean flag that represents whether the paste mode is enabled or
     /// not.
     pub paste: bool,
+    /// The last title set in the terminal window, if any.
+    pub title: Option<String>,
+    /// The style of the cursor, shown in the prompt while it is focused.
+    pub cursor_style: SetCursorStyle,
 }
 
 impl TuiBackend {
@@ -58,6 +62,10 @@ impl TuiBackend {
         let frame_rate = app_context.app_config().frame_rate;
         let mouse = app_context.app_config().mouse_support;
         let paste = app_context.app_config().paste_support;
+        let cursor_style = Self::cursor_style(
+            app_context.app_config().prompt_cursor_shape,
+            app_context.app_config().prompt_cursor_blink,
+        );
         let terminal = Terminal::new(CrosstermBackend::new(std::io::stderr()))?;
         let task: JoinHandle<Result<(), SendError<Event>>> =
             tokio::spawn(async { Err(SendError(Event::Init)) });
@@ -70,8 +78,25 @@ impl TuiBackend {
             frame_rate,
             mouse,
             paste,
+            title: None,
+            cursor_style,
         })
     }
+    /// Get the style of the cursor from its shape and whether it blinks.
+    ///
+    /// # Arguments
+    /// * `shape` - The shape of the cursor.
+    /// * `blink` - Whether the cursor blinks.
+    fn cursor_style(shape: CursorShape, blink: bool) -> SetCursorStyle {
+        match (shape, blink) {
+            (CursorShape::Block, true) => SetCursorStyle::BlinkingBlock,
+            (CursorShape::Block, false) => SetCursorStyle::SteadyBlock,
+            (CursorShape::Bar, true) => SetCursorStyle::BlinkingBar,
+            (CursorShape::Bar, false) => SetCursorStyle::SteadyBar,
+            (CursorShape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+            (CursorShape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+        }
+    }
     /// Enter the user interface and start processing events.
     /// This will enable the raw mode for the terminal and switch to the
     /// alternate screen.
@@ -97,9 +122,28 @@ impl TuiBackend {
         if self.paste {
             crossterm::execute!(std::io::stderr(), EnableBracketedPaste)?;
         }
+        // The cursor is only shown while the prompt is focused.
+        crossterm::execute!(std::io::stderr(), self.cursor_style)?;
         self.start();
         Ok(())
     }
+    /// Enable or disable the mouse capture at runtime, it is disabled to
+    /// select text with the mouse of the terminal.
+    ///
+    /// # Returns
+    /// * `Result<bool, io::Error>` - Whether the mouse is now captured, or an
+    ///   error.
+    pub fn toggle_mouse(&mut self) -> Result<bool, io::Error> {
+        if self.mouse {
+            crossterm::execute!(std::io::stderr(), DisableMouseCapture)?;
+            tracing::info!("Mouse disabled");
+        } else {
+            crossterm::execute!(std::io::stderr(), EnableMouseCapture)?;
+            tracing::info!("Mouse enabled");
+        }
+        self.mouse = !self.mouse;
+        Ok(self.mouse)
+    }
     /// Exit the user interface and stop processing events.
     /// This will disable the raw mode for the terminal and switch back to the
     /// main screen.
@@ -115,7 +159,12 @@ impl TuiBackend {
     pub fn force_exit(mouse: bool, paste: bool) -> Result<(), std::io::Error> {
         crossterm::terminal::disable_raw_mode()?;
         tracing::info!("Raw mode disabled");
-        crossterm::execute!(std::io::stderr(), LeaveAlternateScreen, cursor::Show)?;
+        crossterm::execute!(
+            std::io::stderr(),
+            LeaveAlternateScreen,
+            SetCursorStyle::DefaultUserShape,
+            cursor::Show
+        )?;
         tracing::info!("Alternate screen disabled");
         if mouse {
             crossterm::execute!(std::io::stderr(), DisableMouseCapture)?;
@@ -134,11 +183,43 @@ impl TuiBackend {
     /// # Returns
     /// * `Result<(), io::Error>` - An Ok result or an error.
     pub fn exit(&self) {
+        if self.title.is_some() {
+            if let Err(e) = crossterm::execute!(std::io::stderr(), SetTitle("")) {
+                tracing::error!("Error resetting the terminal title: {}", e);
+            }
+        }
         match TuiBackend::force_exit(self.mouse, self.paste) {
             Ok(_) => tracing::info!("Tui backend exited"),
             Err(e) => tracing::error!("Error exiting tui backend: {}", e),
         }
     }
+    /// Set the title of the terminal window.
+    /// The title is written only when it changes, to avoid flooding the
+    /// terminal with escape sequences at every render.
+    ///
+    /// # Arguments
+    /// * `title` - The new title of the terminal window.
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - An Ok result or an error.
+    pub fn set_title(&mut self, title: &str) -> Result<(), io::Error> {
+        if self.title.as_deref() == Some(title) {
+            return Ok(());
+        }
+        crossterm::execute!(std::io::stderr(), SetTitle(title))?;
+        self.title = Some(title.to_string());
+        Ok(())
+    }
+    /// Ring the bell of the terminal, on the writer the user interface is
+    /// drawn on.
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - An Ok result or an error.
+    pub fn ring_bell(&mut self) -> Result<(), io::Error> {
+        let writer = self.terminal.backend_mut();
+        io::Write::write_all(writer, b"\x07")?;
+        io::Write::flush(writer)
+    }
     /// Suspend the user interface and stop processing events.
     /// This will disable the raw mode for the terminal and switch back to the
     /// main screen.
@@ -1,9 +1,28 @@
+use crate::configs::custom::app_custom::LocationSource;
 use dirs;
-use std::{env, io, path::PathBuf};
+use ratatui::{style::Color, text::Line};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+use tdlib_rs::enums::UserStatus;
 
 pub const TGT: &str = "tgt";
 pub const TGT_CONFIG_DIR: &str = "TGT_CONFIG_DIR";
 
+/// The text that replaces the emoji carrying a meaning when the emoji are
+/// stripped, the other emoji are removed.
+const EMOJI_TEXT: [(char, &str); 7] = [
+    ('📤', "[sent]"),
+    ('👀', "[read]"),
+    ('🟢', "*"),
+    ('⚠', "!"),
+    ('🚫', "[x]"),
+    ('✅', "[v]"),
+    ('🔒', "[secret]"),
+];
+
 /// Get the project directory.
 ///
 /// # Returns
@@ -61,3 +80,388 @@ pub fn unwrap_or_fail<T, E: std::fmt::Debug>(result: Result<T, E>, msg: &str) ->
         Err(e) => fail_with(msg, e),
     }
 }
+
+/// Open an URL with the default application of the system.
+///
+/// # Arguments
+/// * `url` - A string slice that holds the URL to open.
+///
+/// # Returns
+/// * `io::Result<()>` - An Ok result or an error if the command can not be
+///   spawned.
+pub fn open_url(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(url);
+    spawn_detached(command)
+}
+
+/// Play a sound file with the audio player of the system, without waiting for
+/// the end of the sound.
+///
+/// # Arguments
+/// * `path` - The path of the sound file.
+///
+/// # Returns
+/// * `io::Result<()>` - An Ok result or an error if the command can not be
+///   spawned.
+pub fn play_sound(path: &Path) -> io::Result<()> {
+    let command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("afplay");
+        command.arg(path);
+        command
+    } else if cfg!(target_os = "windows") {
+        // The arguments after `-Command` are joined to the command, the path
+        // is quoted inside it, with the single quotes doubled.
+        let path = path.display().to_string().replace('\'', "''");
+        let mut command = Command::new("powershell");
+        command.args([
+            "-Command",
+            &format!("(New-Object Media.SoundPlayer '{path}').PlaySync()"),
+        ]);
+        command
+    } else {
+        let mut command = Command::new("paplay");
+        command.arg(path);
+        command
+    };
+    spawn_detached(command)
+}
+
+/// Spawn a command without waiting for it, its exit status is collected on
+/// a thread so it does not stay as a zombie process.
+///
+/// # Arguments
+/// * `command` - The command to spawn.
+///
+/// # Returns
+/// * `io::Result<()>` - An Ok result or an error if the command can not be
+///   spawned.
+fn spawn_detached(mut command: Command) -> io::Result<()> {
+    let mut child = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Format a size in bytes as a human readable string.
+///
+/// # Arguments
+/// * `bytes` - The size in bytes.
+///
+/// # Returns
+/// * `String` - The formatted size, for example "1.5 MB".
+pub fn human_readable_size(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+/// Get the symbol shown before the name of a user online.
+///
+/// # Arguments
+/// * `status` - The status of the user.
+///
+/// # Returns
+/// * `&'static str` - The symbol, empty if the user is not online.
+pub fn online_symbol(status: &UserStatus) -> &'static str {
+    match status {
+        UserStatus::Online(_) => "🟢 ",
+        UserStatus::Offline(_) => "",
+        UserStatus::Empty => "",
+        UserStatus::Recently(_) => "",
+        UserStatus::LastWeek(_) => "",
+        UserStatus::LastMonth(_) => "",
+    }
+}
+/// Whether a character is an emoji, or a character that only modifies an
+/// emoji like the variation selectors, the skin tones and the joiners.
+///
+/// # Arguments
+/// * `c` - The character.
+pub fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF
+            | 0x2300..=0x23FF
+            | 0x2600..=0x27BF
+            | 0x2B1B..=0x2B1C
+            | 0x2B50
+            | 0x2B55
+            | 0x200D
+            | 0x20E3
+            | 0xFE0F
+            | 0xE0020..=0xE007F
+    )
+}
+
+/// Strip the emoji from a text, the emoji carrying a meaning in the
+/// interface are replaced by text and the others are removed with the space
+/// after them.
+///
+/// # Arguments
+/// * `text` - The text to strip.
+///
+/// # Returns
+/// * `String` - The text without emoji.
+pub fn strip_emoji(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let mut line = Line::raw(line);
+            strip_emoji_line(&mut line);
+            line.spans.into_iter().map(|span| span.content).collect()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Strip the emoji from the spans of a line, like `strip_emoji`. The spans
+/// keep their style, the messages are split into a span per character.
+///
+/// # Arguments
+/// * `line` - The line to strip.
+pub fn strip_emoji_line(line: &mut Line<'_>) {
+    // Whether the stripped text is empty or ends with a blank, then the
+    // space after a removed emoji is removed too.
+    let mut at_blank = true;
+    let mut skip_space = false;
+    for span in line.spans.iter_mut() {
+        if !skip_space && !span.content.chars().any(is_emoji) {
+            if let Some(c) = span.content.chars().last() {
+                at_blank = c.is_whitespace();
+            }
+            continue;
+        }
+        let mut stripped = String::with_capacity(span.content.len());
+        for c in span.content.chars() {
+            if std::mem::take(&mut skip_space) && c == ' ' {
+                continue;
+            }
+            if let Some((_, text)) = EMOJI_TEXT.iter().find(|(emoji, _)| *emoji == c) {
+                stripped.push_str(text);
+                at_blank = false;
+            } else if is_emoji(c) {
+                skip_space = at_blank;
+            } else {
+                stripped.push(c);
+                at_blank = c.is_whitespace();
+            }
+        }
+        span.content = stripped.into();
+    }
+}
+
+/// Recognize a Telegram invite link, `t.me/+hash` or `t.me/joinchat/hash`,
+/// with or without the scheme.
+///
+/// # Arguments
+/// * `text` - The text to recognize.
+///
+/// # Returns
+/// * `Option<String>` - The invite link with its scheme, if the text is one.
+pub fn invite_link(text: &str) -> Option<String> {
+    let text = text.trim();
+    let path = text
+        .strip_prefix("https://")
+        .or_else(|| text.strip_prefix("http://"))
+        .unwrap_or(text);
+    let path = ["t.me/", "telegram.me/", "telegram.dog/"]
+        .iter()
+        .find_map(|host| path.strip_prefix(host))?;
+    let hash = match path.strip_prefix('+') {
+        // `t.me/+` followed by digits is the link of a phone number.
+        Some(hash) if hash.chars().all(|c| c.is_ascii_digit()) => return None,
+        Some(hash) => hash,
+        None => path.strip_prefix("joinchat/")?,
+    };
+    let is_hash = !hash.is_empty()
+        && hash
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    is_hash.then(|| format!("https://t.me/{path}"))
+}
+
+/// A location to send, with the coordinates typed in the prompt or taken
+/// from the `location` of the configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocationInput {
+    /// The latitude in degrees.
+    pub latitude: f64,
+    /// The longitude in degrees.
+    pub longitude: f64,
+    /// The period for which the location is shared live in seconds, 0 for a
+    /// static location.
+    pub live_period: i32,
+    /// The file the coordinates were read from, read again to update a live
+    /// location.
+    pub source: Option<PathBuf>,
+}
+
+/// Parse coordinates in degrees, `latitude, longitude` or
+/// `latitude longitude`.
+///
+/// # Arguments
+/// * `text` - The text to parse.
+///
+/// # Returns
+/// * `Option<(f64, f64)>` - The latitude and the longitude, if they are valid.
+pub fn parse_coordinates(text: &str) -> Option<(f64, f64)> {
+    let mut parts = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty());
+    let latitude = parts.next()?.parse::<f64>().ok()?;
+    let longitude = parts.next()?.parse::<f64>().ok()?;
+    let valid = parts.next().is_none()
+        && (-90.0..=90.0).contains(&latitude)
+        && (-180.0..=180.0).contains(&longitude);
+    valid.then_some((latitude, longitude))
+}
+
+/// Read the coordinates of a location from a file, see `parse_coordinates`.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+pub fn read_coordinates(path: &Path) -> Result<(f64, f64), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read the location from {}: {}", path.display(), e))?;
+    parse_coordinates(text.trim())
+        .ok_or_else(|| format!("Invalid coordinates in {}", path.display()))
+}
+
+/// Parse the location typed in the prompt: the coordinates, see
+/// `parse_coordinates`, optionally followed by the duration of a live
+/// location in minutes or hours, like `15m` or `8h`. Without coordinates,
+/// the configured location is used.
+///
+/// # Arguments
+/// * `text` - The text typed in the prompt.
+/// * `configured` - The location of the configuration.
+///
+/// # Returns
+/// * `Result<LocationInput, String>` - The location, or why it is invalid.
+pub fn parse_location(text: &str, configured: &LocationSource) -> Result<LocationInput, String> {
+    let text = text.trim();
+    let (coordinates, last) = text.rsplit_once(char::is_whitespace).unwrap_or(("", text));
+    let (coordinates, live_period) = match live_period(last) {
+        Some(period) if !(60..=86400).contains(&period) => {
+            return Err("A live location is shared for 1 minute to 24 hours".to_string());
+        }
+        Some(period) => (coordinates.trim(), period as i32),
+        None => (text, 0),
+    };
+    if !coordinates.is_empty() {
+        let (latitude, longitude) = parse_coordinates(coordinates)
+            .ok_or_else(|| format!("Invalid coordinates: {}", coordinates))?;
+        return Ok(LocationInput {
+            latitude,
+            longitude,
+            live_period,
+            source: None,
+        });
+    }
+    let ((latitude, longitude), source) = match configured {
+        LocationSource::None => {
+            return Err("Type the coordinates of the location, like 48.8584, 2.2945".to_string());
+        }
+        LocationSource::Coordinates(latitude, longitude) => ((*latitude, *longitude), None),
+        LocationSource::File(path) => (read_coordinates(path)?, Some(path.clone())),
+    };
+    Ok(LocationInput {
+        latitude,
+        longitude,
+        live_period,
+        source,
+    })
+}
+
+/// Parse the duration of a live location, like `15m` or `8h`.
+///
+/// # Arguments
+/// * `text` - The text to parse.
+///
+/// # Returns
+/// * `Option<u64>` - The duration in seconds, if the text is a duration.
+fn live_period(text: &str) -> Option<u64> {
+    let (count, unit) = match text.strip_suffix('m') {
+        Some(count) => (count, 60),
+        None => (text.strip_suffix('h')?, 3600),
+    };
+    count.parse::<u64>().ok()?.checked_mul(unit)
+}
+
+/// Split a message too long for Telegram in several parts, cut preferably
+/// between paragraphs, then lines, sentences and words, in the second half of
+/// each part so that no part is too short. A word longer than a part is cut.
+///
+/// # Arguments
+/// * `text` - The text of the message.
+/// * `max_length` - The maximum number of characters of a part.
+///
+/// # Returns
+/// * `Vec<String>` - The parts, in order.
+pub fn split_message(text: &str, max_length: usize) -> Vec<String> {
+    let mut parts = vec![];
+    let mut rest = text.trim();
+    while rest.chars().count() > max_length {
+        let end = rest
+            .char_indices()
+            .nth(max_length)
+            .map_or(rest.len(), |(i, _)| i);
+        let head = &rest[..end];
+        let cut = ["\n\n", "\n", ". ", "! ", "? ", " "]
+            .iter()
+            .find_map(|separator| {
+                head.rfind(separator)
+                    .filter(|i| *i >= end / 2)
+                    .map(|i| i + separator.len())
+            })
+            .or_else(|| head.rfind(char::is_whitespace).filter(|i| *i > 0))
+            .unwrap_or(end);
+        parts.push(rest[..cut].trim_end().to_string());
+        rest = rest[cut..].trim_start();
+    }
+    if !rest.is_empty() {
+        parts.push(rest.to_string());
+    }
+    parts
+}
+
+/// Blend two colors, it is used to animate the transition between two
+/// styles. Only the RGB colors can be blended, the other colors switch
+/// halfway.
+///
+/// # Arguments
+/// * `from` - The color at the beginning of the transition.
+/// * `to` - The color at the end of the transition.
+/// * `progress` - The progress of the transition, between 0 and 1.
+///
+/// # Returns
+/// * `Color` - The blended color.
+pub fn blend_colors(from: Color, to: Color, progress: f64) -> Color {
+    let progress = progress.clamp(0.0, 1.0);
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let blend = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * progress).round() as u8;
+            Color::Rgb(blend(r1, r2), blend(g1, g2), blend(b1, b2))
+        }
+        _ if progress < 0.5 => from,
+        _ => to,
+    }
+}